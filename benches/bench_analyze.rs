@@ -89,5 +89,27 @@ fn bench_analyze(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_analyze);
+fn bench_analyze_large_project(c: &mut Criterion) {
+    // 1000 modules: large enough that pass 2 (the cross-file usage union and
+    // RP003/RP004 generation) shows up next to the per-file checks.
+    let dir = TempDir::new().unwrap();
+    let mut files = Vec::new();
+    for i in 0..1000 {
+        let path = dir.path().join(format!("module_{i}.py"));
+        fs::write(&path, make_python_file(i)).unwrap();
+        files.push(path);
+    }
+
+    let mut group = c.benchmark_group("large_project");
+    group.sample_size(10);
+    group.bench_function("analyze_files_1000_modules", |b| {
+        b.iter(|| {
+            let diags = analyze_files(black_box(&files)).unwrap();
+            black_box(diags);
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_analyze, bench_analyze_large_project);
 criterion_main!(benches);
@@ -0,0 +1,46 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use reaper::discovery::discover_python_files;
+use std::fs;
+use tempfile::TempDir;
+
+/// `packages × 10` directories holding 5 `.py` files each, plus some
+/// non-Python noise the walker has to skip.
+fn make_tree(packages: usize) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for pkg in 0..packages {
+        for sub in 0..10 {
+            let d = dir.path().join(format!("pkg_{pkg}/sub_{sub}"));
+            fs::create_dir_all(&d).unwrap();
+            for f in 0..5 {
+                fs::write(d.join(format!("mod_{f}.py")), "x = 1\n").unwrap();
+            }
+            fs::write(d.join("README.md"), "docs\n").unwrap();
+        }
+    }
+    dir
+}
+
+fn bench_discovery(c: &mut Criterion) {
+    let tree = make_tree(20);
+
+    c.bench_function("discover_1000_files_200_dirs", |b| {
+        b.iter(|| {
+            let files = discover_python_files(black_box(tree.path()), &[]).unwrap();
+            assert_eq!(files.len(), 1000);
+            black_box(files);
+        });
+    });
+
+    let tree = make_tree(200);
+
+    c.bench_function("discover_10000_files_2000_dirs", |b| {
+        b.iter(|| {
+            let files = discover_python_files(black_box(tree.path()), &[]).unwrap();
+            assert_eq!(files.len(), 10_000);
+            black_box(files);
+        });
+    });
+}
+
+criterion_group!(benches, bench_discovery);
+criterion_main!(benches);
@@ -0,0 +1,71 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use reaper::fast_parser::lexer::{Lexer, Token};
+use reaper::fast_parser::parse;
+
+/// A ~1000-line module: 30 copies of a 34-line block touching most of the grammar
+/// (imports, decorators, classes, comprehensions, f-strings, try/with/match).
+fn make_source() -> String {
+    (0..30)
+        .map(|i| {
+            format!(
+                r#"import os
+from typing import Optional
+
+@decorator(arg={i})
+def function_{i}(a: int, b: Optional[str] = None, *args, **kwargs) -> int:
+    """Docstring for function {i}."""
+    total = sum(x * 2 for x in range(a) if x % 3)
+    label = f"{{a}}-{{b!r:>10}}"
+    try:
+        with open(os.devnull) as fh:
+            fh.write(label)
+    except (OSError, ValueError) as exc:
+        raise RuntimeError("failed") from exc
+    finally:
+        pass
+    match a:
+        case 0:
+            return 0
+        case [x, *rest] if rest:
+            return x
+        case _:
+            return total
+
+
+class Model_{i}(Base):
+    field: int = 0
+
+    def method(self, value):
+        if value is None:
+            return self.field
+        elif value > {i}:
+            return value
+        else:
+            return [v for v in (value, self.field) if v]
+"#
+            )
+        })
+        .collect()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let source = make_source();
+
+    c.bench_function("lex_1000_lines", |b| {
+        b.iter(|| {
+            let mut lexer = Lexer::new(black_box(&source));
+            let mut count = 0usize;
+            while !matches!(lexer.bump(), Token::Eof) {
+                count += 1;
+            }
+            black_box(count);
+        });
+    });
+
+    c.bench_function("parse_1000_lines", |b| {
+        b.iter(|| black_box(parse(black_box(&source))));
+    });
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
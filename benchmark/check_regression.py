@@ -0,0 +1,52 @@
+#!/usr/bin/env python3
+"""
+Fail when any Criterion benchmark regressed against the saved baseline.
+
+Run after `cargo bench -- --baseline <name>`.  Criterion writes the relative
+change of each benchmark's mean to target/criterion/<bench>/change/estimates.json;
+any change above the threshold (default 10%) is reported and exits 1.
+
+Usage:
+  python3 benchmark/check_regression.py [--threshold 0.10]
+"""
+
+import argparse
+import json
+import sys
+from pathlib import Path
+
+REPO_ROOT = Path(__file__).parent.parent
+CRITERION_DIR = REPO_ROOT / "target" / "criterion"
+
+
+def main() -> int:
+    parser = argparse.ArgumentParser()
+    parser.add_argument("--threshold", type=float, default=0.10)
+    args = parser.parse_args()
+
+    estimates = sorted(CRITERION_DIR.glob("*/change/estimates.json"))
+    if not estimates:
+        print(f"no baseline comparisons found under {CRITERION_DIR}", file=sys.stderr)
+        return 2
+
+    regressions = []
+    for path in estimates:
+        name = path.parent.parent.name
+        change = json.loads(path.read_text())["mean"]["point_estimate"]
+        marker = "REGRESSED" if change > args.threshold else "ok"
+        print(f"{name:45} {change:+8.2%}  {marker}")
+        if change > args.threshold:
+            regressions.append(name)
+
+    if regressions:
+        print(
+            f"\n{len(regressions)} benchmark(s) regressed by more than "
+            f"{args.threshold:.0%}: {', '.join(regressions)}",
+            file=sys.stderr,
+        )
+        return 1
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
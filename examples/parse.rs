@@ -0,0 +1,25 @@
+//! Parse a Python snippet and print its statements.
+//!
+//! ```text
+//! cargo run --example parse
+//! cargo run --example parse -- path/to/module.py
+//! ```
+
+use std::{env, fs};
+
+const SAMPLE: &str = "\
+import os
+
+def greet(name):
+    return f\"hello {name}\"
+";
+
+fn main() {
+    let source = match env::args().nth(1) {
+        Some(path) => fs::read_to_string(&path).unwrap_or_else(|e| panic!("{path}: {e}")),
+        None => SAMPLE.to_string(),
+    };
+    for stmt in reaper::parse(&source) {
+        println!("{stmt:#?}");
+    }
+}
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use reaper::fast_parser::lexer::{Lexer, Token};
+
+// Drain the token stream; every input must reach `Eof`.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let mut lexer = Lexer::new(s);
+        while !matches!(lexer.bump(), Token::Eof) {}
+    }
+});
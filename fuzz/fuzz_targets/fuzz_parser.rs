@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// The parser runs on arbitrary files from disk: it must never panic, loop
+// forever or blow the stack, whatever the input.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = reaper::fast_parser::parse(s);
+    }
+});
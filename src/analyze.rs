@@ -1,58 +1,157 @@
+use crate::cache::{Cache, Entry};
 use crate::checks::{
-    dead_branch::check_dead_branches, unreachable::check_unreachable,
-    unused_args::check_unused_arguments, unused_defs::collect_module_defs,
-    unused_imports::check_unused_imports, unused_loop_var::check_unused_loop_vars,
+    annotation_imports::{check_annotation_imports, check_annotation_only_imports},
+    dead_branch::check_dead_branches,
+    dunder_all::{check_duplicate_exports, check_undefined_exports},
+    eq_without_hash::check_eq_without_hash,
+    exception_message::check_exception_message,
+    framework::collect_url_rule_views,
+    loop_var_after_loop::check_loop_var_after_loop,
+    print_statement::check_print_statements,
+    redundant_else::check_redundant_else,
+    return_consistency::check_return_consistency,
+    scope_declarations::check_scope_declarations,
+    self_assign::check_self_assign,
+    shadowed_builtins::check_shadowed_builtins,
+    string_format::{check_implicit_concatenation, check_pointless_fstrings, check_string_format},
+    suppressed_assign::check_suppressed_assignments,
+    try_else::check_try_else,
+    unreachable::{check_loop_control, check_unreachable},
+    unused_args::check_unused_arguments,
+    unused_defs::collect_module_defs,
+    unused_except_var::check_unused_except_vars,
+    unused_globals::check_unused_globals,
+    unused_imports::check_unused_imports,
+    unused_loop_var::check_unused_loop_vars,
     unused_variables::check_unused_variables,
+    unused_with_target::check_unused_with_targets,
+};
+use crate::config::ReaperConfig;
+use crate::encoding::Source;
+use crate::location::LineIndex;
+use crate::names::{
+    collect_attribute_names, collect_dunder_all, collect_stmt_names, collect_type_comment_names,
 };
-use crate::location::offset_to_line_col;
-use crate::names::{collect_dunder_all, collect_stmt_names};
 use crate::parser::parse_python;
 use crate::types::{Diagnostic, RuleCode};
 use anyhow::Result;
 use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
-use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::ast::Stmt;
+use crate::ast::{Stmt, StmtKind};
 
 // ── per-file analysis result ─────────────────────────────────────────────────
 
 struct FileAnalysis {
-    /// Diagnostics from per-file checks (RP001, RP002, RP005, RP006, RP008, RP009).
+    /// Diagnostics from per-file checks (RP001, RP002, RP005–RP013, RP015, RP047, RP048).
     diags: Vec<Diagnostic>,
-    /// Module-level function/class definitions eligible for cross-file dead-code
-    /// analysis (RP003, RP004).  Diagnostics are NOT generated here — see pass 2.
+    /// Module-level function/class definitions and class variables eligible
+    /// for cross-file dead-code analysis (RP003, RP004, RP014).  Diagnostics
+    /// are NOT generated here — see pass 2.
     module_defs: Vec<crate::checks::unused_defs::ModuleDef>,
     /// Every name *used* in this file plus every name exported via `__all__`.
     /// The union of these sets across all files forms the global usage set for
     /// cross-file RP003/RP004 analysis.
     module_usages: HashSet<String>,
-    /// Raw source, kept so we can apply `# noqa` filtering and generate accurate
-    /// line/col offsets for pass-2 diagnostics.
+    /// Attribute names, keyword arguments and identifier-like strings in this
+    /// file — the ways a class variable can be read (RP014).
+    attr_usages: HashSet<String>,
+    /// `(bound, original)` for each top-level `from .sub import original as
+    /// bound` in an `__init__.py` — the package's re-exports.  Empty for other
+    /// files.
+    reexports: Vec<(String, String)>,
+    /// Raw source, kept so we can apply `# noqa` filtering.
     source: String,
+    /// Newline index of `source`, for line/col offsets of pass-2 diagnostics.
+    lines: LineIndex,
     filename: String,
 }
 
 // ── public entry point ───────────────────────────────────────────────────────
 
+/// Diagnostics for a set of files, plus the totals `--statistics` reports.
+pub struct Report {
+    pub diagnostics: Vec<Diagnostic>,
+    /// Lines across every file that was analysed.
+    pub total_lines: usize,
+}
+
+#[allow(dead_code)] // library entry point; the CLI goes through `analyze_files_with_config`
 pub fn analyze_files(files: &[PathBuf]) -> Result<Vec<Diagnostic>> {
+    analyze_files_with_config(files, &ReaperConfig::default())
+}
+
+/// Like [`analyze_files`], with project-level settings from `config`.
+pub fn analyze_files_with_config(
+    files: &[PathBuf],
+    config: &ReaperConfig,
+) -> Result<Vec<Diagnostic>> {
+    Ok(analyze_report(files, config)?.diagnostics)
+}
+
+/// Like [`analyze_files_with_config`], keeping the size of the analysed code.
+pub fn analyze_report(files: &[PathBuf], config: &ReaperConfig) -> Result<Report> {
     // ── Pass 1 (parallel): per-file checks ───────────────────────────────────
-    let analyses: Vec<FileAnalysis> = files
+    //
+    // With a cache, unchanged files reuse their stored pass-1 results and
+    // skip parsing; the rest are analysed and stored for the next run.
+    let mut cache = config
+        .cache_path
+        .as_deref()
+        .map(|path| Cache::load(path, &Cache::key(config)));
+    let analyses: Vec<(FileAnalysis, bool)> = files
         .par_iter()
-        .filter_map(|path| analyze_file(path).ok())
+        .filter_map(|path| match cache.as_ref().and_then(|c| c.get(path)) {
+            Some(entry) => from_cache(path, entry).ok().map(|a| (a, true)),
+            None => analyze_file(path, config).ok().map(|a| (a, false)),
+        })
         .collect();
+    if let (Some(cache), Some(cache_path)) = (cache.as_mut(), config.cache_path.as_deref()) {
+        for (analysis, _) in analyses.iter().filter(|(_, hit)| !hit) {
+            cache.insert(Path::new(&analysis.filename), |mtime, len| {
+                analysis.to_cache_entry(mtime, len)
+            });
+        }
+        // A cache that cannot be written only costs the next run its speed-up.
+        let _ = cache.save(cache_path);
+    }
+    let analyses: Vec<FileAnalysis> = analyses.into_iter().map(|(a, _)| a).collect();
 
     // ── Pass 2 (sequential): cross-file RP003/RP004 ──────────────────────────
     //
     // A definition is dead if its name never appears in *any* file's usage set.
     // This means a public function defined in utils.py but called from main.py
-    // will correctly NOT be flagged.
-    let global_usages: HashSet<String> = analyses
-        .iter()
-        .flat_map(|a| a.module_usages.iter().cloned())
+    // will correctly NOT be flagged.  The union is built in parallel; Rayon's
+    // `collect` into a `HashSet` merges per-thread sets, so no locking is needed.
+    let mut global_usages: HashSet<String> = analyses
+        .par_iter()
+        .flat_map(|a| a.module_usages.par_iter().cloned())
+        .collect();
+
+    // A package can re-export a name under another one, through several
+    // `__init__.py` files: `from .impl import Helper as Client` in
+    // `pkg/sub/__init__.py`, `from .sub import Client as Api` in
+    // `pkg/__init__.py`, and `Api` in `__all__`.  A re-export whose bound name
+    // is used makes the name it imports used too; repeat until nothing changes.
+    loop {
+        let before = global_usages.len();
+        for (bound, original) in analyses.iter().flat_map(|a| &a.reexports) {
+            if global_usages.contains(bound) && !global_usages.contains(original) {
+                global_usages.insert(original.clone());
+            }
+        }
+        if global_usages.len() == before {
+            break;
+        }
+    }
+    let global_attrs: HashSet<String> = analyses
+        .par_iter()
+        .flat_map(|a| a.attr_usages.par_iter().cloned())
         .collect();
 
+    let total_lines = analyses.iter().map(|a| a.source.lines().count()).sum();
+
     let source_map: HashMap<String, String> = analyses
         .iter()
         .map(|a| (a.filename.clone(), a.source.clone()))
@@ -63,7 +162,7 @@ pub fn analyze_files(files: &[PathBuf]) -> Result<Vec<Diagnostic>> {
         .flat_map(|a| a.diags.iter().cloned())
         .collect();
 
-    // Add RP003/RP004 diagnostics for defs not referenced anywhere.
+    // Add RP003/RP004/RP014 diagnostics for defs not referenced anywhere.
     // Each analysis is independent once global_usages is built, so we can
     // generate diagnostics in parallel and collect them all at once.
     let rp003_rp004: Vec<Diagnostic> = analyses
@@ -72,29 +171,15 @@ pub fn analyze_files(files: &[PathBuf]) -> Result<Vec<Diagnostic>> {
             analysis
                 .module_defs
                 .iter()
-                .filter(|def| !global_usages.contains(&def.name))
-                .map(|def| {
-                    let (line, col) = offset_to_line_col(def.offset, &analysis.source);
-                    let kind = if def.code == RuleCode::UnusedFunction {
-                        "Function"
-                    } else {
-                        "Class"
-                    };
-                    Diagnostic {
-                        file: def.file.clone(),
-                        line,
-                        col,
-                        code: def.code.clone(),
-                        message: format!("{kind} `{}` is defined but never used", def.name),
-                    }
-                })
+                .filter(|def| !def.is_used(&global_usages, &global_attrs))
+                .map(|def| def.diagnostic(&analysis.lines))
                 .collect::<Vec<_>>()
         })
         .collect();
     all_diags.extend(rp003_rp004);
 
     // ── Post-processing: apply `# noqa` suppression ──────────────────────────
-    let all_diags = filter_noqa(all_diags, &source_map);
+    let all_diags = filter_noqa(all_diags, &source_map, config.respect_type_ignore);
 
     // ── Post-processing: deduplicate RP002 shadowed by RP005 ─────────────────
     //
@@ -102,9 +187,15 @@ pub fn analyze_files(files: &[PathBuf]) -> Result<Vec<Diagnostic>> {
     // variable (RP002), the RP002 diagnostic is redundant noise — the user
     // already knows the whole line is dead.  Remove any RP002 that shares the
     // same (file, line) as an RP005.
-    let all_diags = suppress_rp002_under_rp005(all_diags);
+    let mut all_diags = suppress_rp002_under_rp005(all_diags);
 
-    Ok(all_diags)
+    // ── Post-processing: drop diagnostics about ignored names ───────────────
+    all_diags.retain(|d| !config.ignores(&d.message));
+
+    Ok(Report {
+        diagnostics: all_diags,
+        total_lines,
+    })
 }
 
 // ── RP002/RP005 deduplication ─────────────────────────────────────────────────
@@ -144,105 +235,192 @@ fn is_reexport_file(filename: &str) -> bool {
     filename.ends_with("__init__.py") || filename.ends_with("conftest.py")
 }
 
-fn analyze_file(path: &PathBuf) -> Result<FileAnalysis> {
-    let source = fs::read_to_string(path)?;
+/// `(bound, original)` for every name a top-level relative import binds:
+/// `from .impl import Helper as Client` gives `("Client", "Helper")`.
+fn collect_reexports(stmts: &[Stmt<'_>]) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    for stmt in stmts {
+        if let StmtKind::ImportFrom { names, level, .. } = &stmt.kind
+            && *level > 0
+        {
+            for alias in names.iter().filter(|a| a.name != "*") {
+                let bound = alias.asname.unwrap_or(alias.name);
+                out.push((bound.to_string(), alias.name.to_string()));
+            }
+        }
+    }
+    out
+}
+
+impl FileAnalysis {
+    fn to_cache_entry(&self, mtime: u64, len: u64) -> Entry {
+        Entry {
+            mtime,
+            len,
+            diags: self.diags.clone(),
+            module_defs: self.module_defs.clone(),
+            module_usages: self.module_usages.clone(),
+            attr_usages: self.attr_usages.clone(),
+            reexports: self.reexports.clone(),
+        }
+    }
+}
+
+/// Rebuild the pass-1 result for `path` from its cache entry.  The source
+/// is still read, for `# noqa` filtering and line numbers in pass 2.
+fn from_cache(path: &Path, entry: &Entry) -> Result<FileAnalysis> {
+    let source = Source::read(path)?.text;
+    let filename = path.to_string_lossy().to_string();
+    // The entry may have been stored under another spelling of the path.
+    let mut diags = entry.diags.clone();
+    for d in &mut diags {
+        d.file.clone_from(&filename);
+    }
+    let mut module_defs = entry.module_defs.clone();
+    for def in &mut module_defs {
+        def.file.clone_from(&filename);
+    }
+    Ok(FileAnalysis {
+        diags,
+        module_defs,
+        module_usages: entry.module_usages.clone(),
+        attr_usages: entry.attr_usages.clone(),
+        reexports: entry.reexports.clone(),
+        lines: LineIndex::from_source(&source),
+        source,
+        filename,
+    })
+}
+
+fn analyze_file(path: &Path, config: &ReaperConfig) -> Result<FileAnalysis> {
+    let source = Source::read(path)?.text;
     let filename = path.to_string_lossy().to_string();
 
     // The new parser is infallible — unparseable constructs become StmtKind::Other.
     let stmts: Vec<Stmt<'_>> = parse_python(&source, &filename);
+    let lines = LineIndex::from_source(&source);
 
-    // ── Run all six per-file checkers in parallel ────────────────────────────
+    // ── Run all per-file checkers in parallel ────────────────────────────────
     //
-    // rayon::join is opportunistic: if the outer file-level par_iter has
-    // already saturated the thread pool, both branches run sequentially on
-    // the calling thread with zero overhead.  When spare threads exist (e.g.
-    // when analysing a single large file) the work is stolen and runs truly
-    // in parallel.
-    let ((d_imports_raw, d_vars), (d_unreachable, (d_dead, (d_args, d_loop)))) = rayon::join(
-        || {
-            rayon::join(
-                || check_unused_imports(&stmts, &filename, &source),
-                || check_unused_variables(&stmts, &filename, &source),
-            )
-        },
-        || {
-            rayon::join(
-                || check_unreachable(&stmts, &filename, &source),
-                || {
-                    rayon::join(
-                        || check_dead_branches(&stmts, &filename, &source),
-                        || {
-                            rayon::join(
-                                || check_unused_arguments(&stmts, &filename, &source),
-                                || check_unused_loop_vars(&stmts, &filename, &source),
-                            )
-                        },
-                    )
-                },
-            )
-        },
-    );
+    // Every checker only reads `stmts`, `source` and `lines`, so they are
+    // independent.  Rayon is opportunistic: if the outer file-level par_iter
+    // has already saturated the thread pool, the checkers run sequentially on
+    // the calling thread with negligible overhead.  When spare threads exist (e.g. when
+    // analysing a single large file) the work is stolen and runs truly in
+    // parallel.
+    type Checker<'a> = &'a (dyn Fn() -> Vec<Diagnostic> + Sync);
+    let checkers: [Checker; 28] = [
+        &|| check_unused_imports(&stmts, &filename, &source, &lines, config),
+        &|| check_unused_variables(&stmts, &filename, &lines, config),
+        &|| check_unreachable(&stmts, &filename, &lines),
+        &|| check_loop_control(&stmts, &filename, &lines),
+        &|| check_dead_branches(&stmts, &filename, &source, &lines, config.python_version),
+        &|| check_unused_arguments(&stmts, &filename, &lines, config),
+        &|| check_unused_loop_vars(&stmts, &filename, &lines),
+        &|| check_unused_except_vars(&stmts, &filename, &lines),
+        &|| check_unused_with_targets(&stmts, &filename, &lines),
+        &|| check_shadowed_builtins(&stmts, &filename, &lines),
+        &|| check_redundant_else(&stmts, &filename, &source, &lines),
+        &|| check_undefined_exports(&stmts, &filename, &lines, config),
+        &|| check_duplicate_exports(&stmts, &filename, &lines),
+        &|| check_return_consistency(&stmts, &filename, &lines),
+        &|| check_loop_var_after_loop(&stmts, &filename, &lines),
+        &|| check_scope_declarations(&stmts, &filename, &lines),
+        &|| check_unused_globals(&stmts, &filename, &lines),
+        &|| check_annotation_imports(&stmts, &filename, &lines),
+        &|| check_annotation_only_imports(&stmts, &filename, &lines, config.python_version),
+        &|| check_try_else(&stmts, &filename, &lines),
+        &|| check_string_format(&stmts, &filename, &lines),
+        &|| check_pointless_fstrings(&stmts, &filename, &lines),
+        &|| check_implicit_concatenation(&stmts, &filename, &lines),
+        &|| check_eq_without_hash(&stmts, &filename, &lines),
+        &|| check_exception_message(&stmts, &filename, &lines),
+        &|| check_self_assign(&stmts, &filename, &source, &lines),
+        &|| check_print_statements(&stmts, &filename, &source, &lines),
+        &|| check_suppressed_assignments(&stmts, &filename, &lines),
+    ];
+    let mut diags: Vec<Diagnostic> = checkers.par_iter().flat_map_iter(|check| check()).collect();
 
     // In __init__.py and conftest.py, top-level imports are re-exports or
     // pytest-injected fixtures consumed by other files.  Suppress RP001
     // (unused import) only — RP007 (redefined-before-use) still fires.
-    let d_imports: Vec<Diagnostic> = if is_reexport_file(&filename) {
-        d_imports_raw
-            .into_iter()
-            .filter(|d| d.code != RuleCode::UnusedImport)
-            .collect()
-    } else {
-        d_imports_raw
-    };
-
-    let mut diags = Vec::with_capacity(
-        d_imports.len()
-            + d_vars.len()
-            + d_unreachable.len()
-            + d_dead.len()
-            + d_args.len()
-            + d_loop.len(),
-    );
-    diags.extend(d_imports);
-    diags.extend(d_vars);
-    diags.extend(d_unreachable);
-    diags.extend(d_dead);
-    diags.extend(d_args);
-    diags.extend(d_loop);
+    if is_reexport_file(&filename) {
+        diags.retain(|d| d.code != RuleCode::UnusedImport);
+    }
 
     // ── Collect module-level defs + name usages ───────────────────────────────
     //
     // collect_module_defs and collect_stmt_names both only read `stmts`.
     // We run them sequentially here because `stmts` borrows from `source`
     // (a local) which Rayon's scoped join cannot easily cross.
-    let module_defs = collect_module_defs(&stmts, &filename);
+    let module_defs = collect_module_defs(&stmts, &filename, config);
     let module_usages: HashSet<String> = {
         let mut u = HashSet::new();
         collect_stmt_names(&stmts, &mut u);
         // Names exported via __all__ are publicly visible to other modules —
         // treat them as "used" so they are never flagged as dead code.
         u.extend(collect_dunder_all(&stmts));
+        // Legacy `# type: T` comments reference names too.
+        collect_type_comment_names(&source, &mut u);
+        // Flask views registered with `app.add_url_rule(view_func=…)`.
+        collect_url_rule_views(&source, &mut u);
+        // Configured `always_used` names are referenced by machinery we
+        // cannot see, in every file.
+        u.extend(config.always_used.iter().cloned());
         u
     };
+    let mut attr_usages = HashSet::new();
+    collect_attribute_names(&source, &mut attr_usages);
+    let reexports = if filename.ends_with("__init__.py") {
+        collect_reexports(&stmts)
+    } else {
+        Vec::new()
+    };
 
     Ok(FileAnalysis {
         diags,
         module_defs,
         module_usages,
+        attr_usages,
+        reexports,
         source,
+        lines,
         filename,
     })
 }
 
 // ── noqa filtering ───────────────────────────────────────────────────────────
 
-/// Remove diagnostics that are suppressed by a `# noqa` comment on the same line.
+/// Remove diagnostics that are suppressed by a comment.
+///
+/// Supported forms, on the diagnostic's own line:
+/// - `# noqa`                        — suppresses every rule on that line
+/// - `# noqa: RP001`                 — suppresses only RP001
+/// - `# noqa: RP001,RP002`           — suppresses RP001 and RP002
+/// - `# reaper: disable`             — like a bare `# noqa`
+/// - `# reaper: disable=RP001,RP002` — like `# noqa: RP001,RP002`
 ///
-/// Supported forms:
-/// - `# noqa`              — suppresses every rule on that line
-/// - `# noqa: RP001`       — suppresses only RP001
-/// - `# noqa: RP001,RP002` — suppresses RP001 and RP002
-fn filter_noqa(diags: Vec<Diagnostic>, source_map: &HashMap<String, String>) -> Vec<Diagnostic> {
+/// On a line of its own, `# reaper: disable[=…]` turns rules off from there
+/// to the end of the file (so at the top, for the whole file) and
+/// `# reaper: enable[=…]` turns them back on.
+///
+/// With `respect_type_ignore`, `# type: ignore` (with or without a
+/// `[code]` list) also suppresses every rule on its line.
+fn filter_noqa(
+    diags: Vec<Diagnostic>,
+    source_map: &HashMap<String, String>,
+    respect_type_ignore: bool,
+) -> Vec<Diagnostic> {
+    // Own-line directives, parsed once for each file with diagnostics.
+    let files: HashSet<&String> = diags
+        .iter()
+        .filter_map(|d| source_map.get_key_value(&d.file))
+        .map(|(file, _)| file)
+        .collect();
+    let directives: HashMap<&String, Vec<(usize, Directive)>> = files
+        .into_par_iter()
+        .map(|f| (f, block_directives(&source_map[f])))
+        .collect();
     // Diagnostic is Send (contains only String + usize + RuleCode), and
     // source_map is a shared immutable reference (HashMap<String,String>: Sync),
     // so we can filter in parallel with no unsafe code.
@@ -251,14 +429,41 @@ fn filter_noqa(diags: Vec<Diagnostic>, source_map: &HashMap<String, String>) ->
         .filter(|d| {
             source_map
                 .get(&d.file)
-                .map(|src| !is_suppressed_by_noqa(src, d.line, &d.code))
+                .map(|src| {
+                    let blocks = directives.get(&d.file).map_or(&[][..], Vec::as_slice);
+                    !is_suppressed(src, d.line, &d.code, respect_type_ignore, blocks)
+                })
                 .unwrap_or(true)
         })
         .collect()
 }
 
-fn is_suppressed_by_noqa(source: &str, line: usize, code: &RuleCode) -> bool {
+fn is_suppressed(
+    source: &str,
+    line: usize,
+    code: &RuleCode,
+    respect_type_ignore: bool,
+    blocks: &[(usize, Directive)],
+) -> bool {
+    let code_str = code.to_string();
+    // The last own-line directive above `line` that covers `code` decides.
+    let mut disabled = false;
+    for (_, d) in blocks.iter().take_while(|(at, _)| *at < line) {
+        if d.covers(&code_str) {
+            disabled = !d.enable;
+        }
+    }
+    if disabled {
+        return true;
+    }
+
     let line_content = source.lines().nth(line.saturating_sub(1)).unwrap_or("");
+    if respect_type_ignore && line_content.contains("# type: ignore") {
+        return true;
+    }
+    if parse_directive(line_content).is_some_and(|d| !d.enable && d.covers(&code_str)) {
+        return true;
+    }
     let Some(idx) = line_content.find("# noqa") else {
         return false;
     };
@@ -268,10 +473,59 @@ fn is_suppressed_by_noqa(source: &str, line: usize, code: &RuleCode) -> bool {
         return true;
     }
     // `# noqa: CODE[,CODE…]` — suppresses the listed codes.
-    let code_str = code.to_string();
     after[1..].split(',').any(|c| c.trim() == code_str)
 }
 
+/// A `# reaper: disable[=CODES]` or `# reaper: enable[=CODES]` comment.
+struct Directive {
+    enable: bool,
+    /// The listed codes, or `None` for every rule.
+    codes: Option<Vec<String>>,
+}
+
+impl Directive {
+    fn covers(&self, code: &str) -> bool {
+        self.codes
+            .as_ref()
+            .is_none_or(|codes| codes.iter().any(|c| c == code))
+    }
+}
+
+/// The directive in `line_content`, if it has one.
+fn parse_directive(line_content: &str) -> Option<Directive> {
+    let idx = line_content.find("# reaper:")?;
+    let rest = line_content[idx + 9..].trim_start();
+    let (enable, rest) = if let Some(rest) = rest.strip_prefix("disable") {
+        (false, rest)
+    } else {
+        (true, rest.strip_prefix("enable")?)
+    };
+    let rest = rest.trim();
+    let codes = if rest.is_empty() {
+        None
+    } else {
+        let list = rest.strip_prefix('=')?;
+        Some(
+            list.split(',')
+                .map(|c| c.trim().to_ascii_uppercase())
+                .filter(|c| !c.is_empty())
+                .collect(),
+        )
+    };
+    Some(Directive { enable, codes })
+}
+
+/// Directives written on a line of their own, with their 1-based line
+/// numbers, in source order.
+fn block_directives(source: &str) -> Vec<(usize, Directive)> {
+    source
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| l.trim_start().starts_with('#'))
+        .filter_map(|(i, l)| Some((i + 1, parse_directive(l)?)))
+        .collect()
+}
+
 // ── tests ────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -310,6 +564,15 @@ mod tests {
         assert_eq!(diags.len(), 0);
     }
 
+    #[test]
+    fn test_truncated_file_reports_nothing_before_the_break() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("cut.py");
+        fs::write(&path, "import os\nimport sys\n\ndef f():\n    return [1,\n").unwrap();
+        let diags = analyze_files(&[path]).unwrap();
+        assert!(diags.is_empty(), "got: {diags:?}");
+    }
+
     // ── cross-file analysis ──────────────────────────────────────────────────
 
     #[test]
@@ -379,6 +642,41 @@ mod tests {
         assert_eq!(rp003.len(), 0);
     }
 
+    #[test]
+    fn test_always_used_prevents_rp003() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("handlers.py");
+        fs::write(&path, "def helper():\n    return 1\n").unwrap();
+
+        let diags = analyze_files(std::slice::from_ref(&path)).unwrap();
+        assert_eq!(diags.len(), 1, "without config `helper` is flagged");
+        assert_eq!(diags[0].code, RuleCode::UnusedFunction);
+
+        let config = ReaperConfig {
+            always_used: vec!["helper".to_string()],
+            ..ReaperConfig::default()
+        };
+        let diags = analyze_files_with_config(&[path], &config).unwrap();
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_always_used_prevents_rp001_and_rp002() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("views.py");
+        fs::write(
+            &path,
+            "import urls\n\ndef view():\n    handler = 1\n    return 0\n\nview()\n",
+        )
+        .unwrap();
+        let config = ReaperConfig {
+            always_used: vec!["handler".to_string(), "urls".to_string()],
+            ..ReaperConfig::default()
+        };
+        let diags = analyze_files_with_config(&[path], &config).unwrap();
+        assert_eq!(diags.len(), 0, "got: {diags:?}");
+    }
+
     // ── noqa suppression ────────────────────────────────────────────────────
 
     #[test]
@@ -419,6 +717,105 @@ mod tests {
         assert_eq!(diags.len(), 0);
     }
 
+    #[test]
+    fn test_type_ignore_suppresses_only_when_respected() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("t.py");
+        fs::write(&path, "import os  # type: ignore\n").unwrap();
+        let diags = analyze_files(std::slice::from_ref(&path)).unwrap();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::UnusedImport);
+
+        let config = ReaperConfig {
+            respect_type_ignore: true,
+            ..ReaperConfig::default()
+        };
+        let diags = analyze_files_with_config(&[path], &config).unwrap();
+        assert_eq!(diags.len(), 0, "got: {diags:?}");
+    }
+
+    #[test]
+    fn test_type_ignore_with_codes_suppresses_all() {
+        let src = "import os  # type: ignore[import]\n";
+        assert!(is_suppressed(src, 1, &RuleCode::UnusedImport, true, &[]));
+        assert!(!is_suppressed(src, 1, &RuleCode::UnusedImport, false, &[]));
+    }
+
+    #[test]
+    fn test_reaper_disable_on_the_line() {
+        let src = "import os  # reaper: disable=RP001, RP002\nimport re  # reaper: disable\n";
+        assert!(is_suppressed(src, 1, &RuleCode::UnusedImport, false, &[]));
+        assert!(!is_suppressed(
+            src,
+            1,
+            &RuleCode::RedefinedUnused,
+            false,
+            &[]
+        ));
+        assert!(is_suppressed(
+            src,
+            2,
+            &RuleCode::RedefinedUnused,
+            false,
+            &[]
+        ));
+    }
+
+    #[test]
+    fn test_reaper_disable_and_enable_blocks() {
+        let src = "# reaper: disable=RP001\nimport os\n# reaper: enable=RP001\nimport re\n";
+        let blocks = block_directives(src);
+        assert_eq!(blocks.len(), 2);
+        assert!(is_suppressed(
+            src,
+            2,
+            &RuleCode::UnusedImport,
+            false,
+            &blocks
+        ));
+        assert!(!is_suppressed(
+            src,
+            2,
+            &RuleCode::RedefinedUnused,
+            false,
+            &blocks
+        ));
+        assert!(!is_suppressed(
+            src,
+            4,
+            &RuleCode::UnusedImport,
+            false,
+            &blocks
+        ));
+
+        // A bare `disable` covers every rule; `enable=CODE` lifts just one.
+        let src = "# reaper: disable\n# reaper: enable=RP002\nx = 1\n";
+        let blocks = block_directives(src);
+        assert!(is_suppressed(
+            src,
+            3,
+            &RuleCode::UnusedImport,
+            false,
+            &blocks
+        ));
+        assert!(!is_suppressed(
+            src,
+            3,
+            &RuleCode::UnusedVariable,
+            false,
+            &blocks
+        ));
+    }
+
+    #[test]
+    fn test_trailing_reaper_comment_is_not_a_block_directive() {
+        let src = "import os  # reaper: disable=RP001\nimport re\n";
+        assert!(block_directives(src).is_empty());
+        assert!(!is_suppressed(src, 2, &RuleCode::UnusedImport, false, &[]));
+        assert!(parse_directive("# reaper: disabled").is_none());
+        assert!(parse_directive("# reaper: enable RP001").is_none());
+    }
+
     // ── framework-aware exemptions ───────────────────────────────────────────
 
     #[test]
@@ -444,6 +841,127 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_dunder_all_reexport_chain_keeps_submodule_def() {
+        // `pkg/__init__.py` re-exports `helper` from `pkg/submod.py` and lists
+        // it in `__all__`; nothing else calls it, but it is public API.
+        let dir = TempDir::new().unwrap();
+        let pkg = dir.path().join("pkg");
+        fs::create_dir(&pkg).unwrap();
+        fs::write(
+            pkg.join("__init__.py"),
+            "from .submod import helper\n\n__all__ = [\"helper\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            pkg.join("submod.py"),
+            "def helper():\n    return 1\n\ndef orphan():\n    return 2\n",
+        )
+        .unwrap();
+        let files = vec![pkg.join("__init__.py"), pkg.join("submod.py")];
+        let diags = analyze_files(&files).unwrap();
+        let rp003: Vec<_> = diags
+            .iter()
+            .filter(|d| d.code == RuleCode::UnusedFunction)
+            .collect();
+        assert_eq!(rp003.len(), 1, "got: {diags:?}");
+        assert!(rp003[0].message.contains("`orphan`"));
+        assert!(rp003[0].file.ends_with("submod.py"));
+        assert!(
+            !diags.iter().any(|d| d.file.ends_with("__init__.py")),
+            "got: {diags:?}"
+        );
+    }
+
+    #[test]
+    fn test_aliased_reexport_chain_keeps_nested_def() {
+        // `Api` is public; it is `Client` one level down and `Helper` two
+        // levels down, where it is defined.
+        let dir = TempDir::new().unwrap();
+        let pkg = dir.path().join("pkg");
+        let deep = pkg.join("sub").join("deep");
+        fs::create_dir_all(&deep).unwrap();
+        let files = vec![
+            pkg.join("__init__.py"),
+            pkg.join("sub").join("__init__.py"),
+            deep.join("__init__.py"),
+            deep.join("impl.py"),
+        ];
+        fs::write(&files[0], "from .sub import Api\n\n__all__ = [\"Api\"]\n").unwrap();
+        fs::write(&files[1], "from .deep import Client as Api\n").unwrap();
+        fs::write(&files[2], "from .impl import Helper as Client\n").unwrap();
+        fs::write(
+            &files[3],
+            "class Helper:\n    pass\n\nclass Orphan:\n    pass\n",
+        )
+        .unwrap();
+        let diags = analyze_files(&files).unwrap();
+        let rp004: Vec<_> = diags
+            .iter()
+            .filter(|d| d.code == RuleCode::UnusedClass)
+            .collect();
+        assert_eq!(rp004.len(), 1, "got: {diags:?}");
+        assert!(rp004[0].message.contains("`Orphan`"));
+    }
+
+    #[test]
+    fn test_unchanged_file_uses_cache() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app.py");
+        fs::write(&path, "import os\n\ndef helper():\n    pass\n").unwrap();
+        let cache_path = dir.path().join("cache.json");
+        let config = ReaperConfig {
+            cache_path: Some(cache_path.clone()),
+            ..ReaperConfig::default()
+        };
+        let files = vec![path.clone()];
+        let first = analyze_files_with_config(&files, &config).unwrap();
+        assert_eq!(first.len(), 2, "got: {first:?}");
+
+        // Tamper with the cached RP001 message: a run that shows it did not
+        // re-analyse the file.
+        let text = fs::read_to_string(&cache_path).unwrap();
+        assert!(text.contains("`os` imported but unused"));
+        fs::write(
+            &cache_path,
+            text.replace("`os` imported", "`os` (cached) imported"),
+        )
+        .unwrap();
+        let second = analyze_files_with_config(&files, &config).unwrap();
+        assert!(
+            second.iter().any(|d| d.message.contains("(cached)")),
+            "got: {second:?}"
+        );
+        // RP003 comes from pass 2 and is still reported.
+        assert!(second.iter().any(|d| d.code == RuleCode::UnusedFunction));
+
+        // Editing the file invalidates its entry.
+        fs::write(&path, "import os\nimport sys\n\ndef helper():\n    pass\n").unwrap();
+        let third = analyze_files_with_config(&files, &config).unwrap();
+        assert_eq!(third.len(), 3, "got: {third:?}");
+        assert!(!third.iter().any(|d| d.message.contains("(cached)")));
+    }
+
+    #[test]
+    fn test_cached_defs_still_meet_new_usages() {
+        // `helper` is cached as unused in lib.py; a new file that calls it
+        // clears RP003 without lib.py being re-analysed.
+        let dir = TempDir::new().unwrap();
+        let lib = dir.path().join("lib.py");
+        fs::write(&lib, "def helper():\n    pass\n").unwrap();
+        let config = ReaperConfig {
+            cache_path: Some(dir.path().join("cache.json")),
+            ..ReaperConfig::default()
+        };
+        let diags = analyze_files_with_config(std::slice::from_ref(&lib), &config).unwrap();
+        assert_eq!(diags.len(), 1);
+
+        let main = dir.path().join("main.py");
+        fs::write(&main, "from lib import helper\nhelper()\n").unwrap();
+        let diags = analyze_files_with_config(&[lib, main], &config).unwrap();
+        assert!(diags.is_empty(), "got: {diags:?}");
+    }
+
     #[test]
     fn test_init_py_redefined_import_still_flagged() {
         // RP007 (redefined-before-use) must still fire inside __init__.py.
@@ -39,9 +39,41 @@ pub struct ExprInfo<'src> {
     pub kind: ExprKind<'src>,
 
     /// String literals found inside list/tuple brackets, e.g. the `["foo", "bar"]`
-    /// in `__all__ = ["foo", "bar"]`.  Used by `collect_dunder_all` to extract
-    /// exported names without needing a full recursive expression tree.
-    pub string_list: Vec<String>,
+    /// in `__all__ = ["foo", "bar"]`, with the offset of each literal.  Used by
+    /// `collect_dunder_all` to extract exported names without needing a full
+    /// recursive expression tree.
+    pub string_list: Vec<(String, Offset)>,
+
+    /// String literals formatted where they are written — `"{} {}".format(a)`
+    /// or `"%s" % (a,)` — when the number of positional arguments is known.
+    pub format_calls: Vec<FormatCall>,
+
+    /// Offsets of f-strings with no replacement fields, e.g. `f"hello"`.
+    pub plain_fstrings: Vec<Offset>,
+
+    /// Offsets of string literals implicitly joined to the literal before
+    /// them inside brackets — the `"b"` in `("a" "b")`.  One per run.
+    pub implicit_concats: Vec<Offset>,
+}
+
+/// A string literal followed directly by `.format(…)` or `% operand`.
+#[derive(Debug, Clone)]
+pub struct FormatCall {
+    pub style: FormatStyle,
+    /// The decoded literal.
+    pub template: String,
+    /// Number of positional arguments supplied.
+    pub args: usize,
+    /// Byte offset of the string literal.
+    pub offset: Offset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatStyle {
+    /// `str.format` replacement fields: `{}`, `{0}`, `{name}`.
+    Brace,
+    /// printf-style conversions: `%s`, `%d`.
+    Percent,
 }
 
 /// Top-level "shape" of an expression — only the patterns checkers care about.
@@ -60,6 +92,9 @@ pub enum ExprKind<'src> {
     EllipsisLit,
     /// `obj.attr` — used to detect `@abstractmethod` / `@abc.abstractmethod`.
     Attr(&'src str, &'src str),
+    /// A call on a bare name: `foo(a, b=1)`.  Only the callee and the number
+    /// of arguments are kept.
+    Call { func: &'src str, args_count: usize },
     /// Anything more complex.
     #[default]
     Other,
@@ -106,6 +141,8 @@ pub struct ArgDef<'src> {
     pub offset: Offset,
     /// Annotation expression (for usage tracking — annotation names are usages).
     pub annotation: Option<ExprInfo<'src>>,
+    /// Default value expression (`= expr`), if present.  Its names are usages.
+    pub default: Option<ExprInfo<'src>>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -152,6 +189,8 @@ pub struct ExceptHandler<'src> {
     pub type_expr: Option<ExprInfo<'src>>,
     pub body: Vec<Stmt<'src>>,
     pub offset: Offset,
+    /// `except* E:` (PEP 654) — the name binds an `ExceptionGroup`.
+    pub is_star: bool,
 }
 
 // ── with items ────────────────────────────────────────────────────────────────
@@ -168,13 +207,18 @@ pub struct WithItem<'src> {
 /// One arm of a `match` statement (`case <pattern> [if <guard>]: <body>`).
 ///
 /// Because Python's pattern-matching syntax is complex, we do not try to parse
-/// the pattern into a structured form.  Instead we collect every `Name` token
-/// found in the case header (pattern + optional guard) conservatively — this
-/// over-approximates usages, which is safe for our dead-code checks.
+/// the pattern into a structured form.  Instead the case header is scanned
+/// token by token and its names are split into reads and capture bindings.
 #[derive(Debug, Clone)]
 pub struct MatchArm<'src> {
-    /// All names found in the `case` header (pattern and guard expression).
+    /// Names *read* by the `case` header: class names, dotted value patterns
+    /// and everything in the guard expression.
     pub pattern_names: Vec<(&'src str, Offset)>,
+    /// Capture names bound by the pattern: `x` in `case [x, *rest]:`, `px`
+    /// in `case Point(x=px):`, `y` in `case 1 as y:`.  `_` is never bound.
+    pub bindings: Vec<(&'src str, Offset)>,
+    /// `case _:` or `case name:` with no guard — matches every subject.
+    pub irrefutable: bool,
     /// Body statements of this arm.
     pub body: Vec<Stmt<'src>>,
 }
@@ -224,6 +268,11 @@ pub enum StmtKind<'src> {
         target: AssignTarget<'src>,
         value: ExprInfo<'src>,
     },
+    /// `type Name[params] = expr` (Python 3.12).  Type parameters are skipped.
+    TypeAlias {
+        name: &'src str,
+        value: ExprInfo<'src>,
+    },
 
     // ── control flow ────────────────────────────────────────────────────────
     /// `for target in iter: body [else: orelse]`
@@ -48,6 +48,116 @@ const RULES: &[(&str, &str, &str)] = &[
         "def f(x, y): return x  # y unused",
     ),
     ("RP009", "Unused loop variable", "for _ in items: pass"),
+    ("RP010", "Unused exception variable", "except E as e: pass"),
+    ("RP011", "Unused with target", "with open(p) as fh: pass"),
+    ("RP012", "Shadowed builtin", "list = [1, 2]"),
+    ("RP013", "Redundant else", "if x: return 1\nelse: ..."),
+    (
+        "RP014",
+        "Unused class variable",
+        "class C:\n    retries = 3",
+    ),
+    (
+        "RP015",
+        "Dead version branch",
+        "if sys.version_info < (3, 8): ...",
+    ),
+    (
+        "RP016",
+        "Unused global declaration",
+        "def f(): global x; return 1",
+    ),
+    (
+        "RP047",
+        "Star import",
+        "from os import *  # outside __init__",
+    ),
+    (
+        "RP048",
+        "Undefined __all__ entry",
+        "__all__ = [\"render\"]  # never defined",
+    ),
+    (
+        "RP049",
+        "__init__ param not stored",
+        "def __init__(self, y): ...  # no self.y",
+    ),
+    (
+        "RP050",
+        "Inconsistent return",
+        "if x: return 1  # else falls off the end",
+    ),
+    (
+        "RP051",
+        "Unused override parameter",
+        "def __contains__(self, item): return True",
+    ),
+    (
+        "RP052",
+        "Loop variable used after loop",
+        "for i in xs: ...; print(i)  # xs empty?",
+    ),
+    (
+        "RP053",
+        "Undefined nonlocal name",
+        "def g(): nonlocal x  # no `x` in outer",
+    ),
+    ("RP054", "Global declared after use", "x = 1; global x"),
+    (
+        "RP055",
+        "Unimported annotation name",
+        "def f(x: List[int])  # no typing import",
+    ),
+    (
+        "RP056",
+        "Re-import after conditional import",
+        "import json  # if/else above both import it",
+    ),
+    (
+        "RP057",
+        "Unnecessary try else",
+        "try: a() / except E: ... / else: b()",
+    ),
+    ("RP058", "Throwaway name used", "_ = compute(); print(_)"),
+    (
+        "RP059",
+        "Import only used in annotations",
+        "from typing import Optional  # only in hints",
+    ),
+    (
+        "RP061",
+        "Import shadowed by local",
+        "import json; def f(): json = {}",
+    ),
+    ("RP062", "Duplicate export", "__all__ = [\"foo\", \"foo\"]"),
+    ("RP063", "Empty try body", "try: pass; except E: handle()"),
+    ("RP064", "Loop control outside loop", "def f(): continue"),
+    ("RP065", "Format argument mismatch", "\"{} {}\".format(a)"),
+    (
+        "RP066",
+        "F-string without placeholders",
+        "x = f\"hello world\"",
+    ),
+    (
+        "RP067",
+        "Implicit string concatenation",
+        "[\"alice\", \"bob\" \"carol\"]",
+    ),
+    (
+        "RP068",
+        "Eq without hash",
+        "class P: def __eq__(self, o): ...",
+    ),
+    ("RP069", "Exception without message", "raise ValueError()"),
+    ("RP070", "Explicit re-raise", "except E as e: raise e"),
+    ("RP071", "Self assignment", "obj.attr = obj.attr"),
+    ("RP072", "Print statement", "print \"hello\""),
+    ("RP073", "Global builtin", "def f(): global list"),
+    (
+        "RP074",
+        "Possibly unbound after with",
+        "with suppress(E): x = f()",
+    ),
 ];
 
 // ── Helpers ───────────────────────────────────────────────────────────────────
@@ -228,3 +338,23 @@ fn static_welcome() {
     println!("          reaper --help                     full help text");
     println!();
 }
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RuleCode;
+
+    #[test]
+    fn test_every_rule_in_catalogue() {
+        for rule in RuleCode::ALL {
+            let code = rule.to_string();
+            assert!(
+                RULES.iter().any(|(c, _, _)| *c == code),
+                "{code} missing from RULES"
+            );
+        }
+        assert_eq!(RULES.len(), RuleCode::ALL.len());
+    }
+}
@@ -0,0 +1,181 @@
+//! Incremental analysis: per-file results cached by modification time.
+//!
+//! The cache is one JSON file (by default `~/.cache/reaper/cache.json`)
+//! mapping each analysed file to its modification time and what pass 1
+//! produced for it: the per-file diagnostics and the definitions and usages
+//! that feed cross-file analysis.  An unchanged file is not parsed again.
+//! Cross-file diagnostics (RP003, RP004, RP014) are never cached; pass 2
+//! recomputes them from the cached definitions and usages of every file.
+//!
+//! Entries are only valid for the reaper version and settings that produced
+//! them.  A cache written under different ones is discarded as a whole.
+
+use crate::checks::unused_defs::ModuleDef;
+use crate::config::ReaperConfig;
+use crate::types::Diagnostic;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Pass-1 results for one file, and the file state they belong to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    /// Modification time in nanoseconds since the Unix epoch.
+    pub mtime: u64,
+    /// File size in bytes, in case an edit keeps the modification time.
+    pub len: u64,
+    pub diags: Vec<Diagnostic>,
+    pub module_defs: Vec<ModuleDef>,
+    pub module_usages: HashSet<String>,
+    pub attr_usages: HashSet<String>,
+    pub reexports: Vec<(String, String)>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Cache {
+    /// See [`Cache::key`].
+    key: String,
+    files: HashMap<PathBuf, Entry>,
+}
+
+impl Cache {
+    /// What entries depend on besides the file itself: the reaper version
+    /// and every analysis setting.
+    pub fn key(config: &ReaperConfig) -> String {
+        let config = ReaperConfig {
+            cache_path: None,
+            ..config.clone()
+        };
+        format!("{} {config:?}", env!("CARGO_PKG_VERSION"))
+    }
+
+    /// Read the cache at `path`.  A missing or unreadable file, or one
+    /// written with another [`Cache::key`], gives an empty cache.
+    pub fn load(path: &Path, key: &str) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|text| serde_json::from_str::<Cache>(&text).ok())
+            .filter(|cache| cache.key == key)
+            .unwrap_or_else(|| Cache {
+                key: key.to_string(),
+                files: HashMap::new(),
+            })
+    }
+
+    /// The entry for `path` when the file has not changed since it was
+    /// stored.
+    pub fn get(&self, path: &Path) -> Option<&Entry> {
+        let (mtime, len) = file_state(path)?;
+        self.files
+            .get(&canonical(path))
+            .filter(|e| e.mtime == mtime && e.len == len)
+    }
+
+    /// Store pass-1 results for `path`, stamped with its current state.
+    pub fn insert(&mut self, path: &Path, make: impl FnOnce(u64, u64) -> Entry) {
+        if let Some((mtime, len)) = file_state(path) {
+            self.files.insert(canonical(path), make(mtime, len));
+        }
+    }
+
+    /// Write the cache to `path`, dropping entries for files that no longer
+    /// exist.  The file is replaced atomically, so concurrent runs never see
+    /// a partial write.
+    pub fn save(&mut self, path: &Path) -> Result<()> {
+        self.files.retain(|file, _| file.exists());
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("cannot create {}", dir.display()))?;
+        }
+        let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        std::fs::write(&tmp, serde_json::to_vec(self)?)
+            .with_context(|| format!("cannot write {}", tmp.display()))?;
+        std::fs::rename(&tmp, path).with_context(|| format!("cannot write {}", path.display()))
+    }
+}
+
+/// `$XDG_CACHE_HOME/reaper/cache.json`, else `~/.cache/reaper/cache.json`.
+pub fn default_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+    Some(base.join("reaper").join("cache.json"))
+}
+
+fn file_state(path: &Path) -> Option<(u64, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((mtime.as_nanos() as u64, meta.len()))
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(mtime: u64, len: u64) -> Entry {
+        Entry {
+            mtime,
+            len,
+            diags: Vec::new(),
+            module_defs: Vec::new(),
+            module_usages: HashSet::from(["helper".to_string()]),
+            attr_usages: HashSet::new(),
+            reexports: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_and_invalidation() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("a.py");
+        let cache_file = dir.path().join("cache").join("cache.json");
+        std::fs::write(&file, "x = 1\n").unwrap();
+
+        let mut cache = Cache::load(&cache_file, "k");
+        assert!(cache.get(&file).is_none());
+        cache.insert(&file, entry);
+        cache.save(&cache_file).unwrap();
+
+        let cache = Cache::load(&cache_file, "k");
+        assert!(cache.get(&file).unwrap().module_usages.contains("helper"));
+        // Another version or configuration discards everything.
+        assert!(Cache::load(&cache_file, "other").get(&file).is_none());
+
+        std::fs::write(&file, "x = 10\n").unwrap();
+        assert!(cache.get(&file).is_none(), "a changed file is a miss");
+    }
+
+    #[test]
+    fn test_corrupt_cache_is_empty() {
+        let dir = TempDir::new().unwrap();
+        let cache_file = dir.path().join("cache.json");
+        std::fs::write(&cache_file, "{not json").unwrap();
+        let mut cache = Cache::load(&cache_file, "k");
+        assert!(cache.files.is_empty());
+        cache.save(&cache_file).unwrap();
+        assert!(Cache::load(&cache_file, "k").files.is_empty());
+    }
+
+    #[test]
+    fn test_save_drops_deleted_files() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("gone.py");
+        let cache_file = dir.path().join("cache.json");
+        std::fs::write(&file, "").unwrap();
+        let mut cache = Cache::load(&cache_file, "k");
+        cache.insert(&file, entry);
+        std::fs::remove_file(&file).unwrap();
+        cache.save(&cache_file).unwrap();
+        assert!(Cache::load(&cache_file, "k").files.is_empty());
+    }
+}
@@ -0,0 +1,354 @@
+use crate::ast::{ExprInfo, Stmt, StmtKind};
+use crate::builtins::is_builtin;
+use crate::location::LineIndex;
+use crate::names::{collect_dunder_all, collect_module_bindings, collect_runtime_names};
+use crate::types::{Diagnostic, RuleCode};
+use std::collections::HashSet;
+
+/// RP055: a name in a parameter, return or variable annotation that nothing
+/// in the file imports or defines — `def f(x: List[int])` without
+/// `from typing import List` raises `NameError` when the `def` runs.
+///
+/// A name bound anywhere in the file (any scope) counts as defined, so only
+/// names that cannot resolve at all are reported.  A star import makes the
+/// module's names unknowable and disables the check.
+pub fn check_annotation_imports<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut bound = HashSet::new();
+    if !collect_all_bindings(stmts, &mut bound) {
+        return Vec::new();
+    }
+    let mut annotations = Vec::new();
+    collect_annotations(stmts, &mut annotations);
+
+    let mut diags = Vec::new();
+    for info in annotations {
+        for &(name, offset) in &info.names {
+            if bound.contains(name) || is_builtin(name) {
+                continue;
+            }
+            let (line, col) = lines.offset_to_line_col(offset as usize);
+            diags.push(Diagnostic {
+                file: filename.to_string(),
+                line,
+                col,
+                start_byte: offset as usize,
+                end_byte: offset as usize + name.len(),
+                code: RuleCode::UnimportedAnnotation,
+                message: format!("Name `{name}` used in annotation but not imported"),
+                fix: None,
+            });
+        }
+    }
+    diags
+}
+
+/// RP059: a module-level import whose name is read only inside annotations.
+/// Under `from __future__ import annotations` it can move into an
+/// `if TYPE_CHECKING:` block and cost nothing at runtime.
+///
+/// Only reported when annotations are evaluated lazily: the module has that
+/// `__future__` import, or `python_version` is 3.14 or later (PEP 649).
+/// Otherwise moving the import breaks every unquoted annotation using it.
+/// Names listed in `__all__` are re-exports and never reported.
+pub fn check_annotation_only_imports<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+    python_version: Option<(u32, u32)>,
+) -> Vec<Diagnostic> {
+    let lazy = python_version.is_some_and(|v| v >= (3, 14)) || has_future_annotations(stmts);
+    if !lazy {
+        return Vec::new();
+    }
+    let mut annotations = Vec::new();
+    collect_annotations(stmts, &mut annotations);
+    let in_annotations: HashSet<&str> = annotations
+        .iter()
+        .flat_map(|info| info.names.iter().map(|(n, _)| *n))
+        .collect();
+    if in_annotations.is_empty() {
+        return Vec::new();
+    }
+    let mut runtime = HashSet::new();
+    collect_runtime_names(stmts, &mut runtime);
+    runtime.extend(collect_dunder_all(stmts));
+
+    let mut diags = Vec::new();
+    for stmt in stmts {
+        let aliases = match &stmt.kind {
+            StmtKind::Import(aliases) => aliases,
+            StmtKind::ImportFrom { module, names, .. } if *module != Some("__future__") => names,
+            _ => continue,
+        };
+        for alias in aliases {
+            let local = alias
+                .asname
+                .unwrap_or_else(|| alias.name.split('.').next().unwrap_or(""));
+            if local == "*" || !in_annotations.contains(local) || runtime.contains(local) {
+                continue;
+            }
+            let (line, col) = lines.offset_to_line_col(alias.offset as usize);
+            diags.push(Diagnostic {
+                file: filename.to_string(),
+                line,
+                col,
+                start_byte: alias.offset as usize,
+                end_byte: alias.offset as usize + alias.name.len(),
+                code: RuleCode::AnnotationOnlyImport,
+                message: format!(
+                    "Import `{local}` is used only in type annotations; consider moving to `if TYPE_CHECKING:` block to avoid runtime overhead"
+                ),
+                fix: None,
+            });
+        }
+    }
+    diags
+}
+
+/// True when the module starts with `from __future__ import annotations`.
+fn has_future_annotations(stmts: &[Stmt<'_>]) -> bool {
+    stmts.iter().any(|stmt| {
+        matches!(&stmt.kind, StmtKind::ImportFrom { module: Some("__future__"), names, .. }
+            if names.iter().any(|a| a.name == "annotations"))
+    })
+}
+
+/// Bindings of every scope in the file, including parameters.  Returns
+/// `false` when a star import makes the set incomplete.
+fn collect_all_bindings<'src>(stmts: &[Stmt<'src>], out: &mut HashSet<&'src str>) -> bool {
+    let mut complete = collect_module_bindings(stmts, out);
+    for_each_nested_body(stmts, &mut |stmt| match &stmt.kind {
+        StmtKind::FunctionDef(f) => {
+            let args = &f.args;
+            out.extend(
+                args.posonlyargs
+                    .iter()
+                    .chain(&args.args)
+                    .chain(&args.vararg)
+                    .chain(&args.kwonlyargs)
+                    .chain(&args.kwarg)
+                    .map(|a| a.name),
+            );
+            complete &= collect_all_bindings(&f.body, out);
+        }
+        StmtKind::ClassDef(c) => complete &= collect_all_bindings(&c.body, out),
+        _ => {}
+    });
+    complete
+}
+
+/// Every annotation expression in the file, in every scope.
+fn collect_annotations<'a, 'src>(stmts: &'a [Stmt<'src>], out: &mut Vec<&'a ExprInfo<'src>>) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::AnnAssign { annotation, .. } => out.push(annotation),
+            StmtKind::FunctionDef(f) => {
+                let args = &f.args;
+                out.extend(
+                    args.posonlyargs
+                        .iter()
+                        .chain(&args.args)
+                        .chain(&args.vararg)
+                        .chain(&args.kwonlyargs)
+                        .chain(&args.kwarg)
+                        .filter_map(|a| a.annotation.as_ref()),
+                );
+                out.extend(&f.returns);
+                collect_annotations(&f.body, out);
+            }
+            StmtKind::ClassDef(c) => collect_annotations(&c.body, out),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                collect_annotations(body, out);
+                collect_annotations(orelse, out);
+            }
+            StmtKind::With { body, .. } => collect_annotations(body, out),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                collect_annotations(body, out);
+                for h in handlers {
+                    collect_annotations(&h.body, out);
+                }
+                collect_annotations(orelse, out);
+                collect_annotations(finalbody, out);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    collect_annotations(&arm.body, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Call `f` on every function and class definition directly in `stmts`,
+/// looking through compound statements but not into the definitions.
+fn for_each_nested_body<'src>(stmts: &[Stmt<'src>], f: &mut impl FnMut(&Stmt<'src>)) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::FunctionDef(_) | StmtKind::ClassDef(_) => f(stmt),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                for_each_nested_body(body, f);
+                for_each_nested_body(orelse, f);
+            }
+            StmtKind::With { body, .. } => for_each_nested_body(body, f),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                for_each_nested_body(body, f);
+                for h in handlers {
+                    for_each_nested_body(&h.body, f);
+                }
+                for_each_nested_body(orelse, f);
+                for_each_nested_body(finalbody, f);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    for_each_nested_body(&arm.body, f);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_annotation_imports(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_unimported_typing_name_fires() {
+        let diags = check("def f(x: List[int]):\n    pass\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::UnimportedAnnotation);
+        assert_eq!((diags[0].line, diags[0].col), (1, 10));
+        assert_eq!(
+            diags[0].message,
+            "Name `List` used in annotation but not imported"
+        );
+    }
+
+    #[test]
+    fn test_imported_name_clean() {
+        assert!(check("from typing import List\ndef f(x: List[int]):\n    pass\n").is_empty());
+        assert!(check("import typing\ndef f() -> typing.List[int]:\n    pass\n").is_empty());
+    }
+
+    #[test]
+    fn test_builtins_clean() {
+        assert!(check("def f(x: int, *a: str, **k: dict) -> list[bytes]:\n    pass\n").is_empty());
+    }
+
+    #[test]
+    fn test_locally_defined_names_clean() {
+        let src = "class Node:\n    def link(self, other: Node) -> Node:\n        pass\nAlias = int\ny: Alias = 1\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_return_and_variable_annotations_fire() {
+        assert_eq!(check("def f() -> Optional[int]:\n    pass\n").len(), 1);
+        assert_eq!(check("def f():\n    x: Dict = {}\n").len(), 1);
+    }
+
+    #[test]
+    fn test_type_checking_import_clean() {
+        let src = "from typing import TYPE_CHECKING\nif TYPE_CHECKING:\n    from pathlib import Path\ndef f(p: Path):\n    pass\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_star_import_disables_check() {
+        assert!(check("from typing import *\ndef f(x: List[int]):\n    pass\n").is_empty());
+    }
+
+    #[test]
+    fn test_string_annotation_ignored() {
+        assert!(check("def f(x: \"List[int]\"):\n    pass\n").is_empty());
+    }
+
+    fn check_only_on(src: &str, python_version: Option<(u32, u32)>) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_annotation_only_imports(
+            &stmts,
+            "test.py",
+            &LineIndex::from_source(src),
+            python_version,
+        )
+    }
+
+    /// RP059 under `from __future__ import annotations`.
+    fn check_only(src: &str) -> Vec<Diagnostic> {
+        check_only_on(&format!("from __future__ import annotations\n{src}"), None)
+    }
+
+    #[test]
+    fn test_import_used_only_in_parameter_annotation_fires() {
+        let diags = check_only("from typing import Optional\ndef f(x: Optional[int]):\n    pass\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::AnnotationOnlyImport);
+        assert_eq!((diags[0].line, diags[0].col), (2, 20));
+        assert!(
+            diags[0]
+                .message
+                .starts_with("Import `Optional` is used only in type annotations")
+        );
+    }
+
+    #[test]
+    fn test_import_used_only_in_variable_annotation_fires() {
+        assert_eq!(
+            check_only("from typing import Optional\nx: Optional[int] = None\n").len(),
+            1
+        );
+        assert_eq!(
+            check_only("import pathlib\ndef f() -> pathlib.Path:\n    pass\n").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_runtime_use_clean() {
+        assert!(check_only("from typing import cast\ny = cast(int, x)\n").is_empty());
+        let src = "from typing import Optional\nAlias = Optional[int]\ndef f(x: Optional[int]):\n    pass\n";
+        assert!(check_only(src).is_empty());
+    }
+
+    #[test]
+    fn test_eager_annotations_clean() {
+        let src = "from typing import Optional\ndef f(x: Optional[int]):\n    pass\n";
+        assert!(check_only_on(src, None).is_empty());
+        assert!(check_only_on(src, Some((3, 13))).is_empty());
+        assert_eq!(check_only_on(src, Some((3, 14))).len(), 1);
+    }
+
+    #[test]
+    fn test_unused_and_exported_imports_clean() {
+        assert!(check_only("from typing import Optional\n").is_empty());
+        let src = "from typing import Optional\n__all__ = [\"Optional\"]\ndef f(x: Optional[int]):\n    pass\n";
+        assert!(check_only(src).is_empty());
+    }
+}
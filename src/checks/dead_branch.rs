@@ -1,5 +1,5 @@
 use crate::ast::{ExprKind, Stmt, StmtKind};
-use crate::location::offset_to_line_col;
+use crate::location::LineIndex;
 use crate::types::{Diagnostic, RuleCode};
 
 /// The kind of always-false condition we detected.
@@ -57,82 +57,225 @@ fn dead_condition_message(kind: &DeadCondition, in_while: bool) -> String {
     }
 }
 
+/// A `sys.version_info <op> (X, Y)` test, as written after `if`/`elif`.
+struct VersionCheck<'a> {
+    text: &'a str,
+    op: &'a str,
+    version: Vec<u32>,
+}
+
+/// Parse the test of the `if`/`elif` at `offset` when it is a comparison of
+/// `sys.version_info` against a tuple literal, with nothing else on the line.
+fn parse_version_check(source: &str, offset: usize) -> Option<VersionCheck<'_>> {
+    let rest = &source[offset..];
+    let rest = rest
+        .strip_prefix("elif")
+        .or_else(|| rest.strip_prefix("if"))?;
+    let start = rest.len() - rest.trim_start().len();
+    let after_attr = rest
+        .trim_start()
+        .strip_prefix("sys.version_info")?
+        .trim_start();
+    let op = ["<=", ">=", "==", "!=", "<", ">"]
+        .into_iter()
+        .find(|op| after_attr.starts_with(op))?;
+    let tuple = after_attr[op.len()..].trim_start().strip_prefix('(')?;
+    let close = tuple.find(')')?;
+    let version = tuple[..close]
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<u32>>>()?;
+    let tail = &tuple[close + 1..];
+    if version.is_empty() || version.len() > 3 || !tail.trim_start().starts_with(':') {
+        return None;
+    }
+    let end = rest.len() - tail.len();
+    Some(VersionCheck {
+        text: &rest[start..end],
+        op,
+        version,
+    })
+}
+
+/// The value of `check` when running under `python`, or `None` when it
+/// depends on the micro version (`(3, 11, 4)` against Python 3.11).
+fn evaluate_version_check(check: &VersionCheck<'_>, python: (u32, u32)) -> Option<bool> {
+    let running = [python.0, python.1];
+    let n = check.version.len().min(2);
+    let ordering = match running[..n].cmp(&check.version[..n]) {
+        // `sys.version_info` has five fields, so it is greater than any
+        // tuple of two or fewer that it starts with.
+        std::cmp::Ordering::Equal if check.version.len() <= 2 => std::cmp::Ordering::Greater,
+        std::cmp::Ordering::Equal => return None,
+        ordering => ordering,
+    };
+    Some(match check.op {
+        "<" => ordering.is_lt(),
+        "<=" => ordering.is_le(),
+        ">" => ordering.is_gt(),
+        ">=" => ordering.is_ge(),
+        "==" => ordering.is_eq(),
+        _ => ordering.is_ne(),
+    })
+}
+
+/// RP006 (constant conditions) and RP015 (`sys.version_info` checks decided
+/// by `python_version`, when it is known).
 pub fn check_dead_branches<'src>(
     stmts: &[Stmt<'src>],
     filename: &str,
     source: &str,
+    lines: &LineIndex,
+    python_version: Option<(u32, u32)>,
 ) -> Vec<Diagnostic> {
+    let ctx = Context {
+        filename,
+        source,
+        lines,
+        python_version,
+    };
     let mut diags = Vec::new();
-    walk_for_dead_branches(stmts, filename, source, &mut diags);
+    walk_for_dead_branches(stmts, &ctx, &mut diags);
     diags
 }
 
+struct Context<'a> {
+    filename: &'a str,
+    source: &'a str,
+    lines: &'a LineIndex,
+    python_version: Option<(u32, u32)>,
+}
+
+/// RP015 for the `if`/`elif` `stmt`: `Some(value)` when its test is a
+/// `sys.version_info` comparison with a known value on the running Python,
+/// after reporting whichever branch can never run.
+fn check_version_branch(
+    stmt: &Stmt<'_>,
+    has_else: bool,
+    ctx: &Context<'_>,
+    diags: &mut Vec<Diagnostic>,
+) -> Option<bool> {
+    let python = ctx.python_version?;
+    let offset = stmt.offset as usize;
+    let check = parse_version_check(ctx.source, offset)?;
+    let value = evaluate_version_check(&check, python)?;
+    let (major, minor) = python;
+    let message = if !value {
+        format!(
+            "`{}` is always false on Python {major}.{minor}; the branch never runs",
+            check.text
+        )
+    } else if has_else {
+        format!(
+            "`{}` is always true on Python {major}.{minor}; the `else` branch never runs",
+            check.text
+        )
+    } else {
+        return Some(value);
+    };
+    let (line, col) = ctx.lines.offset_to_line_col(offset);
+    diags.push(Diagnostic {
+        file: ctx.filename.to_string(),
+        line,
+        col,
+        start_byte: offset,
+        end_byte: ctx.lines.line_end(offset),
+        code: RuleCode::DeadVersionBranch,
+        message,
+        fix: None,
+    });
+    Some(value)
+}
+
 fn walk_for_dead_branches<'src>(
     stmts: &[Stmt<'src>],
-    filename: &str,
-    source: &str,
+    ctx: &Context<'_>,
     diags: &mut Vec<Diagnostic>,
 ) {
+    let (filename, lines) = (ctx.filename, ctx.lines);
     for stmt in stmts {
         match &stmt.kind {
+            StmtKind::If { test, body, orelse }
+                if matches!(test.kind, ExprKind::Attr("sys", "version_info")) =>
+            {
+                match check_version_branch(stmt, !orelse.is_empty(), ctx, diags) {
+                    Some(true) => walk_for_dead_branches(body, ctx, diags),
+                    Some(false) => walk_for_dead_branches(orelse, ctx, diags),
+                    None => {
+                        walk_for_dead_branches(body, ctx, diags);
+                        walk_for_dead_branches(orelse, ctx, diags);
+                    }
+                }
+            }
             StmtKind::If { test, body, orelse } => {
                 if let Some(dead) = classify_dead_condition(&test.kind) {
-                    let (line, col) = offset_to_line_col(stmt.offset as usize, source);
+                    let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
                     diags.push(Diagnostic {
                         file: filename.to_string(),
                         line,
                         col,
+                        start_byte: stmt.offset as usize,
+                        end_byte: lines.line_end(stmt.offset as usize),
                         code: RuleCode::DeadBranch,
                         message: dead_condition_message(&dead, false),
+                        fix: None,
                     });
                     // The `else` branch of a dead `if` IS executed — recurse into it.
-                    walk_for_dead_branches(orelse, filename, source, diags);
+                    walk_for_dead_branches(orelse, ctx, diags);
                 } else if let Some(LiveCondition::TrueLiteral) = classify_live_condition(&test.kind)
                 {
                     if !orelse.is_empty() {
-                        let (line, col) = offset_to_line_col(stmt.offset as usize, source);
+                        let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
                         diags.push(Diagnostic {
                             file: filename.to_string(),
                             line,
                             col,
+                            start_byte: stmt.offset as usize,
+                            end_byte: lines.line_end(stmt.offset as usize),
                             code: RuleCode::DeadBranch,
                             message: "`else` branch of `if True:` is never executed".to_string(),
+                            fix: None,
                         });
                     }
                     // The `if True:` body IS executed — recurse into it.
-                    walk_for_dead_branches(body, filename, source, diags);
+                    walk_for_dead_branches(body, ctx, diags);
                 } else {
-                    walk_for_dead_branches(body, filename, source, diags);
-                    walk_for_dead_branches(orelse, filename, source, diags);
+                    walk_for_dead_branches(body, ctx, diags);
+                    walk_for_dead_branches(orelse, ctx, diags);
                 }
             }
             StmtKind::While { test, body, orelse } => {
                 if let Some(dead) = classify_dead_condition(&test.kind) {
-                    let (line, col) = offset_to_line_col(stmt.offset as usize, source);
+                    let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
                     diags.push(Diagnostic {
                         file: filename.to_string(),
                         line,
                         col,
+                        start_byte: stmt.offset as usize,
+                        end_byte: lines.line_end(stmt.offset as usize),
                         code: RuleCode::DeadBranch,
                         message: dead_condition_message(&dead, true),
+                        fix: None,
                     });
                 } else {
-                    walk_for_dead_branches(body, filename, source, diags);
-                    walk_for_dead_branches(orelse, filename, source, diags);
+                    walk_for_dead_branches(body, ctx, diags);
+                    walk_for_dead_branches(orelse, ctx, diags);
                 }
             }
             StmtKind::FunctionDef(f) => {
-                walk_for_dead_branches(&f.body, filename, source, diags);
+                walk_for_dead_branches(&f.body, ctx, diags);
             }
             StmtKind::ClassDef(c) => {
-                walk_for_dead_branches(&c.body, filename, source, diags);
+                walk_for_dead_branches(&c.body, ctx, diags);
             }
             StmtKind::For { body, orelse, .. } => {
-                walk_for_dead_branches(body, filename, source, diags);
-                walk_for_dead_branches(orelse, filename, source, diags);
+                walk_for_dead_branches(body, ctx, diags);
+                walk_for_dead_branches(orelse, ctx, diags);
             }
             StmtKind::With { body, .. } => {
-                walk_for_dead_branches(body, filename, source, diags);
+                walk_for_dead_branches(body, ctx, diags);
             }
             StmtKind::Try {
                 body,
@@ -140,16 +283,16 @@ fn walk_for_dead_branches<'src>(
                 orelse,
                 finalbody,
             } => {
-                walk_for_dead_branches(body, filename, source, diags);
-                walk_for_dead_branches(orelse, filename, source, diags);
-                walk_for_dead_branches(finalbody, filename, source, diags);
+                walk_for_dead_branches(body, ctx, diags);
+                walk_for_dead_branches(orelse, ctx, diags);
+                walk_for_dead_branches(finalbody, ctx, diags);
                 for h in handlers {
-                    walk_for_dead_branches(&h.body, filename, source, diags);
+                    walk_for_dead_branches(&h.body, ctx, diags);
                 }
             }
             StmtKind::Match { arms, .. } => {
                 for arm in arms {
-                    walk_for_dead_branches(&arm.body, filename, source, diags);
+                    walk_for_dead_branches(&arm.body, ctx, diags);
                 }
             }
             _ => {}
@@ -166,7 +309,7 @@ mod tests {
 
     fn check(src: &str) -> Vec<Diagnostic> {
         let stmts = parse(src);
-        check_dead_branches(&stmts, "test.py", src)
+        check_dead_branches(&stmts, "test.py", src, &LineIndex::from_source(src), None)
     }
 
     #[test]
@@ -261,4 +404,80 @@ mod tests {
         let diags = check("some_flag = True\nif some_flag:\n    pass\n");
         assert_eq!(diags.len(), 0);
     }
+
+    fn check_on_311(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_dead_branches(
+            &stmts,
+            "test.py",
+            src,
+            &LineIndex::from_source(src),
+            Some((3, 11)),
+        )
+    }
+
+    #[test]
+    fn test_old_version_branch_flagged() {
+        let diags = check_on_311("import sys\nif sys.version_info < (3, 8):\n    x = 1\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::DeadVersionBranch);
+        assert_eq!((diags[0].line, diags[0].col), (2, 1));
+        assert_eq!(
+            diags[0].message,
+            "`sys.version_info < (3, 8)` is always false on Python 3.11; the branch never runs"
+        );
+    }
+
+    #[test]
+    fn test_version_branch_else_flagged() {
+        let src = "if sys.version_info >= (3, 8):\n    a = 1\nelse:\n    a = 2\n";
+        let diags = check_on_311(src);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("always true"));
+        assert!(diags[0].message.contains("`else`"));
+        // Without an `else` an always-true test is fine.
+        assert!(check_on_311("if sys.version_info >= (3, 8):\n    a = 1\n").is_empty());
+    }
+
+    #[test]
+    fn test_version_boundaries() {
+        // `sys.version_info` is (3, 11, micro, ...), greater than (3, 11).
+        assert_eq!(
+            check_on_311("if sys.version_info < (3, 11):\n    pass\n").len(),
+            1
+        );
+        assert_eq!(
+            check_on_311("if sys.version_info == (3, 11):\n    pass\n").len(),
+            1
+        );
+        assert_eq!(
+            check_on_311("if sys.version_info >= (3, 12):\n    pass\n").len(),
+            1
+        );
+        assert_eq!(
+            check_on_311("if sys.version_info < (4,):\n    pass\nelse:\n    pass\n").len(),
+            1
+        );
+        // The micro version is unknown.
+        assert!(check_on_311("if sys.version_info < (3, 11, 4):\n    pass\n").is_empty());
+    }
+
+    #[test]
+    fn test_version_branch_elif_and_recursion() {
+        let src = "if x:\n    pass\nelif sys.version_info < (3, 6):\n    if False:\n        pass\n";
+        let diags = check_on_311(src);
+        // The dead `elif` body is not searched for RP006.
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].line, 3);
+        assert!(diags[0].message.starts_with("`sys.version_info < (3, 6)`"));
+    }
+
+    #[test]
+    fn test_version_check_not_flagged_when_unknown_or_compound() {
+        assert!(check("if sys.version_info < (3, 8):\n    pass\n").is_empty());
+        let src = "if sys.version_info < (3, 8) and x:\n    pass\n";
+        assert!(check_on_311(src).is_empty());
+        assert!(check_on_311("if sys.version_info[0] == 2:\n    pass\n").is_empty());
+        assert!(check_on_311("if sys.version_info < MIN:\n    pass\n").is_empty());
+    }
 }
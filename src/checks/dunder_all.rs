@@ -0,0 +1,253 @@
+use crate::ast::{Stmt, StmtKind};
+use crate::config::ReaperConfig;
+use crate::location::LineIndex;
+use crate::names::{
+    collect_dunder_all_entries, collect_dunder_all_statements, collect_module_bindings,
+};
+use crate::types::{Diagnostic, RuleCode};
+use std::collections::HashSet;
+
+/// RP048: every `__all__` entry must name something bound in this module.
+///
+/// The check is skipped entirely when the module contains a star import (any
+/// name could have been imported) or defines a module-level `__getattr__`
+/// (PEP 562 — attributes are resolved dynamically).  Entries named in
+/// `config.exempt_dunders` are never reported.
+pub fn check_undefined_exports<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+    config: &ReaperConfig,
+) -> Vec<Diagnostic> {
+    let entries = collect_dunder_all_entries(stmts);
+    if entries.is_empty() {
+        return Vec::new();
+    }
+
+    let mut bound: HashSet<&str> = HashSet::new();
+    if !collect_module_bindings(stmts, &mut bound) {
+        return Vec::new();
+    }
+    let has_getattr = stmts
+        .iter()
+        .any(|s| matches!(&s.kind, StmtKind::FunctionDef(f) if f.name == "__getattr__"));
+    if has_getattr {
+        return Vec::new();
+    }
+
+    entries
+        .into_iter()
+        .filter(|(name, _)| !bound.contains(name.as_str()))
+        .filter(|(name, _)| !config.exempt_dunders.contains(name))
+        .map(|(name, offset)| {
+            let (line, col) = lines.offset_to_line_col(offset as usize);
+            Diagnostic {
+                file: filename.to_string(),
+                line,
+                col,
+                start_byte: offset as usize,
+                end_byte: lines.line_end(offset as usize),
+                code: RuleCode::UndefinedExport,
+                message: format!("Name `{name}` listed in `__all__` is not defined in this module"),
+                fix: None,
+            }
+        })
+        .collect()
+}
+
+/// RP062: a name listed more than once across the module's `__all__`
+/// assignments.  Every repeat after the first is reported at its literal.
+/// A plain `__all__ = […]` starts over; only `+=`, `.extend()` and
+/// `.append()` add to the entries before them.
+pub fn check_duplicate_exports<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut seen = HashSet::new();
+    collect_dunder_all_statements(stmts)
+        .into_iter()
+        .flat_map(|(replaces, entries)| {
+            if replaces {
+                seen.clear();
+            }
+            entries
+                .into_iter()
+                .filter(|(name, _)| !seen.insert(name.clone()))
+                .collect::<Vec<_>>()
+        })
+        .map(|(name, offset)| {
+            let (line, col) = lines.offset_to_line_col(offset as usize);
+            Diagnostic {
+                file: filename.to_string(),
+                line,
+                col,
+                start_byte: offset as usize,
+                end_byte: lines.line_end(offset as usize),
+                code: RuleCode::DuplicateExport,
+                message: format!("Duplicate entry `{name}` in `__all__`"),
+                fix: None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_undefined_exports(
+            &stmts,
+            "test.py",
+            &LineIndex::from_source(src),
+            &ReaperConfig::default(),
+        )
+    }
+
+    fn check_duplicates(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_duplicate_exports(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_undefined_entry_flagged() {
+        let diags = check("__all__ = [\"undefined\"]\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::UndefinedExport);
+        assert!(diags[0].message.contains("undefined"));
+        // Points at the string literal, not the statement.
+        assert_eq!((diags[0].line, diags[0].col), (1, 12));
+    }
+
+    #[test]
+    fn test_defined_function_clean() {
+        let diags = check("__all__ = [\"defined\"]\ndef defined():\n    pass\n");
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_import_and_assign_count_as_definitions() {
+        let diags = check(
+            "from os import path\nimport json as j\nVERSION = 1\nclass C: pass\n\
+             __all__ = [\"path\", \"j\", \"VERSION\", \"C\"]\n",
+        );
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_bracketed_unpacking_counts_as_definitions() {
+        let diags =
+            check("__all__ = [\"A\", \"B\", \"C\", \"D\"]\n(A, B) = 1, 2\n[C, D] = range(2)\n");
+        assert!(diags.is_empty(), "got: {diags:?}");
+    }
+
+    #[test]
+    fn test_conditional_definition_counts() {
+        let diags = check(
+            "try:\n    from ujson import loads\nexcept ImportError:\n    from json import loads\n\
+             __all__ = [\"loads\"]\n",
+        );
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_slots_entries_are_not_module_bindings() {
+        let diags = check("__all__ = [\"P\", \"x\"]\nclass P:\n    __slots__ = (\"x\",)\n");
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`x`"));
+    }
+
+    #[test]
+    fn test_star_import_suppresses() {
+        let diags = check("from os.path import *\n__all__ = [\"join\", \"missing\"]\n");
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_module_getattr_suppresses() {
+        let diags = check("__all__ = [\"lazy\"]\ndef __getattr__(name):\n    return name\n");
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_augmented_all_checked() {
+        let diags = check("def a(): pass\n__all__ = [\"a\"]\n__all__ += [\"b\"]\n");
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`b`"));
+    }
+
+    #[test]
+    fn test_no_dunder_all_clean() {
+        assert!(check("x = 1\n").is_empty());
+    }
+
+    #[test]
+    fn test_tooling_dunder_exempt() {
+        assert!(check("__version__ = \"1.0\"\n").is_empty());
+        // Injected at build time and never bound in the source.
+        assert!(check("__all__ = [\"__version__\", \"__author__\"]\n").is_empty());
+    }
+
+    #[test]
+    fn test_custom_dunder_flagged_without_config() {
+        let diags = check("__all__ = [\"__custom_dunder__\"]\n");
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`__custom_dunder__`"));
+    }
+
+    #[test]
+    fn test_exempt_dunders_configurable() {
+        let src = "__all__ = [\"__custom_dunder__\"]\n";
+        let stmts = parse(src);
+        let config = ReaperConfig {
+            exempt_dunders: vec!["__custom_dunder__".to_string()],
+            ..ReaperConfig::default()
+        };
+        let diags =
+            check_undefined_exports(&stmts, "test.py", &LineIndex::from_source(src), &config);
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_entry_flagged_at_second_occurrence() {
+        let diags = check_duplicates("__all__ = [\"foo\", \"foo\"]\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::DuplicateExport);
+        assert_eq!((diags[0].line, diags[0].col), (1, 19));
+        assert_eq!(diags[0].message, "Duplicate entry `foo` in `__all__`");
+    }
+
+    #[test]
+    fn test_distinct_entries_clean() {
+        assert!(check_duplicates("__all__ = [\"foo\", \"bar\"]\n").is_empty());
+    }
+
+    #[test]
+    fn test_every_repeat_flagged() {
+        let diags = check_duplicates("__all__ = (\"a\", \"a\", \"a\")\n");
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].col, 17);
+        assert_eq!(diags[1].col, 22);
+    }
+
+    #[test]
+    fn test_duplicate_across_augmented_assignment() {
+        let diags = check_duplicates("__all__ = [\"a\"]\n__all__ += [\"b\", \"a\"]\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].line, 2);
+        let diags = check_duplicates("__all__ = [\"a\"]\n__all__.append(\"a\")\n");
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_reassignment_starts_over() {
+        let src = "__all__ = [\"a\"]\n__all__ = [\"a\", \"b\"]\n";
+        assert!(check_duplicates(src).is_empty());
+        let diags = check_duplicates("__all__ = [\"a\"]\n__all__ = [\"b\", \"b\"]\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].line, 2);
+    }
+}
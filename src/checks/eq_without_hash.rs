@@ -0,0 +1,178 @@
+use crate::ast::{AssignTarget, ClassDef, Stmt, StmtKind};
+use crate::location::LineIndex;
+use crate::names::decorator_name;
+use crate::types::{Diagnostic, RuleCode};
+use std::collections::HashSet;
+
+/// Immutable builtins whose subclasses are expected to stay hashable.
+const HASHABLE_BUILTINS: &[&str] = &[
+    "bool",
+    "bytes",
+    "complex",
+    "float",
+    "frozenset",
+    "int",
+    "str",
+    "tuple",
+];
+
+/// RP068: a class that defines `__eq__` but never binds `__hash__`.  Python
+/// then sets `__hash__ = None` and instances can no longer go in a set or be
+/// dict keys.
+///
+/// A class binding `__hash__` in any way (including `__hash__ = None`) made
+/// a choice and is not reported.  Subclasses of immutable builtins and of
+/// classes in the same file that define `__hash__` are exempt.
+pub fn check_eq_without_hash<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut classes = Vec::new();
+    collect_classes(stmts, &mut classes);
+    let hashed: HashSet<&str> = classes
+        .iter()
+        .filter(|c| binds_hash(c))
+        .map(|c| c.name)
+        .collect();
+
+    let mut diags = Vec::new();
+    for class in classes {
+        if !defines(class, "__eq__") || binds_hash(class) {
+            continue;
+        }
+        let inherits_hash = class
+            .bases
+            .iter()
+            .filter_map(decorator_name)
+            .any(|base| HASHABLE_BUILTINS.contains(&base) || hashed.contains(base));
+        if inherits_hash {
+            continue;
+        }
+        let (line, col) = lines.offset_to_line_col(class.offset as usize);
+        diags.push(Diagnostic {
+            file: filename.to_string(),
+            line,
+            col,
+            start_byte: class.offset as usize,
+            end_byte: lines.line_end(class.offset as usize),
+            code: RuleCode::EqWithoutHash,
+            message: format!(
+                "Class `{}` defines `__eq__` but not `__hash__`; instances will be unhashable",
+                class.name
+            ),
+            fix: None,
+        });
+    }
+    diags
+}
+
+/// True when the class body defines a method called `name`.
+fn defines(class: &ClassDef<'_>, name: &str) -> bool {
+    class
+        .body
+        .iter()
+        .any(|s| matches!(&s.kind, StmtKind::FunctionDef(f) if f.name == name))
+}
+
+/// True when the class body binds `__hash__`, by `def` or by assignment.
+fn binds_hash(class: &ClassDef<'_>) -> bool {
+    defines(class, "__hash__")
+        || class.body.iter().any(|s| match &s.kind {
+            StmtKind::Assign { targets, .. } => targets
+                .iter()
+                .any(|t| matches!(t, AssignTarget::Name("__hash__", _))),
+            StmtKind::AnnAssign {
+                target: AssignTarget::Name("__hash__", _),
+                value,
+                ..
+            } => value.is_some(),
+            _ => false,
+        })
+}
+
+/// Every class definition in the file, at any depth.
+fn collect_classes<'a, 'src>(stmts: &'a [Stmt<'src>], out: &mut Vec<&'a ClassDef<'src>>) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::ClassDef(c) => {
+                out.push(c);
+                collect_classes(&c.body, out);
+            }
+            StmtKind::FunctionDef(f) => collect_classes(&f.body, out),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                collect_classes(body, out);
+                collect_classes(orelse, out);
+            }
+            StmtKind::With { body, .. } => collect_classes(body, out),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                collect_classes(body, out);
+                for h in handlers {
+                    collect_classes(&h.body, out);
+                }
+                collect_classes(orelse, out);
+                collect_classes(finalbody, out);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    collect_classes(&arm.body, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_eq_without_hash(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_eq_without_hash_fires() {
+        let diags = check("class Foo:\n    def __eq__(self, o):\n        pass\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::EqWithoutHash);
+        assert_eq!((diags[0].line, diags[0].col), (1, 1));
+        assert_eq!(
+            diags[0].message,
+            "Class `Foo` defines `__eq__` but not `__hash__`; instances will be unhashable"
+        );
+    }
+
+    #[test]
+    fn test_hash_defined_clean() {
+        let src = "class Foo:\n    def __eq__(self, o):\n        pass\n    def __hash__(self):\n        return 0\n";
+        assert!(check(src).is_empty());
+        let src = "class Foo:\n    def __eq__(self, o):\n        pass\n    __hash__ = None\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_hashable_bases_exempt() {
+        let src = "class Foo(frozenset):\n    def __eq__(self, o):\n        pass\n";
+        assert!(check(src).is_empty());
+        let src = "class Base:\n    def __hash__(self):\n        return 0\nclass Foo(Base):\n    def __eq__(self, o):\n        pass\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_nested_class_fires() {
+        let src = "def make():\n    class Local(Base):\n        def __eq__(self, o):\n            pass\n    return Local\n";
+        assert_eq!(check(src).len(), 1);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::ast::{ExprKind, Stmt, StmtKind};
+use crate::location::LineIndex;
+use crate::types::{Diagnostic, RuleCode};
+
+/// Builtin exceptions that are expected to carry a message.  Exceptions that
+/// signal a protocol (`KeyError` from `__getitem__`, `AttributeError` from
+/// `__getattr__`, `StopIteration`, `NotImplementedError`) are left out:
+/// raising them bare is idiomatic.
+const MESSAGE_EXCEPTIONS: &[&str] = &[
+    "ArithmeticError",
+    "BaseException",
+    "Exception",
+    "FileExistsError",
+    "FileNotFoundError",
+    "IOError",
+    "OSError",
+    "OverflowError",
+    "PermissionError",
+    "RuntimeError",
+    "TimeoutError",
+    "TypeError",
+    "UnicodeError",
+    "ValueError",
+    "ZeroDivisionError",
+];
+
+/// RP069: `raise ValueError()` — a common builtin exception instantiated with
+/// no arguments, leaving only a bare type name in the traceback.  A bare
+/// `raise` re-raises and is not reported.
+///
+/// RP070: `except E as e: raise e` — re-raising the caught exception by name
+/// instead of with a bare `raise`.
+pub fn check_exception_message<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    walk(stmts, filename, lines, &mut diags);
+    diags
+}
+
+fn walk<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Raise { exc: Some(exc), .. } => {
+                let ExprKind::Call {
+                    func,
+                    args_count: 0,
+                } = exc.kind
+                else {
+                    continue;
+                };
+                if !MESSAGE_EXCEPTIONS.contains(&func) {
+                    continue;
+                }
+                let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
+                diags.push(Diagnostic {
+                    file: filename.to_string(),
+                    line,
+                    col,
+                    start_byte: stmt.offset as usize,
+                    end_byte: lines.line_end(stmt.offset as usize),
+                    code: RuleCode::ExceptionWithoutMessage,
+                    message: "Raised exception has no message; add a descriptive message"
+                        .to_string(),
+                    fix: None,
+                });
+            }
+            StmtKind::FunctionDef(f) => walk(&f.body, filename, lines, diags),
+            StmtKind::ClassDef(c) => walk(&c.body, filename, lines, diags),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                walk(body, filename, lines, diags);
+                walk(orelse, filename, lines, diags);
+            }
+            StmtKind::With { body, .. } => walk(body, filename, lines, diags),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                walk(body, filename, lines, diags);
+                for h in handlers {
+                    if let Some((name, _)) = h.name {
+                        let mut raises = Vec::new();
+                        collect_reraises(&h.body, name, &mut raises);
+                        for raise in raises {
+                            let (line, col) = lines.offset_to_line_col(raise.offset as usize);
+                            diags.push(Diagnostic {
+                                file: filename.to_string(),
+                                line,
+                                col,
+                                start_byte: raise.offset as usize,
+                                end_byte: lines.line_end(raise.offset as usize),
+                                code: RuleCode::ExplicitReraise,
+                                message: format!(
+                                    "`raise {name}` loses original traceback; use bare `raise` instead"
+                                ),
+                                fix: None,
+                            });
+                        }
+                    }
+                    walk(&h.body, filename, lines, diags);
+                }
+                walk(orelse, filename, lines, diags);
+                walk(finalbody, filename, lines, diags);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    walk(&arm.body, filename, lines, diags);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Every `raise name` in a handler body, outside nested functions and
+/// classes.  `raise name from cause` deliberately rewrites the chain and is
+/// skipped.
+fn collect_reraises<'a, 'src>(stmts: &'a [Stmt<'src>], name: &str, out: &mut Vec<&'a Stmt<'src>>) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Raise {
+                exc: Some(exc),
+                cause: None,
+            } if matches!(exc.kind, ExprKind::Name(n, _) if n == name) => out.push(stmt),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                collect_reraises(body, name, out);
+                collect_reraises(orelse, name, out);
+            }
+            StmtKind::With { body, .. } => collect_reraises(body, name, out),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                collect_reraises(body, name, out);
+                for h in handlers {
+                    collect_reraises(&h.body, name, out);
+                }
+                collect_reraises(orelse, name, out);
+                collect_reraises(finalbody, name, out);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    collect_reraises(&arm.body, name, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_exception_message(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_empty_call_fires() {
+        let diags = check("def f():\n    raise ValueError()\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::ExceptionWithoutMessage);
+        assert_eq!((diags[0].line, diags[0].col), (2, 5));
+        assert_eq!(
+            diags[0].message,
+            "Raised exception has no message; add a descriptive message"
+        );
+    }
+
+    #[test]
+    fn test_exception_call_fires() {
+        assert_eq!(check("raise Exception()\n").len(), 1);
+    }
+
+    #[test]
+    fn test_message_clean() {
+        assert!(check("raise ValueError(\"bad\")\n").is_empty());
+        assert!(check("raise RuntimeError(msg)\n").is_empty());
+    }
+
+    #[test]
+    fn test_reraise_clean() {
+        assert!(check("try:\n    f()\nexcept E:\n    raise\n").is_empty());
+    }
+
+    #[test]
+    fn test_signalling_and_custom_exceptions_clean() {
+        assert!(check("raise NotImplementedError()\n").is_empty());
+        assert!(check("raise KeyError()\n").is_empty());
+        assert!(check("raise AppError()\n").is_empty());
+        assert!(check("raise errors.ValueError()\n").is_empty());
+    }
+
+    #[test]
+    fn test_reraise_by_name_fires() {
+        let diags = check("try:\n    risky()\nexcept E as e:\n    log(e)\n    raise e\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::ExplicitReraise);
+        assert_eq!((diags[0].line, diags[0].col), (5, 5));
+        assert_eq!(
+            diags[0].message,
+            "`raise e` loses original traceback; use bare `raise` instead"
+        );
+    }
+
+    #[test]
+    fn test_conditional_reraise_by_name_fires() {
+        let src = "def f():\n    try:\n        risky()\n    except E as err:\n        if fatal(err):\n            raise err\n";
+        assert_eq!(check(src).len(), 1);
+    }
+
+    #[test]
+    fn test_wrapped_or_bare_reraise_clean() {
+        assert!(check("try:\n    f()\nexcept E as e:\n    raise ValueError(e)\n").is_empty());
+        assert!(check("try:\n    f()\nexcept E as e:\n    raise Other() from e\n").is_empty());
+        assert!(check("try:\n    f()\nexcept E:\n    raise\n").is_empty());
+    }
+}
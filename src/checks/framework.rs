@@ -0,0 +1,70 @@
+use crate::fast_parser::lexer::{Lexer, Token};
+use std::collections::HashSet;
+
+/// Collect the functions registered as Flask views without a decorator:
+/// `app.add_url_rule("/", view_func=index)`.  The framework calls them on
+/// each request, so they count as used.  For a dotted target such as
+/// `view_func=views.index` the last name is recorded, since that is the
+/// function defined in the other module.
+pub fn collect_url_rule_views(source: &str, out: &mut HashSet<String>) {
+    let mut lex = Lexer::new(source);
+    let mut prev = Token::Newline;
+    // Paren depth inside an `add_url_rule(` call, 0 outside one.
+    let mut depth = 0usize;
+    loop {
+        let tok = lex.bump();
+        match &tok {
+            Token::Eof => break,
+            Token::LParen if depth > 0 => depth += 1,
+            Token::LParen if prev == Token::Name("add_url_rule") => depth = 1,
+            Token::RParen if depth > 0 => depth -= 1,
+            Token::Eq if depth == 1 && prev == Token::Name("view_func") => {
+                let mut last = None;
+                while let Token::Name(n) = lex.bump() {
+                    last = Some(n);
+                    if lex.peek() != &Token::Dot {
+                        break;
+                    }
+                    lex.bump();
+                }
+                // Only a bare (dotted) name, not `view_func=wrap(index)`.
+                if matches!(lex.peek(), Token::Comma | Token::RParen) {
+                    out.extend(last.map(str::to_string));
+                }
+                prev = Token::Newline;
+                continue;
+            }
+            _ => {}
+        }
+        prev = tok;
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(src: &str) -> HashSet<String> {
+        let mut out = HashSet::new();
+        collect_url_rule_views(src, &mut out);
+        out
+    }
+
+    #[test]
+    fn test_view_func_names_collected() {
+        let views = check("app.add_url_rule('/', view_func=index)\n");
+        assert_eq!(views, HashSet::from(["index".to_string()]));
+        let views = check("bp.add_url_rule(\n    '/u',\n    'u',\n    view_func=views.users,\n)\n");
+        assert_eq!(views, HashSet::from(["users".to_string()]));
+    }
+
+    #[test]
+    fn test_other_calls_ignored() {
+        assert!(check("register(view_func=index)\n").is_empty());
+        assert!(check("app.add_url_rule('/', endpoint=index)\n").is_empty());
+        // A nested call's own `view_func=` is not the rule's.
+        assert!(check("app.add_url_rule('/', view_func=wrap(view_func=index))\n").is_empty());
+    }
+}
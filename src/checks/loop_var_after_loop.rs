@@ -0,0 +1,320 @@
+use crate::ast::{Arguments, AssignTarget, Stmt, StmtKind};
+use crate::location::LineIndex;
+use crate::names::{collect_module_bindings, collect_stmt_names, collect_target_bindings};
+use crate::types::{Diagnostic, RuleCode};
+use std::collections::HashSet;
+
+/// RP052: a `for` target read after the loop, in the same scope, when nothing
+/// bound the name before the loop — it is undefined if the iterable was empty.
+pub fn check_loop_var_after_loop<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    walk(stmts, &HashSet::new(), filename, lines, &mut diags);
+    diags
+}
+
+/// `bound` holds the names already bound in this scope on entry to `stmts`.
+fn walk<'src>(
+    stmts: &[Stmt<'src>],
+    bound: &HashSet<&'src str>,
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    let mut before = bound.clone();
+    for (i, stmt) in stmts.iter().enumerate() {
+        match &stmt.kind {
+            StmtKind::For {
+                target,
+                body,
+                orelse,
+                ..
+            } => {
+                check_for(
+                    target,
+                    orelse,
+                    &stmts[i + 1..],
+                    &before,
+                    filename,
+                    lines,
+                    diags,
+                );
+                let mut in_body = before.clone();
+                collect_target_bindings(target, &mut in_body);
+                walk(body, &in_body, filename, lines, diags);
+                walk(orelse, &in_body, filename, lines, diags);
+            }
+            StmtKind::FunctionDef(f) => {
+                walk(&f.body, &arg_names(&f.args), filename, lines, diags);
+            }
+            StmtKind::ClassDef(c) => walk(&c.body, &HashSet::new(), filename, lines, diags),
+            StmtKind::If { body, orelse, .. } | StmtKind::While { body, orelse, .. } => {
+                walk(body, &before, filename, lines, diags);
+                walk(orelse, &before, filename, lines, diags);
+            }
+            StmtKind::With { items, body, .. } => {
+                let mut in_body = before.clone();
+                for t in items.iter().filter_map(|item| item.target.as_ref()) {
+                    collect_target_bindings(t, &mut in_body);
+                }
+                walk(body, &in_body, filename, lines, diags);
+            }
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                walk(body, &before, filename, lines, diags);
+                for h in handlers {
+                    let mut in_handler = before.clone();
+                    in_handler.extend(h.name.map(|(n, _)| n));
+                    walk(&h.body, &in_handler, filename, lines, diags);
+                }
+                walk(orelse, &before, filename, lines, diags);
+                walk(finalbody, &before, filename, lines, diags);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    let mut in_arm = before.clone();
+                    in_arm.extend(arm.bindings.iter().map(|(n, _)| *n));
+                    walk(&arm.body, &in_arm, filename, lines, diags);
+                }
+            }
+            // `global x` / `nonlocal x`: bound in another scope.
+            StmtKind::Global(names) | StmtKind::Nonlocal(names) => before.extend(names),
+            _ => {}
+        }
+        collect_module_bindings(std::slice::from_ref(stmt), &mut before);
+    }
+}
+
+/// Report each target name of one loop that `rest` (the statements after the
+/// loop) reads before rebinding it.
+fn check_for<'src>(
+    target: &AssignTarget<'src>,
+    orelse: &[Stmt<'src>],
+    rest: &[Stmt<'src>],
+    before: &HashSet<&'src str>,
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    let mut targets = HashSet::new();
+    collect_target_bindings(target, &mut targets);
+    // An `else:` clause also runs for an empty iterable, so a name it binds
+    // is always defined after the loop.
+    let mut in_else = HashSet::new();
+    collect_module_bindings(orelse, &mut in_else);
+    let mut tracked: Vec<&str> = targets
+        .into_iter()
+        .filter(|n| !n.starts_with('_') && !before.contains(n) && !in_else.contains(n))
+        .collect();
+    tracked.sort_unstable();
+
+    for stmt in rest {
+        if tracked.is_empty() {
+            return;
+        }
+        match &stmt.kind {
+            // Closures read the name when called, not here.
+            StmtKind::FunctionDef(_) | StmtKind::ClassDef(_) => continue,
+            // `del i` after a loop is cleanup, not a use.
+            StmtKind::Delete(targets) => {
+                tracked.retain(|n| !targets.iter().any(|t| t.names.iter().any(|(m, _)| m == n)));
+                continue;
+            }
+            _ => {}
+        }
+        // A later loop over the same name rebinds it before its body runs;
+        // only its iterable can still see the old value.
+        if let StmtKind::For { target, iter, .. } = &stmt.kind {
+            let reads: HashSet<String> = iter.names.iter().map(|(n, _)| n.to_string()).collect();
+            report_reads(&mut tracked, &reads, stmt, filename, lines, diags);
+            let mut rebound = HashSet::new();
+            collect_target_bindings(target, &mut rebound);
+            tracked.retain(|n| !rebound.contains(n));
+        }
+        // A compound statement that rebinds the name somewhere inside may do
+        // so before reading it; without flow analysis, stay quiet.
+        if is_compound(stmt) {
+            let mut inner = HashSet::new();
+            collect_module_bindings(std::slice::from_ref(stmt), &mut inner);
+            tracked.retain(|n| !inner.contains(n));
+        }
+        let mut reads = HashSet::new();
+        collect_stmt_names(std::slice::from_ref(stmt), &mut reads);
+        report_reads(&mut tracked, &reads, stmt, filename, lines, diags);
+        // A plain rebinding makes later reads safe.
+        if let StmtKind::Assign { targets, .. } = &stmt.kind {
+            let mut rebound = HashSet::new();
+            for t in targets {
+                collect_target_bindings(t, &mut rebound);
+            }
+            tracked.retain(|n| !rebound.contains(n));
+        }
+    }
+}
+
+/// Emit RP052 at `stmt` for every tracked name in `reads`, and stop tracking
+/// it so each name is reported once per loop.
+fn report_reads(
+    tracked: &mut Vec<&str>,
+    reads: &HashSet<String>,
+    stmt: &Stmt<'_>,
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    tracked.retain(|n| {
+        if !reads.contains(*n) {
+            return true;
+        }
+        let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
+        diags.push(Diagnostic {
+            file: filename.to_string(),
+            line,
+            col,
+            start_byte: stmt.offset as usize,
+            end_byte: lines.line_end(stmt.offset as usize),
+            code: RuleCode::LoopVarUsedAfterLoop,
+            message: format!("Loop variable `{n}` used after loop; undefined if iterable is empty"),
+            fix: None,
+        });
+        false
+    });
+}
+
+fn is_compound(stmt: &Stmt<'_>) -> bool {
+    matches!(
+        stmt.kind,
+        StmtKind::If { .. }
+            | StmtKind::While { .. }
+            | StmtKind::For { .. }
+            | StmtKind::With { .. }
+            | StmtKind::Try { .. }
+            | StmtKind::Match { .. }
+    )
+}
+
+fn arg_names<'src>(args: &Arguments<'src>) -> HashSet<&'src str> {
+    args.posonlyargs
+        .iter()
+        .chain(&args.args)
+        .chain(&args.vararg)
+        .chain(&args.kwonlyargs)
+        .chain(&args.kwarg)
+        .map(|a| a.name)
+        .collect()
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_loop_var_after_loop(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_use_after_loop_fires() {
+        let diags = check("for i in items:\n    pass\nprint(i)\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::LoopVarUsedAfterLoop);
+        assert_eq!((diags[0].line, diags[0].col), (3, 1));
+        assert!(diags[0].message.contains("`i`"));
+    }
+
+    #[test]
+    fn test_no_use_after_loop_clean() {
+        assert!(check("for i in items:\n    pass\n").is_empty());
+    }
+
+    #[test]
+    fn test_initialized_before_loop_clean() {
+        assert!(check("i = None\nfor i in items:\n    pass\nprint(i)\n").is_empty());
+    }
+
+    #[test]
+    fn test_function_scope_and_parameters() {
+        let src = "def f(items):\n    for item in items:\n        pass\n    return item\n";
+        assert_eq!(check(src).len(), 1);
+        let src =
+            "def f(items, item=None):\n    for item in items:\n        pass\n    return item\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_tuple_target_reports_each_name_once() {
+        let src = "for k, v in d.items():\n    pass\nprint(k)\nprint(k, v)\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 2);
+        assert!(diags[0].message.contains("`k`"));
+        assert!(diags[1].message.contains("`v`"));
+        assert_eq!(diags[1].line, 4);
+    }
+
+    #[test]
+    fn test_rebinding_after_loop_clean() {
+        assert!(check("for i in items:\n    pass\ni = 0\nprint(i)\n").is_empty());
+    }
+
+    #[test]
+    fn test_del_after_loop_clean() {
+        assert!(check("for name in names:\n    pass\ndel name\n").is_empty());
+    }
+
+    #[test]
+    fn test_else_clause_binding_clean() {
+        let src = "for i in items:\n    pass\nelse:\n    i = None\nprint(i)\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_underscore_target_ignored() {
+        assert!(check("for _i in items:\n    pass\nprint(_i)\n").is_empty());
+    }
+
+    #[test]
+    fn test_nested_block_loop_fires() {
+        let src = "def f(rows):\n    if rows:\n        for r in rows:\n            pass\n        return r\n";
+        assert_eq!(check(src).len(), 1);
+    }
+
+    #[test]
+    fn test_closure_after_loop_not_flagged() {
+        let src = "for i in items:\n    pass\ndef g():\n    return i\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_later_loop_rebinding_same_name_clean() {
+        let src = "for t in a:\n    t.cancel()\nfor t in a:\n    t.result()\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_later_loop_reading_old_name_fires() {
+        assert_eq!(
+            check("for i in a:\n    pass\nfor j in b:\n    print(i)\n").len(),
+            1
+        );
+        assert_eq!(
+            check("for i in a:\n    pass\nfor i in range(i):\n    pass\n").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_compound_statement_rebinding_not_flagged() {
+        let src = "for f in fs:\n    pass\ntry:\n    wait()\nfinally:\n    for f in fs:\n        f.close()\n";
+        assert!(check(src).is_empty());
+    }
+}
@@ -1,7 +1,25 @@
+pub mod annotation_imports;
 pub mod dead_branch;
+pub mod dunder_all;
+pub mod eq_without_hash;
+pub mod exception_message;
+pub mod framework;
+pub mod loop_var_after_loop;
+pub mod print_statement;
+pub mod redundant_else;
+pub mod return_consistency;
+pub mod scope_declarations;
+pub mod self_assign;
+pub mod shadowed_builtins;
+pub mod string_format;
+pub mod suppressed_assign;
+pub mod try_else;
 pub mod unreachable;
 pub mod unused_args;
 pub mod unused_defs;
+pub mod unused_except_var;
+pub mod unused_globals;
 pub mod unused_imports;
 pub mod unused_loop_var;
 pub mod unused_variables;
+pub mod unused_with_target;
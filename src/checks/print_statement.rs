@@ -0,0 +1,132 @@
+use crate::ast::{ExprKind, Stmt, StmtKind};
+use crate::location::LineIndex;
+use crate::types::{Diagnostic, RuleCode};
+
+/// RP072: the Python 2 `print "hello"` statement — a `print` followed by a
+/// string literal without parentheses, which is a `SyntaxError` in Python 3.
+///
+/// The parser reads the line as one expression statement starting with the
+/// name `print`; the source after the name tells it apart from `print(…)`.
+pub fn check_print_statements<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    source: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    walk(stmts, filename, source, lines, &mut diags);
+    diags
+}
+
+fn walk<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    source: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Expr(info) => {
+                let ExprKind::Name("print", offset) = info.kind else {
+                    continue;
+                };
+                let start = offset as usize;
+                let end = start + "print".len();
+                if !source.get(end..).is_some_and(starts_with_string) {
+                    continue;
+                }
+                let (line, col) = lines.offset_to_line_col(start);
+                diags.push(Diagnostic {
+                    file: filename.to_string(),
+                    line,
+                    col,
+                    start_byte: start,
+                    end_byte: lines.line_end(start),
+                    code: RuleCode::PrintStatement,
+                    message: "`print` used as a statement; Python 3 requires `print(...)`"
+                        .to_string(),
+                    fix: None,
+                });
+            }
+            StmtKind::FunctionDef(f) => walk(&f.body, filename, source, lines, diags),
+            StmtKind::ClassDef(c) => walk(&c.body, filename, source, lines, diags),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                walk(body, filename, source, lines, diags);
+                walk(orelse, filename, source, lines, diags);
+            }
+            StmtKind::With { body, .. } => walk(body, filename, source, lines, diags),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                walk(body, filename, source, lines, diags);
+                for h in handlers {
+                    walk(&h.body, filename, source, lines, diags);
+                }
+                walk(orelse, filename, source, lines, diags);
+                walk(finalbody, filename, source, lines, diags);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    walk(&arm.body, filename, source, lines, diags);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// True when `rest` is whitespace followed by a string literal, prefix
+/// (`r`, `u`, `b`, `f`, …) included.
+fn starts_with_string(rest: &str) -> bool {
+    let trimmed = rest.trim_start_matches([' ', '\t']);
+    let literal = trimmed.trim_start_matches(|c: char| "rRuUbBfF".contains(c));
+    trimmed.len() - literal.len() <= 2 && literal.starts_with(['"', '\''])
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_print_statements(&stmts, "test.py", src, &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_print_statement_fires() {
+        let diags = check("print \"hello\"\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::PrintStatement);
+        assert_eq!((diags[0].line, diags[0].col), (1, 1));
+        assert_eq!(
+            diags[0].message,
+            "`print` used as a statement; Python 3 requires `print(...)`"
+        );
+    }
+
+    #[test]
+    fn test_prefixed_and_nested_fire() {
+        assert_eq!(check("def f():\n    print u'%s' % x\n").len(), 1);
+    }
+
+    #[test]
+    fn test_print_call_clean() {
+        assert!(check("print(\"hello\")\n").is_empty());
+        assert!(check("print (\"hello\")\n").is_empty());
+    }
+
+    #[test]
+    fn test_print_as_value_clean() {
+        assert!(check("x = print\n").is_empty());
+        assert!(check("print\n").is_empty());
+    }
+}
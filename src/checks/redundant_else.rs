@@ -0,0 +1,150 @@
+use crate::ast::{Stmt, StmtKind};
+use crate::location::LineIndex;
+use crate::types::{Diagnostic, RuleCode};
+
+/// RP013: an `else` (or `elif`) after an `if` body that always leaves the
+/// block through `return`, `raise`, `break` or `continue`.  Control only
+/// reaches the `else` when the test is false, so its body can be dedented
+/// and an `elif` can become a plain `if`.
+///
+/// The diagnostic points at the `elif` keyword, or at the first statement of
+/// an `else` body.
+pub fn check_redundant_else<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    source: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    walk(stmts, filename, source, lines, &mut diags);
+    diags
+}
+
+fn walk<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    source: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::If { body, orelse, .. } => {
+                if let (Some(first), Some(exit)) = (orelse.first(), block_always_terminates(body)) {
+                    let offset = first.offset as usize;
+                    let is_elif = source[offset..].strip_prefix("elif").is_some_and(|rest| {
+                        !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_')
+                    });
+                    let keyword = if is_elif { "elif" } else { "else" };
+                    let (line, col) = lines.offset_to_line_col(offset);
+                    diags.push(Diagnostic {
+                        file: filename.to_string(),
+                        line,
+                        col,
+                        start_byte: offset,
+                        end_byte: lines.line_end(offset),
+                        code: RuleCode::RedundantElse,
+                        message: format!("Unnecessary `{keyword}` after `{exit}`"),
+                        fix: None,
+                    });
+                }
+                walk(body, filename, source, lines, diags);
+                walk(orelse, filename, source, lines, diags);
+            }
+            StmtKind::FunctionDef(f) => walk(&f.body, filename, source, lines, diags),
+            StmtKind::ClassDef(c) => walk(&c.body, filename, source, lines, diags),
+            StmtKind::While { body, orelse, .. } | StmtKind::For { body, orelse, .. } => {
+                walk(body, filename, source, lines, diags);
+                walk(orelse, filename, source, lines, diags);
+            }
+            StmtKind::With { body, .. } => walk(body, filename, source, lines, diags),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                walk(body, filename, source, lines, diags);
+                for h in handlers {
+                    walk(&h.body, filename, source, lines, diags);
+                }
+                walk(orelse, filename, source, lines, diags);
+                walk(finalbody, filename, source, lines, diags);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    walk(&arm.body, filename, source, lines, diags);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The keyword that ends `body` when its last statement always leaves the
+/// block: a `return`, `raise`, `break` or `continue`, or an `if`/`else`
+/// whose branches all do.
+fn block_always_terminates(body: &[Stmt<'_>]) -> Option<&'static str> {
+    match &body.last()?.kind {
+        StmtKind::Return(_) => Some("return"),
+        StmtKind::Raise { .. } => Some("raise"),
+        StmtKind::Break => Some("break"),
+        StmtKind::Continue => Some("continue"),
+        StmtKind::If { body, orelse, .. } => {
+            let exit = block_always_terminates(body)?;
+            block_always_terminates(orelse)?;
+            Some(exit)
+        }
+        _ => None,
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_redundant_else(&stmts, "test.py", src, &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_else_after_return_fires() {
+        let diags =
+            check("def f(c, x, y):\n    if c:\n        return x\n    else:\n        return y\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::RedundantElse);
+        assert_eq!((diags[0].line, diags[0].col), (5, 9));
+        assert_eq!(diags[0].message, "Unnecessary `else` after `return`");
+    }
+
+    #[test]
+    fn test_plain_if_else_clean() {
+        assert!(check("if c:\n    x = 1\nelse:\n    x = 2\n").is_empty());
+        assert!(check("def f(c):\n    if c:\n        return 1\n    return 2\n").is_empty());
+    }
+
+    #[test]
+    fn test_nested_branch_that_falls_through_clean() {
+        let src = "def f(a, b):\n    if a:\n        if b:\n            return 1\n    else:\n        return 2\n";
+        assert!(check(src).is_empty());
+        let src = "def f(a, b):\n    if a:\n        if b:\n            return 1\n        else:\n            raise E\n    else:\n        return 2\n";
+        let diags = check(src);
+        // The inner `else` after `return 1` and the outer one both fire.
+        assert_eq!(diags.len(), 2);
+    }
+
+    #[test]
+    fn test_elif_after_raise_and_loop_exits_fire() {
+        let diags = check("if a:\n    raise E\nelif b:\n    go()\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!((diags[0].line, diags[0].col), (3, 1));
+        assert_eq!(diags[0].message, "Unnecessary `elif` after `raise`");
+
+        let src = "for x in xs:\n    if x:\n        continue\n    else:\n        elif_count = 1\n";
+        assert_eq!(check(src)[0].message, "Unnecessary `else` after `continue`");
+    }
+}
@@ -0,0 +1,285 @@
+use crate::ast::{ExprKind, FuncDef, Stmt, StmtKind};
+use crate::location::LineIndex;
+use crate::names::decorator_name;
+use crate::types::{Diagnostic, RuleCode};
+
+/// Decorators whose functions are not called for their return value in the
+/// usual way, or whose implicit `None` is part of the protocol.
+const EXEMPT_DECORATORS: &[&str] = &[
+    "property",
+    "cached_property",
+    "setter",
+    "getter",
+    "deleter",
+    "abstractmethod",
+    "overload",
+];
+
+/// RP050: a function that returns a value on some paths but can also fall off
+/// the end of its body (an implicit `return None`).  Properties, abstract
+/// methods and overloads are exempt.
+pub fn check_return_consistency<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    walk_for_functions(stmts, filename, lines, &mut diags);
+    diags
+}
+
+fn walk_for_functions<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::FunctionDef(f) => {
+                check_function(f, filename, lines, diags);
+                walk_for_functions(&f.body, filename, lines, diags);
+            }
+            StmtKind::ClassDef(c) => walk_for_functions(&c.body, filename, lines, diags),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                walk_for_functions(body, filename, lines, diags);
+                walk_for_functions(orelse, filename, lines, diags);
+            }
+            StmtKind::With { body, .. } => walk_for_functions(body, filename, lines, diags),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                walk_for_functions(body, filename, lines, diags);
+                for h in handlers {
+                    walk_for_functions(&h.body, filename, lines, diags);
+                }
+                walk_for_functions(orelse, filename, lines, diags);
+                walk_for_functions(finalbody, filename, lines, diags);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    walk_for_functions(&arm.body, filename, lines, diags);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_function(f: &FuncDef<'_>, filename: &str, lines: &LineIndex, diags: &mut Vec<Diagnostic>) {
+    let exempt = f
+        .decorators
+        .iter()
+        .any(|d| decorator_name(d).is_some_and(|n| EXEMPT_DECORATORS.contains(&n)));
+    if exempt || !has_value_return(&f.body) || always_exits(&f.body) {
+        return;
+    }
+    let (line, col) = lines.offset_to_line_col(f.offset as usize);
+    diags.push(Diagnostic {
+        file: filename.to_string(),
+        line,
+        col,
+        start_byte: f.offset as usize,
+        end_byte: lines.line_end(f.offset as usize),
+        code: RuleCode::InconsistentReturn,
+        message: format!(
+            "Function `{}` has inconsistent return types (explicit and implicit None returns)",
+            f.name
+        ),
+        fix: None,
+    });
+}
+
+/// True when `body` contains `return <expr>` with something other than a
+/// literal `None`.  Nested functions and classes have their own returns.
+pub(crate) fn has_value_return(body: &[Stmt<'_>]) -> bool {
+    body.iter().any(|stmt| match &stmt.kind {
+        StmtKind::Return(Some(value)) => !matches!(value.kind, ExprKind::NoneLit),
+        StmtKind::If { body, orelse, .. }
+        | StmtKind::While { body, orelse, .. }
+        | StmtKind::For { body, orelse, .. } => has_value_return(body) || has_value_return(orelse),
+        StmtKind::With { body, .. } => has_value_return(body),
+        StmtKind::Try {
+            body,
+            handlers,
+            orelse,
+            finalbody,
+        } => {
+            has_value_return(body)
+                || handlers.iter().any(|h| has_value_return(&h.body))
+                || has_value_return(orelse)
+                || has_value_return(finalbody)
+        }
+        StmtKind::Match { arms, .. } => arms.iter().any(|arm| has_value_return(&arm.body)),
+        _ => false,
+    })
+}
+
+/// True when control can never reach the end of `body`: every path ends in
+/// `return`, `raise`, or an infinite `while True` loop.
+pub(crate) fn always_exits(body: &[Stmt<'_>]) -> bool {
+    body.iter().any(stmt_always_exits)
+}
+
+fn stmt_always_exits(stmt: &Stmt<'_>) -> bool {
+    match &stmt.kind {
+        StmtKind::Return(_) | StmtKind::Raise { .. } => true,
+        StmtKind::If { body, orelse, .. } => always_exits(body) && always_exits(orelse),
+        StmtKind::While { test, body, .. } => {
+            matches!(test.kind, ExprKind::BoolLit(true)) && !contains_break(body)
+        }
+        StmtKind::With { body, .. } => always_exits(body),
+        StmtKind::Try {
+            body,
+            handlers,
+            orelse,
+            finalbody,
+        } => {
+            always_exits(finalbody)
+                || ((always_exits(body) || always_exits(orelse))
+                    && handlers.iter().all(|h| always_exits(&h.body)))
+        }
+        // Only an irrefutable arm (`case _:`, `case name:`) makes a match exhaustive.
+        StmtKind::Match { arms, .. } => {
+            arms.iter().all(|arm| always_exits(&arm.body)) && arms.iter().any(|arm| arm.irrefutable)
+        }
+        _ => false,
+    }
+}
+
+/// `break` that leaves the enclosing loop — breaks inside nested loops don't.
+fn contains_break(body: &[Stmt<'_>]) -> bool {
+    body.iter().any(|stmt| match &stmt.kind {
+        StmtKind::Break => true,
+        StmtKind::If { body, orelse, .. } => contains_break(body) || contains_break(orelse),
+        StmtKind::With { body, .. } => contains_break(body),
+        StmtKind::Try {
+            body,
+            handlers,
+            orelse,
+            finalbody,
+        } => {
+            contains_break(body)
+                || handlers.iter().any(|h| contains_break(&h.body))
+                || contains_break(orelse)
+                || contains_break(finalbody)
+        }
+        StmtKind::Match { arms, .. } => arms.iter().any(|arm| contains_break(&arm.body)),
+        _ => false,
+    })
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_return_consistency(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_implicit_fallthrough_fires() {
+        let diags = check("def foo(x):\n    if x:\n        return 1\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::InconsistentReturn);
+        assert!(diags[0].message.contains("`foo`"));
+    }
+
+    #[test]
+    fn test_property_exempt() {
+        let src =
+            "class C:\n    @property\n    def v(self):\n        if self.x:\n            return 1\n";
+        assert!(check(src).is_empty());
+        let src = "class C:\n    @functools.cached_property\n    def v(self):\n        if self.x:\n            return 1\n";
+        assert!(check(src).is_empty());
+        let src = "@overload\ndef f(x):\n    if x:\n        return 1\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_single_return_none_clean() {
+        assert!(check("def f():\n    return None\n").is_empty());
+    }
+
+    #[test]
+    fn test_all_paths_explicit_clean() {
+        assert!(check("def f(x):\n    if x:\n        return 1\n    return None\n").is_empty());
+    }
+
+    #[test]
+    fn test_no_value_returns_clean() {
+        assert!(check("def f(x):\n    if x:\n        return\n    print(x)\n").is_empty());
+    }
+
+    #[test]
+    fn test_if_else_both_return_clean() {
+        let src = "def f(x):\n    if x:\n        return 1\n    else:\n        return 2\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_raise_counts_as_exit() {
+        let src = "def f(x):\n    if x:\n        return 1\n    raise ValueError(x)\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_try_with_handlers_that_exit_clean() {
+        let src = "def f():\n    try:\n        return load()\n    except OSError:\n        raise\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_try_handler_falls_through_fires() {
+        let src = "def f():\n    try:\n        return load()\n    except OSError:\n        log()\n";
+        assert_eq!(check(src).len(), 1);
+    }
+
+    #[test]
+    fn test_while_true_without_break_clean() {
+        let src = "def f():\n    while True:\n        if ready():\n            return 1\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_while_true_with_break_fires() {
+        let src =
+            "def f():\n    while True:\n        if ready():\n            return 1\n        break\n";
+        assert_eq!(check(src).len(), 1);
+    }
+
+    #[test]
+    fn test_match_with_wildcard_clean() {
+        let src = "def f(x):\n    match x:\n        case 1:\n            return 1\n        case _:\n            return 0\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_nested_function_checked_separately() {
+        let src = "def outer():\n    def inner(x):\n        if x:\n            return 1\n    return inner\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`inner`"));
+    }
+
+    #[test]
+    fn test_match_with_capture_arm_clean() {
+        let src = "def f(x):\n    match x:\n        case 1:\n            return 1\n        case other:\n            return other\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_match_with_guarded_wildcard_fires() {
+        let src = "def f(x):\n    match x:\n        case 1:\n            return 1\n        case _ if x:\n            return 0\n";
+        assert_eq!(check(src).len(), 1);
+    }
+}
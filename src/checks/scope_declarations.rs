@@ -0,0 +1,487 @@
+use crate::ast::{Arguments, AssignTarget, ExprInfo, ExprKind, Stmt, StmtKind};
+use crate::builtins::BUILTINS;
+use crate::location::LineIndex;
+use crate::names::{collect_module_bindings, collect_stmt_names, collect_target_bindings};
+use crate::types::{Diagnostic, RuleCode};
+use std::collections::HashSet;
+
+/// Misplaced `global` / `nonlocal` declarations, all of which make CPython
+/// reject the whole module with a `SyntaxError`:
+///
+/// - RP053: a `nonlocal` name that no enclosing function binds.
+/// - RP054: a `global` name that its scope already assigned or read, or
+///   that is also a parameter.
+///
+/// Also RP073: a function or class declaring a builtin such as `list`
+/// `global`, so that assigning it replaces the builtin for the whole module.
+pub fn check_scope_declarations<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    check_global_order(stmts, &HashSet::new(), filename, lines, &mut diags);
+    walk(stmts, &mut Vec::new(), filename, lines, &mut diags);
+    diags
+}
+
+/// `enclosing` holds the names bound by each function or class around
+/// `stmts`, innermost last.
+fn walk<'src>(
+    stmts: &[Stmt<'src>],
+    enclosing: &mut Vec<HashSet<&'src str>>,
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Nonlocal(names) => {
+                // The last scope is the declaring function's (or class's) own.
+                let outer = enclosing.split_last().map_or(&[][..], |(_, outer)| outer);
+                for name in names {
+                    if !outer.iter().any(|scope| scope.contains(name)) {
+                        let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
+                        diags.push(Diagnostic {
+                            file: filename.to_string(),
+                            line,
+                            col,
+                            start_byte: stmt.offset as usize,
+                            end_byte: lines.line_end(stmt.offset as usize),
+                            code: RuleCode::UndefinedNonlocal,
+                            message: format!(
+                                "Name `{name}` in `nonlocal` declaration not found in any enclosing scope"
+                            ),
+                            fix: None,
+                        });
+                    }
+                }
+            }
+            StmtKind::Global(names) if !enclosing.is_empty() => {
+                for name in names {
+                    if BUILTINS.binary_search(name).is_err() {
+                        continue;
+                    }
+                    let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
+                    diags.push(Diagnostic {
+                        file: filename.to_string(),
+                        line,
+                        col,
+                        start_byte: stmt.offset as usize,
+                        end_byte: lines.line_end(stmt.offset as usize),
+                        code: RuleCode::GlobalBuiltin,
+                        message: format!(
+                            "Global declaration of `{name}` overwrites a Python builtin; this affects the whole module"
+                        ),
+                        fix: None,
+                    });
+                }
+            }
+            StmtKind::FunctionDef(f) => {
+                check_global_order(&f.body, &arg_names(&f.args), filename, lines, diags);
+                enclosing.push(function_bindings(&f.args, &f.body));
+                walk(&f.body, enclosing, filename, lines, diags);
+                enclosing.pop();
+            }
+            StmtKind::ClassDef(c) => {
+                check_global_order(&c.body, &HashSet::new(), filename, lines, diags);
+                // Only the implicit `__class__` cell of its methods comes
+                // from a class body.
+                enclosing.push(HashSet::from(["__class__"]));
+                walk(&c.body, enclosing, filename, lines, diags);
+                enclosing.pop();
+            }
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                walk(body, enclosing, filename, lines, diags);
+                walk(orelse, enclosing, filename, lines, diags);
+            }
+            StmtKind::With { body, .. } => walk(body, enclosing, filename, lines, diags),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                walk(body, enclosing, filename, lines, diags);
+                for h in handlers {
+                    walk(&h.body, enclosing, filename, lines, diags);
+                }
+                walk(orelse, enclosing, filename, lines, diags);
+                walk(finalbody, enclosing, filename, lines, diags);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    walk(&arm.body, enclosing, filename, lines, diags);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Names local to a function: its parameters and everything its body binds
+/// or deletes, minus names it declares `global`.
+fn function_bindings<'src>(args: &Arguments<'src>, body: &[Stmt<'src>]) -> HashSet<&'src str> {
+    let mut bound = arg_names(args);
+    collect_module_bindings(body, &mut bound);
+    let mut globals = HashSet::new();
+    collect_declared(body, &mut globals, &mut bound);
+    bound.retain(|n| !globals.contains(n));
+    bound
+}
+
+/// Names declared `global` in `body` into `globals`, and names it `del`s
+/// into `deleted`, not entering nested scopes.
+fn collect_declared<'src>(
+    body: &[Stmt<'src>],
+    globals: &mut HashSet<&'src str>,
+    deleted: &mut HashSet<&'src str>,
+) {
+    for stmt in body {
+        match &stmt.kind {
+            StmtKind::Global(names) => globals.extend(names),
+            StmtKind::Delete(targets) => {
+                deleted.extend(targets.iter().filter_map(|t| match t.kind {
+                    ExprKind::Name(n, _) => Some(n),
+                    _ => None,
+                }));
+            }
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                collect_declared(body, globals, deleted);
+                collect_declared(orelse, globals, deleted);
+            }
+            StmtKind::With { body, .. } => collect_declared(body, globals, deleted),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                collect_declared(body, globals, deleted);
+                for h in handlers {
+                    collect_declared(&h.body, globals, deleted);
+                }
+                collect_declared(orelse, globals, deleted);
+                collect_declared(finalbody, globals, deleted);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    collect_declared(&arm.body, globals, deleted);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn arg_names<'src>(args: &Arguments<'src>) -> HashSet<&'src str> {
+    args.posonlyargs
+        .iter()
+        .chain(&args.args)
+        .chain(&args.vararg)
+        .chain(&args.kwonlyargs)
+        .chain(&args.kwarg)
+        .map(|a| a.name)
+        .collect()
+}
+
+// ── RP054: `global` after use ─────────────────────────────────────────────────
+
+/// Names one scope has bound or read so far, in source order.
+#[derive(Default)]
+struct Seen<'src> {
+    assigned: HashSet<&'src str>,
+    used: HashSet<String>,
+}
+
+impl<'src> Seen<'src> {
+    fn read(&mut self, info: &ExprInfo<'src>) {
+        self.used
+            .extend(info.names.iter().map(|(n, _)| n.to_string()));
+        self.assigned.extend(info.walrus.iter().map(|(n, _)| *n));
+    }
+
+    fn bind(&mut self, target: &AssignTarget<'src>) {
+        collect_target_bindings(target, &mut self.assigned);
+    }
+}
+
+/// Check every `global` statement of one scope body against what the body
+/// did before it.  Nested functions and classes are separate scopes.
+fn check_global_order<'src>(
+    body: &[Stmt<'src>],
+    params: &HashSet<&'src str>,
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    scan_in_order(body, params, &mut Seen::default(), filename, lines, diags);
+}
+
+fn scan_in_order<'src>(
+    stmts: &[Stmt<'src>],
+    params: &HashSet<&'src str>,
+    seen: &mut Seen<'src>,
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Global(names) => {
+                for name in names {
+                    let problem = if params.contains(name) {
+                        "is a parameter and declared `global`"
+                    } else if seen.assigned.contains(name) {
+                        "is assigned to before `global` declaration"
+                    } else if seen.used.contains(*name) {
+                        "is used prior to `global` declaration"
+                    } else {
+                        continue;
+                    };
+                    let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
+                    diags.push(Diagnostic {
+                        file: filename.to_string(),
+                        line,
+                        col,
+                        start_byte: stmt.offset as usize,
+                        end_byte: lines.line_end(stmt.offset as usize),
+                        code: RuleCode::GlobalAfterUse,
+                        message: format!("Name `{name}` {problem}"),
+                        fix: None,
+                    });
+                }
+            }
+            StmtKind::FunctionDef(f) => {
+                f.decorators.iter().for_each(|d| seen.read(d));
+                seen.assigned.insert(f.name);
+            }
+            StmtKind::ClassDef(c) => {
+                c.decorators
+                    .iter()
+                    .chain(&c.bases)
+                    .for_each(|e| seen.read(e));
+                seen.assigned.insert(c.name);
+            }
+            StmtKind::For {
+                target,
+                iter,
+                body,
+                orelse,
+                ..
+            } => {
+                seen.read(iter);
+                seen.bind(target);
+                scan_in_order(body, params, seen, filename, lines, diags);
+                scan_in_order(orelse, params, seen, filename, lines, diags);
+            }
+            StmtKind::If { test, body, orelse } | StmtKind::While { test, body, orelse } => {
+                seen.read(test);
+                scan_in_order(body, params, seen, filename, lines, diags);
+                scan_in_order(orelse, params, seen, filename, lines, diags);
+            }
+            StmtKind::With { items, body, .. } => {
+                for item in items {
+                    seen.read(&item.context);
+                    if let Some(t) = &item.target {
+                        seen.bind(t);
+                    }
+                }
+                scan_in_order(body, params, seen, filename, lines, diags);
+            }
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                scan_in_order(body, params, seen, filename, lines, diags);
+                for h in handlers {
+                    if let Some(t) = &h.type_expr {
+                        seen.read(t);
+                    }
+                    seen.assigned.extend(h.name.map(|(n, _)| n));
+                    scan_in_order(&h.body, params, seen, filename, lines, diags);
+                }
+                scan_in_order(orelse, params, seen, filename, lines, diags);
+                scan_in_order(finalbody, params, seen, filename, lines, diags);
+            }
+            StmtKind::Match { subject, arms } => {
+                seen.read(subject);
+                for arm in arms {
+                    seen.used
+                        .extend(arm.pattern_names.iter().map(|(n, _)| n.to_string()));
+                    seen.assigned.extend(arm.bindings.iter().map(|(n, _)| *n));
+                    scan_in_order(&arm.body, params, seen, filename, lines, diags);
+                }
+            }
+            _ => {
+                let one = std::slice::from_ref(stmt);
+                collect_stmt_names(one, &mut seen.used);
+                collect_module_bindings(one, &mut seen.assigned);
+            }
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_scope_declarations(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_nonlocal_without_binding_fires() {
+        let diags = check("def outer():\n    def inner():\n        nonlocal x\n        x = 1\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::UndefinedNonlocal);
+        assert_eq!((diags[0].line, diags[0].col), (3, 9));
+        assert!(diags[0].message.contains("`x`"));
+    }
+
+    #[test]
+    fn test_valid_nonlocal_clean() {
+        let src = "def outer():\n    x = 0\n    def inner():\n        nonlocal x\n        x += 1\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_deeply_nested_nonlocal_clean() {
+        let src = "def a():\n    x = 0\n    def b():\n        def c():\n            nonlocal x\n            x = 1\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_parameter_is_a_binding() {
+        let src =
+            "def outer(count):\n    def inner():\n        nonlocal count\n        count += 1\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_top_level_function_fires() {
+        assert_eq!(check("x = 0\ndef f():\n    nonlocal x\n").len(), 1);
+    }
+
+    #[test]
+    fn test_class_scope_is_skipped() {
+        let src = "def outer():\n    class C:\n        x = 0\n        def m(self):\n            nonlocal x\n";
+        assert_eq!(check(src).len(), 1);
+        let src =
+            "def outer():\n    x = 0\n    class C:\n        def m(self):\n            nonlocal x\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_global_in_enclosing_function_fires() {
+        let src = "def outer():\n    global x\n    x = 0\n    def inner():\n        nonlocal x\n";
+        assert_eq!(check(src).len(), 1);
+    }
+
+    #[test]
+    fn test_nonlocal_in_class_body_resolves_to_function() {
+        let src = "def f(x):\n    class C:\n        nonlocal x\n        x += 1\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_implicit_class_cell_and_del_are_bindings() {
+        let src = "class C:\n    def m(self):\n        nonlocal __class__\n";
+        assert!(check(src).is_empty());
+        let src = "def f():\n    def g():\n        nonlocal frame\n    del frame\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_each_missing_name_reported() {
+        let src = "def outer():\n    a = 0\n    def inner():\n        nonlocal a, b, c\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 2);
+        assert!(diags[0].message.contains("`b`"));
+        assert!(diags[1].message.contains("`c`"));
+    }
+
+    #[test]
+    fn test_global_after_assignment_fires() {
+        let diags = check("def f():\n    x = 1\n    global x\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::GlobalAfterUse);
+        assert_eq!((diags[0].line, diags[0].col), (3, 5));
+        assert_eq!(
+            diags[0].message,
+            "Name `x` is assigned to before `global` declaration"
+        );
+    }
+
+    #[test]
+    fn test_global_after_read_fires_with_own_message() {
+        let diags = check("def f():\n    print(x)\n    global x\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(
+            diags[0].message,
+            "Name `x` is used prior to `global` declaration"
+        );
+    }
+
+    #[test]
+    fn test_global_before_use_clean() {
+        assert!(check("def f():\n    global x\n    f(x)\n").is_empty());
+        assert!(check("def f():\n    global x\n    x = 1\n").is_empty());
+        assert!(check("def f():\n    global x\n    global x\n").is_empty());
+    }
+
+    #[test]
+    fn test_global_parameter_fires() {
+        let diags = check("def f(x):\n    global x\n");
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("parameter"));
+    }
+
+    #[test]
+    fn test_global_after_nested_block_assignment_fires() {
+        let src = "def f(a):\n    if a:\n        for x in a:\n            pass\n    global x\n";
+        assert_eq!(check(src).len(), 1);
+    }
+
+    #[test]
+    fn test_global_scopes_are_separate() {
+        let src = "x = 1\ndef f():\n    global x\n    x = 2\n";
+        assert!(check(src).is_empty());
+        let src = "def f():\n    def g():\n        x = 1\n    global x\n";
+        assert!(check(src).is_empty());
+        assert_eq!(check("x = 1\nglobal x\n").len(), 1);
+    }
+
+    #[test]
+    fn test_global_builtin_fires() {
+        let diags = check("def f():\n    global list\n    list = []\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::GlobalBuiltin);
+        assert_eq!((diags[0].line, diags[0].col), (2, 5));
+        assert_eq!(
+            diags[0].message,
+            "Global declaration of `list` overwrites a Python builtin; this affects the whole module"
+        );
+    }
+
+    #[test]
+    fn test_global_non_builtin_clean() {
+        assert!(check("def f():\n    global my_custom_name\n    my_custom_name = 1\n").is_empty());
+        assert!(check("import os\ndef f():\n    global os\n    os = None\n").is_empty());
+    }
+
+    #[test]
+    fn test_module_level_global_builtin_clean() {
+        assert!(check("global list\n").is_empty());
+    }
+}
@@ -0,0 +1,236 @@
+use crate::ast::{Stmt, StmtKind};
+use crate::builtins::is_builtin;
+use crate::location::LineIndex;
+use crate::types::{Diagnostic, RuleCode};
+
+/// RP071: an assignment whose target and value are the same expression —
+/// `x = x`, `obj.attr = obj.attr`, `items[0] = items[0]`.  It does nothing,
+/// and usually stands where a different right-hand side was meant.
+///
+/// Targets and values are compared as source text with whitespace removed.
+/// Only single-line, single-target assignments are considered, and
+/// expressions containing a call are skipped since evaluating them twice
+/// may not be idempotent.  `x = x` directly in a class body copies a name
+/// from the enclosing scope, and `dict = dict` at module level turns a
+/// builtin into a module attribute; neither is reported.
+pub fn check_self_assign<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    source: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    walk(stmts, Scope::Module, filename, source, lines, &mut diags);
+    diags
+}
+
+/// Where a statement runs, for the exemptions on bare names.
+#[derive(Clone, Copy, PartialEq)]
+enum Scope {
+    Module,
+    Class,
+    Function,
+}
+
+fn walk<'src>(
+    stmts: &[Stmt<'src>],
+    scope: Scope,
+    filename: &str,
+    source: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Assign { .. } => {
+                let Some(target) = self_assigned(source, stmt.offset as usize) else {
+                    continue;
+                };
+                let copies_name = match scope {
+                    Scope::Module => is_builtin(target),
+                    Scope::Class => is_identifier(target),
+                    Scope::Function => false,
+                };
+                if copies_name {
+                    continue;
+                }
+                let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
+                diags.push(Diagnostic {
+                    file: filename.to_string(),
+                    line,
+                    col,
+                    start_byte: stmt.offset as usize,
+                    end_byte: lines.line_end(stmt.offset as usize),
+                    code: RuleCode::SelfAssignment,
+                    message: format!("`{target}` is assigned to itself"),
+                    fix: None,
+                });
+            }
+            StmtKind::FunctionDef(f) => {
+                walk(&f.body, Scope::Function, filename, source, lines, diags)
+            }
+            StmtKind::ClassDef(c) => walk(&c.body, Scope::Class, filename, source, lines, diags),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                walk(body, scope, filename, source, lines, diags);
+                walk(orelse, scope, filename, source, lines, diags);
+            }
+            StmtKind::With { body, .. } => walk(body, scope, filename, source, lines, diags),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                walk(body, scope, filename, source, lines, diags);
+                for h in handlers {
+                    walk(&h.body, scope, filename, source, lines, diags);
+                }
+                walk(orelse, scope, filename, source, lines, diags);
+                walk(finalbody, scope, filename, source, lines, diags);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    walk(&arm.body, scope, filename, source, lines, diags);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The target of the assignment starting at `offset` when its value is the
+/// same expression, else `None`.
+fn self_assigned(source: &str, offset: usize) -> Option<&str> {
+    let rest = source.get(offset..)?;
+    let (target, value) = split_assignment(rest)?;
+    let (target, value) = (target.trim(), value.trim());
+    let same = target
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .eq(value.chars().filter(|c| !c.is_whitespace()));
+    (same && !target.is_empty() && !target.contains('(')).then_some(target)
+}
+
+/// Split a one-line `target = value` statement at its `=`.  `None` for
+/// chained or augmented assignments and for statements that continue past
+/// the end of the line.
+fn split_assignment(stmt: &str) -> Option<(&str, &str)> {
+    let bytes = stmt.as_bytes();
+    let mut depth = 0i32;
+    let mut quote: Option<u8> = None;
+    let mut eq = None;
+    let mut end = bytes.len();
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if let Some(q) = quote {
+            match b {
+                b'\\' => i += 1,
+                b'\n' => return None,
+                _ if b == q => quote = None,
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+        match b {
+            b'\'' | b'"' => quote = Some(b),
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth -= 1,
+            b'\\' => return None,
+            b'\n' | b'#' | b';' if depth == 0 => {
+                end = i;
+                break;
+            }
+            b'=' if depth == 0 => {
+                let prev = i.checked_sub(1).map(|p| bytes[p]);
+                let next = bytes.get(i + 1).copied();
+                let compound = matches!(
+                    prev,
+                    Some(b'=' | b'!' | b'<' | b'>' | b':' | b'+' | b'-' | b'*' | b'/')
+                        | Some(b'%' | b'&' | b'|' | b'^' | b'@')
+                ) || next == Some(b'=');
+                if compound || eq.is_some() {
+                    return None;
+                }
+                eq = Some(i);
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    if depth != 0 || quote.is_some() {
+        return None;
+    }
+    let eq = eq?;
+    Some((&stmt[..eq], &stmt[eq + 1..end]))
+}
+
+/// True for a bare identifier such as `name`.
+fn is_identifier(s: &str) -> bool {
+    s.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_self_assign(&stmts, "test.py", src, &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_name_self_assign_fires() {
+        let diags = check("def f(x):\n    x = x\n    return x\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::SelfAssignment);
+        assert_eq!((diags[0].line, diags[0].col), (2, 5));
+        assert_eq!(diags[0].message, "`x` is assigned to itself");
+    }
+
+    #[test]
+    fn test_attribute_and_subscript_self_assign_fire() {
+        let diags = check("obj.attr = obj.attr\nlst[0] = lst[ 0 ]\n");
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].message, "`obj.attr` is assigned to itself");
+        assert_eq!(diags[1].message, "`lst[0]` is assigned to itself");
+    }
+
+    #[test]
+    fn test_different_value_clean() {
+        assert!(check("obj.attr = other.attr\n").is_empty());
+        assert!(check("lst[0] = lst[1]\n").is_empty());
+        assert!(check("x = x + 1\n").is_empty());
+        assert!(check("x == x\n").is_empty());
+    }
+
+    #[test]
+    fn test_calls_and_chains_clean() {
+        assert!(check("f().x = f().x\n").is_empty());
+        assert!(check("a = b = a\n").is_empty());
+    }
+
+    #[test]
+    fn test_copied_names_clean() {
+        assert!(check("x = 1\nclass A:\n    x = x\n").is_empty());
+        assert!(check("dict = dict\n").is_empty());
+        assert_eq!(check("def f():\n    dict = dict\n").len(), 1);
+        assert_eq!(
+            check("class A:\n    def f(self):\n        self.v = self.v\n").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_multiline_and_comments() {
+        assert!(check("x = (\n    x\n)\n").is_empty());
+        assert_eq!(check("x = x  # noop = yes\n").len(), 1);
+        assert!(check("s = \"a\" ; s = s2\n").is_empty());
+    }
+}
@@ -0,0 +1,232 @@
+use crate::ast::{AssignTarget, FuncDef, Stmt, StmtKind};
+use crate::builtins::BUILTINS;
+use crate::location::LineIndex;
+use crate::types::{Diagnostic, RuleCode};
+
+/// RP012: a module- or function-scope binding that reuses a builtin name —
+/// `list = []`, `def input(): ...`, a parameter called `id`.  Every later
+/// read in that scope gets the local value instead of the builtin.
+///
+/// Names bound directly in a class body are exempt: `def id(self)` becomes
+/// an attribute and never hides the builtin from other code.
+pub fn check_shadowed_builtins<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    walk(stmts, false, filename, lines, &mut diags);
+    diags
+}
+
+fn walk<'src>(
+    stmts: &[Stmt<'src>],
+    in_class: bool,
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Assign { targets, .. } if !in_class => {
+                for t in targets {
+                    check_target(t, filename, lines, diags);
+                }
+            }
+            StmtKind::AnnAssign { target, .. } if !in_class => {
+                check_target(target, filename, lines, diags);
+            }
+            StmtKind::FunctionDef(f) => {
+                if !in_class && is_builtin_name(f.name) {
+                    diags.push(definition(f.name, "Function", stmt.offset, filename, lines));
+                }
+                check_args(f, filename, lines, diags);
+                walk(&f.body, false, filename, lines, diags);
+            }
+            StmtKind::ClassDef(c) => {
+                if !in_class && is_builtin_name(c.name) {
+                    diags.push(definition(c.name, "Class", stmt.offset, filename, lines));
+                }
+                walk(&c.body, true, filename, lines, diags);
+            }
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                walk(body, in_class, filename, lines, diags);
+                walk(orelse, in_class, filename, lines, diags);
+            }
+            StmtKind::With { body, .. } => walk(body, in_class, filename, lines, diags),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                walk(body, in_class, filename, lines, diags);
+                for h in handlers {
+                    walk(&h.body, in_class, filename, lines, diags);
+                }
+                walk(orelse, in_class, filename, lines, diags);
+                walk(finalbody, in_class, filename, lines, diags);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    walk(&arm.body, in_class, filename, lines, diags);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Parameters of `f` named after a builtin.
+fn check_args<'src>(
+    f: &FuncDef<'src>,
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    let params = f
+        .args
+        .posonlyargs
+        .iter()
+        .chain(f.args.args.iter())
+        .chain(f.args.vararg.iter())
+        .chain(f.args.kwonlyargs.iter())
+        .chain(f.args.kwarg.iter());
+    for arg in params.filter(|a| is_builtin_name(a.name)) {
+        diags.push(named(
+            arg.name,
+            arg.offset,
+            format!("Parameter `{}` shadows a builtin", arg.name),
+            filename,
+            lines,
+        ));
+    }
+}
+
+fn check_target<'src>(
+    target: &AssignTarget<'src>,
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    match target {
+        AssignTarget::Name(n, o) if is_builtin_name(n) => diags.push(named(
+            n,
+            *o,
+            format!("Variable `{n}` shadows a builtin"),
+            filename,
+            lines,
+        )),
+        AssignTarget::Tuple(elts) | AssignTarget::List(elts) => {
+            for e in elts {
+                check_target(e, filename, lines, diags);
+            }
+        }
+        AssignTarget::Starred(inner) => check_target(inner, filename, lines, diags),
+        _ => {}
+    }
+}
+
+fn is_builtin_name(name: &str) -> bool {
+    BUILTINS.binary_search(&name).is_ok()
+}
+
+fn named(
+    name: &str,
+    offset: u32,
+    message: String,
+    filename: &str,
+    lines: &LineIndex,
+) -> Diagnostic {
+    let (line, col) = lines.offset_to_line_col(offset as usize);
+    Diagnostic {
+        file: filename.to_string(),
+        line,
+        col,
+        start_byte: offset as usize,
+        end_byte: offset as usize + name.len(),
+        code: RuleCode::ShadowedBuiltin,
+        message,
+        fix: None,
+    }
+}
+
+fn definition(
+    name: &str,
+    kind: &str,
+    offset: u32,
+    filename: &str,
+    lines: &LineIndex,
+) -> Diagnostic {
+    let (line, col) = lines.offset_to_line_col(offset as usize);
+    Diagnostic {
+        file: filename.to_string(),
+        line,
+        col,
+        start_byte: offset as usize,
+        end_byte: lines.line_end(offset as usize),
+        code: RuleCode::ShadowedBuiltin,
+        message: format!("{kind} `{name}` shadows a builtin"),
+        fix: None,
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_shadowed_builtins(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_assignment_to_builtin_fires() {
+        let diags = check("list = []\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::ShadowedBuiltin);
+        assert_eq!((diags[0].line, diags[0].col), (1, 1));
+        assert_eq!(diags[0].message, "Variable `list` shadows a builtin");
+    }
+
+    #[test]
+    fn test_builtin_method_call_clean() {
+        assert!(check("items = []\nitems.append(x)\nlist.append(items, x)\n").is_empty());
+    }
+
+    #[test]
+    fn test_annotated_and_unpacked_targets_fire() {
+        assert_eq!(check("def f():\n    id: int = 3\n").len(), 1);
+        assert_eq!(check("a, type = v\n").len(), 1);
+    }
+
+    #[test]
+    fn test_parameter_named_input_fires() {
+        let diags = check("def ask(input, *, max=3):\n    return input\n");
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].message, "Parameter `input` shadows a builtin");
+        assert_eq!((diags[0].line, diags[0].col), (1, 9));
+    }
+
+    #[test]
+    fn test_function_and_class_definitions_fire() {
+        let diags = check("def open(path):\n    pass\nclass dict:\n    pass\n");
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].message, "Function `open` shadows a builtin");
+        assert_eq!(diags[1].message, "Class `dict` shadows a builtin");
+    }
+
+    #[test]
+    fn test_class_body_names_exempt() {
+        let src = "class Row:\n    id = None\n    def type(self):\n        return self.id\n";
+        assert!(check(src).is_empty());
+        // …but a method's own locals still count.
+        let src = "class Row:\n    def load(self):\n        max = 3\n        return max\n";
+        assert_eq!(check(src).len(), 1);
+    }
+}
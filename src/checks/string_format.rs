@@ -0,0 +1,426 @@
+use crate::ast::{AssignTarget, ExprInfo, FormatCall, FormatStyle, Stmt, StmtKind};
+use crate::location::LineIndex;
+use crate::types::{Diagnostic, RuleCode};
+
+/// RP065: a string literal formatted in place with the wrong number of
+/// positional arguments — `"{} {}".format(a)` raises `IndexError`,
+/// `"%s %s" % (a,)` raises `TypeError`.
+///
+/// Only literals followed directly by `.format(…)` or `% (…)` are checked,
+/// and only when both counts are known: templates with named fields
+/// (`{name}`, `%(name)s`) and calls that unpack `*args` are skipped.
+pub fn check_string_format<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    for_each_expr(stmts, &mut |_, info| {
+        for call in &info.format_calls {
+            let Some(expected) = expected_args(call) else {
+                continue;
+            };
+            if expected == call.args {
+                continue;
+            }
+            let (line, col) = lines.offset_to_line_col(call.offset as usize);
+            diags.push(Diagnostic {
+                file: filename.to_string(),
+                line,
+                col,
+                start_byte: call.offset as usize,
+                end_byte: lines.line_end(call.offset as usize),
+                code: RuleCode::StringFormatMismatch,
+                message: format!("Format string expects {expected} args, got {}", call.args),
+                fix: None,
+            });
+        }
+    });
+    diags
+}
+
+/// RP066: an f-string with no replacement fields — `f"hello"` is just a
+/// plain string with an extra prefix.
+pub fn check_pointless_fstrings<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    for_each_expr(stmts, &mut |_, info| {
+        for &offset in &info.plain_fstrings {
+            let (line, col) = lines.offset_to_line_col(offset as usize);
+            diags.push(Diagnostic {
+                file: filename.to_string(),
+                line,
+                col,
+                start_byte: offset as usize,
+                end_byte: lines.line_end(offset as usize),
+                code: RuleCode::PointlessFString,
+                message: "f-string has no placeholders; use a plain string".to_string(),
+                fix: None,
+            });
+        }
+    });
+    diags
+}
+
+/// RP067: adjacent string literals inside brackets, which Python joins into
+/// one — as a list or tuple item this is usually a missing comma.  Text
+/// wrapped over lines in call arguments, dict values or a parenthesised
+/// group is idiomatic and exempt, as are `__all__` entries.
+pub fn check_implicit_concatenation<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    for_each_expr(stmts, &mut |stmt, info| {
+        if is_dunder_all_assignment(stmt) {
+            return;
+        }
+        for &offset in &info.implicit_concats {
+            let (line, col) = lines.offset_to_line_col(offset as usize);
+            diags.push(Diagnostic {
+                file: filename.to_string(),
+                line,
+                col,
+                start_byte: offset as usize,
+                end_byte: lines.line_end(offset as usize),
+                code: RuleCode::ImplicitStringConcat,
+                message: "Implicit string concatenation inside parentheses; use `+` or a list if intentional".to_string(),
+                fix: None,
+            });
+        }
+    });
+    diags
+}
+
+fn is_dunder_all_assignment(stmt: &Stmt<'_>) -> bool {
+    match &stmt.kind {
+        StmtKind::Assign { targets, .. } => targets
+            .iter()
+            .any(|t| matches!(t, AssignTarget::Name("__all__", _))),
+        StmtKind::AugAssign { target, .. } | StmtKind::AnnAssign { target, .. } => {
+            matches!(target, AssignTarget::Name("__all__", _))
+        }
+        _ => false,
+    }
+}
+
+fn expected_args(call: &FormatCall) -> Option<usize> {
+    match call.style {
+        FormatStyle::Brace => brace_placeholders(&call.template),
+        FormatStyle::Percent => percent_placeholders(&call.template),
+    }
+}
+
+/// Positional arguments a `str.format` template consumes: the number of
+/// `{}` fields, or one past the highest `{N}`.  `None` when the template
+/// mixes the two (a `ValueError` of its own) or is malformed.
+fn brace_placeholders(template: &str) -> Option<usize> {
+    let mut auto = 0;
+    let mut needed = 0;
+    let mut fields = Vec::new();
+    collect_fields(template, &mut fields)?;
+    for field in fields {
+        let name = field.split(['!', ':', '.', '[']).next().unwrap_or("");
+        if name.is_empty() {
+            auto += 1;
+        } else if let Ok(index) = name.parse::<usize>() {
+            needed = needed.max(index + 1);
+        }
+    }
+    match (auto, needed) {
+        (0, n) => Some(n),
+        (n, 0) => Some(n),
+        _ => None,
+    }
+}
+
+/// Push the contents of every replacement field in `template`, including
+/// fields nested in a format spec (`{:{width}}`).
+fn collect_fields<'a>(template: &'a str, out: &mut Vec<&'a str>) -> Option<()> {
+    let bytes = template.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' if bytes.get(i + 1) == Some(&b'{') => i += 2,
+            b'}' if bytes.get(i + 1) == Some(&b'}') => i += 2,
+            b'{' => {
+                let mut depth = 1;
+                let mut end = i + 1;
+                while depth > 0 {
+                    match bytes.get(end)? {
+                        b'{' => depth += 1,
+                        b'}' => depth -= 1,
+                        _ => {}
+                    }
+                    end += 1;
+                }
+                let field = &template[i + 1..end - 1];
+                // The spec after the first `:` may hold nested fields.
+                match field.find(['{', ':']) {
+                    Some(colon) if field.as_bytes()[colon] == b':' => {
+                        out.push(&field[..colon]);
+                        collect_fields(&field[colon + 1..], out)?;
+                    }
+                    _ => out.push(field),
+                }
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    Some(())
+}
+
+/// Arguments a printf-style template consumes, counting `*` widths and
+/// precisions.  `None` for mapping keys (`%(name)s`) or an unknown
+/// conversion.
+fn percent_placeholders(template: &str) -> Option<usize> {
+    let bytes = template.as_bytes();
+    let mut count = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'%' {
+            i += 1;
+            continue;
+        }
+        i += 1;
+        match bytes.get(i) {
+            Some(b'%') => {
+                i += 1;
+                continue;
+            }
+            Some(b'(') => return None,
+            _ => {}
+        }
+        while matches!(bytes.get(i), Some(b'#' | b'0' | b'-' | b' ' | b'+')) {
+            i += 1;
+        }
+        i = skip_width(bytes, i, &mut count);
+        if bytes.get(i) == Some(&b'.') {
+            i = skip_width(bytes, i + 1, &mut count);
+        }
+        while matches!(bytes.get(i), Some(b'h' | b'l' | b'L')) {
+            i += 1;
+        }
+        match bytes.get(i) {
+            Some(c) if b"diouxXeEfFgGcrsa".contains(c) => {
+                count += 1;
+                i += 1;
+            }
+            _ => return None,
+        }
+    }
+    Some(count)
+}
+
+/// Skip a width or precision at `i`; a `*` takes an argument of its own.
+fn skip_width(bytes: &[u8], mut i: usize, count: &mut usize) -> usize {
+    if bytes.get(i) == Some(&b'*') {
+        *count += 1;
+        return i + 1;
+    }
+    while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+        i += 1;
+    }
+    i
+}
+
+/// Call `f` on every expression in `stmts`, in every scope, together with
+/// the innermost statement holding it.
+fn for_each_expr<'src>(stmts: &[Stmt<'src>], f: &mut impl FnMut(&Stmt<'src>, &ExprInfo<'src>)) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::FunctionDef(func) => {
+                func.decorators.iter().for_each(|e| f(stmt, e));
+                let args = &func.args;
+                args.posonlyargs
+                    .iter()
+                    .chain(&args.args)
+                    .chain(&args.kwonlyargs)
+                    .filter_map(|a| a.default.as_ref())
+                    .for_each(|e| f(stmt, e));
+                for_each_expr(&func.body, f);
+            }
+            StmtKind::ClassDef(c) => {
+                c.decorators.iter().chain(&c.bases).for_each(|e| f(stmt, e));
+                for_each_expr(&c.body, f);
+            }
+            StmtKind::Assign { value, .. } | StmtKind::AugAssign { value, .. } => f(stmt, value),
+            StmtKind::AnnAssign { value, .. } => value.iter().for_each(|e| f(stmt, e)),
+            StmtKind::Expr(info) => f(stmt, info),
+            StmtKind::Return(value) => value.iter().for_each(|e| f(stmt, e)),
+            StmtKind::Raise { exc, cause } => exc.iter().chain(cause).for_each(|e| f(stmt, e)),
+            StmtKind::Assert { test, msg } => {
+                f(stmt, test);
+                msg.iter().for_each(|e| f(stmt, e));
+            }
+            StmtKind::Delete(targets) => targets.iter().for_each(|e| f(stmt, e)),
+            StmtKind::For {
+                iter, body, orelse, ..
+            } => {
+                f(stmt, iter);
+                for_each_expr(body, f);
+                for_each_expr(orelse, f);
+            }
+            StmtKind::While { test, body, orelse } | StmtKind::If { test, body, orelse } => {
+                f(stmt, test);
+                for_each_expr(body, f);
+                for_each_expr(orelse, f);
+            }
+            StmtKind::With { items, body, .. } => {
+                items.iter().for_each(|item| f(stmt, &item.context));
+                for_each_expr(body, f);
+            }
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                for_each_expr(body, f);
+                for h in handlers {
+                    h.type_expr.iter().for_each(|e| f(stmt, e));
+                    for_each_expr(&h.body, f);
+                }
+                for_each_expr(orelse, f);
+                for_each_expr(finalbody, f);
+            }
+            StmtKind::Match { subject, arms } => {
+                f(stmt, subject);
+                for arm in arms {
+                    for_each_expr(&arm.body, f);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_string_format(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_too_few_format_args_fires() {
+        let diags = check("print(\"{} {}\".format(a))\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::StringFormatMismatch);
+        assert_eq!((diags[0].line, diags[0].col), (1, 7));
+        assert_eq!(diags[0].message, "Format string expects 2 args, got 1");
+    }
+
+    #[test]
+    fn test_matching_format_args_clean() {
+        assert!(check("s = \"{} {}\".format(a, b)\n").is_empty());
+        assert!(check("s = \"{0} {0} {{}}\".format(a)\n").is_empty());
+        assert!(check("s = \"{} {name}\".format(a, name=b)\n").is_empty());
+        assert!(check("s = \"{:>{}}\".format(a, width)\n").is_empty());
+    }
+
+    #[test]
+    fn test_unknowable_format_args_skipped() {
+        assert!(check("s = \"{} {}\".format(*pair)\n").is_empty());
+        assert!(check("s = \"{} {1}\".format(a)\n").is_empty());
+        assert!(check("s = (\"{} \"\n     \"{}\".format(a))\n").is_empty());
+        assert!(check("s = \"%s \" \\\n    \"%s\" % (a, b)\n").is_empty());
+    }
+
+    #[test]
+    fn test_percent_tuple_mismatch_fires() {
+        let diags = check("def f(a):\n    return \"%s %s\" % (a,)\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].message, "Format string expects 2 args, got 1");
+        assert_eq!(check("log(\"%d%%\" % (a, b))\n").len(), 1);
+    }
+
+    #[test]
+    fn test_percent_clean_and_skipped() {
+        assert!(check("s = \"%s=%r\" % (k, v)\n").is_empty());
+        assert!(check("s = \"%*d\" % (width, n)\n").is_empty());
+        assert!(check("s = \"%s %s\" % pair\n").is_empty());
+        assert!(check("s = \"%(a)s\" % {\"a\": 1}\n").is_empty());
+        assert!(check("s = \"%s\" % \"x\"\n").is_empty());
+    }
+
+    fn check_fstrings(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_pointless_fstrings(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_fstring_without_placeholders_fires() {
+        let diags = check_fstrings("x = f\"hello\"\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::PointlessFString);
+        assert_eq!((diags[0].line, diags[0].col), (1, 5));
+        assert_eq!(check_fstrings("x = f\"\"\n").len(), 1);
+        assert_eq!(check_fstrings("x = rf\"raw no interp\"\n").len(), 1);
+        assert_eq!(check_fstrings("print(f\"{{literal}}\")\n").len(), 1);
+    }
+
+    #[test]
+    fn test_fstring_with_placeholders_clean() {
+        assert!(check_fstrings("x = f\"hello {name}\"\n").is_empty());
+        assert!(check_fstrings("x = f\"{{{name}}}\"\n").is_empty());
+    }
+
+    #[test]
+    fn test_implicit_concatenation_is_one_fstring() {
+        assert!(check_fstrings("x = (f\"a: \"\n     f\"{b}\")\n").is_empty());
+        assert!(check_fstrings("x = f\"a \" \"{b}\" f\"{c}\"\n").is_empty());
+        let diags = check_fstrings("x = (\"a \"\n     f\"b\")\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].col, 6);
+    }
+
+    fn check_concat(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_implicit_concatenation(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_parenthesised_concatenation_fires() {
+        let diags = check_concat("x = (\"hello\" \"world\")\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::ImplicitStringConcat);
+        assert_eq!((diags[0].line, diags[0].col), (1, 14));
+        let src = "names = [\n    \"alice\",\n    \"bob\"\n    \"carol\",\n]\n";
+        assert_eq!(check_concat(src)[0].line, 4);
+    }
+
+    #[test]
+    fn test_one_report_per_run() {
+        assert_eq!(
+            check_concat("x = [\"a\" \"b\" \"c\", \"d\" \"e\"]\n").len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_call_arguments_clean() {
+        assert!(check_concat("raise ValueError(\"a \"\n                 \"b\")\n").is_empty());
+        assert_eq!(check_concat("f([\"a\" \"b\"])\n").len(), 1);
+        assert!(check_concat("d = {\"k\": \"a \"\n     \"b\"}\n").is_empty());
+        assert!(check_concat("msg = (\"a \"\n       \"b\")\n").is_empty());
+        assert_eq!(check_concat("t = (1,\n     \"a\"\n     \"b\")\n").len(), 1);
+    }
+
+    #[test]
+    fn test_unbracketed_and_exempt_concatenation_clean() {
+        assert!(check_concat("x = \"hello\" \"world\"\n").is_empty());
+        assert!(check_concat("__all__ = (\"a\" \"b\",)\n").is_empty());
+        assert!(check_concat("x = [\"a\", \"b\"]\n").is_empty());
+    }
+}
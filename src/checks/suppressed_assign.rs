@@ -0,0 +1,216 @@
+use crate::ast::{ExprKind, Stmt, StmtKind, WithItem};
+use crate::location::LineIndex;
+use crate::names::{collect_module_bindings, collect_stmt_names};
+use crate::types::{Diagnostic, RuleCode};
+use std::collections::HashSet;
+
+/// Context managers that swallow an exception raised in their body, so the
+/// code after the `with` runs even when the body stopped half way.
+const SWALLOWING_MANAGERS: &[&str] = &["assertRaises", "assertRaisesRegex", "raises", "suppress"];
+
+/// RP074: a name assigned inside `with suppress(...)` (or `pytest.raises`,
+/// `self.assertRaises`) and read after the block.  When the manager swallows
+/// an exception raised before the assignment, the read is a `NameError`.
+///
+/// Only reads in the statements following the `with` in the same block are
+/// considered, and a name bound before the `with` in that block (or a
+/// parameter) keeps its old value and is not reported.
+pub fn check_suppressed_assignments<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    walk(stmts, HashSet::new(), filename, lines, &mut diags);
+    diags
+}
+
+/// `bound` holds the names already bound in the scope when `stmts` starts.
+fn walk<'src>(
+    stmts: &[Stmt<'src>],
+    mut bound: HashSet<&'src str>,
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for (i, stmt) in stmts.iter().enumerate() {
+        match &stmt.kind {
+            StmtKind::With { items, body, .. } => {
+                if items.iter().any(swallows_exceptions) {
+                    let mut assigned = HashSet::new();
+                    collect_module_bindings(body, &mut assigned);
+                    assigned.retain(|n| !bound.contains(n));
+                    for name in read_before_rebinding(assigned, &stmts[i + 1..]) {
+                        let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
+                        diags.push(Diagnostic {
+                            file: filename.to_string(),
+                            line,
+                            col,
+                            start_byte: stmt.offset as usize,
+                            end_byte: lines.line_end(stmt.offset as usize),
+                            code: RuleCode::SuppressedAssignment,
+                            message: format!(
+                                "Variable `{name}` assigned inside `with` block may not be defined if the block exits early via exception"
+                            ),
+                            fix: None,
+                        });
+                    }
+                }
+                walk(body, bound.clone(), filename, lines, diags);
+            }
+            StmtKind::FunctionDef(f) => {
+                let args = &f.args;
+                let params = args
+                    .posonlyargs
+                    .iter()
+                    .chain(&args.args)
+                    .chain(&args.vararg)
+                    .chain(&args.kwonlyargs)
+                    .chain(&args.kwarg)
+                    .map(|a| a.name)
+                    .collect();
+                walk(&f.body, params, filename, lines, diags);
+            }
+            StmtKind::ClassDef(c) => walk(&c.body, HashSet::new(), filename, lines, diags),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                walk(body, bound.clone(), filename, lines, diags);
+                walk(orelse, bound.clone(), filename, lines, diags);
+            }
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                walk(body, bound.clone(), filename, lines, diags);
+                for h in handlers {
+                    walk(&h.body, bound.clone(), filename, lines, diags);
+                }
+                walk(orelse, bound.clone(), filename, lines, diags);
+                walk(finalbody, bound.clone(), filename, lines, diags);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    walk(&arm.body, bound.clone(), filename, lines, diags);
+                }
+            }
+            _ => {}
+        }
+        collect_module_bindings(std::slice::from_ref(stmt), &mut bound);
+    }
+}
+
+/// The names in `pending` that `stmts` reads before binding them again,
+/// sorted.  A rebinding nested in a later block (a loop body, one branch of
+/// an `if`) may not run, but it still ends the search: the name is not
+/// reported rather than guessed at.
+fn read_before_rebinding<'src>(
+    mut pending: HashSet<&'src str>,
+    stmts: &[Stmt<'src>],
+) -> Vec<&'src str> {
+    let mut read = Vec::new();
+    for stmt in stmts {
+        if pending.is_empty() {
+            break;
+        }
+        let mut names = HashSet::new();
+        collect_stmt_names(std::slice::from_ref(stmt), &mut names);
+        pending.retain(|n| {
+            let is_read = names.contains(*n);
+            if is_read {
+                read.push(*n);
+            }
+            !is_read
+        });
+        let mut rebound = HashSet::new();
+        collect_module_bindings(std::slice::from_ref(stmt), &mut rebound);
+        pending.retain(|n| !rebound.contains(n));
+    }
+    read.sort_unstable();
+    read
+}
+
+/// True for `suppress(...)`, `contextlib.suppress(...)`, `pytest.raises(...)`
+/// and the like.
+fn swallows_exceptions(item: &WithItem<'_>) -> bool {
+    match item.context.kind {
+        ExprKind::Call { func, .. } | ExprKind::Attr(_, func) => {
+            SWALLOWING_MANAGERS.contains(&func)
+        }
+        _ => false,
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_suppressed_assignments(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_read_after_suppress_fires() {
+        let src =
+            "def f(d):\n    with suppress(KeyError):\n        value = d[\"k\"]\n    return value\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::SuppressedAssignment);
+        assert_eq!((diags[0].line, diags[0].col), (2, 5));
+        assert_eq!(
+            diags[0].message,
+            "Variable `value` assigned inside `with` block may not be defined if the block exits early via exception"
+        );
+    }
+
+    #[test]
+    fn test_pytest_raises_fires() {
+        let src = "def test_f():\n    with pytest.raises(E):\n        result = f()\n    assert result is None\n";
+        assert_eq!(check(src).len(), 1);
+    }
+
+    #[test]
+    fn test_read_only_inside_block_clean() {
+        let src = "with contextlib.suppress(OSError):\n    data = load()\n    use(data)\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_bound_before_clean() {
+        let src = "value = None\nwith suppress(KeyError):\n    value = d[\"k\"]\nprint(value)\n";
+        assert!(check(src).is_empty());
+        let src = "def f(value):\n    with suppress(KeyError):\n        value = d[\"k\"]\n    return value\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_ordinary_context_manager_clean() {
+        let src = "with lock:\n    result = compute()\nprint(result)\n";
+        assert!(check(src).is_empty());
+        let src = "with open(path) as fh:\n    data = fh.read()\nprint(data)\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_rebound_before_read_clean() {
+        let src = "with suppress(E):\n    resp = f()\nresp = g()\nprint(resp)\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_rebound_in_nested_block_clean() {
+        let src = "with suppress(E):\n    x = f()\nfor i in y:\n    x = i\nprint(x)\n";
+        assert!(check(src).is_empty());
+        let src = "with suppress(E):\n    x = f()\nif y:\n    x = 1\nprint(x)\n";
+        assert!(check(src).is_empty());
+        // A read in the nested block before its rebinding is still reported.
+        let src = "with suppress(E):\n    x = f()\nfor i in y:\n    print(x)\n    x = i\n";
+        assert_eq!(check(src).len(), 1);
+    }
+}
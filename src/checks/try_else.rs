@@ -0,0 +1,185 @@
+use crate::ast::{ExprKind, Stmt, StmtKind};
+use crate::location::LineIndex;
+use crate::types::{Diagnostic, RuleCode};
+
+/// RP057: a `try` with an `else:` clause whose body could simply follow the
+/// guarded code inside `try`.  An `else` body that raises is exempt: moved
+/// into `try`, its exceptions would reach the handlers.
+///
+/// RP063: a `try` whose body is only `pass` or `...` — nothing in it can
+/// raise, so its `except` handlers are dead code.
+pub fn check_try_else<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    walk(stmts, filename, lines, &mut diags);
+    diags
+}
+
+fn walk<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                if !handlers.is_empty() && is_empty_body(body) {
+                    let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
+                    diags.push(Diagnostic {
+                        file: filename.to_string(),
+                        line,
+                        col,
+                        start_byte: stmt.offset as usize,
+                        end_byte: lines.line_end(stmt.offset as usize),
+                        code: RuleCode::EmptyTryBody,
+                        message: "Try block body is `pass`; `except` handlers are never triggered"
+                            .to_string(),
+                        fix: None,
+                    });
+                }
+                if let Some(first) = orelse.first()
+                    && !handlers.is_empty()
+                    && !contains_raise(orelse)
+                {
+                    let (line, col) = lines.offset_to_line_col(first.offset as usize);
+                    diags.push(Diagnostic {
+                        file: filename.to_string(),
+                        line,
+                        col,
+                        start_byte: first.offset as usize,
+                        end_byte: lines.line_end(first.offset as usize),
+                        code: RuleCode::TryElseBody,
+                        message: "Try/else clause body could be moved into the `try` block"
+                            .to_string(),
+                        fix: None,
+                    });
+                }
+                walk(body, filename, lines, diags);
+                for h in handlers {
+                    walk(&h.body, filename, lines, diags);
+                }
+                walk(orelse, filename, lines, diags);
+                walk(finalbody, filename, lines, diags);
+            }
+            StmtKind::FunctionDef(f) => walk(&f.body, filename, lines, diags),
+            StmtKind::ClassDef(c) => walk(&c.body, filename, lines, diags),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                walk(body, filename, lines, diags);
+                walk(orelse, filename, lines, diags);
+            }
+            StmtKind::With { body, .. } => walk(body, filename, lines, diags),
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    walk(&arm.body, filename, lines, diags);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// True for a body that is a lone `pass` or `...`.
+fn is_empty_body(body: &[Stmt<'_>]) -> bool {
+    match body {
+        [only] => match &only.kind {
+            StmtKind::Pass => true,
+            StmtKind::Expr(info) => matches!(info.kind, ExprKind::EllipsisLit),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// True when `body` has a `raise` outside nested functions and classes.
+fn contains_raise(body: &[Stmt<'_>]) -> bool {
+    body.iter().any(|stmt| match &stmt.kind {
+        StmtKind::Raise { .. } => true,
+        StmtKind::If { body, orelse, .. }
+        | StmtKind::While { body, orelse, .. }
+        | StmtKind::For { body, orelse, .. } => contains_raise(body) || contains_raise(orelse),
+        StmtKind::With { body, .. } => contains_raise(body),
+        StmtKind::Try {
+            body,
+            handlers,
+            orelse,
+            finalbody,
+        } => {
+            contains_raise(body)
+                || handlers.iter().any(|h| contains_raise(&h.body))
+                || contains_raise(orelse)
+                || contains_raise(finalbody)
+        }
+        StmtKind::Match { arms, .. } => arms.iter().any(|arm| contains_raise(&arm.body)),
+        _ => false,
+    })
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_try_else(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_try_else_fires() {
+        let diags = check("try:\n    risky()\nexcept E:\n    pass\nelse:\n    do_more()\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::TryElseBody);
+        assert_eq!((diags[0].line, diags[0].col), (6, 5));
+    }
+
+    #[test]
+    fn test_try_without_else_clean() {
+        assert!(check("try:\n    f()\nexcept E:\n    g()\n").is_empty());
+    }
+
+    #[test]
+    fn test_else_that_raises_clean() {
+        let src = "try:\n    v = parse(s)\nexcept ValueError:\n    v = None\nelse:\n    if v < 0:\n        raise ValueError(v)\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_nested_try_in_function_fires() {
+        let src = "def f():\n    for x in xs:\n        try:\n            a()\n        except E:\n            continue\n        else:\n            b()\n";
+        assert_eq!(check(src).len(), 1);
+    }
+
+    #[test]
+    fn test_pass_try_body_fires() {
+        let diags = check("try:\n    pass\nexcept E:\n    handle()\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::EmptyTryBody);
+        assert_eq!((diags[0].line, diags[0].col), (1, 1));
+    }
+
+    #[test]
+    fn test_ellipsis_try_body_fires() {
+        let diags = check("def f():\n    try:\n        ...\n    except E:\n        pass\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::EmptyTryBody);
+    }
+
+    #[test]
+    fn test_try_body_that_can_raise_clean() {
+        assert!(check("try:\n    risky()\nexcept E:\n    handle()\n").is_empty());
+        assert!(check("try:\n    pass\nfinally:\n    cleanup()\n").is_empty());
+    }
+}
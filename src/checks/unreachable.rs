@@ -1,33 +1,36 @@
 use crate::ast::{Stmt, StmtKind};
-use crate::location::offset_to_line_col;
+use crate::location::LineIndex;
 use crate::types::{Diagnostic, RuleCode};
 
 pub fn check_unreachable<'src>(
     stmts: &[Stmt<'src>],
     filename: &str,
-    source: &str,
+    lines: &LineIndex,
 ) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
-    check_stmt_list(stmts, filename, source, &mut diags);
+    check_stmt_list(stmts, filename, lines, &mut diags);
     diags
 }
 
 fn check_stmt_list<'src>(
     stmts: &[Stmt<'src>],
     filename: &str,
-    source: &str,
+    lines: &LineIndex,
     diags: &mut Vec<Diagnostic>,
 ) {
     let mut terminated = false;
     for stmt in stmts {
         if terminated {
-            let (line, col) = offset_to_line_col(stmt.offset as usize, source);
+            let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
             diags.push(Diagnostic {
                 file: filename.to_string(),
                 line,
                 col,
+                start_byte: stmt.offset as usize,
+                end_byte: lines.line_end(stmt.offset as usize),
                 code: RuleCode::UnreachableCode,
                 message: "Code is unreachable".to_string(),
+                fix: None,
             });
             // Only report the first unreachable statement per block.
             return;
@@ -38,25 +41,25 @@ fn check_stmt_list<'src>(
                 terminated = true;
             }
             StmtKind::FunctionDef(f) => {
-                check_stmt_list(&f.body, filename, source, diags);
+                check_stmt_list(&f.body, filename, lines, diags);
             }
             StmtKind::ClassDef(c) => {
-                check_stmt_list(&c.body, filename, source, diags);
+                check_stmt_list(&c.body, filename, lines, diags);
             }
             StmtKind::If { body, orelse, .. } => {
-                check_stmt_list(body, filename, source, diags);
-                check_stmt_list(orelse, filename, source, diags);
+                check_stmt_list(body, filename, lines, diags);
+                check_stmt_list(orelse, filename, lines, diags);
             }
             StmtKind::For { body, orelse, .. } => {
-                check_stmt_list(body, filename, source, diags);
-                check_stmt_list(orelse, filename, source, diags);
+                check_stmt_list(body, filename, lines, diags);
+                check_stmt_list(orelse, filename, lines, diags);
             }
             StmtKind::While { body, orelse, .. } => {
-                check_stmt_list(body, filename, source, diags);
-                check_stmt_list(orelse, filename, source, diags);
+                check_stmt_list(body, filename, lines, diags);
+                check_stmt_list(orelse, filename, lines, diags);
             }
             StmtKind::With { body, .. } => {
-                check_stmt_list(body, filename, source, diags);
+                check_stmt_list(body, filename, lines, diags);
             }
             StmtKind::Try {
                 body,
@@ -64,11 +67,11 @@ fn check_stmt_list<'src>(
                 orelse,
                 finalbody,
             } => {
-                check_stmt_list(body, filename, source, diags);
-                check_stmt_list(orelse, filename, source, diags);
-                check_stmt_list(finalbody, filename, source, diags);
+                check_stmt_list(body, filename, lines, diags);
+                check_stmt_list(orelse, filename, lines, diags);
+                check_stmt_list(finalbody, filename, lines, diags);
                 for h in handlers {
-                    check_stmt_list(&h.body, filename, source, diags);
+                    check_stmt_list(&h.body, filename, lines, diags);
                 }
             }
             StmtKind::Match { arms, .. } => {
@@ -76,7 +79,7 @@ fn check_stmt_list<'src>(
                 // `raise` in arm N does NOT make arm N+1 unreachable.
                 // Recurse into every arm body but do NOT set `terminated`.
                 for arm in arms {
-                    check_stmt_list(&arm.body, filename, source, diags);
+                    check_stmt_list(&arm.body, filename, lines, diags);
                 }
             }
             _ => {}
@@ -84,6 +87,95 @@ fn check_stmt_list<'src>(
     }
 }
 
+// ── RP064: loop control outside a loop ───────────────────────────────────────
+
+/// RP064: `break` / `continue` with no enclosing `for` or `while` in the same
+/// scope.  CPython rejects these at compile time; the parser's error recovery
+/// keeps them, so they are reported here instead of lost.
+pub fn check_loop_control<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    walk_loop_control(stmts, false, filename, lines, &mut diags);
+    diags
+}
+
+fn walk_loop_control<'src>(
+    stmts: &[Stmt<'src>],
+    in_loop: bool,
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for stmt in stmts {
+        let keyword = match &stmt.kind {
+            StmtKind::Break => "break",
+            StmtKind::Continue => "continue",
+            // A new scope: an enclosing loop does not carry over.
+            StmtKind::FunctionDef(f) => {
+                walk_loop_control(&f.body, false, filename, lines, diags);
+                continue;
+            }
+            StmtKind::ClassDef(c) => {
+                walk_loop_control(&c.body, false, filename, lines, diags);
+                continue;
+            }
+            // The `else:` clause runs after the loop, not inside it.
+            StmtKind::For { body, orelse, .. } | StmtKind::While { body, orelse, .. } => {
+                walk_loop_control(body, true, filename, lines, diags);
+                walk_loop_control(orelse, in_loop, filename, lines, diags);
+                continue;
+            }
+            StmtKind::If { body, orelse, .. } => {
+                walk_loop_control(body, in_loop, filename, lines, diags);
+                walk_loop_control(orelse, in_loop, filename, lines, diags);
+                continue;
+            }
+            StmtKind::With { body, .. } => {
+                walk_loop_control(body, in_loop, filename, lines, diags);
+                continue;
+            }
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                walk_loop_control(body, in_loop, filename, lines, diags);
+                for h in handlers {
+                    walk_loop_control(&h.body, in_loop, filename, lines, diags);
+                }
+                walk_loop_control(orelse, in_loop, filename, lines, diags);
+                walk_loop_control(finalbody, in_loop, filename, lines, diags);
+                continue;
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    walk_loop_control(&arm.body, in_loop, filename, lines, diags);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        if in_loop {
+            continue;
+        }
+        let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
+        diags.push(Diagnostic {
+            file: filename.to_string(),
+            line,
+            col,
+            start_byte: stmt.offset as usize,
+            end_byte: lines.line_end(stmt.offset as usize),
+            code: RuleCode::LoopControlOutsideLoop,
+            message: format!("`{keyword}` outside of loop"),
+            fix: None,
+        });
+    }
+}
+
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -93,7 +185,7 @@ mod tests {
 
     fn check(src: &str) -> Vec<Diagnostic> {
         let stmts = parse(src);
-        check_unreachable(&stmts, "test.py", src)
+        check_unreachable(&stmts, "test.py", &LineIndex::from_source(src))
     }
 
     #[test]
@@ -207,4 +299,51 @@ mod tests {
             "guarded match arms are independent — no RP005 expected, got: {diags:?}"
         );
     }
+
+    // ── RP064 ────────────────────────────────────────────────────────────────
+
+    fn check_control(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_loop_control(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_module_level_continue_fires() {
+        let diags = check_control("x = 1\ncontinue\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::LoopControlOutsideLoop);
+        assert_eq!((diags[0].line, diags[0].col), (2, 1));
+        assert_eq!(diags[0].message, "`continue` outside of loop");
+    }
+
+    #[test]
+    fn test_loop_control_inside_loop_clean() {
+        assert!(check_control("for i in x:\n    continue\n").is_empty());
+        let src = "while True:\n    try:\n        f()\n    except E:\n        break\n";
+        assert!(check_control(src).is_empty());
+    }
+
+    #[test]
+    fn test_function_body_break_fires() {
+        let diags = check_control("def f():\n    if x:\n        break\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].message, "`break` outside of loop");
+    }
+
+    #[test]
+    fn test_function_nested_in_loop_fires() {
+        let diags = check_control("for i in x:\n    def g():\n        continue\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].line, 3);
+    }
+
+    #[test]
+    fn test_loop_else_clause_is_outside_loop() {
+        assert_eq!(
+            check_control("for i in x:\n    pass\nelse:\n    break\n").len(),
+            1
+        );
+        let src = "while a:\n    for i in x:\n        pass\n    else:\n        break\n";
+        assert!(check_control(src).is_empty());
+    }
 }
@@ -1,48 +1,72 @@
-use crate::ast::{ExprKind, FuncDef, Stmt, StmtKind};
-use crate::location::offset_to_line_col;
-use crate::names::collect_stmt_names;
+use crate::ast::{ArgDef, AssignTarget, ExprInfo, ExprKind, FuncDef, Stmt, StmtKind};
+use crate::config::ReaperConfig;
+use crate::location::LineIndex;
+use crate::names::{
+    collect_stmt_names, collect_urlpatterns_views, decorator_name, is_pytest_decorator,
+};
 use crate::types::{Diagnostic, RuleCode};
 use std::collections::HashSet;
 
 pub fn check_unused_arguments<'src>(
     stmts: &[Stmt<'src>],
     filename: &str,
-    source: &str,
+    lines: &LineIndex,
+    config: &ReaperConfig,
 ) -> Vec<Diagnostic> {
+    let exemptions = Exemptions {
+        strict: config.strict,
+        decorators: config.exempt_decorators(),
+        url_views: if config.is_django() {
+            collect_urlpatterns_views(stmts)
+        } else {
+            HashSet::new()
+        },
+    };
     let mut diags = Vec::new();
-    walk_for_functions(stmts, filename, source, &mut diags);
+    walk_for_functions(stmts, filename, lines, &exemptions, &mut diags);
     diags
 }
 
+/// Framework knowledge resolved once per file from [`ReaperConfig`].
+struct Exemptions<'a> {
+    /// `--strict`: check pytest test functions and fixtures too.
+    strict: bool,
+    /// Decorator names whose functions are called by a framework.
+    decorators: Vec<&'a str>,
+    /// Functions registered as views in this file's `urlpatterns`.
+    url_views: HashSet<&'a str>,
+}
+
 fn walk_for_functions<'src>(
     stmts: &[Stmt<'src>],
     filename: &str,
-    source: &str,
+    lines: &LineIndex,
+    exemptions: &Exemptions<'_>,
     diags: &mut Vec<Diagnostic>,
 ) {
     for stmt in stmts {
         match &stmt.kind {
             StmtKind::FunctionDef(f) => {
-                check_args(f, filename, source, diags);
-                walk_for_functions(&f.body, filename, source, diags);
+                check_args(f, filename, lines, exemptions, diags);
+                walk_for_functions(&f.body, filename, lines, exemptions, diags);
             }
             StmtKind::ClassDef(c) => {
-                walk_for_functions(&c.body, filename, source, diags);
+                walk_for_functions(&c.body, filename, lines, exemptions, diags);
             }
             StmtKind::If { body, orelse, .. } => {
-                walk_for_functions(body, filename, source, diags);
-                walk_for_functions(orelse, filename, source, diags);
+                walk_for_functions(body, filename, lines, exemptions, diags);
+                walk_for_functions(orelse, filename, lines, exemptions, diags);
             }
             StmtKind::While { body, orelse, .. } => {
-                walk_for_functions(body, filename, source, diags);
-                walk_for_functions(orelse, filename, source, diags);
+                walk_for_functions(body, filename, lines, exemptions, diags);
+                walk_for_functions(orelse, filename, lines, exemptions, diags);
             }
             StmtKind::For { body, orelse, .. } => {
-                walk_for_functions(body, filename, source, diags);
-                walk_for_functions(orelse, filename, source, diags);
+                walk_for_functions(body, filename, lines, exemptions, diags);
+                walk_for_functions(orelse, filename, lines, exemptions, diags);
             }
             StmtKind::With { body, .. } => {
-                walk_for_functions(body, filename, source, diags);
+                walk_for_functions(body, filename, lines, exemptions, diags);
             }
             StmtKind::Try {
                 body,
@@ -50,16 +74,16 @@ fn walk_for_functions<'src>(
                 orelse,
                 finalbody,
             } => {
-                walk_for_functions(body, filename, source, diags);
-                walk_for_functions(orelse, filename, source, diags);
-                walk_for_functions(finalbody, filename, source, diags);
+                walk_for_functions(body, filename, lines, exemptions, diags);
+                walk_for_functions(orelse, filename, lines, exemptions, diags);
+                walk_for_functions(finalbody, filename, lines, exemptions, diags);
                 for h in handlers {
-                    walk_for_functions(&h.body, filename, source, diags);
+                    walk_for_functions(&h.body, filename, lines, exemptions, diags);
                 }
             }
             StmtKind::Match { arms, .. } => {
                 for arm in arms {
-                    walk_for_functions(&arm.body, filename, source, diags);
+                    walk_for_functions(&arm.body, filename, lines, exemptions, diags);
                 }
             }
             _ => {}
@@ -67,12 +91,20 @@ fn walk_for_functions<'src>(
     }
 }
 
-fn check_args<'src>(f: &FuncDef<'src>, filename: &str, source: &str, diags: &mut Vec<Diagnostic>) {
+fn check_args<'src>(
+    f: &FuncDef<'src>,
+    filename: &str,
+    lines: &LineIndex,
+    exemptions: &Exemptions<'_>,
+    diags: &mut Vec<Diagnostic>,
+) {
     // pytest test functions: every parameter is a fixture injected by name.
     // The function body may never reference the name directly (e.g. a
     // side-effect fixture like `db_setup` or `autouse_fixture`), so flagging
     // those parameters as unused would be a false positive.
-    if f.name.starts_with("test_") {
+    // Fixtures get the same treatment: their parameters are other fixtures.
+    let is_pytest = f.name.starts_with("test_") || f.decorators.iter().any(is_pytest_decorator);
+    if is_pytest && !exemptions.strict {
         return;
     }
 
@@ -87,11 +119,30 @@ fn check_args<'src>(f: &FuncDef<'src>, filename: &str, source: &str, diags: &mut
         return;
     }
 
+    // Framework-registered callables (e.g. Celery's `@app.task`, Django views)
+    // receive their arguments from the framework, not from a visible call site.
+    let is_framework_hook = exemptions.url_views.contains(f.name)
+        || f.decorators
+            .iter()
+            .any(|d| decorator_name(d).is_some_and(|n| exemptions.decorators.contains(&n)));
+    if is_framework_hook {
+        return;
+    }
+
     // Stub bodies (pass / ... / docstring) exempt arguments.
     if is_stub_body(&f.body) {
         return;
     }
 
+    // A static method has no implicit first parameter, so a `self` or `cls`
+    // there is an ordinary (and usually mistaken) argument.
+    let is_static = f.decorators.iter().any(|d| {
+        matches!(
+            &d.kind,
+            ExprKind::Name("staticmethod", _) | ExprKind::Attr(_, "staticmethod")
+        )
+    });
+
     let mut usages: HashSet<String> = HashSet::new();
     collect_stmt_names(&f.body, &mut usages);
 
@@ -103,53 +154,204 @@ fn check_args<'src>(f: &FuncDef<'src>, filename: &str, source: &str, diags: &mut
         .chain(f.args.kwonlyargs.iter());
 
     for arg in all_args {
-        if is_arg_exempt(arg.name) {
+        if is_arg_exempt(arg.name, is_static) || is_injected(arg) {
             continue;
         }
         if !usages.contains(arg.name) {
-            let (line, col) = offset_to_line_col(arg.offset as usize, source);
-            diags.push(Diagnostic {
-                file: filename.to_string(),
-                line,
-                col,
-                code: RuleCode::UnusedArgument,
-                message: format!("Argument `{}` is not used", arg.name),
-            });
+            diags.push(unused_arg_diagnostic(f, arg, filename, lines));
+        }
+    }
+
+    // RP049: an `__init__` parameter that the body reads but never stores on
+    // `self` (or forwards to `super()`) is usually a forgotten `self.y = y`.
+    // Parameters the body never reads at all are already reported as RP008.
+    if f.name == "__init__" {
+        let mut stored: HashSet<&str> = HashSet::new();
+        collect_self_stored(&f.body, &mut stored);
+        let params = f
+            .args
+            .posonlyargs
+            .iter()
+            .chain(f.args.args.iter())
+            .chain(f.args.kwonlyargs.iter());
+        for arg in params {
+            if is_arg_exempt(arg.name, is_static) || !usages.contains(arg.name) {
+                continue;
+            }
+            if !stored.contains(arg.name) {
+                let (line, col) = lines.offset_to_line_col(arg.offset as usize);
+                diags.push(Diagnostic {
+                    file: filename.to_string(),
+                    line,
+                    col,
+                    start_byte: arg.offset as usize,
+                    end_byte: arg.offset as usize + arg.name.len(),
+                    code: RuleCode::InitParamNotStored,
+                    message: format!(
+                        "Parameter `{}` passed to `__init__` but never assigned to `self`",
+                        arg.name
+                    ),
+                    fix: None,
+                });
+            }
         }
     }
 
     if let Some(vararg) = &f.args.vararg
-        && !is_arg_exempt(vararg.name)
+        && !is_arg_exempt(vararg.name, is_static)
         && !usages.contains(vararg.name)
     {
-        let (line, col) = offset_to_line_col(vararg.offset as usize, source);
-        diags.push(Diagnostic {
-            file: filename.to_string(),
-            line,
-            col,
-            code: RuleCode::UnusedArgument,
-            message: format!("Argument `{}` is not used", vararg.name),
-        });
+        diags.push(unused_arg_diagnostic(f, vararg, filename, lines));
     }
 
     if let Some(kwarg) = &f.args.kwarg
-        && !is_arg_exempt(kwarg.name)
+        && !is_arg_exempt(kwarg.name, is_static)
         && !usages.contains(kwarg.name)
     {
-        let (line, col) = offset_to_line_col(kwarg.offset as usize, source);
-        diags.push(Diagnostic {
-            file: filename.to_string(),
-            line,
-            col,
-            code: RuleCode::UnusedArgument,
-            message: format!("Argument `{}` is not used", kwarg.name),
-        });
+        diags.push(unused_arg_diagnostic(f, kwarg, filename, lines));
     }
 }
 
-/// `self`, `cls`, and any name starting with `_` are exempt from RP008.
-fn is_arg_exempt(name: &str) -> bool {
-    name == "self" || name == "cls" || name.starts_with('_')
+/// RP008, or RP051 when `f` overrides a dunder method: there the signature is
+/// dictated by the protocol, so an unused parameter may well be deliberate.
+fn unused_arg_diagnostic(
+    f: &FuncDef<'_>,
+    arg: &ArgDef<'_>,
+    filename: &str,
+    lines: &LineIndex,
+) -> Diagnostic {
+    let (line, col) = lines.offset_to_line_col(arg.offset as usize);
+    let (code, message) = if is_dunder_override(f.name) {
+        (
+            RuleCode::UnusedOverrideParam,
+            format!(
+                "Parameter `{}` of overriding method `{}` is unused; if intentional, prefix with `_`",
+                arg.name, f.name
+            ),
+        )
+    } else {
+        (
+            RuleCode::UnusedArgument,
+            format!("Argument `{}` is not used", arg.name),
+        )
+    };
+    Diagnostic {
+        file: filename.to_string(),
+        line,
+        col,
+        start_byte: arg.offset as usize,
+        end_byte: arg.offset as usize + arg.name.len(),
+        code,
+        message,
+        fix: None,
+    }
+}
+
+/// `__eq__`, `__contains__`, `__exit__`… — but not the constructors, whose
+/// parameters are the class's own API rather than a protocol's.
+fn is_dunder_override(name: &str) -> bool {
+    name.len() > 4
+        && name.starts_with("__")
+        && name.ends_with("__")
+        && name != "__init__"
+        && name != "__new__"
+}
+
+/// Names read by the right-hand side of `self.attr = …` (plain, annotated or
+/// augmented, including `self.a, self.b = a, b`) or by a `super()…` or
+/// `Base.__init__(self, …)` call, anywhere in `body` outside nested function
+/// and class definitions.
+fn collect_self_stored<'src>(body: &[Stmt<'src>], stored: &mut HashSet<&'src str>) {
+    for stmt in body {
+        match &stmt.kind {
+            StmtKind::Assign { targets, value } if targets.iter().any(is_self_attr) => {
+                stored.extend(value.names.iter().map(|(n, _)| *n));
+            }
+            StmtKind::AnnAssign {
+                target,
+                value: Some(value),
+                ..
+            }
+            | StmtKind::AugAssign { target, value }
+                if is_self_attr(target) =>
+            {
+                stored.extend(value.names.iter().map(|(n, _)| *n));
+            }
+            StmtKind::Expr(info) if is_parent_init_call(info) => {
+                stored.extend(info.names.iter().map(|(n, _)| *n));
+            }
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                collect_self_stored(body, stored);
+                collect_self_stored(orelse, stored);
+            }
+            StmtKind::With { body, .. } => collect_self_stored(body, stored),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                collect_self_stored(body, stored);
+                for h in handlers {
+                    collect_self_stored(&h.body, stored);
+                }
+                collect_self_stored(orelse, stored);
+                collect_self_stored(finalbody, stored);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    collect_self_stored(&arm.body, stored);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `self.attr = …` (also `self.attr.sub = …` and `self.attr[k] = …`), or an
+/// unpacking target with such an element.
+fn is_self_attr(target: &AssignTarget<'_>) -> bool {
+    match target {
+        AssignTarget::Complex(info) => matches!(info.kind, ExprKind::Attr("self", _)),
+        AssignTarget::Tuple(elts) | AssignTarget::List(elts) => elts.iter().any(is_self_attr),
+        AssignTarget::Starred(inner) => is_self_attr(inner),
+        AssignTarget::Name(..) => false,
+    }
+}
+
+/// `super().__init__(…)` and the like, or an explicit `Base.__init__(self, …)`.
+fn is_parent_init_call(info: &ExprInfo<'_>) -> bool {
+    info.names.first().is_some_and(|(n, _)| *n == "super")
+        || (matches!(info.kind, ExprKind::Attr(_, "__init__"))
+            && info.names.get(1).is_some_and(|(n, _)| *n == "self"))
+}
+
+/// Any name starting with `_` is exempt from RP008, and so are `self` and
+/// `cls` unless the function is a `@staticmethod`.
+fn is_arg_exempt(name: &str, is_static: bool) -> bool {
+    name.starts_with('_') || (!is_static && (name == "self" || name == "cls"))
+}
+
+/// FastAPI parameter markers: a parameter declared with one of these as its
+/// default (or inside `Annotated[...]`) is filled in by the framework.
+const INJECTION_MARKERS: &[&str] = &[
+    "Depends", "Security", "Query", "Path", "Body", "Header", "Cookie", "Form", "File",
+];
+
+/// `db: Session = Depends(get_db)` or `db: Annotated[Session, Depends(get_db)]`.
+fn is_injected(arg: &ArgDef<'_>) -> bool {
+    let default_is_marker = arg
+        .default
+        .as_ref()
+        .and_then(|d| d.names.first())
+        .is_some_and(|(n, _)| INJECTION_MARKERS.contains(n));
+    let annotated_marker = arg.annotation.as_ref().is_some_and(|ann| {
+        ann.names.first().is_some_and(|(n, _)| *n == "Annotated")
+            && ann.names.iter().any(|(n, _)| INJECTION_MARKERS.contains(n))
+    });
+    default_is_marker || annotated_marker
 }
 
 /// Returns `true` when the function body is purely a placeholder.
@@ -191,7 +393,12 @@ mod tests {
 
     fn check(src: &str) -> Vec<Diagnostic> {
         let stmts = parse(src);
-        check_unused_arguments(&stmts, "test.py", src)
+        check_unused_arguments(
+            &stmts,
+            "test.py",
+            &LineIndex::from_source(src),
+            &ReaperConfig::default(),
+        )
     }
 
     #[test]
@@ -272,6 +479,14 @@ mod tests {
         assert!(diags[0].message.contains("`y`"));
     }
 
+    #[test]
+    fn test_positional_only_args_checked() {
+        let diags = check("def f(a, b, /, c):\n    return c\n");
+        assert_eq!(diags.len(), 2);
+        assert!(diags.iter().any(|d| d.message.contains("`a`")));
+        assert!(diags.iter().any(|d| d.message.contains("`b`")));
+    }
+
     // ── pytest fixture injection exemptions ───────────────────────────────────
 
     #[test]
@@ -308,4 +523,254 @@ mod tests {
         let diags = check("def test_sum(a, b):\n    assert a + b == 3\n");
         assert_eq!(diags.len(), 0);
     }
+
+    // ── framework exemptions ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_celery_task_exempt() {
+        let diags = check("@app.task\ndef send_email(recipient):\n    return 1\n");
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_celery_task_with_options_exempt() {
+        let diags = check("@app.task(bind=True)\ndef retry(self, job_id):\n    return 1\n");
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_shared_task_exempt() {
+        let diags = check("@shared_task\ndef cleanup(days):\n    return 1\n");
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_framework_exemptions_configurable() {
+        let src = "@app.job\ndef nightly(when):\n    return 1\n";
+        let stmts = parse(src);
+        assert_eq!(check(src).len(), 1, "unknown decorator must not exempt");
+
+        let config = ReaperConfig {
+            framework_exemptions: vec!["job".to_string()],
+            ..ReaperConfig::default()
+        };
+        let diags =
+            check_unused_arguments(&stmts, "test.py", &LineIndex::from_source(src), &config);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_strict_checks_test_functions() {
+        let src = "def test_login(client, db_session):\n    assert client\n";
+        let stmts = parse(src);
+        assert!(check(src).is_empty());
+
+        let config = ReaperConfig {
+            strict: true,
+            ..ReaperConfig::default()
+        };
+        let diags =
+            check_unused_arguments(&stmts, "test.py", &LineIndex::from_source(src), &config);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`db_session`"));
+    }
+
+    #[test]
+    fn test_other_decorator_still_checked() {
+        let diags = check("@functools.cache\ndef compute(x, y):\n    return x\n");
+        assert_eq!(diags.len(), 1);
+    }
+
+    fn check_django(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        let config = ReaperConfig {
+            framework: Some("django".to_string()),
+            ..ReaperConfig::default()
+        };
+        check_unused_arguments(&stmts, "test.py", &LineIndex::from_source(src), &config)
+    }
+
+    #[test]
+    fn test_django_receiver_exempt() {
+        let src = "@receiver(post_save, sender=User)\ndef on_save(sender, instance, **kwargs):\n    return 1\n";
+        assert!(
+            !check(src).is_empty(),
+            "without the preset the handler is checked"
+        );
+        assert_eq!(check_django(src).len(), 0);
+    }
+
+    #[test]
+    fn test_django_view_decorators_exempt() {
+        let src = "@login_required\n@require_POST\ndef update(request, pk):\n    return 1\n";
+        assert_eq!(check_django(src).len(), 0);
+    }
+
+    #[test]
+    fn test_django_urlpatterns_view_exempt() {
+        let src = "def index(request):\n    return 1\n\ndef helper(x):\n    return 1\n\nurlpatterns = [path('', index)]\n";
+        let diags = check_django(src);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`x`"));
+    }
+
+    // ── dependency injection ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_fastapi_depends_default_exempt() {
+        let src =
+            "@app.get('/items')\ndef list_items(db: Session = Depends(get_db)):\n    return []\n";
+        assert_eq!(check(src).len(), 0);
+    }
+
+    #[test]
+    fn test_fastapi_query_and_security_exempt() {
+        let src = "def search(q: str = Query(None), user=Security(auth, scopes=['a']), page=1):\n    return []\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`page`"));
+    }
+
+    #[test]
+    fn test_fastapi_annotated_depends_exempt() {
+        let src = "def me(user: Annotated[User, Depends(current_user)]):\n    return 1\n";
+        assert_eq!(check(src).len(), 0);
+    }
+
+    #[test]
+    fn test_plain_call_default_still_checked() {
+        let src = "def f(items=list()):\n    return 1\n";
+        assert_eq!(check(src).len(), 1);
+    }
+
+    #[test]
+    fn test_pytest_fixture_params_exempt() {
+        let diags = check("@pytest.fixture\ndef db(tmp_path, settings):\n    return 1\n");
+        assert_eq!(diags.len(), 0);
+    }
+
+    // ── RP049: __init__ parameter never stored on self ───────────────────────
+
+    fn rp049(src: &str) -> Vec<Diagnostic> {
+        check(src)
+            .into_iter()
+            .filter(|d| d.code == RuleCode::InitParamNotStored)
+            .collect()
+    }
+
+    #[test]
+    fn test_init_param_stored_clean() {
+        let src = "class P:\n    def __init__(self, x):\n        self.x = x\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_init_param_not_stored_fires() {
+        let src = "class P:\n    def __init__(self, x, y):\n        self.x = x\n        print(y)\n";
+        let diags = rp049(src);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`y`"));
+        assert_eq!((diags[0].line, diags[0].col), (2, 27));
+    }
+
+    #[test]
+    fn test_init_param_unused_reported_as_rp008_only() {
+        let src = "class P:\n    def __init__(self, x, y):\n        self.x = x\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::UnusedArgument);
+    }
+
+    #[test]
+    fn test_init_param_stored_via_expression() {
+        let src = "class P:\n    def __init__(self, a, b):\n        self.total = a + b\n";
+        assert!(rp049(src).is_empty());
+    }
+
+    #[test]
+    fn test_init_param_stored_in_branch_or_annotated() {
+        let src = "class P:\n    def __init__(self, a, b):\n        if a:\n            self.a: int = a\n        self.n += b\n";
+        assert!(rp049(src).is_empty());
+    }
+
+    #[test]
+    fn test_init_param_forwarded_to_super() {
+        let src = "class P(B):\n    def __init__(self, a, b):\n        super().__init__(a)\n        self.b = b\n";
+        assert!(rp049(src).is_empty());
+    }
+
+    #[test]
+    fn test_init_params_stored_by_tuple_assignment() {
+        let src = "class P:\n    def __init__(self, a, b):\n        self.a, self.b = a, b\n";
+        assert!(rp049(src).is_empty());
+        let src = "class P:\n    def __init__(self, a, b):\n        first, self.b = a, b\n";
+        assert!(rp049(src).is_empty());
+    }
+
+    #[test]
+    fn test_init_param_forwarded_to_explicit_base_init() {
+        let src = "class P(B):\n    def __init__(self, a, b):\n        B.__init__(self, a)\n        self.b = b\n";
+        assert!(rp049(src).is_empty());
+        // Another object's `__init__` does not store anything on `self`.
+        let src = "class P:\n    def __init__(self, a):\n        other.__init__(a)\n";
+        assert_eq!(rp049(src).len(), 1);
+    }
+
+    #[test]
+    fn test_non_init_method_not_checked_for_rp049() {
+        let src = "class P:\n    def setup(self, x):\n        print(x)\n";
+        assert!(rp049(src).is_empty());
+    }
+
+    #[test]
+    fn test_classmethod_cls_not_flagged() {
+        let src = "class C:\n    @classmethod\n    def create(cls):\n        return 1\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_staticmethod_unused_param_flagged() {
+        let src = "class C:\n    @staticmethod\n    def f(x):\n        return 1\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`x`"));
+    }
+
+    #[test]
+    fn test_staticmethod_self_not_exempt() {
+        let src = "class C:\n    @staticmethod\n    def f(self, x):\n        return x\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`self`"));
+    }
+
+    #[test]
+    fn test_dunder_override_unused_param_is_rp051() {
+        let src = "class L(list):\n    def __contains__(self, item):\n        return True\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::UnusedOverrideParam);
+        assert!(diags[0].message.contains("`item`"));
+        assert!(diags[0].message.contains("`__contains__`"));
+    }
+
+    #[test]
+    fn test_dunder_override_used_param_clean() {
+        let src = "class L(list):\n    def __getitem__(self, index):\n        return super().__getitem__(index)\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_dunder_override_underscore_param_exempt() {
+        let src = "class C:\n    def __exit__(self, *_exc):\n        return False\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_init_unused_param_stays_rp008() {
+        let src = "class C:\n    def __init__(self, x):\n        self.y = 1\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::UnusedArgument);
+    }
 }
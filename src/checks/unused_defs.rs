@@ -1,13 +1,29 @@
-use crate::ast::{ExprInfo, Stmt, StmtKind};
-use crate::location::offset_to_line_col;
-use crate::names::{collect_dunder_all, collect_stmt_names};
+use crate::ast::{AssignTarget, ExprInfo, ExprKind, Stmt, StmtKind};
+use crate::config::ReaperConfig;
+use crate::location::LineIndex;
+use crate::names::{collect_attribute_names, collect_dunder_all, collect_stmt_names};
 use crate::types::{Diagnostic, RuleCode};
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
+/// Module-level dunders read by packaging and documentation tools rather than
+/// by Python code.  The default for `exempt_dunders` in the config.
+pub const DEFAULT_EXEMPT_DUNDERS: &[&str] = &[
+    "__version__",
+    "__author__",
+    "__email__",
+    "__license__",
+    "__copyright__",
+    "__maintainer__",
+    "__status__",
+    "__credits__",
+];
+
 // ── ModuleDef ─────────────────────────────────────────────────────────────────
 
-/// A module-level function or class definition, captured for cross-file
-/// dead-code analysis (RP003 / RP004).
+/// A module-level function or class definition, or a class variable,
+/// captured for cross-file dead-code analysis (RP003 / RP004 / RP014).
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModuleDef {
     pub name: String,
     pub offset: usize,
@@ -17,71 +33,142 @@ pub struct ModuleDef {
 
 // ── Public entry points ───────────────────────────────────────────────────────
 
-/// Collect all non-exempt module-level function and class definitions.
+/// Collect all non-exempt module-level function and class definitions, and
+/// the class variables of plain classes (see [`collect_class_vars`]).
 /// Does NOT generate diagnostics — the caller aggregates across files.
-pub fn collect_module_defs<'src>(stmts: &[Stmt<'src>], filename: &str) -> Vec<ModuleDef> {
+pub fn collect_module_defs<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    config: &ReaperConfig,
+) -> Vec<ModuleDef> {
     let mut defs = Vec::new();
     for stmt in stmts {
         match &stmt.kind {
-            StmtKind::FunctionDef(f) => {
-                if !is_exempt(f.name, &f.decorators) {
-                    defs.push(ModuleDef {
-                        name: f.name.to_string(),
-                        offset: f.offset as usize,
-                        code: RuleCode::UnusedFunction,
-                        file: filename.to_string(),
-                    });
-                }
+            StmtKind::FunctionDef(f)
+                if !is_exempt(f.name, &f.decorators)
+                    && !config.lenient.exempts_function(f.name) =>
+            {
+                defs.push(ModuleDef {
+                    name: f.name.to_string(),
+                    offset: f.offset as usize,
+                    code: RuleCode::UnusedFunction,
+                    file: filename.to_string(),
+                });
             }
-            StmtKind::ClassDef(c) => {
-                if !is_exempt(c.name, &c.decorators) {
-                    defs.push(ModuleDef {
-                        name: c.name.to_string(),
-                        offset: c.offset as usize,
-                        code: RuleCode::UnusedClass,
-                        file: filename.to_string(),
-                    });
-                }
+            StmtKind::ClassDef(c)
+                if !is_exempt(c.name, &c.decorators) && !config.lenient.exempts_class(c.name) =>
+            {
+                defs.push(ModuleDef {
+                    name: c.name.to_string(),
+                    offset: c.offset as usize,
+                    code: RuleCode::UnusedClass,
+                    file: filename.to_string(),
+                });
             }
             _ => {}
         }
     }
+    collect_class_vars(stmts, filename, &mut defs);
     defs
 }
 
+/// RP014 candidates: `name = value` in the body of a module-level (or nested)
+/// class, where the body itself never reads `name`.  Whether anything else
+/// does — `self.name`, `Cls.name`, `getattr(obj, "name")` — is only known
+/// once every file is analysed.
+///
+/// Only plain classes are checked.  Subclasses and decorated classes are
+/// skipped: enums, ORM models, dataclasses and settings objects declare
+/// attributes that their framework reads.  So are inner `Meta` and `Config`
+/// classes.  Names starting with `_` and dunders are exempt.
+fn collect_class_vars(stmts: &[Stmt<'_>], filename: &str, defs: &mut Vec<ModuleDef>) {
+    for stmt in stmts {
+        let StmtKind::ClassDef(c) = &stmt.kind else {
+            continue;
+        };
+        let is_plain = c.decorators.is_empty()
+            && c.bases
+                .iter()
+                .all(|b| matches!(b.kind, ExprKind::Name("object", _)))
+            && !matches!(c.name, "Meta" | "Config");
+        if is_plain {
+            let mut read = HashSet::new();
+            collect_stmt_names(&c.body, &mut read);
+            for member in &c.body {
+                let StmtKind::Assign { targets, .. } = &member.kind else {
+                    continue;
+                };
+                for target in targets {
+                    if let AssignTarget::Name(name, offset) = target
+                        && !name.starts_with('_')
+                        && !read.contains(*name)
+                    {
+                        defs.push(ModuleDef {
+                            name: name.to_string(),
+                            offset: *offset as usize,
+                            code: RuleCode::UnusedClassVar,
+                            file: filename.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        collect_class_vars(&c.body, filename, defs);
+    }
+}
+
 /// Per-file wrapper used by unit tests and single-file analysis.
 #[allow(dead_code)]
 pub fn check_unused_defs<'src>(
     stmts: &[Stmt<'src>],
     filename: &str,
     source: &str,
+    lines: &LineIndex,
+    config: &ReaperConfig,
 ) -> Vec<Diagnostic> {
-    let defs = collect_module_defs(stmts, filename);
+    let defs = collect_module_defs(stmts, filename, config);
 
     let mut usages: HashSet<String> = HashSet::new();
     collect_stmt_names(stmts, &mut usages);
     usages.extend(collect_dunder_all(stmts));
+    let mut attributes: HashSet<String> = HashSet::new();
+    collect_attribute_names(source, &mut attributes);
 
     defs.into_iter()
-        .filter(|d| !usages.contains(&d.name))
-        .map(|d| {
-            let (line, col) = offset_to_line_col(d.offset, source);
-            let kind = if d.code == RuleCode::UnusedFunction {
-                "Function"
-            } else {
-                "Class"
-            };
-            Diagnostic {
-                file: d.file,
-                line,
-                col,
-                code: d.code,
-                message: format!("{kind} `{}` is defined but never used", d.name),
-            }
-        })
+        .filter(|d| !d.is_used(&usages, &attributes))
+        .map(|d| d.diagnostic(lines))
         .collect()
 }
 
+impl ModuleDef {
+    /// True when `usages` (bare names read anywhere) or, for a class
+    /// variable, `attributes` (see [`collect_attribute_names`]) mention it.
+    pub fn is_used(&self, usages: &HashSet<String>, attributes: &HashSet<String>) -> bool {
+        usages.contains(&self.name)
+            || (self.code == RuleCode::UnusedClassVar && attributes.contains(&self.name))
+    }
+
+    /// The RP003 / RP004 / RP014 diagnostic for this unused definition.
+    pub fn diagnostic(&self, lines: &LineIndex) -> Diagnostic {
+        let (line, col) = lines.offset_to_line_col(self.offset);
+        let kind = match self.code {
+            RuleCode::UnusedFunction => "Function",
+            RuleCode::UnusedClassVar => "Class variable",
+            _ => "Class",
+        };
+        Diagnostic {
+            file: self.file.clone(),
+            line,
+            col,
+            start_byte: self.offset,
+            end_byte: lines.line_end(self.offset),
+            code: self.code.clone(),
+            message: format!("{kind} `{}` is defined but never used", self.name),
+            fix: None,
+        }
+    }
+}
+
 // ── Exemption logic ───────────────────────────────────────────────────────────
 
 pub fn is_exempt(name: &str, decorators: &[ExprInfo<'_>]) -> bool {
@@ -125,7 +212,13 @@ mod tests {
 
     fn check(src: &str) -> Vec<Diagnostic> {
         let stmts = parse(src);
-        check_unused_defs(&stmts, "test.py", src)
+        check_unused_defs(
+            &stmts,
+            "test.py",
+            src,
+            &LineIndex::from_source(src),
+            &ReaperConfig::default(),
+        )
     }
 
     #[test]
@@ -176,6 +269,30 @@ mod tests {
         assert_eq!(diags.len(), 0);
     }
 
+    #[test]
+    fn test_pytest_fixture_not_flagged() {
+        let diags = check("import pytest\n\n@pytest.fixture\ndef db():\n    return 1\n");
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_pytest_fixture_with_args_not_flagged() {
+        let diags = check("@pytest.fixture(scope=\"module\")\ndef conn():\n    return 1\n");
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_bare_fixture_not_flagged() {
+        let diags = check("from pytest import fixture\n\n@fixture\ndef client():\n    return 1\n");
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_pytest_fixture_exempt_from_cross_file_defs() {
+        let stmts = parse("@pytest.fixture(autouse=True)\ndef reset():\n    pass\n");
+        assert!(collect_module_defs(&stmts, "conftest.py", &ReaperConfig::default()).is_empty());
+    }
+
     #[test]
     fn test_underscore_prefix_not_flagged() {
         let diags = check("def _private():\n    pass\n");
@@ -211,4 +328,95 @@ mod tests {
         assert_eq!(diags.len(), 1);
         assert!(diags[0].message.contains("helper"));
     }
+
+    #[test]
+    fn test_lenient_callbacks_and_meta_classes_exempt() {
+        use crate::config::LenientHeuristics;
+        let src = "def click_handler():\n    pass\ndef on_ready():\n    pass\nclass ModelMeta:\n    pass\nclass Plain:\n    pass\n";
+        assert_eq!(check(src).len(), 4);
+
+        let stmts = parse(src);
+        let config = ReaperConfig {
+            lenient: LenientHeuristics {
+                callbacks: true,
+                meta_classes: true,
+                loggers: true,
+            },
+            ..ReaperConfig::default()
+        };
+        let diags = check_unused_defs(
+            &stmts,
+            "test.py",
+            src,
+            &LineIndex::from_source(src),
+            &config,
+        );
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`Plain`"));
+    }
+
+    fn class_vars(src: &str) -> Vec<Diagnostic> {
+        check(src)
+            .into_iter()
+            .filter(|d| d.code == RuleCode::UnusedClassVar)
+            .collect()
+    }
+
+    #[test]
+    fn test_unused_class_var_detected() {
+        let diags = class_vars("class Foo:\n    bar = 1\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!((diags[0].line, diags[0].col), (2, 5));
+        assert_eq!(
+            diags[0].message,
+            "Class variable `bar` is defined but never used"
+        );
+    }
+
+    #[test]
+    fn test_class_var_read_as_attribute_not_flagged() {
+        let src = "class Foo:\n    bar = 1\n    def get(self):\n        return self.bar\n";
+        assert!(class_vars(src).is_empty());
+        assert!(class_vars("class Foo:\n    bar = 1\nprint(Foo.bar)\n").is_empty());
+        assert!(class_vars("class Foo:\n    bar = 1\ngetattr(Foo, 'bar')\n").is_empty());
+    }
+
+    #[test]
+    fn test_class_var_read_in_class_body_not_flagged() {
+        let diags = class_vars("class Foo:\n    bar = 1\n    baz = bar * 2\n");
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`baz`"));
+    }
+
+    #[test]
+    fn test_private_dunder_and_exported_class_vars_exempt() {
+        let src = "class Foo:\n    _cache = {}\n    __slots__ = ()\n";
+        assert!(class_vars(src).is_empty());
+        let src = "__all__ = ['bar']\nclass Foo:\n    bar = 1\n";
+        assert!(class_vars(src).is_empty());
+    }
+
+    #[test]
+    fn test_framework_classes_skipped() {
+        assert!(class_vars("class Color(Enum):\n    RED = 1\n").is_empty());
+        assert!(class_vars("@dataclass\nclass P:\n    x = 0\n").is_empty());
+        let src = "class Model:\n    class Meta:\n        ordering = ['id']\n";
+        assert!(class_vars(src).is_empty());
+        assert_eq!(class_vars("class P(object):\n    x = 0\n").len(), 1);
+    }
+
+    #[test]
+    fn test_slots_entries_not_flagged() {
+        let src = "class Foo:\n    __slots__ = (\"x\", \"y\")\n    z = 1\n";
+        let diags = class_vars(src);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`z`"));
+    }
+
+    #[test]
+    fn test_nested_class_vars_checked() {
+        let diags = class_vars("class Outer:\n    class Inner:\n        limit = 5\n");
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`limit`"));
+    }
 }
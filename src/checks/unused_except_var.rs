@@ -0,0 +1,145 @@
+use crate::ast::{Stmt, StmtKind};
+use crate::location::LineIndex;
+use crate::names::collect_stmt_names;
+use crate::types::{Diagnostic, RuleCode};
+use std::collections::HashSet;
+
+/// RP010: `except E as e:` where the handler body never reads `e`.  Python
+/// unbinds the name when the handler exits, so only reads inside the body
+/// count; `except E:` says the same thing without the dead binding.
+///
+/// Names starting with `_` are exempt.  `except* E as eg:` is checked the
+/// same way: the group is usually inspected (`eg.exceptions`, `for e in eg`),
+/// and any such read counts.
+pub fn check_unused_except_vars<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    walk(stmts, filename, lines, &mut diags);
+    diags
+}
+
+fn walk<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                walk(body, filename, lines, diags);
+                for h in handlers {
+                    if let Some((name, offset)) = h.name
+                        && !name.starts_with('_')
+                    {
+                        let mut used = HashSet::new();
+                        collect_stmt_names(&h.body, &mut used);
+                        if !used.contains(name) {
+                            let (line, col) = lines.offset_to_line_col(offset as usize);
+                            diags.push(Diagnostic {
+                                file: filename.to_string(),
+                                line,
+                                col,
+                                start_byte: offset as usize,
+                                end_byte: offset as usize + name.len(),
+                                code: RuleCode::UnusedExceptVar,
+                                message: if h.is_star {
+                                    format!("Exception group variable `{name}` is never used")
+                                } else {
+                                    format!("Exception variable `{name}` is never used")
+                                },
+                                fix: None,
+                            });
+                        }
+                    }
+                    walk(&h.body, filename, lines, diags);
+                }
+                walk(orelse, filename, lines, diags);
+                walk(finalbody, filename, lines, diags);
+            }
+            StmtKind::FunctionDef(f) => walk(&f.body, filename, lines, diags),
+            StmtKind::ClassDef(c) => walk(&c.body, filename, lines, diags),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                walk(body, filename, lines, diags);
+                walk(orelse, filename, lines, diags);
+            }
+            StmtKind::With { body, .. } => walk(body, filename, lines, diags),
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    walk(&arm.body, filename, lines, diags);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_unused_except_vars(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_unused_except_var_fires() {
+        let diags = check("try:\n    f()\nexcept ValueError as e:\n    pass\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::UnusedExceptVar);
+        assert_eq!((diags[0].line, diags[0].col), (3, 22));
+        assert_eq!(diags[0].message, "Exception variable `e` is never used");
+    }
+
+    #[test]
+    fn test_used_except_var_clean() {
+        assert!(check("try:\n    f()\nexcept ValueError as e:\n    log(e)\n").is_empty());
+        let src = "try:\n    f()\nexcept E as e:\n    if retry:\n        raise Other() from e\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_underscore_and_unnamed_exempt() {
+        assert!(check("try:\n    f()\nexcept E as _e:\n    pass\n").is_empty());
+        assert!(check("try:\n    f()\nexcept E:\n    pass\n").is_empty());
+    }
+
+    #[test]
+    fn test_except_star_group_inspected_clean() {
+        let src = "try:\n    f()\nexcept* ValueError as eg:\n    for e in eg.exceptions:\n        log(e)\n";
+        assert!(check(src).is_empty());
+        let src = "try:\n    f()\nexcept* ValueError as eg:\n    print(len(eg.exceptions))\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_unused_except_star_var_fires() {
+        let diags = check("try:\n    f()\nexcept* OSError as eg:\n    pass\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(
+            diags[0].message,
+            "Exception group variable `eg` is never used"
+        );
+    }
+
+    #[test]
+    fn test_read_after_handler_does_not_count() {
+        let src =
+            "def g():\n    try:\n        f()\n    except E as err:\n        pass\n    return err\n";
+        assert_eq!(check(src).len(), 1);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::ast::{Stmt, StmtKind};
+use crate::location::LineIndex;
+use crate::names::{collect_assigns_and_usages, collect_module_bindings};
+use crate::types::{Diagnostic, RuleCode};
+use std::collections::{HashMap, HashSet};
+
+/// RP016: a `global` or `nonlocal` declaration in a function that never
+/// assigns or reads the name.  The declaration only changes how the name
+/// resolves inside that function, so there it does nothing.
+///
+/// Only declarations directly in the function body are checked; one nested
+/// in an `if` or a loop counts as a mention of its names.
+pub fn check_unused_globals<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    walk(stmts, filename, lines, &mut diags);
+    diags
+}
+
+fn walk<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::FunctionDef(f) => {
+                check_function(&f.body, filename, lines, diags);
+                walk(&f.body, filename, lines, diags);
+            }
+            StmtKind::ClassDef(c) => walk(&c.body, filename, lines, diags),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                walk(body, filename, lines, diags);
+                walk(orelse, filename, lines, diags);
+            }
+            StmtKind::With { body, .. } => walk(body, filename, lines, diags),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                walk(body, filename, lines, diags);
+                for h in handlers {
+                    walk(&h.body, filename, lines, diags);
+                }
+                walk(orelse, filename, lines, diags);
+                walk(finalbody, filename, lines, diags);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    walk(&arm.body, filename, lines, diags);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_function<'src>(
+    body: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    // Every name the body mentions outside its own declarations: reads,
+    // plain assignments, and any other binding (unpacking, `for`, `import`,
+    // `def`, …).  Nested functions count through their closure reads.
+    let mut assigns = HashMap::new();
+    let mut usages = HashSet::new();
+    let mut bound = HashSet::new();
+    for stmt in body {
+        if matches!(stmt.kind, StmtKind::Global(_) | StmtKind::Nonlocal(_)) {
+            continue;
+        }
+        let one = std::slice::from_ref(stmt);
+        collect_assigns_and_usages(one, &mut assigns, &mut usages);
+        collect_module_bindings(one, &mut bound);
+    }
+
+    for stmt in body {
+        let (keyword, names) = match &stmt.kind {
+            StmtKind::Global(names) => ("global", names),
+            StmtKind::Nonlocal(names) => ("nonlocal", names),
+            _ => continue,
+        };
+        for name in names {
+            if assigns.contains_key(*name) || usages.contains(*name) || bound.contains(name) {
+                continue;
+            }
+            let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
+            diags.push(Diagnostic {
+                file: filename.to_string(),
+                line,
+                col,
+                start_byte: stmt.offset as usize,
+                end_byte: lines.line_end(stmt.offset as usize),
+                code: RuleCode::UnusedGlobal,
+                message: format!(
+                    "`{keyword} {name}` is unnecessary: `{name}` is never assigned or used in this function"
+                ),
+                fix: None,
+            });
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_unused_globals(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_unused_global_fires() {
+        let diags = check("def f(y):\n    global x\n    return y\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::UnusedGlobal);
+        assert_eq!((diags[0].line, diags[0].col), (2, 5));
+        assert_eq!(
+            diags[0].message,
+            "`global x` is unnecessary: `x` is never assigned or used in this function"
+        );
+    }
+
+    #[test]
+    fn test_assigned_or_read_global_clean() {
+        assert!(check("def f():\n    global x\n    x = 1\n    return x\n").is_empty());
+        assert!(check("def f():\n    global x\n    return x\n").is_empty());
+        assert!(check("def f():\n    global n\n    n += 1\n").is_empty());
+    }
+
+    #[test]
+    fn test_other_bindings_count() {
+        assert!(check("def f(p):\n    global a\n    a, b = p\n").is_empty());
+        assert!(check("def f():\n    global cfg\n    import cfg\n").is_empty());
+        assert!(check("def f(xs):\n    global i\n    for i in xs:\n        pass\n").is_empty());
+        assert!(check("def f():\n    global x\n    del x\n").is_empty());
+        assert!(check("def f():\n    global g\n    def g():\n        pass\n").is_empty());
+    }
+
+    #[test]
+    fn test_only_unused_names_of_a_declaration_fire() {
+        let diags = check("def f():\n    global a, b\n    a = 1\n");
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.starts_with("`global b`"));
+    }
+
+    #[test]
+    fn test_unused_nonlocal_fires() {
+        let src = "def outer():\n    n = 0\n    def inner():\n        nonlocal n\n        return 1\n    return inner\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        assert_eq!((diags[0].line, diags[0].col), (4, 9));
+        assert!(diags[0].message.starts_with("`nonlocal n`"));
+
+        let src = "def outer():\n    n = 0\n    def inner():\n        nonlocal n\n        n += 1\n    return inner\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_module_level_global_ignored() {
+        assert!(check("global x\n").is_empty());
+    }
+}
@@ -1,6 +1,10 @@
-use crate::ast::{AssignTarget, Stmt, StmtKind};
-use crate::location::offset_to_line_col;
-use crate::names::{collect_dunder_all, collect_stmt_names};
+use crate::ast::{AssignTarget, ExceptHandler, FuncDef, Stmt, StmtKind};
+use crate::config::ReaperConfig;
+use crate::fix::remove_import_aliases;
+use crate::location::LineIndex;
+use crate::names::{
+    collect_dunder_all, collect_stmt_names, collect_target_bindings, collect_type_comment_names,
+};
 use crate::types::{Diagnostic, RuleCode};
 use std::collections::{HashMap, HashSet};
 
@@ -13,6 +17,20 @@ struct ImportDef<'src> {
     /// True for `import a.b.c` (dotted, no alias) — multiple such imports
     /// sharing the same root do NOT redefine each other; skip RP007 for these.
     skip_rp007: bool,
+    /// Where the alias sits, for building a removal fix.  `None` for imports
+    /// that cannot be removed on their own (try/except fallbacks).
+    site: Option<ImportSite>,
+}
+
+/// Position of one alias within its import statement.
+#[derive(Clone, Copy)]
+struct ImportSite {
+    /// Byte offset of the statement.
+    stmt: usize,
+    /// Number of aliases the statement imports.
+    total: usize,
+    /// This alias's index among them.
+    index: usize,
 }
 
 // ── Public entry point ────────────────────────────────────────────────────────
@@ -21,18 +39,72 @@ pub fn check_unused_imports<'src>(
     stmts: &[Stmt<'src>],
     filename: &str,
     source: &str,
+    lines: &LineIndex,
+    config: &ReaperConfig,
 ) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
 
+    // Names that appear only in `# type:` comments still count as used, as do
+    // the configured `always_used` names.
+    let mut extra_usages: HashSet<String> = config.always_used.iter().cloned().collect();
+    collect_type_comment_names(source, &mut extra_usages);
+
     // Pass 1: top-level imports vs whole-file usages.
-    check_scope_imports(stmts, stmts, filename, source, &mut diags);
+    check_scope_imports(
+        stmts,
+        stmts,
+        filename,
+        source,
+        lines,
+        &extra_usages,
+        &mut diags,
+    );
 
     // Pass 2: function-scoped imports.
-    check_nested_scopes(stmts, filename, source, &mut diags);
+    check_nested_scopes(stmts, filename, source, lines, &extra_usages, &mut diags);
+
+    // Star imports outside package `__init__.py` files.
+    if !filename.ends_with("__init__.py") {
+        check_star_imports(stmts, filename, lines, &mut diags);
+    }
+
+    check_branch_reimports(stmts, filename, lines, &mut diags);
+
+    check_import_shadowing(stmts, filename, lines, &mut diags);
 
     diags
 }
 
+// ── Star imports ──────────────────────────────────────────────────────────────
+
+/// RP047: one diagnostic per `from x import *` statement.  Star imports are
+/// only legal at module level, so the walk is shallow.
+fn check_star_imports(
+    stmts: &[Stmt<'_>],
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for stmt in stmts {
+        if let StmtKind::ImportFrom { names, .. } = &stmt.kind
+            && names.iter().any(|a| a.name == "*")
+        {
+            let (line, col) = lines.offset_to_line_col(stmt.offset as usize);
+            diags.push(Diagnostic {
+                file: filename.to_string(),
+                line,
+                col,
+                start_byte: stmt.offset as usize,
+                end_byte: lines.line_end(stmt.offset as usize),
+                code: RuleCode::StarImport,
+                message: "Star import makes namespace analysis incomplete; prefer explicit imports"
+                    .to_string(),
+                fix: None,
+            });
+        }
+    }
+}
+
 // ── Scope-level import checker ────────────────────────────────────────────────
 
 fn check_scope_imports<'src>(
@@ -40,14 +112,23 @@ fn check_scope_imports<'src>(
     usage_scope: &[Stmt<'src>],
     filename: &str,
     source: &str,
+    lines: &LineIndex,
+    extra_usages: &HashSet<String>,
     diags: &mut Vec<Diagnostic>,
 ) {
     let mut imports: Vec<ImportDef<'src>> = Vec::new();
 
     for stmt in import_scope {
+        let site = |total: usize, index: usize| {
+            Some(ImportSite {
+                stmt: stmt.offset as usize,
+                total,
+                index,
+            })
+        };
         match &stmt.kind {
             StmtKind::Import(aliases) => {
-                for alias in aliases {
+                for (index, alias) in aliases.iter().enumerate() {
                     let has_alias = alias.asname.is_some();
                     let is_dotted = alias.name.contains('.');
                     let local_name: &'src str = alias
@@ -58,6 +139,7 @@ fn check_scope_imports<'src>(
                         original: alias.name,
                         offset: alias.offset as usize,
                         skip_rp007: is_dotted && !has_alias,
+                        site: site(aliases.len(), index),
                     });
                 }
             }
@@ -66,7 +148,7 @@ fn check_scope_imports<'src>(
                 if module.map(|m| m == "__future__").unwrap_or(false) {
                     continue;
                 }
-                for alias in names {
+                for (index, alias) in names.iter().enumerate() {
                     // Star imports are never flagged.
                     if alias.name == "*" {
                         continue;
@@ -77,9 +159,13 @@ fn check_scope_imports<'src>(
                         original: alias.name,
                         offset: alias.offset as usize,
                         skip_rp007: false,
+                        site: site(names.len(), index),
                     });
                 }
             }
+            StmtKind::Try { body, handlers, .. } => {
+                imports.extend(unified_fallback_imports(body, handlers));
+            }
             _ => {}
         }
     }
@@ -91,6 +177,7 @@ fn check_scope_imports<'src>(
     // Collect all name usages within the usage scope.
     let mut usages: HashSet<String> = HashSet::new();
     collect_stmt_names(usage_scope, &mut usages);
+    usages.extend(extra_usages.iter().cloned());
 
     // Names exported via __all__ count as used.
     let exported = collect_dunder_all(usage_scope);
@@ -110,39 +197,77 @@ fn check_scope_imports<'src>(
     let import_names: HashSet<&str> = imports.iter().map(|i| i.local_name).collect();
     let assign_clobbers = collect_assignment_clobbers(usage_scope, &import_names);
 
+    // RP001 diagnostics that could be fixed, by statement: (index in `diags`,
+    // alias index).  The fix is built once every unused alias is known.
+    let mut removable: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
+
     for (i, imp) in imports.iter().enumerate() {
         let is_last = last_index.get(imp.local_name) == Some(&i);
 
         if !is_last && !imp.skip_rp007 {
             // Non-last, non-dotted: superseded by a later import → RP007.
-            let (line, col) = offset_to_line_col(imp.offset, source);
+            let (line, col) = lines.offset_to_line_col(imp.offset);
             diags.push(Diagnostic {
                 file: filename.to_string(),
                 line,
                 col,
+                start_byte: imp.offset,
+                end_byte: imp.offset + imp.original.len(),
                 code: RuleCode::RedefinedUnused,
                 message: format!("`{}` imported but redefined before use", imp.original),
+                fix: None,
             });
         } else if assign_clobbers.contains(imp.local_name) && !imp.skip_rp007 {
             // Import was overwritten by a plain assignment before being read → RP007.
-            let (line, col) = offset_to_line_col(imp.offset, source);
+            let (line, col) = lines.offset_to_line_col(imp.offset);
             diags.push(Diagnostic {
                 file: filename.to_string(),
                 line,
                 col,
+                start_byte: imp.offset,
+                end_byte: imp.offset + imp.original.len(),
                 code: RuleCode::RedefinedUnused,
                 message: format!("`{}` imported but redefined before use", imp.original),
+                fix: None,
             });
         } else if !usages.contains(imp.local_name) && !assign_clobbers.contains(imp.local_name) {
             // Unused (including every dotted-no-alias import whose root is unused).
-            let (line, col) = offset_to_line_col(imp.offset, source);
+            let (line, col) = lines.offset_to_line_col(imp.offset);
             diags.push(Diagnostic {
                 file: filename.to_string(),
                 line,
                 col,
+                start_byte: imp.offset,
+                end_byte: imp.offset + imp.original.len(),
                 code: RuleCode::UnusedImport,
                 message: format!("`{}` imported but unused", imp.original),
+                fix: None,
             });
+            if let Some(site) = imp.site {
+                removable
+                    .entry((site.stmt, site.total))
+                    .or_default()
+                    .push((diags.len() - 1, site.index));
+            }
+        }
+    }
+
+    // Deleting every statement of an indented body must leave `pass` behind,
+    // in place of the first one.
+    let deleted = removable
+        .iter()
+        .filter(|((_, total), unused)| unused.len() == *total)
+        .count();
+    let emptied = deleted == import_scope.len();
+
+    // Every unused alias of a statement carries the same fix, which removes
+    // all of them at once; `--fix` applies identical fixes only once.
+    for ((stmt, total), unused) in removable {
+        let remove: Vec<usize> = unused.iter().map(|&(_, index)| index).collect();
+        let sole_in_block = emptied && stmt == import_scope[0].offset as usize;
+        let fix = remove_import_aliases(source, stmt, total, &remove, sole_in_block);
+        for (d, _) in unused {
+            diags[d].fix = fix.clone();
         }
     }
 }
@@ -233,12 +358,328 @@ fn collect_assignment_clobbers<'src>(
     clobbered
 }
 
+// ── Fallback imports ──────────────────────────────────────────────────────────
+
+/// `try: import ujson as json` / `except ImportError: import json` binds one
+/// name whichever branch runs.  Returns a single import for every local name
+/// bound both in the `try` body and in a handler that catches `ImportError`,
+/// located at the `try` branch.  No fix is offered: removing one branch alone
+/// would break the other.
+fn unified_fallback_imports<'src>(
+    body: &[Stmt<'src>],
+    handlers: &[ExceptHandler<'src>],
+) -> Vec<ImportDef<'src>> {
+    let fallback_names: HashSet<&str> = handlers
+        .iter()
+        .filter(|h| catches_import_error(h))
+        .flat_map(|h| import_bindings(&h.body))
+        .map(|(local_name, _, _)| local_name)
+        .collect();
+    import_bindings(body)
+        .into_iter()
+        .filter(|(local_name, _, _)| fallback_names.contains(local_name))
+        .map(|(local_name, original, offset)| ImportDef {
+            local_name,
+            original,
+            offset,
+            skip_rp007: false,
+            site: None,
+        })
+        .collect()
+}
+
+/// Bare `except:` and handlers naming `ImportError` / `ModuleNotFoundError`.
+fn catches_import_error(handler: &ExceptHandler<'_>) -> bool {
+    handler.type_expr.as_ref().is_none_or(|t| {
+        t.names
+            .iter()
+            .any(|(n, _)| matches!(*n, "ImportError" | "ModuleNotFoundError"))
+    })
+}
+
+/// `(local_name, original, offset)` for each name imported directly in `stmts`.
+fn import_bindings<'src>(stmts: &[Stmt<'src>]) -> Vec<(&'src str, &'src str, usize)> {
+    let mut out = Vec::new();
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Import(aliases) => {
+                for alias in aliases {
+                    let local_name = alias
+                        .asname
+                        .unwrap_or_else(|| alias.name.split('.').next().unwrap_or(""));
+                    out.push((local_name, alias.name, alias.offset as usize));
+                }
+            }
+            StmtKind::ImportFrom { names, .. } => {
+                for alias in names.iter().filter(|a| a.name != "*") {
+                    let local_name = alias.asname.unwrap_or(alias.name);
+                    out.push((local_name, alias.name, alias.offset as usize));
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+// ── Imports repeated after conditional imports ────────────────────────────────
+
+/// RP056: an import of a name that every branch of an earlier `if`/`else` in
+/// the same block already imports, e.g. `import json` after
+/// `if FAST: import ujson as json` / `else: import json`.  The later import
+/// silently overrides whichever branch ran.  Checks every block of every scope.
+fn check_branch_reimports<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    // Local name → offset of the `if` whose every branch imports it.
+    let mut branch_imported: HashMap<&str, usize> = HashMap::new();
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::If { body, orelse, .. } => {
+                for name in imported_in_every_branch(body, orelse) {
+                    branch_imported.insert(name, stmt.offset as usize);
+                }
+            }
+            StmtKind::Import(_) | StmtKind::ImportFrom { .. } => {
+                for (local_name, original, offset) in import_bindings(std::slice::from_ref(stmt)) {
+                    let Some(&if_offset) = branch_imported.get(local_name) else {
+                        continue;
+                    };
+                    let (if_line, _) = lines.offset_to_line_col(if_offset);
+                    let (line, col) = lines.offset_to_line_col(offset);
+                    diags.push(Diagnostic {
+                        file: filename.to_string(),
+                        line,
+                        col,
+                        start_byte: offset,
+                        end_byte: offset + original.len(),
+                        code: RuleCode::ReimportAfterBranches,
+                        message: format!(
+                            "`{local_name}` already imported in every branch of the `if` on line {if_line}"
+                        ),
+                        fix: None,
+                    });
+                }
+            }
+            _ => {}
+        }
+        match &stmt.kind {
+            StmtKind::FunctionDef(f) => check_branch_reimports(&f.body, filename, lines, diags),
+            StmtKind::ClassDef(c) => check_branch_reimports(&c.body, filename, lines, diags),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                check_branch_reimports(body, filename, lines, diags);
+                check_branch_reimports(orelse, filename, lines, diags);
+            }
+            StmtKind::With { body, .. } => check_branch_reimports(body, filename, lines, diags),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                check_branch_reimports(body, filename, lines, diags);
+                for h in handlers {
+                    check_branch_reimports(&h.body, filename, lines, diags);
+                }
+                check_branch_reimports(orelse, filename, lines, diags);
+                check_branch_reimports(finalbody, filename, lines, diags);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    check_branch_reimports(&arm.body, filename, lines, diags);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Names imported by both `body` and `orelse` of an `if`; an `elif` chain
+/// counts only when it ends in `else`.
+fn imported_in_every_branch<'src>(
+    body: &[Stmt<'src>],
+    orelse: &[Stmt<'src>],
+) -> HashSet<&'src str> {
+    if orelse.is_empty() {
+        return HashSet::new();
+    }
+    let then = block_imports(body);
+    block_imports(orelse)
+        .into_iter()
+        .filter(|n| then.contains(n))
+        .collect()
+}
+
+/// Names a block always imports: its own import statements plus names every
+/// branch of a nested `if`/`else` imports.
+fn block_imports<'src>(block: &[Stmt<'src>]) -> HashSet<&'src str> {
+    let mut names: HashSet<&str> = import_bindings(block)
+        .into_iter()
+        .map(|(local_name, _, _)| local_name)
+        .collect();
+    for stmt in block {
+        if let StmtKind::If { body, orelse, .. } = &stmt.kind {
+            names.extend(imported_in_every_branch(body, orelse));
+        }
+    }
+    names
+}
+
+// ── Locals shadowing module imports ───────────────────────────────────────────
+
+/// RP061: a function assigns a local with the same name as a module-level
+/// import, hiding the module inside that function.  Reported once per name
+/// per function, at its first assignment; `global` names are rebindings of
+/// the import itself and are skipped.
+fn check_import_shadowing<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    let imported: HashMap<&str, &str> = import_bindings(stmts)
+        .into_iter()
+        .map(|(local_name, original, _)| (local_name, original))
+        .collect();
+    if imported.is_empty() {
+        return;
+    }
+    for_each_function(stmts, &mut |f| {
+        let mut assigned = Vec::new();
+        let mut globals = HashSet::new();
+        scope_assignments(&f.body, &mut assigned, &mut globals);
+        let mut seen = HashSet::new();
+        for (name, offset) in assigned {
+            let Some(original) = imported.get(name) else {
+                continue;
+            };
+            if globals.contains(name) || !seen.insert(name) {
+                continue;
+            }
+            let (line, col) = lines.offset_to_line_col(offset);
+            diags.push(Diagnostic {
+                file: filename.to_string(),
+                line,
+                col,
+                start_byte: offset,
+                end_byte: offset + name.len(),
+                code: RuleCode::ImportShadowedByLocal,
+                message: format!(
+                    "Local name `{name}` shadows the module-level import of `{original}`"
+                ),
+                fix: None,
+            });
+        }
+    });
+}
+
+/// Call `f` on every function in `stmts`, at any depth.
+fn for_each_function<'a, 'src>(stmts: &'a [Stmt<'src>], f: &mut impl FnMut(&'a FuncDef<'src>)) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::FunctionDef(func) => {
+                f(func);
+                for_each_function(&func.body, f);
+            }
+            StmtKind::ClassDef(c) => for_each_function(&c.body, f),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                for_each_function(body, f);
+                for_each_function(orelse, f);
+            }
+            StmtKind::With { body, .. } => for_each_function(body, f),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                for_each_function(body, f);
+                for h in handlers {
+                    for_each_function(&h.body, f);
+                }
+                for_each_function(orelse, f);
+                for_each_function(finalbody, f);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    for_each_function(&arm.body, f);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Names assigned (`x = …`, `x: T = …`, unpacking) in one function scope, in
+/// source order with the statement offset, plus its `global` names.  Nested
+/// functions and classes are separate scopes and are not entered.
+fn scope_assignments<'src>(
+    stmts: &[Stmt<'src>],
+    out: &mut Vec<(&'src str, usize)>,
+    globals: &mut HashSet<&'src str>,
+) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Assign { targets, .. } => {
+                let mut names = HashSet::new();
+                for t in targets {
+                    collect_target_bindings(t, &mut names);
+                }
+                let mut names: Vec<&str> = names.into_iter().collect();
+                names.sort_unstable();
+                out.extend(names.into_iter().map(|n| (n, stmt.offset as usize)));
+            }
+            StmtKind::AnnAssign {
+                target: AssignTarget::Name(n, _),
+                value: Some(_),
+                ..
+            } => out.push((n, stmt.offset as usize)),
+            StmtKind::Global(names) => globals.extend(names),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                scope_assignments(body, out, globals);
+                scope_assignments(orelse, out, globals);
+            }
+            StmtKind::With { body, .. } => scope_assignments(body, out, globals),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                scope_assignments(body, out, globals);
+                for h in handlers {
+                    scope_assignments(&h.body, out, globals);
+                }
+                scope_assignments(orelse, out, globals);
+                scope_assignments(finalbody, out, globals);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    scope_assignments(&arm.body, out, globals);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 // ── Recursive scope descent ───────────────────────────────────────────────────
 
 fn check_nested_scopes<'src>(
     stmts: &[Stmt<'src>],
     filename: &str,
     source: &str,
+    lines: &LineIndex,
+    extra_usages: &HashSet<String>,
     diags: &mut Vec<Diagnostic>,
 ) {
     for stmt in stmts {
@@ -246,28 +687,36 @@ fn check_nested_scopes<'src>(
             StmtKind::FunctionDef(f) => {
                 // Check imports declared inside this function against usages
                 // within the same function body.
-                check_scope_imports(&f.body, &f.body, filename, source, diags);
+                check_scope_imports(
+                    &f.body,
+                    &f.body,
+                    filename,
+                    source,
+                    lines,
+                    extra_usages,
+                    diags,
+                );
                 // Recurse into nested functions.
-                check_nested_scopes(&f.body, filename, source, diags);
+                check_nested_scopes(&f.body, filename, source, lines, extra_usages, diags);
             }
             StmtKind::ClassDef(c) => {
                 // Descend into class bodies to find nested functions.
-                check_nested_scopes(&c.body, filename, source, diags);
+                check_nested_scopes(&c.body, filename, source, lines, extra_usages, diags);
             }
             StmtKind::If { body, orelse, .. } => {
-                check_nested_scopes(body, filename, source, diags);
-                check_nested_scopes(orelse, filename, source, diags);
+                check_nested_scopes(body, filename, source, lines, extra_usages, diags);
+                check_nested_scopes(orelse, filename, source, lines, extra_usages, diags);
             }
             StmtKind::While { body, orelse, .. } => {
-                check_nested_scopes(body, filename, source, diags);
-                check_nested_scopes(orelse, filename, source, diags);
+                check_nested_scopes(body, filename, source, lines, extra_usages, diags);
+                check_nested_scopes(orelse, filename, source, lines, extra_usages, diags);
             }
             StmtKind::For { body, orelse, .. } => {
-                check_nested_scopes(body, filename, source, diags);
-                check_nested_scopes(orelse, filename, source, diags);
+                check_nested_scopes(body, filename, source, lines, extra_usages, diags);
+                check_nested_scopes(orelse, filename, source, lines, extra_usages, diags);
             }
             StmtKind::With { body, .. } => {
-                check_nested_scopes(body, filename, source, diags);
+                check_nested_scopes(body, filename, source, lines, extra_usages, diags);
             }
             StmtKind::Try {
                 body,
@@ -275,16 +724,16 @@ fn check_nested_scopes<'src>(
                 orelse,
                 finalbody,
             } => {
-                check_nested_scopes(body, filename, source, diags);
-                check_nested_scopes(orelse, filename, source, diags);
-                check_nested_scopes(finalbody, filename, source, diags);
+                check_nested_scopes(body, filename, source, lines, extra_usages, diags);
+                check_nested_scopes(orelse, filename, source, lines, extra_usages, diags);
+                check_nested_scopes(finalbody, filename, source, lines, extra_usages, diags);
                 for h in handlers {
-                    check_nested_scopes(&h.body, filename, source, diags);
+                    check_nested_scopes(&h.body, filename, source, lines, extra_usages, diags);
                 }
             }
             StmtKind::Match { arms, .. } => {
                 for arm in arms {
-                    check_nested_scopes(&arm.body, filename, source, diags);
+                    check_nested_scopes(&arm.body, filename, source, lines, extra_usages, diags);
                 }
             }
             _ => {}
@@ -301,7 +750,13 @@ mod tests {
 
     fn check(src: &str) -> Vec<Diagnostic> {
         let stmts = parse(src);
-        check_unused_imports(&stmts, "test.py", src)
+        check_unused_imports(
+            &stmts,
+            "test.py",
+            src,
+            &LineIndex::from_source(src),
+            &ReaperConfig::default(),
+        )
     }
 
     // ── function-scoped imports ──────────────────────────────────────────────
@@ -394,10 +849,99 @@ mod tests {
         assert!(diags[0].message.contains("sys"));
     }
 
+    #[test]
+    fn test_type_comment_counts_as_usage() {
+        let diags = check("from typing import List\nx = []  # type: List[str]\n");
+        assert!(diags.is_empty());
+    }
+
     #[test]
     fn test_star_import_ignored() {
         let diags = check("from os.path import *\n");
-        assert_eq!(diags.len(), 0);
+        assert!(!diags.iter().any(|d| d.code == RuleCode::UnusedImport));
+    }
+
+    // ── try / except ImportError fallbacks ───────────────────────────────────
+
+    const FALLBACK: &str = "try:\n    import ujson as json\nexcept ImportError:\n    import json\n";
+
+    #[test]
+    fn test_fallback_import_used_not_flagged() {
+        let src = format!("{FALLBACK}json.dumps({{}})\n");
+        assert!(check(&src).is_empty());
+    }
+
+    #[test]
+    fn test_fallback_import_unused_flagged_once() {
+        let diags = check(FALLBACK);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::UnusedImport);
+        assert_eq!((diags[0].line, diags[0].col), (2, 12));
+        assert!(diags[0].fix.is_none());
+    }
+
+    #[test]
+    fn test_fallback_from_import_module_not_found() {
+        let src = "try:\n    from lxml import etree\nexcept ModuleNotFoundError:\n    from xml import etree\n";
+        assert_eq!(check(src).len(), 1);
+    }
+
+    #[test]
+    fn test_fallback_import_in_function_scope() {
+        let src = "def load():\n    try:\n        import ujson as json\n    except ImportError:\n        import json\n    return json.loads('1')\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_try_import_with_other_handler_not_unified() {
+        let src = "try:\n    import ujson as json\nexcept ValueError:\n    import json\n";
+        assert!(check(src).is_empty());
+    }
+
+    // ── RP047: star imports ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_star_import_flagged_outside_init() {
+        let stmts = parse("from os import *\n");
+        let diags = check_unused_imports(
+            &stmts,
+            "utils.py",
+            "from os import *\n",
+            &LineIndex::from_source("from os import *\n"),
+            &ReaperConfig::default(),
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::StarImport);
+        assert_eq!((diags[0].line, diags[0].col), (1, 1));
+    }
+
+    #[test]
+    fn test_star_import_allowed_in_init() {
+        let stmts = parse("from os import *\n");
+        let diags = check_unused_imports(
+            &stmts,
+            "pkg/__init__.py",
+            "from os import *\n",
+            &LineIndex::from_source("from os import *\n"),
+            &ReaperConfig::default(),
+        );
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_explicit_from_import_not_star() {
+        let diags = check("from os import path\npath.join('a')\n");
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_star_import_fires_once_per_statement() {
+        let diags = check("from os import *\nfrom sys import *\n");
+        let star: Vec<_> = diags
+            .iter()
+            .filter(|d| d.code == RuleCode::StarImport)
+            .collect();
+        assert_eq!(star.len(), 2);
     }
 
     #[test]
@@ -468,4 +1012,196 @@ mod tests {
         let diags = check("import os\nimport sys\nos.getcwd()\nsys.exit()\n");
         assert_eq!(diags.len(), 0);
     }
+
+    #[test]
+    fn test_always_used_import_not_flagged() {
+        let src = "import urls\n";
+        let stmts = parse(src);
+        let config = ReaperConfig {
+            always_used: vec!["urls".to_string()],
+            ..ReaperConfig::default()
+        };
+        assert_eq!(check(src).len(), 1);
+        assert_eq!(
+            check_unused_imports(
+                &stmts,
+                "test.py",
+                src,
+                &LineIndex::from_source(src),
+                &config
+            )
+            .len(),
+            0
+        );
+    }
+
+    // ── fixes ─────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_unused_import_has_removal_fix() {
+        let diags = check("import os\n");
+        let fix = diags[0].fix.as_ref().expect("RP001 should carry a fix");
+        assert_eq!((fix.start, fix.end), (0, 10));
+        assert_eq!(fix.replacement, "");
+    }
+
+    #[test]
+    fn test_from_import_fix_covers_line() {
+        let src = "import sys\nfrom os import path\nprint(sys)\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        let fix = diags[0].fix.as_ref().unwrap();
+        assert_eq!(&src[fix.start..fix.end], "from os import path\n");
+    }
+
+    #[test]
+    fn test_multi_name_import_fix_removes_only_unused() {
+        let src = "import os, sys\nprint(sys)\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        let fix = diags[0].fix.as_ref().unwrap();
+        assert_eq!(&src[fix.start..fix.end], "os, sys");
+        assert_eq!(fix.replacement, "sys");
+    }
+
+    #[test]
+    fn test_unused_aliases_of_one_statement_share_a_fix() {
+        let src = "from m import a, b as bee, c\nprint(bee)\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].fix, diags[1].fix);
+        let fix = diags[0].fix.as_ref().unwrap();
+        assert_eq!(fix.replacement, "b as bee");
+
+        let diags = check("from m import a, b\n");
+        assert_eq!(diags.len(), 2);
+        let fix = diags[0].fix.as_ref().unwrap();
+        assert_eq!((fix.start, fix.end), (0, 19));
+        assert_eq!(diags[1].fix, diags[0].fix);
+    }
+
+    #[test]
+    fn test_sole_function_import_fix_leaves_pass() {
+        let src = "def f():\n    import os\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        let fix = diags[0].fix.as_ref().unwrap();
+        assert_eq!(&src[fix.start..fix.end], "import os");
+        assert_eq!(fix.replacement, "pass");
+    }
+
+    #[test]
+    fn test_emptied_function_body_keeps_pass() {
+        let src = "def f():\n    import os\n    from m import a, b\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 3);
+        let fix = diags[0].fix.as_ref().unwrap();
+        assert_eq!(&src[fix.start..fix.end], "import os");
+        assert_eq!(fix.replacement, "pass");
+        let fix = diags[1].fix.as_ref().unwrap();
+        assert_eq!(&src[fix.start..fix.end], "    from m import a, b\n");
+    }
+
+    #[test]
+    fn test_redefined_import_has_no_fix() {
+        let diags = check("import os\nimport os\nprint(os)\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::RedefinedUnused);
+        assert!(diags[0].fix.is_none());
+    }
+
+    fn reimports(src: &str) -> Vec<Diagnostic> {
+        check(src)
+            .into_iter()
+            .filter(|d| d.code == RuleCode::ReimportAfterBranches)
+            .collect()
+    }
+
+    #[test]
+    fn test_reimport_after_if_else_fires() {
+        let src = "if FAST:\n    import ujson as json\nelse:\n    import json\nimport json\nprint(json)\n";
+        let diags = reimports(src);
+        assert_eq!(diags.len(), 1);
+        assert_eq!((diags[0].line, diags[0].col), (5, 8));
+        assert_eq!(
+            diags[0].message,
+            "`json` already imported in every branch of the `if` on line 1"
+        );
+    }
+
+    #[test]
+    fn test_reimport_after_if_without_else_clean() {
+        let src = "if FAST:\n    import ujson as json\nimport json\nprint(json)\n";
+        assert!(reimports(src).is_empty());
+        let src = "if a:\n    import json\nelif b:\n    import json\nimport json\nprint(json)\n";
+        assert!(reimports(src).is_empty());
+    }
+
+    #[test]
+    fn test_reimport_after_elif_chain_with_else_fires() {
+        let src = "if a:\n    from x import f\nelif b:\n    from y import f\nelse:\n    from z import f\nfrom w import f\nf()\n";
+        assert_eq!(reimports(src).len(), 1);
+    }
+
+    #[test]
+    fn test_reimport_in_function_body_fires() {
+        let src = "def g():\n    if a:\n        import json\n    else:\n        import simplejson as json\n    import json\n    return json\n";
+        assert_eq!(reimports(src).len(), 1);
+    }
+
+    #[test]
+    fn test_only_one_branch_imports_name_clean() {
+        let src = "if a:\n    import json\nelse:\n    json = None\nimport json\nprint(json)\n";
+        assert!(reimports(src).is_empty());
+    }
+
+    // ── RP061: locals shadowing module imports ──────────────────────────────
+
+    fn shadowing(src: &str) -> Vec<Diagnostic> {
+        check(src)
+            .into_iter()
+            .filter(|d| d.code == RuleCode::ImportShadowedByLocal)
+            .collect()
+    }
+
+    #[test]
+    fn test_local_shadowing_import_fires() {
+        let diags = shadowing("import json\n\ndef process():\n    json = {}\n    return json\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!((diags[0].line, diags[0].col), (4, 5));
+        assert_eq!(
+            diags[0].message,
+            "Local name `json` shadows the module-level import of `json`"
+        );
+        assert_eq!(
+            shadowing("import os\ndef f():\n    os = 1\n    return os\n").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_differently_named_local_clean() {
+        assert!(shadowing("import os\ndef f():\n    my_os = os\n    return my_os\n").is_empty());
+    }
+
+    #[test]
+    fn test_shadowing_reports_alias_original_once() {
+        let src =
+            "import numpy as np\ndef f(c):\n    np = 1\n    if c:\n        np = 2\n    return np\n";
+        let diags = shadowing(src);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.ends_with("import of `numpy`"));
+    }
+
+    #[test]
+    fn test_global_rebinding_not_shadowing() {
+        let src = "import json\ndef f():\n    global json\n    json = None\n";
+        assert!(shadowing(src).is_empty());
+    }
+
+    #[test]
+    fn test_unpacking_and_method_scopes_fire() {
+        let src = "from os import path\nclass C:\n    def m(self, p):\n        path, name = p\n        return path, name\n";
+        assert_eq!(shadowing(src).len(), 1);
+    }
 }
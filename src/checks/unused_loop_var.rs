@@ -1,5 +1,5 @@
 use crate::ast::{AssignTarget, Stmt, StmtKind};
-use crate::location::offset_to_line_col;
+use crate::location::LineIndex;
 use crate::names::{collect_stmt_names, stmts_contain_any_name};
 use crate::types::{Diagnostic, RuleCode};
 use std::collections::HashSet;
@@ -7,10 +7,10 @@ use std::collections::HashSet;
 pub fn check_unused_loop_vars<'src>(
     stmts: &[Stmt<'src>],
     filename: &str,
-    source: &str,
+    lines: &LineIndex,
 ) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
-    walk_inner(stmts, filename, source, &mut diags, false);
+    walk_inner(stmts, filename, lines, &mut diags, false);
     diags
 }
 
@@ -21,11 +21,11 @@ pub fn check_unused_loop_vars<'src>(
 fn walk_inner<'src>(
     stmts: &[Stmt<'src>],
     filename: &str,
-    source: &str,
+    lines: &LineIndex,
     diags: &mut Vec<Diagnostic>,
     suppress: bool,
 ) {
-    for stmt in stmts {
+    for (i, stmt) in stmts.iter().enumerate() {
         match &stmt.kind {
             StmtKind::For {
                 target,
@@ -34,31 +34,32 @@ fn walk_inner<'src>(
                 ..
             } => {
                 if !suppress {
-                    check_for_target(target, body, filename, source, diags);
+                    let after = &stmts[i + 1..];
+                    check_for_target(target, body, orelse, after, filename, lines, diags);
                 }
-                walk_inner(body, filename, source, diags, suppress);
-                walk_inner(orelse, filename, source, diags, suppress);
+                walk_inner(body, filename, lines, diags, suppress);
+                walk_inner(orelse, filename, lines, diags, suppress);
             }
             StmtKind::FunctionDef(f) => {
                 // Determine whether this function calls locals() or vars()
                 // anywhere in its body. Use early-exit scanner to avoid
                 // building a full HashSet per function.
                 let fn_suppress = stmts_contain_any_name(&f.body, &["locals", "vars"]);
-                walk_inner(&f.body, filename, source, diags, fn_suppress);
+                walk_inner(&f.body, filename, lines, diags, fn_suppress);
             }
             StmtKind::ClassDef(c) => {
-                walk_inner(&c.body, filename, source, diags, suppress);
+                walk_inner(&c.body, filename, lines, diags, suppress);
             }
             StmtKind::If { body, orelse, .. } => {
-                walk_inner(body, filename, source, diags, suppress);
-                walk_inner(orelse, filename, source, diags, suppress);
+                walk_inner(body, filename, lines, diags, suppress);
+                walk_inner(orelse, filename, lines, diags, suppress);
             }
             StmtKind::While { body, orelse, .. } => {
-                walk_inner(body, filename, source, diags, suppress);
-                walk_inner(orelse, filename, source, diags, suppress);
+                walk_inner(body, filename, lines, diags, suppress);
+                walk_inner(orelse, filename, lines, diags, suppress);
             }
             StmtKind::With { body, .. } => {
-                walk_inner(body, filename, source, diags, suppress);
+                walk_inner(body, filename, lines, diags, suppress);
             }
             StmtKind::Try {
                 body,
@@ -66,16 +67,16 @@ fn walk_inner<'src>(
                 orelse,
                 finalbody,
             } => {
-                walk_inner(body, filename, source, diags, suppress);
-                walk_inner(orelse, filename, source, diags, suppress);
-                walk_inner(finalbody, filename, source, diags, suppress);
+                walk_inner(body, filename, lines, diags, suppress);
+                walk_inner(orelse, filename, lines, diags, suppress);
+                walk_inner(finalbody, filename, lines, diags, suppress);
                 for h in handlers {
-                    walk_inner(&h.body, filename, source, diags, suppress);
+                    walk_inner(&h.body, filename, lines, diags, suppress);
                 }
             }
             StmtKind::Match { arms, .. } => {
                 for arm in arms {
-                    walk_inner(&arm.body, filename, source, diags, suppress);
+                    walk_inner(&arm.body, filename, lines, diags, suppress);
                 }
             }
             _ => {}
@@ -83,11 +84,16 @@ fn walk_inner<'src>(
     }
 }
 
+/// A target read after the loop (in its `else` or the statements following
+/// it) keeps the last item on purpose, so it is not reported here; RP052
+/// covers the case where the loop never ran.
 fn check_for_target<'src>(
     target: &AssignTarget<'src>,
     body: &[Stmt<'src>],
+    orelse: &[Stmt<'src>],
+    after: &[Stmt<'src>],
     filename: &str,
-    source: &str,
+    lines: &LineIndex,
     diags: &mut Vec<Diagnostic>,
 ) {
     let mut bound: Vec<(&'src str, u32)> = Vec::new();
@@ -99,6 +105,8 @@ fn check_for_target<'src>(
 
     let mut usages: HashSet<String> = HashSet::new();
     collect_stmt_names(body, &mut usages);
+    collect_stmt_names(orelse, &mut usages);
+    collect_stmt_names(after, &mut usages);
 
     for (name, offset) in bound {
         // Names starting with `_` are intentionally unused by convention.
@@ -106,13 +114,16 @@ fn check_for_target<'src>(
             continue;
         }
         if !usages.contains(name) {
-            let (line, col) = offset_to_line_col(offset as usize, source);
+            let (line, col) = lines.offset_to_line_col(offset as usize);
             diags.push(Diagnostic {
                 file: filename.to_string(),
                 line,
                 col,
+                start_byte: offset as usize,
+                end_byte: offset as usize + name.len(),
                 code: RuleCode::UnusedLoopVariable,
                 message: format!("Loop variable `{name}` is not used"),
+                fix: None,
             });
         }
     }
@@ -141,7 +152,7 @@ mod tests {
 
     fn check(src: &str) -> Vec<Diagnostic> {
         let stmts = parse(src);
-        check_unused_loop_vars(&stmts, "test.py", src)
+        check_unused_loop_vars(&stmts, "test.py", &LineIndex::from_source(src))
     }
 
     #[test]
@@ -198,6 +209,17 @@ mod tests {
         assert_eq!(diags.len(), 0);
     }
 
+    #[test]
+    fn test_loop_var_read_after_loop_not_flagged() {
+        let src = "def f(items):\n    for item in items:\n        pass\n    return item\n";
+        assert!(check(src).is_empty());
+        let src = "for line in lines:\n    pass\nelse:\n    print(line)\n";
+        assert!(check(src).is_empty());
+        // A read in an unrelated later scope does not count.
+        let src = "def f(items):\n    for item in items:\n        pass\n\ndef g(item):\n    return item\n";
+        assert_eq!(check(src).len(), 1);
+    }
+
     // ── locals() / vars() suppression ────────────────────────────────────────
 
     #[test]
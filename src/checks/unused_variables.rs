@@ -1,5 +1,6 @@
 use crate::ast::{Stmt, StmtKind};
-use crate::location::offset_to_line_col;
+use crate::config::ReaperConfig;
+use crate::location::LineIndex;
 use crate::names::collect_assigns_and_usages;
 use crate::types::{Diagnostic, RuleCode};
 use std::collections::{HashMap, HashSet};
@@ -7,42 +8,44 @@ use std::collections::{HashMap, HashSet};
 pub fn check_unused_variables<'src>(
     stmts: &[Stmt<'src>],
     filename: &str,
-    source: &str,
+    lines: &LineIndex,
+    config: &ReaperConfig,
 ) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
-    visit_for_functions(stmts, filename, source, &mut diags);
+    visit_for_functions(stmts, filename, lines, config, &mut diags);
     diags
 }
 
 fn visit_for_functions<'src>(
     stmts: &[Stmt<'src>],
     filename: &str,
-    source: &str,
+    lines: &LineIndex,
+    config: &ReaperConfig,
     diags: &mut Vec<Diagnostic>,
 ) {
     for stmt in stmts {
         match &stmt.kind {
             StmtKind::FunctionDef(f) => {
-                check_function_body(&f.body, filename, source, diags);
-                visit_for_functions(&f.body, filename, source, diags);
+                check_function_body(&f.body, filename, lines, config, diags);
+                visit_for_functions(&f.body, filename, lines, config, diags);
             }
             StmtKind::ClassDef(c) => {
-                visit_for_functions(&c.body, filename, source, diags);
+                visit_for_functions(&c.body, filename, lines, config, diags);
             }
             StmtKind::If { body, orelse, .. } => {
-                visit_for_functions(body, filename, source, diags);
-                visit_for_functions(orelse, filename, source, diags);
+                visit_for_functions(body, filename, lines, config, diags);
+                visit_for_functions(orelse, filename, lines, config, diags);
             }
             StmtKind::While { body, orelse, .. } => {
-                visit_for_functions(body, filename, source, diags);
-                visit_for_functions(orelse, filename, source, diags);
+                visit_for_functions(body, filename, lines, config, diags);
+                visit_for_functions(orelse, filename, lines, config, diags);
             }
             StmtKind::For { body, orelse, .. } => {
-                visit_for_functions(body, filename, source, diags);
-                visit_for_functions(orelse, filename, source, diags);
+                visit_for_functions(body, filename, lines, config, diags);
+                visit_for_functions(orelse, filename, lines, config, diags);
             }
             StmtKind::With { body, .. } => {
-                visit_for_functions(body, filename, source, diags);
+                visit_for_functions(body, filename, lines, config, diags);
             }
             StmtKind::Try {
                 body,
@@ -50,16 +53,16 @@ fn visit_for_functions<'src>(
                 orelse,
                 finalbody,
             } => {
-                visit_for_functions(body, filename, source, diags);
-                visit_for_functions(orelse, filename, source, diags);
-                visit_for_functions(finalbody, filename, source, diags);
+                visit_for_functions(body, filename, lines, config, diags);
+                visit_for_functions(orelse, filename, lines, config, diags);
+                visit_for_functions(finalbody, filename, lines, config, diags);
                 for h in handlers {
-                    visit_for_functions(&h.body, filename, source, diags);
+                    visit_for_functions(&h.body, filename, lines, config, diags);
                 }
             }
             StmtKind::Match { arms, .. } => {
                 for arm in arms {
-                    visit_for_functions(&arm.body, filename, source, diags);
+                    visit_for_functions(&arm.body, filename, lines, config, diags);
                 }
             }
             _ => {}
@@ -70,7 +73,8 @@ fn visit_for_functions<'src>(
 fn check_function_body<'src>(
     body: &[Stmt<'src>],
     filename: &str,
-    source: &str,
+    lines: &LineIndex,
+    config: &ReaperConfig,
     diags: &mut Vec<Diagnostic>,
 ) {
     let mut assigns: HashMap<String, usize> = HashMap::new();
@@ -85,17 +89,43 @@ fn check_function_body<'src>(
     }
 
     for (name, offset) in &assigns {
+        // `_` is a throwaway by convention: never unused, but misleading
+        // when something reads it back.
+        if name == "_" {
+            if usages.contains(name) {
+                let (line, col) = lines.offset_to_line_col(*offset);
+                diags.push(Diagnostic {
+                    file: filename.to_string(),
+                    line,
+                    col,
+                    start_byte: *offset,
+                    end_byte: *offset + name.len(),
+                    code: RuleCode::ThrowawayNameUsed,
+                    message:
+                        "Name `_` is used after being assigned; consider using a descriptive name"
+                            .to_string(),
+                    fix: None,
+                });
+            }
+            continue;
+        }
         if name.starts_with('_') {
             continue;
         }
-        if !usages.contains(name) {
-            let (line, col) = offset_to_line_col(*offset, source);
+        if !usages.contains(name)
+            && !config.always_used.contains(name)
+            && !config.lenient.exempts_variable(name)
+        {
+            let (line, col) = lines.offset_to_line_col(*offset);
             diags.push(Diagnostic {
                 file: filename.to_string(),
                 line,
                 col,
+                start_byte: *offset,
+                end_byte: *offset + name.len(),
                 code: RuleCode::UnusedVariable,
                 message: format!("Local variable `{name}` is assigned but never used"),
+                fix: None,
             });
         }
     }
@@ -110,7 +140,12 @@ mod tests {
 
     fn check(src: &str) -> Vec<Diagnostic> {
         let stmts = parse(src);
-        check_unused_variables(&stmts, "test.py", src)
+        check_unused_variables(
+            &stmts,
+            "test.py",
+            &LineIndex::from_source(src),
+            &ReaperConfig::default(),
+        )
     }
 
     #[test]
@@ -225,9 +260,133 @@ mod tests {
         assert_eq!(diags.len(), 0);
     }
 
+    #[test]
+    fn test_walrus_in_comprehension_condition() {
+        let unused = check("def f(data):\n    return [x for x in data if (y := g(x))]\n");
+        assert_eq!(unused.len(), 1);
+        assert!(unused[0].message.contains("`y`"));
+        let used =
+            check("def f(data):\n    r = [x for x in data if (y := g(x))]\n    return r, y\n");
+        assert!(used.is_empty());
+    }
+
+    #[test]
+    fn test_nested_walrus_inner_target_unused() {
+        let diags = check("def f():\n    (a := (b := 0))\n    return a\n");
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`b`"));
+    }
+
+    #[test]
+    fn test_walrus_in_assert_message() {
+        let unused = check("def f(ok):\n    assert ok, (msg := \"bad\")\n");
+        assert_eq!(unused.len(), 1);
+        assert!(unused[0].message.contains("`msg`"));
+        assert!(check("def f(ok):\n    assert ok, (msg := \"bad\")\n    return msg\n").is_empty());
+    }
+
+    #[test]
+    fn test_walrus_in_with_context() {
+        let unused = check("def f():\n    with (cm := open_it()):\n        pass\n");
+        assert_eq!(unused.len(), 1);
+        assert!(unused[0].message.contains("`cm`"));
+        assert!(check("def f():\n    with (cm := open_it()):\n        return cm\n").is_empty());
+    }
+
     #[test]
     fn test_walrus_underscore_exempt() {
         let diags = check("def f():\n    (_ := side_effect())\n    return 0\n");
         assert_eq!(diags.len(), 0);
     }
+
+    #[test]
+    fn test_always_used_not_flagged() {
+        let src = "def foo():\n    handler = 1\n    return 0\n";
+        let stmts = parse(src);
+        let config = ReaperConfig {
+            always_used: vec!["handler".to_string()],
+            ..ReaperConfig::default()
+        };
+        assert_eq!(check(src).len(), 1);
+        assert_eq!(
+            check_unused_variables(&stmts, "test.py", &LineIndex::from_source(src), &config).len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_with_target_left_to_rp011() {
+        assert!(check("def f():\n    with ctx() as x:\n        pass\n").is_empty());
+        let src = "def f():\n    with open(\"f\") as fh:\n        data = fh.read()\n    return 1\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`data`"));
+    }
+
+    #[test]
+    fn test_unused_match_capture_flagged() {
+        let src = "def f(p):\n    match p:\n        case [x, *rest]:\n            return x\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`rest`"));
+    }
+
+    #[test]
+    fn test_unused_keyword_pattern_capture_flagged() {
+        let src = "def f(p):\n    match p:\n        case Point(x=px):\n            return 1\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`px`"));
+    }
+
+    #[test]
+    fn test_match_capture_used_in_guard_not_flagged() {
+        let src = "def f(p):\n    match p:\n        case n if n > 0:\n            return 1\n        case _:\n            return 0\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_tuple_unpacking_not_flagged() {
+        assert!(check("def f():\n    a, b = pair()\n    return a\n").is_empty());
+        assert!(check("def f():\n    head, *rest = items()\n").is_empty());
+    }
+
+    #[test]
+    fn test_throwaway_name_read_fires() {
+        let diags = check("def f():\n    _ = compute()\n    print(_)\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::ThrowawayNameUsed);
+        assert_eq!((diags[0].line, diags[0].col), (2, 5));
+    }
+
+    #[test]
+    fn test_throwaway_name_unused_clean() {
+        assert!(check("def f():\n    _ = compute()\n").is_empty());
+    }
+
+    #[test]
+    fn test_lenient_logger_exempt() {
+        let src = "def foo():\n    logger = get_logger()\n    log = 1\n    return 0\n";
+        let stmts = parse(src);
+        let config = ReaperConfig {
+            lenient: crate::config::LenientHeuristics {
+                loggers: true,
+                ..Default::default()
+            },
+            ..ReaperConfig::default()
+        };
+        assert_eq!(check(src).len(), 2);
+        assert!(
+            check_unused_variables(&stmts, "test.py", &LineIndex::from_source(src), &config)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_subscript_store_is_not_a_binding() {
+        let diags = check("def f():\n    y = {}\n    y[1] = 2\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].line, 2);
+        assert!(check("def f():\n    y = {}\n    y[1] = 2\n    return y\n").is_empty());
+    }
 }
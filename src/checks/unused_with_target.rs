@@ -0,0 +1,184 @@
+use crate::ast::{AssignTarget, Stmt, StmtKind};
+use crate::location::LineIndex;
+use crate::names::collect_stmt_names;
+use crate::types::{Diagnostic, RuleCode};
+use std::collections::HashSet;
+
+/// RP011: `with ctx() as name:` where `name` is never read.  The binding
+/// outlives the block, so a read anywhere in the enclosing scope counts —
+/// `with Timer() as t: work()` followed by `t.elapsed` is clean.
+///
+/// Names starting with `_` are exempt.  A tuple target is reported only when
+/// none of its names is read, since unpacking often discards some elements on
+/// purpose.
+pub fn check_unused_with_targets<'src>(
+    stmts: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    check_scope(stmts, filename, lines, &mut diags);
+    diags
+}
+
+/// Check the `with` statements of one function or module body against the
+/// names read anywhere in it.
+fn check_scope<'src>(
+    body: &[Stmt<'src>],
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    let mut used = HashSet::new();
+    collect_stmt_names(body, &mut used);
+    walk(body, &used, filename, lines, diags);
+}
+
+fn walk<'src>(
+    stmts: &[Stmt<'src>],
+    used: &HashSet<String>,
+    filename: &str,
+    lines: &LineIndex,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::With { items, body, .. } => {
+                for target in items.iter().filter_map(|item| item.target.as_ref()) {
+                    let mut names = Vec::new();
+                    bound_names(target, &mut names);
+                    if names.iter().any(|(n, _)| used.contains(*n)) {
+                        continue;
+                    }
+                    for (name, offset) in names {
+                        if name.starts_with('_') {
+                            continue;
+                        }
+                        let (line, col) = lines.offset_to_line_col(offset as usize);
+                        diags.push(Diagnostic {
+                            file: filename.to_string(),
+                            line,
+                            col,
+                            start_byte: offset as usize,
+                            end_byte: offset as usize + name.len(),
+                            code: RuleCode::UnusedWithTarget,
+                            message: format!("`with` target `{name}` is never used"),
+                            fix: None,
+                        });
+                    }
+                }
+                walk(body, used, filename, lines, diags);
+            }
+            StmtKind::FunctionDef(f) => check_scope(&f.body, filename, lines, diags),
+            StmtKind::ClassDef(c) => walk(&c.body, used, filename, lines, diags),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                walk(body, used, filename, lines, diags);
+                walk(orelse, used, filename, lines, diags);
+            }
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                walk(body, used, filename, lines, diags);
+                for h in handlers {
+                    walk(&h.body, used, filename, lines, diags);
+                }
+                walk(orelse, used, filename, lines, diags);
+                walk(finalbody, used, filename, lines, diags);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    walk(&arm.body, used, filename, lines, diags);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Simple names bound by a `with` target; attribute and subscript targets
+/// bind none.
+fn bound_names<'src>(target: &AssignTarget<'src>, out: &mut Vec<(&'src str, u32)>) {
+    match target {
+        AssignTarget::Name(n, o) => out.push((n, *o)),
+        AssignTarget::Tuple(elts) | AssignTarget::List(elts) => {
+            for e in elts {
+                bound_names(e, out);
+            }
+        }
+        AssignTarget::Starred(inner) => bound_names(inner, out),
+        AssignTarget::Complex(_) => {}
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast_parser::parse;
+
+    fn check(src: &str) -> Vec<Diagnostic> {
+        let stmts = parse(src);
+        check_unused_with_targets(&stmts, "test.py", &LineIndex::from_source(src))
+    }
+
+    #[test]
+    fn test_unused_with_target_fires() {
+        let diags = check("def f():\n    with ctx() as x:\n        pass\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, RuleCode::UnusedWithTarget);
+        assert_eq!((diags[0].line, diags[0].col), (2, 19));
+        assert_eq!(diags[0].message, "`with` target `x` is never used");
+    }
+
+    #[test]
+    fn test_used_with_target_clean() {
+        assert!(check("def f():\n    with ctx() as x:\n        return x\n").is_empty());
+        let src =
+            "def f():\n    with open(\"f\") as fh:\n        data = fh.read()\n    return data\n";
+        assert!(check(src).is_empty());
+        assert!(check("with open(p) as fh:\n    print(fh.read())\n").is_empty());
+    }
+
+    #[test]
+    fn test_read_after_block_clean() {
+        let src = "def f():\n    with Timer() as t:\n        work()\n    return t.elapsed\n";
+        assert!(check(src).is_empty());
+    }
+
+    #[test]
+    fn test_underscore_and_attribute_targets_exempt() {
+        assert!(check("with ctx() as _unused:\n    pass\n").is_empty());
+        assert!(check("with ctx() as self.conn:\n    pass\n").is_empty());
+    }
+
+    #[test]
+    fn test_tuple_target_reported_only_when_all_unused() {
+        assert!(check("with pair() as (a, b):\n    print(a)\n").is_empty());
+        let diags = check("with pair() as (a, b):\n    pass\n");
+        assert_eq!(diags.len(), 2);
+    }
+
+    #[test]
+    fn test_nested_and_async_with() {
+        let src =
+            "def f():\n    with outer() as a:\n        with inner(a) as b:\n            pass\n";
+        let diags = check(src);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("`b`"));
+
+        let src = "async def f():\n    async with lock() as held:\n        pass\n";
+        assert_eq!(check(src).len(), 1);
+    }
+
+    #[test]
+    fn test_scopes_are_separate() {
+        let src = "def f():\n    with ctx() as x:\n        pass\ndef g(x):\n    return x\n";
+        assert_eq!(check(src).len(), 1);
+    }
+}
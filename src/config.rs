@@ -0,0 +1,511 @@
+//! Analysis configuration.
+//!
+//! [`ReaperConfig`] carries the project-level knobs that change what the
+//! checkers treat as "used".  [`ReaperConfig::default`] is the behaviour you
+//! get with no configuration at all.
+//!
+//! [`Config`] is the on-disk `reaper.toml`, or the `[tool.reaper]` table of a
+//! `pyproject.toml`.  The CLI looks for one in the directory of the first
+//! analysis path and then in each ancestor up to the filesystem root; the
+//! nearest file wins, and `reaper.toml` beats `pyproject.toml` in the same
+//! directory.  Its values are defaults that command-line flags override.
+
+use crate::checks::unused_defs::DEFAULT_EXEMPT_DUNDERS;
+use crate::python;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer};
+use std::path::{Path, PathBuf};
+
+/// Name of the project configuration file.
+pub const CONFIG_FILE: &str = "reaper.toml";
+
+/// Name of the standard Python project file, read for its `[tool.reaper]`
+/// table.
+pub const PYPROJECT_FILE: &str = "pyproject.toml";
+
+/// Decorators whose functions receive their arguments from a framework rather
+/// than from a direct call — Celery's `@app.task` and `@shared_task`.
+pub const DEFAULT_FRAMEWORK_EXEMPTIONS: &[&str] = &["task", "shared_task"];
+
+/// Django decorators whose functions are invoked by the URL dispatcher or the
+/// signal machinery, enabled by `framework = "django"`.
+pub const DJANGO_EXEMPTIONS: &[&str] = &[
+    "receiver",
+    "login_required",
+    "permission_required",
+    "user_passes_test",
+    "staff_member_required",
+    "require_POST",
+    "require_GET",
+    "require_safe",
+    "require_http_methods",
+    "csrf_exempt",
+    "csrf_protect",
+    "ensure_csrf_cookie",
+    "never_cache",
+    "cache_page",
+    "api_view",
+];
+
+/// Name-based heuristics enabled by `--lenient`.  Each one trades possible
+/// findings for fewer false positives in codebases full of implicit uses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LenientHeuristics {
+    /// Functions named `*_handler`, `*_callback` or `on_*` are exempt from
+    /// RP003: they are usually registered with an event system.
+    pub callbacks: bool,
+    /// Classes with `Meta` in their name are exempt from RP004.
+    pub meta_classes: bool,
+    /// Locals named `logger` or `log` are exempt from RP002.
+    pub loggers: bool,
+}
+
+impl LenientHeuristics {
+    /// True when a function called `name` is exempt from RP003.
+    pub fn exempts_function(&self, name: &str) -> bool {
+        self.callbacks
+            && (name.ends_with("_handler")
+                || name.ends_with("_callback")
+                || name.starts_with("on_"))
+    }
+
+    /// True when a class called `name` is exempt from RP004.
+    pub fn exempts_class(&self, name: &str) -> bool {
+        self.meta_classes && name.contains("Meta")
+    }
+
+    /// True when a local variable called `name` is exempt from RP002.
+    pub fn exempts_variable(&self, name: &str) -> bool {
+        self.loggers && matches!(name, "logger" | "log")
+    }
+}
+
+/// Settings read from `reaper.toml` or `[tool.reaper]`.  Keys mirror the CLI flags of the same
+/// name; a missing key leaves the CLI default in place.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Rule codes to report, like `--select`.
+    pub select: Option<Vec<String>>,
+    /// Path names to skip, like `--exclude`.
+    pub exclude: Option<Vec<String>>,
+    /// Exit 0 even with findings, like `--no-exit-code`.
+    pub no_exit_code: Option<bool>,
+    /// Name patterns never to report, like `--ignore-names`.
+    pub ignore_names: Option<Vec<String>>,
+    /// Target Python version written `"3.11"`, like `--python-version`.
+    #[serde(default, deserialize_with = "python_version")]
+    pub python_version: Option<(u32, u32)>,
+    /// Globs of files to analyse, like `--include`.
+    pub include: Option<Vec<String>>,
+    /// Web framework preset, like `--framework`.
+    #[serde(default, deserialize_with = "framework")]
+    pub framework: Option<String>,
+    /// See [`ReaperConfig::framework_exemptions`]; like `--framework-exemptions`.
+    pub framework_exemptions: Option<Vec<String>>,
+    /// See [`ReaperConfig::always_used`]; like `--always-used`.
+    pub always_used: Option<Vec<String>>,
+    /// See [`ReaperConfig::exempt_dunders`]; like `--exempt-dunders`.
+    pub exempt_dunders: Option<Vec<String>>,
+}
+
+/// Frameworks `framework` / `--framework` accept.
+pub const FRAMEWORKS: &[&str] = &["django"];
+
+fn framework<'de, D: Deserializer<'de>>(d: D) -> Result<Option<String>, D::Error> {
+    let text = String::deserialize(d)?;
+    if FRAMEWORKS.iter().any(|f| f.eq_ignore_ascii_case(&text)) {
+        Ok(Some(text.to_ascii_lowercase()))
+    } else {
+        Err(serde::de::Error::custom(format!(
+            "unknown framework `{text}`, expected one of: {}",
+            FRAMEWORKS.join(", ")
+        )))
+    }
+}
+
+fn python_version<'de, D: Deserializer<'de>>(d: D) -> Result<Option<(u32, u32)>, D::Error> {
+    let text = String::deserialize(d)?;
+    python::parse_target(&text).map(Some).ok_or_else(|| {
+        serde::de::Error::custom(format!(
+            "invalid Python version `{text}`, expected e.g. \"3.11\""
+        ))
+    })
+}
+
+impl Config {
+    /// Parse the contents of a `reaper.toml`.
+    pub fn parse(text: &str) -> Result<Self> {
+        Ok(toml::from_str(text)?)
+    }
+
+    /// Parse the `[tool.reaper]` table of a `pyproject.toml`.  A file
+    /// without one yields the default (empty) configuration.
+    pub fn parse_pyproject(text: &str) -> Result<Self> {
+        let pyproject: Pyproject = toml::from_str(text)?;
+        Ok(pyproject.tool.reaper.unwrap_or_default())
+    }
+
+    /// Read and parse the configuration file at `path`: `[tool.reaper]` for
+    /// a `pyproject.toml`, the whole file otherwise.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read {}", path.display()))?;
+        let config = if is_pyproject(path) {
+            Self::parse_pyproject(&text)
+        } else {
+            Self::parse(&text)
+        };
+        config.with_context(|| format!("invalid {}", path.display()))
+    }
+}
+
+/// The parts of `pyproject.toml` reaper reads; other tools' tables are
+/// ignored.
+#[derive(Deserialize)]
+struct Pyproject {
+    #[serde(default)]
+    tool: Tool,
+}
+
+#[derive(Default, Deserialize)]
+struct Tool {
+    reaper: Option<Config>,
+}
+
+fn is_pyproject(path: &Path) -> bool {
+    path.file_name().is_some_and(|n| n == PYPROJECT_FILE)
+}
+
+/// True when the `pyproject.toml` at `path` has a `[tool.reaper]` table.  A
+/// file that does not parse counts when it mentions the table, so its error
+/// is reported instead of silently skipped.
+fn has_reaper_table(path: &Path) -> bool {
+    let Ok(text) = std::fs::read_to_string(path) else {
+        return false;
+    };
+    match toml::from_str::<toml::Table>(&text) {
+        Ok(table) => table
+            .get("tool")
+            .and_then(|tool| tool.get("reaper"))
+            .is_some(),
+        Err(_) => text.contains("[tool.reaper"),
+    }
+}
+
+/// The nearest configuration file for an analysis path: in the path itself
+/// (or its directory, for a file), then in each ancestor.  A directory's
+/// `reaper.toml` wins over its `pyproject.toml`, which only counts when it
+/// has a `[tool.reaper]` table.
+pub fn find_config(start: &Path) -> Option<PathBuf> {
+    let start = start.canonicalize().ok()?;
+    let dir = if start.is_file() {
+        start.parent()?
+    } else {
+        &start
+    };
+    dir.ancestors().find_map(|d| {
+        let reaper = d.join(CONFIG_FILE);
+        if reaper.is_file() {
+            return Some(reaper);
+        }
+        let pyproject = d.join(PYPROJECT_FILE);
+        (pyproject.is_file() && has_reaper_table(&pyproject)).then_some(pyproject)
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct ReaperConfig {
+    /// Decorator names (the last dotted component, so `task` matches
+    /// `@app.task` and `@app.task(bind=True)`) that exempt a function's
+    /// parameters from RP008.
+    pub framework_exemptions: Vec<String>,
+    /// Web framework in use.  `Some("django")` adds [`DJANGO_EXEMPTIONS`] to
+    /// the decorator list and exempts views registered in `urlpatterns`.
+    pub framework: Option<String>,
+    /// Names treated as used in every file, for references reaper cannot see
+    /// (string-based lookups, framework registries).  Suppresses RP001, RP002,
+    /// RP003 and RP004 for those names.
+    pub always_used: Vec<String>,
+    /// Module-level dunders consumed by tooling (`__version__`, `__author__`…).
+    /// Listing one in `__all__` never triggers RP048, even when the module
+    /// does not bind it itself.
+    pub exempt_dunders: Vec<String>,
+    /// Treat `# type: ignore` comments like a bare `# noqa`, suppressing
+    /// every rule on the line.
+    pub respect_type_ignore: bool,
+    /// Heuristic exemptions for fewer false positives (`--lenient`).
+    pub lenient: LenientHeuristics,
+    /// Drop exemptions that can hide real findings (`--strict`): parameters
+    /// of pytest test functions and fixtures are checked by RP008.
+    pub strict: bool,
+    /// Glob patterns (`*`, `?`) for names never to report (`--ignore-names`).
+    /// A diagnostic is dropped when its message quotes a matching name in
+    /// backticks; rule codes are never matched.
+    pub ignore_names: Vec<String>,
+    /// `(major, minor)` of the interpreter the code runs under.  RP015
+    /// reports `sys.version_info` branches only when this is known.
+    pub python_version: Option<(u32, u32)>,
+    /// Where pass-1 results are cached between runs (see [`crate::cache`]);
+    /// `None` analyses every file from scratch.
+    pub cache_path: Option<PathBuf>,
+}
+
+impl ReaperConfig {
+    /// True when `framework = "django"` is configured.
+    pub fn is_django(&self) -> bool {
+        self.framework
+            .as_deref()
+            .is_some_and(|f| f.eq_ignore_ascii_case("django"))
+    }
+
+    /// Every decorator name that exempts a function from RP008: the
+    /// configured `framework_exemptions` plus the framework preset, if any.
+    pub fn exempt_decorators(&self) -> Vec<&str> {
+        let mut out: Vec<&str> = self
+            .framework_exemptions
+            .iter()
+            .map(String::as_str)
+            .collect();
+        if self.is_django() {
+            out.extend_from_slice(DJANGO_EXEMPTIONS);
+        }
+        out
+    }
+
+    /// True when `message` quotes a name matching one of `ignore_names`.
+    pub fn ignores(&self, message: &str) -> bool {
+        !self.ignore_names.is_empty()
+            && message
+                .split('`')
+                .skip(1)
+                .step_by(2)
+                .any(|name| self.ignore_names.iter().any(|p| glob_match(p, name)))
+    }
+}
+
+/// Match `text` against a glob `pattern`: `*` matches any run of characters,
+/// `?` exactly one, and every other character itself.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it was tried at, so a
+    // failed match can retry with the star swallowing one more character.
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((sp, st)) = star {
+            p = sp + 1;
+            t = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+impl Default for ReaperConfig {
+    fn default() -> Self {
+        Self {
+            framework_exemptions: DEFAULT_FRAMEWORK_EXEMPTIONS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            framework: None,
+            always_used: Vec::new(),
+            exempt_dunders: DEFAULT_EXEMPT_DUNDERS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            respect_type_ignore: false,
+            lenient: LenientHeuristics::default(),
+            strict: false,
+            ignore_names: Vec::new(),
+            python_version: None,
+            cache_path: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_file_keys() {
+        let config = Config::parse(
+            "select = [\"RP001\"]\nexclude = [\"tests\"]\nno_exit_code = true\nignore_names = [\"app\"]\n",
+        )
+        .unwrap();
+        assert_eq!(config.select, Some(vec!["RP001".to_string()]));
+        assert_eq!(config.exclude, Some(vec!["tests".to_string()]));
+        assert_eq!(config.no_exit_code, Some(true));
+        assert_eq!(config.ignore_names, Some(vec!["app".to_string()]));
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_config_file_analysis_keys() {
+        let config = Config::parse(
+            "include = [\"src/**\"]\nframework = \"Django\"\nframework_exemptions = [\"route\"]\n\
+             always_used = [\"handler\"]\nexempt_dunders = [\"__build__\"]\n",
+        )
+        .unwrap();
+        assert_eq!(config.include, Some(vec!["src/**".to_string()]));
+        assert_eq!(config.framework.as_deref(), Some("django"));
+        assert_eq!(config.framework_exemptions, Some(vec!["route".to_string()]));
+        assert_eq!(config.always_used, Some(vec!["handler".to_string()]));
+        assert_eq!(config.exempt_dunders, Some(vec!["__build__".to_string()]));
+        assert!(Config::parse("framework = \"rails\"\n").is_err());
+    }
+
+    #[test]
+    fn test_config_file_python_version() {
+        let config = Config::parse("python_version = \"3.8\"\n").unwrap();
+        assert_eq!(config.python_version, Some((3, 8)));
+        assert!(Config::parse("python_version = \"three\"\n").is_err());
+        assert!(Config::parse("python_version = 3.8\n").is_err());
+    }
+
+    #[test]
+    fn test_config_file_rejects_unknown_keys() {
+        assert!(Config::parse("selct = [\"RP001\"]\n").is_err());
+        assert!(Config::parse("select = \"RP001\"\n").is_err());
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("dummy", "dummy"));
+        assert!(!glob_match("dummy", "dummy2"));
+        assert!(glob_match("UNUSED_*", "UNUSED_FOO"));
+        assert!(glob_match("UNUSED_*", "UNUSED_"));
+        assert!(!glob_match("UNUSED_*", "unused_foo"));
+        assert!(glob_match("?x", "ax"));
+        assert!(!glob_match("?x", "x"));
+        assert!(glob_match("*_handler*", "on_click_handler_v2"));
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+        assert!(!glob_match("a*b*c", "aXbYbZ"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn test_ignores_matches_quoted_names_only() {
+        let config = ReaperConfig {
+            ignore_names: vec!["RP*".to_string(), "tmp_?".to_string()],
+            ..ReaperConfig::default()
+        };
+        assert!(config.ignores("Local variable `tmp_1` is assigned but never used"));
+        assert!(config.ignores("Function `RPC` is defined but never used"));
+        assert!(!config.ignores("RP001 `os` imported but unused"));
+        assert!(!ReaperConfig::default().ignores("`os` imported but unused"));
+    }
+
+    #[test]
+    fn test_find_config_walks_up() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/pkg")).unwrap();
+        std::fs::write(dir.path().join("src/pkg/mod.py"), "").unwrap();
+        std::fs::write(dir.path().join(CONFIG_FILE), "").unwrap();
+        let expected = dir.path().canonicalize().unwrap().join(CONFIG_FILE);
+        assert_eq!(
+            find_config(&dir.path().join("src/pkg")),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            find_config(&dir.path().join("src/pkg/mod.py")),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn test_pyproject_tool_reaper_table() {
+        let config = Config::parse_pyproject(
+            "[project]\nname = \"app\"\n\n[tool.black]\nline-length = 100\n\n[tool.reaper]\nselect = [\"RP001\"]\nno_exit_code = true\n",
+        )
+        .unwrap();
+        assert_eq!(config.select, Some(vec!["RP001".to_string()]));
+        assert_eq!(config.no_exit_code, Some(true));
+        assert_eq!(
+            Config::parse_pyproject("[project]\nname = \"app\"\n").unwrap(),
+            Config::default()
+        );
+        assert!(Config::parse_pyproject("[tool.reaper]\nselct = []\n").is_err());
+    }
+
+    #[test]
+    fn test_find_config_reads_pyproject_with_reaper_table() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("pkg")).unwrap();
+        std::fs::write(root.join(PYPROJECT_FILE), "[tool.reaper]\n").unwrap();
+        // A nearer pyproject.toml without the table does not stop the search.
+        std::fs::write(root.join("pkg").join(PYPROJECT_FILE), "[project]\n").unwrap();
+        assert_eq!(
+            find_config(&root.join("pkg")),
+            Some(root.join(PYPROJECT_FILE))
+        );
+
+        let path = find_config(&root).unwrap();
+        assert_eq!(Config::load(&path).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_find_config_prefers_reaper_toml() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::write(root.join(PYPROJECT_FILE), "[tool.reaper]\n").unwrap();
+        std::fs::write(root.join(CONFIG_FILE), "").unwrap();
+        assert_eq!(find_config(&root), Some(root.join(CONFIG_FILE)));
+    }
+
+    #[test]
+    fn test_default_has_no_framework() {
+        let config = ReaperConfig::default();
+        assert!(!config.is_django());
+        assert_eq!(config.exempt_decorators(), vec!["task", "shared_task"]);
+    }
+
+    #[test]
+    fn test_django_preset_adds_decorators() {
+        let config = ReaperConfig {
+            framework: Some("Django".to_string()),
+            ..ReaperConfig::default()
+        };
+        assert!(config.is_django());
+        let decorators = config.exempt_decorators();
+        assert!(decorators.contains(&"task"));
+        assert!(decorators.contains(&"receiver"));
+        assert!(decorators.contains(&"login_required"));
+    }
+
+    #[test]
+    fn test_lenient_heuristics_are_independent() {
+        let all = LenientHeuristics {
+            callbacks: true,
+            meta_classes: true,
+            loggers: true,
+        };
+        assert!(all.exempts_function("click_handler"));
+        assert!(all.exempts_function("done_callback"));
+        assert!(all.exempts_function("on_message"));
+        assert!(!all.exempts_function("handler_for"));
+        assert!(all.exempts_class("ModelMeta"));
+        assert!(all.exempts_variable("logger") && all.exempts_variable("log"));
+
+        let only_loggers = LenientHeuristics {
+            loggers: true,
+            ..LenientHeuristics::default()
+        };
+        assert!(only_loggers.exempts_variable("logger"));
+        assert!(!only_loggers.exempts_function("on_message"));
+        assert!(!only_loggers.exempts_class("ModelMeta"));
+        assert!(!LenientHeuristics::default().exempts_variable("logger"));
+    }
+}
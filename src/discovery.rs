@@ -1,4 +1,5 @@
-//! File discovery: walk directory trees and collect `.py` files.
+//! File discovery: walk directory trees and collect Python source files
+//! (`.py` and `.pyw` by default; see [`DEFAULT_EXTENSIONS`]).
 //!
 //! By default the walker:
 //!  - Respects `.gitignore` (and `.ignore`) files at every level.
@@ -9,11 +10,15 @@
 //!    gitignored (e.g. a `venv/` directory at the project root).
 //!
 //! Additional paths to exclude can be supplied by the caller via the
-//! `exclude` parameter of [`discover_python_files`].
+//! `exclude` parameter of [`discover_python_files`].  Callers that need more
+//! control (e.g. scanning hidden directories or restricting discovery to
+//! `include` globs) use [`discover_with_options`].
 
 use anyhow::Result;
-use ignore::WalkBuilder;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::{DirEntry, WalkBuilder, WalkState};
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 
 /// Directory names that are always excluded regardless of `.gitignore` or the
 /// `--exclude` flag.  These are conventional virtual-environment, cache, and
@@ -46,68 +51,198 @@ const ALWAYS_EXCLUDE: &[&str] = &[
     ".nox",
 ];
 
-/// Discover all `.py` files reachable from `root`, excluding:
+/// File extensions treated as Python source when no `--extensions` are given.
+/// `.pyw` is a Windows GUI script — ordinary Python run without a console.
+pub const DEFAULT_EXTENSIONS: &[&str] = &["py", "pyw"];
+
+/// Options controlling which files [`discover_with_options`] returns.
+#[derive(Debug, Clone)]
+pub struct DiscoveryOptions {
+    /// Skip any path whose components include (or contain) one of these names.
+    pub exclude: Vec<String>,
+    /// Walk hidden directories and files too (e.g. `.github/scripts/`).
+    /// [`ALWAYS_EXCLUDE`] still applies, so `.git`, `.venv`, etc. stay skipped.
+    pub include_hidden: bool,
+    /// Extensions (without the leading dot) of the files to collect.
+    pub extensions: Vec<String>,
+    /// When non-empty, keep only files whose path relative to the walked
+    /// root matches at least one of these globs (e.g. `src/**/*.py`).
+    pub include: Vec<String>,
+}
+
+impl Default for DiscoveryOptions {
+    fn default() -> Self {
+        Self {
+            exclude: Vec::new(),
+            include_hidden: false,
+            extensions: DEFAULT_EXTENSIONS.iter().map(|e| e.to_string()).collect(),
+            include: Vec::new(),
+        }
+    }
+}
+
+/// True when `path` ends in one of `extensions` (compared without the dot,
+/// so `--extensions .pyw` and `--extensions pyw` behave the same).
+pub fn has_extension<S: AsRef<str>>(path: &Path, extensions: &[S]) -> bool {
+    path.extension().and_then(|e| e.to_str()).is_some_and(|e| {
+        extensions
+            .iter()
+            .any(|x| x.as_ref().trim_start_matches('.') == e)
+    })
+}
+
+/// Discover all Python files reachable from `root`, excluding:
 ///
 /// * Hidden directories / files (names starting with `.`)
 /// * Entries matched by `.gitignore` / `.ignore` files
 /// * The hardcoded [`ALWAYS_EXCLUDE`] directory names
 /// * Any path whose components include a name listed in `exclude`
 ///
-/// The returned paths are **not** guaranteed to be in any particular order.
+/// The returned paths are sorted.
+#[allow(dead_code)] // library entry point; the CLI goes through `discover_with_options`
 pub fn discover_python_files(root: &Path, exclude: &[String]) -> Result<Vec<PathBuf>> {
+    let options = DiscoveryOptions {
+        exclude: exclude.to_vec(),
+        ..DiscoveryOptions::default()
+    };
+    discover_with_options(root, &options)
+}
+
+/// Like [`discover_python_files`], with every knob exposed via `options`.
+///
+/// Directories are walked in parallel (`ignore`'s work-stealing
+/// [`WalkParallel`](ignore::WalkParallel)); each worker sends the files it
+/// keeps over a channel.  The result is sorted so runs are reproducible.
+pub fn discover_with_options(root: &Path, options: &DiscoveryOptions) -> Result<Vec<PathBuf>> {
+    let filter = Filter::new(root, options)?;
+    let (tx, rx) = mpsc::channel();
+
+    walk_builder(root, options).build_parallel().run(|| {
+        let tx = tx.clone();
+        let filter = &filter;
+        Box::new(move |entry| match entry {
+            Ok(entry) => {
+                if filter.keep(&entry) {
+                    // The receiver outlives the walk, so this cannot fail.
+                    let _ = tx.send(Ok(entry.into_path()));
+                }
+                WalkState::Continue
+            }
+            Err(e) => {
+                let _ = tx.send(Err(e));
+                WalkState::Quit
+            }
+        })
+    });
+    drop(tx);
+
+    let mut files = rx.into_iter().collect::<Result<Vec<_>, _>>()?;
+    files.sort();
+    Ok(files)
+}
+
+/// The single-threaded walk [`discover_with_options`] replaced, kept so tests
+/// can check that both find the same files.
+#[cfg(test)]
+fn discover_sequential(root: &Path, options: &DiscoveryOptions) -> Result<Vec<PathBuf>> {
+    let filter = Filter::new(root, options)?;
     let mut files = Vec::new();
+    for entry in walk_builder(root, options).build() {
+        let entry = entry?;
+        if filter.keep(&entry) {
+            files.push(entry.into_path());
+        }
+    }
+    Ok(files)
+}
 
-    let walker = WalkBuilder::new(root)
-        // Skip hidden files/directories (starts with `.`).
-        // This alone covers .git, .venv, .tox, .mypy_cache, etc.
-        .hidden(true)
+fn walk_builder(root: &Path, options: &DiscoveryOptions) -> WalkBuilder {
+    let mut builder = WalkBuilder::new(root);
+    builder
+        // Skip hidden files/directories (starts with `.`) unless asked not to.
+        // This alone covers .git, .venv, .tox, .mypy_cache, etc.; the
+        // ALWAYS_EXCLUDE filter below prunes those when hidden entries are on.
+        .hidden(!options.include_hidden)
         // Honour .gitignore and .ignore at every ancestor level.
         .git_ignore(true)
         // Do not require a .git root — still apply .gitignore rules if found.
         .require_git(false)
-        .build();
+        // Never descend into ALWAYS_EXCLUDE directories: with hidden entries
+        // on, `.git` or `.tox` alone can hold tens of thousands of files.
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_type().is_some_and(|t| t.is_dir())
+                || !ALWAYS_EXCLUDE.contains(&entry.file_name().to_string_lossy().as_ref())
+        });
+    builder
+}
 
-    'entries: for entry in walker {
-        let entry = entry?;
+/// Decides which walked entries are collected.  Shared by every walker
+/// thread, so it only holds read-only state.
+struct Filter<'a> {
+    root: &'a Path,
+    options: &'a DiscoveryOptions,
+    include: Option<GlobSet>,
+}
 
-        // Only care about regular files with a .py extension.
+impl<'a> Filter<'a> {
+    fn new(root: &'a Path, options: &'a DiscoveryOptions) -> Result<Self> {
+        Ok(Self {
+            root,
+            options,
+            include: build_globset(&options.include)?,
+        })
+    }
+
+    fn keep(&self, entry: &DirEntry) -> bool {
+        // Only care about regular files with a Python extension.
         if !entry.file_type().is_some_and(|t| t.is_file()) {
-            continue;
-        }
-        if entry.path().extension().and_then(|e| e.to_str()) != Some("py") {
-            continue;
+            return false;
         }
-
         let path = entry.path();
+        if !has_extension(path, &self.options.extensions) {
+            return false;
+        }
 
         for component in path.components() {
             if let std::path::Component::Normal(name) = component {
                 let name_str = name.to_string_lossy();
                 if ALWAYS_EXCLUDE.contains(&name_str.as_ref()) {
-                    continue 'entries;
+                    return false;
+                }
+                // Simple substring / exact-name match.
+                // Callers can pass "tests", "migrations", "vendor", etc.
+                if self
+                    .options
+                    .exclude
+                    .iter()
+                    .any(|pat| name_str == pat.as_str() || name_str.contains(pat.as_str()))
+                {
+                    return false;
                 }
             }
         }
 
-        if !exclude.is_empty() {
-            for component in path.components() {
-                if let std::path::Component::Normal(name) = component {
-                    let name_str = name.to_string_lossy();
-                    for pat in exclude {
-                        // Simple substring / exact-name match.
-                        // Callers can pass "tests", "migrations", "vendor", etc.
-                        if name_str == pat.as_str() || name_str.contains(pat.as_str()) {
-                            continue 'entries;
-                        }
-                    }
-                }
+        if let Some(include) = &self.include {
+            let relative = path.strip_prefix(self.root).unwrap_or(path);
+            if !include.is_match(relative) {
+                return false;
             }
         }
-
-        files.push(path.to_path_buf());
+        true
     }
+}
 
-    Ok(files)
+/// Compile `patterns` into one matcher; `None` when there are no patterns.
+fn build_globset(patterns: &[String]) -> Result<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pat in patterns {
+        builder.add(Glob::new(pat)?);
+    }
+    Ok(Some(builder.build()?))
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -122,6 +257,14 @@ mod tests {
         discover_python_files(root, &[]).unwrap()
     }
 
+    fn discover_hidden(root: &Path) -> Vec<PathBuf> {
+        let options = DiscoveryOptions {
+            include_hidden: true,
+            ..DiscoveryOptions::default()
+        };
+        discover_with_options(root, &options).unwrap()
+    }
+
     fn discover_ex(root: &Path, exclude: &[&str]) -> Vec<PathBuf> {
         let ex: Vec<String> = exclude.iter().map(|s| s.to_string()).collect();
         discover_python_files(root, &ex).unwrap()
@@ -243,4 +386,142 @@ mod tests {
         let files = discover_ex(dir.path(), &["tests"]);
         assert_eq!(files.len(), 2);
     }
+
+    #[test]
+    fn test_include_hidden_scans_hidden_directories() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join(".github/scripts")).unwrap();
+        fs::write(dir.path().join(".github/scripts/deploy.py"), "import os").unwrap();
+        fs::write(dir.path().join("app.py"), "x = 1").unwrap();
+
+        let files = discover_hidden(dir.path());
+        assert_eq!(files.len(), 2);
+        assert!(
+            files
+                .iter()
+                .any(|p| p.ends_with(".github/scripts/deploy.py"))
+        );
+    }
+
+    #[test]
+    fn test_include_hidden_still_skips_always_excluded() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join(".git/hooks")).unwrap();
+        fs::write(dir.path().join(".git/hooks/pre-commit.py"), "import os").unwrap();
+        fs::create_dir_all(dir.path().join(".venv/lib")).unwrap();
+        fs::write(dir.path().join(".venv/lib/site.py"), "import os").unwrap();
+        fs::write(dir.path().join("app.py"), "x = 1").unwrap();
+
+        let files = discover_hidden(dir.path());
+        assert_eq!(files.len(), 1, ".git and .venv must stay excluded");
+        assert_eq!(files[0].file_name().unwrap(), "app.py");
+    }
+
+    #[test]
+    fn test_always_excluded_directories_are_not_entered() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join(".git/objects/ab")).unwrap();
+        fs::write(dir.path().join(".git/objects/ab/cdef"), "").unwrap();
+        fs::create_dir_all(dir.path().join(".tox/py311/lib")).unwrap();
+        fs::write(dir.path().join("app.py"), "x = 1").unwrap();
+
+        let options = DiscoveryOptions {
+            include_hidden: true,
+            ..DiscoveryOptions::default()
+        };
+        let walked: Vec<PathBuf> = walk_builder(dir.path(), &options)
+            .build()
+            .map(|e| e.unwrap().into_path())
+            .collect();
+        assert!(
+            walked
+                .iter()
+                .all(|p| !p.starts_with(dir.path().join(".git"))
+                    && !p.starts_with(dir.path().join(".tox"))),
+            "walked into an excluded directory: {walked:?}"
+        );
+        assert!(walked.contains(&dir.path().join("app.py")));
+    }
+
+    #[test]
+    fn test_finds_pyw_files_but_not_pyc() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("gui.pyw"), "import os").unwrap();
+        fs::write(dir.path().join("app.py"), "x = 1").unwrap();
+        fs::write(dir.path().join("app.pyc"), "\0\0").unwrap();
+
+        let files = discover(dir.path());
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().any(|p| p.ends_with("gui.pyw")));
+        assert!(!files.iter().any(|p| p.ends_with("app.pyc")));
+    }
+
+    #[test]
+    fn test_custom_extensions() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("gui.pyw"), "import os").unwrap();
+        fs::write(dir.path().join("app.py"), "x = 1").unwrap();
+
+        let options = DiscoveryOptions {
+            extensions: vec![".pyw".to_string()],
+            ..DiscoveryOptions::default()
+        };
+        let files = discover_with_options(dir.path(), &options).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].file_name().unwrap(), "gui.pyw");
+    }
+
+    #[test]
+    fn test_include_globs_restrict_discovery() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("src/pkg")).unwrap();
+        fs::write(dir.path().join("src/pkg/a.py"), "x = 1").unwrap();
+        fs::create_dir(dir.path().join("tests")).unwrap();
+        fs::write(dir.path().join("tests/test_a.py"), "x = 1").unwrap();
+        fs::write(dir.path().join("setup.py"), "x = 1").unwrap();
+
+        let options = DiscoveryOptions {
+            include: vec!["src/**".to_string()],
+            ..DiscoveryOptions::default()
+        };
+        let files = discover_with_options(dir.path(), &options).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("src/pkg/a.py"));
+    }
+
+    #[test]
+    fn test_invalid_include_glob_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        let options = DiscoveryOptions {
+            include: vec!["src/[".to_string()],
+            ..DiscoveryOptions::default()
+        };
+        assert!(discover_with_options(dir.path(), &options).is_err());
+    }
+
+    #[test]
+    fn test_parallel_walk_matches_sequential() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(".gitignore"), "generated/\n").unwrap();
+        for pkg in 0..8 {
+            for sub in ["core", "tests", "generated", "__pycache__"] {
+                let d = dir.path().join(format!("pkg_{pkg}/{sub}"));
+                fs::create_dir_all(&d).unwrap();
+                for f in 0..6 {
+                    fs::write(d.join(format!("mod_{f}.py")), "x = 1").unwrap();
+                }
+                fs::write(d.join("notes.txt"), "").unwrap();
+            }
+        }
+
+        let options = DiscoveryOptions {
+            exclude: vec!["tests".to_string()],
+            ..DiscoveryOptions::default()
+        };
+        let parallel = discover_with_options(dir.path(), &options).unwrap();
+        let mut sequential = discover_sequential(dir.path(), &options).unwrap();
+        sequential.sort();
+        assert_eq!(parallel.len(), 8 * 6);
+        assert_eq!(parallel, sequential);
+    }
 }
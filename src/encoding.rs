@@ -0,0 +1,190 @@
+//! Reading Python source files: UTF-8 BOMs and PEP 263 coding declarations.
+//!
+//! Python 3 source is UTF-8 unless the first or second line carries a
+//! `# -*- coding: NAME -*-` comment.  Every checker works on UTF-8 text, so
+//! files are decoded up front; [`Source::encode`] converts fixed text back so
+//! `--fix` leaves a file in the encoding (and BOM) it was read with.
+
+use anyhow::{Result, anyhow, bail};
+use encoding_rs::{Encoding, UTF_8};
+use std::fs;
+use std::path::Path;
+
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// A Python source file decoded to UTF-8.
+#[derive(Debug)]
+pub struct Source {
+    /// The decoded source, without any BOM.
+    pub text: String,
+    encoding: &'static Encoding,
+    bom: bool,
+}
+
+impl Source {
+    pub fn read(path: &Path) -> Result<Self> {
+        Self::decode(&fs::read(path)?)
+    }
+
+    /// Strip a UTF-8 BOM, then decode `bytes` with the declared encoding
+    /// (UTF-8 when there is no declaration).  Malformed input is an error
+    /// rather than being patched with replacement characters.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let (bom, body) = match bytes.strip_prefix(UTF8_BOM) {
+            Some(rest) => (true, rest),
+            None => (false, bytes),
+        };
+        let encoding = match declared_encoding(body) {
+            Some(label) => lookup(label)
+                .ok_or_else(|| anyhow!("unknown encoding `{label}` in coding declaration"))?,
+            None => UTF_8,
+        };
+        if bom && encoding != UTF_8 {
+            bail!(
+                "UTF-8 BOM conflicts with declared encoding `{}`",
+                encoding.name()
+            );
+        }
+        let text = encoding
+            .decode_without_bom_handling_and_without_replacement(body)
+            .ok_or_else(|| anyhow!("source is not valid {}", encoding.name()))?
+            .into_owned();
+        Ok(Self {
+            text,
+            encoding,
+            bom,
+        })
+    }
+
+    /// Convert `text` (an edited copy of [`Source::text`]) back to the bytes
+    /// of the original encoding, restoring the BOM if there was one.
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>> {
+        let mut out = if self.bom {
+            UTF8_BOM.to_vec()
+        } else {
+            Vec::new()
+        };
+        let (bytes, _, had_errors) = self.encoding.encode(text);
+        if had_errors {
+            bail!("text cannot be represented in {}", self.encoding.name());
+        }
+        out.extend_from_slice(&bytes);
+        Ok(out)
+    }
+}
+
+/// The encoding named by a PEP 263 declaration on the first line, or on the
+/// second line when the first is blank or a comment (e.g. a shebang).
+pub fn declared_encoding(bytes: &[u8]) -> Option<&str> {
+    let mut lines = bytes.split(|&b| b == b'\n');
+    let first = lines.next()?;
+    if let Some(label) = coding_in_line(first) {
+        return Some(label);
+    }
+    let first = trim_start(first);
+    if !(first.is_empty() || first.starts_with(b"#") || first == b"\r") {
+        return None;
+    }
+    coding_in_line(lines.next()?)
+}
+
+/// Match `^[ \t\f]*#.*?coding[:=][ \t]*([-\w.]+)` against one line.
+fn coding_in_line(line: &[u8]) -> Option<&str> {
+    let line = trim_start(line);
+    if !line.starts_with(b"#") {
+        return None;
+    }
+    let at = line
+        .windows(7)
+        .position(|w| w.starts_with(b"coding") && matches!(w[6], b':' | b'='))?;
+    let rest = trim_start(&line[at + 7..]);
+    let len = rest
+        .iter()
+        .take_while(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+        .count();
+    if len == 0 {
+        return None;
+    }
+    std::str::from_utf8(&rest[..len]).ok()
+}
+
+fn trim_start(line: &[u8]) -> &[u8] {
+    let n = line
+        .iter()
+        .take_while(|b| matches!(b, b' ' | b'\t' | b'\x0c'))
+        .count();
+    &line[n..]
+}
+
+/// Resolve a Python codec name to an encoding.  WHATWG labels cover most
+/// names; Python spells a few differently (`latin-1`, `utf_8`, `utf-8-sig`).
+fn lookup(label: &str) -> Option<&'static Encoding> {
+    let normalized = label.to_ascii_lowercase().replace('_', "-");
+    let python_alias = match normalized.as_str() {
+        "latin-1" | "iso-8859-1" | "iso8859-1" | "8859" | "cp819" | "l1" => "latin1",
+        "utf-8-sig" | "utf8" | "u8" => "utf-8",
+        other => other,
+    };
+    Encoding::for_label(label.as_bytes()).or_else(|| Encoding::for_label(python_alias.as_bytes()))
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_utf8() {
+        let src = Source::decode("name = 'café'\n".as_bytes()).unwrap();
+        assert_eq!(src.text, "name = 'café'\n");
+    }
+
+    #[test]
+    fn test_bom_is_stripped_and_restored() {
+        let src = Source::decode(b"\xef\xbb\xbfimport os\n").unwrap();
+        assert_eq!(src.text, "import os\n");
+        assert_eq!(src.encode("").unwrap(), UTF8_BOM);
+    }
+
+    #[test]
+    fn test_latin1_declaration() {
+        let bytes = b"# coding: latin-1\nname = '\xe9t\xe9'\n";
+        let src = Source::decode(bytes).unwrap();
+        assert_eq!(src.text, "# coding: latin-1\nname = 'été'\n");
+        assert_eq!(src.encode(&src.text).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_emacs_style_declaration_after_shebang() {
+        let bytes = b"#!/usr/bin/env python\n# -*- coding: cp1252 -*-\nx = '\x80'\n";
+        let src = Source::decode(bytes).unwrap();
+        assert!(src.text.ends_with("x = '€'\n"));
+    }
+
+    #[test]
+    fn test_declaration_below_line_two_ignored() {
+        assert_eq!(declared_encoding(b"\n\n# coding: latin-1\n"), None);
+        assert_eq!(declared_encoding(b"x = 1\n# coding: latin-1\n"), None);
+        assert!(Source::decode(b"import os\n\n# coding: latin-1\n'\xe9'\n").is_err());
+    }
+
+    #[test]
+    fn test_vim_style_declaration() {
+        assert_eq!(
+            declared_encoding(b"# vim: set fileencoding=utf_8 :\n"),
+            Some("utf_8")
+        );
+    }
+
+    #[test]
+    fn test_unknown_encoding_is_an_error() {
+        let err = Source::decode(b"# coding: klingon\n").unwrap_err();
+        assert!(err.to_string().contains("klingon"));
+    }
+
+    #[test]
+    fn test_invalid_utf8_without_declaration_is_an_error() {
+        assert!(Source::decode(b"x = '\xe9'\n").is_err());
+    }
+}
@@ -115,6 +115,9 @@ pub struct TokenWithOffset<'src> {
 
 // ── Lexer ─────────────────────────────────────────────────────────────────────
 
+/// Cloning is cheap and lets the parser look ahead speculatively (e.g. to
+/// disambiguate soft keywords) without consuming tokens.
+#[derive(Clone)]
 pub struct Lexer<'src> {
     pub(crate) src: &'src [u8],
     /// The same source as a `&str` — used for safe UTF-8 slicing without `unsafe`.
@@ -131,6 +134,8 @@ pub struct Lexer<'src> {
     bracket_depth: i32,
     /// One-token lookahead buffer.
     peeked: Option<TokenWithOffset<'src>>,
+    /// Number of tokens handed out by [`Lexer::consume`] so far.
+    consumed: usize,
 }
 
 impl<'src> Lexer<'src> {
@@ -144,6 +149,7 @@ impl<'src> Lexer<'src> {
             at_line_start: true,
             bracket_depth: 0,
             peeked: None,
+            consumed: 0,
         }
     }
 
@@ -176,6 +182,7 @@ impl<'src> Lexer<'src> {
 
     /// Consume and return the next token with its offset.
     pub fn consume(&mut self) -> TokenWithOffset<'src> {
+        self.consumed += 1;
         match self.peeked.take() {
             Some(t) => t,
             None => self.next_inner(),
@@ -187,6 +194,12 @@ impl<'src> Lexer<'src> {
         self.consume().token
     }
 
+    /// Number of tokens consumed so far — lets the parser detect a statement
+    /// rule that made no progress.
+    pub fn tokens_consumed(&self) -> usize {
+        self.consumed
+    }
+
     /// Return the current bracket nesting depth.
     ///
     /// At the end of a complete, well-formed module this is always 0.
@@ -320,7 +333,7 @@ impl<'src> Lexer<'src> {
             }
 
             // ── Identifiers and keywords ──────────────────────────────────
-            if b.is_ascii_alphabetic() || b == b'_' {
+            if b.is_ascii_alphabetic() || b == b'_' || !b.is_ascii() {
                 return self.lex_name(start);
             }
 
@@ -475,7 +488,15 @@ impl<'src> Lexer<'src> {
                         Token::Op
                     }
                 }
-                b'~' | b'!' => Token::Op,
+                b'!' => {
+                    // `!=` must not leave a stray `=` behind to be read as an
+                    // assignment.
+                    if self.src.get(self.pos) == Some(&b'=') {
+                        self.pos += 1;
+                    }
+                    Token::Op
+                }
+                b'~' => Token::Op,
                 b'`' => Token::Op, // backtick not valid Python 3 but skip gracefully
                 _ => Token::Op,
             };
@@ -591,16 +612,13 @@ impl<'src> Lexer<'src> {
 
     fn lex_name(&mut self, start: usize) -> TokenWithOffset<'src> {
         // Advance past the rest of the identifier.
-        while self.pos < self.src.len() {
-            let b = self.src[self.pos];
-            if b.is_ascii_alphanumeric() || b == b'_' {
-                self.pos += 1;
-            } else {
-                break;
-            }
+        while self.pos < self.src.len() && is_name_byte(self.src[self.pos]) {
+            self.pos += 1;
         }
-        // All bytes we advanced over are ASCII, so `start..pos` is always on a
-        // valid UTF-8 char boundary.  Slice through the `&str` — no unsafe needed.
+        // `is_name_byte` accepts every byte of a multi-byte character, so the
+        // loop stops on an ASCII byte or at the end and `start..pos` is always
+        // on a valid UTF-8 char boundary.  Slice through the `&str` — no
+        // unsafe needed.
         let s = &self.src_str[start..self.pos];
         let tok = match s {
             "False" => Token::KwFalse,
@@ -779,6 +797,8 @@ impl<'src> Lexer<'src> {
                 self.pos += 1;
             }
         }
+        // A backslash as the very last byte steps one past the end.
+        self.pos = self.pos.min(self.src.len());
 
         // The string body starts and ends on ASCII boundaries (opening/closing quote
         // or newline), so `start..pos` is always a valid UTF-8 char-boundary slice.
@@ -959,13 +979,14 @@ pub fn collect_fstring_names<'src>(
             i += 1;
             continue;
         }
-        if brace_depth > 0 && (b.is_ascii_alphabetic() || b == b'_') {
+        if brace_depth > 0 && (b.is_ascii_alphabetic() || b == b'_' || !b.is_ascii()) {
             let name_start = i;
-            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+            while i < bytes.len() && is_name_byte(bytes[i]) {
                 i += 1;
             }
-            // Only ASCII bytes were scanned, so this is always a valid char-boundary
-            // slice.  We reconstruct the &str from the original `raw` slice.
+            // Multi-byte characters are scanned whole, so this is always a valid
+            // char-boundary slice.  We reconstruct the &str from the original
+            // `raw` slice.
             let name = &raw[name_start..i];
             // Skip Python keywords that can't be variable names.
             if !is_keyword(name) {
@@ -977,6 +998,14 @@ pub fn collect_fstring_names<'src>(
     }
 }
 
+/// True for a byte that can continue an identifier.  Python 3 identifiers may
+/// contain any `XID_Continue` character; outside strings and comments no other
+/// non-ASCII character is valid, so every byte of a multi-byte character is
+/// accepted rather than classifying the decoded scalar.
+fn is_name_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || !b.is_ascii()
+}
+
 fn is_keyword(s: &str) -> bool {
     matches!(
         s,
@@ -1132,4 +1161,51 @@ mod tests {
         assert!(names.contains(&"name"));
         assert!(names.contains(&"age"));
     }
+
+    #[test]
+    fn test_unicode_names() {
+        assert_eq!(
+            tokens("café = naïve_2"),
+            vec![
+                Token::Name("café"),
+                Token::Eq,
+                Token::Name("naïve_2"),
+                Token::Eof
+            ]
+        );
+        assert_eq!(tokens("数量")[0], Token::Name("数量"));
+        assert_eq!(tokens("def عدد():")[1], Token::Name("عدد"));
+    }
+
+    #[test]
+    fn test_unicode_name_offsets() {
+        let mut lex = Lexer::new("π + café");
+        assert_eq!(lex.consume().offset, 0);
+        lex.consume();
+        let t = lex.consume();
+        assert_eq!((t.token, t.offset), (Token::Name("café"), 5));
+    }
+
+    #[test]
+    fn test_collect_fstring_unicode_names() {
+        let mut out = Vec::new();
+        collect_fstring_names("f'{prix} {café:>8}'", &mut out, 0);
+        let names: Vec<&str> = out.iter().map(|(n, _)| *n).collect();
+        assert_eq!(names, ["prix", "café"]);
+    }
+
+    #[test]
+    fn test_backslash_at_eof_inside_string() {
+        for src in ["'\\", "x = '''(\\", "r\"\\"] {
+            assert!(matches!(tokens(src).last(), Some(Token::Eof)));
+        }
+    }
+
+    #[test]
+    fn test_not_equal_is_one_operator() {
+        assert_eq!(
+            tokens("a != b"),
+            vec![Token::Name("a"), Token::Op, Token::Name("b"), Token::Eof]
+        );
+    }
 }
@@ -12,8 +12,8 @@
 //! name-usage data.
 
 use crate::ast::{
-    ArgDef, Arguments, AssignTarget, ClassDef, ExceptHandler, ExprInfo, ExprKind, FuncDef,
-    ImportAlias, Offset, Stmt, StmtKind, WithItem,
+    ArgDef, Arguments, AssignTarget, ClassDef, ExceptHandler, ExprInfo, ExprKind, FormatCall,
+    FormatStyle, FuncDef, ImportAlias, Offset, Stmt, StmtKind, WithItem,
 };
 use crate::fast_parser::lexer::{Lexer, Token, collect_fstring_names, extract_str_value};
 
@@ -75,6 +75,7 @@ impl<'src> Parser<'src> {
 
     fn parse_stmt(&mut self) -> Option<Stmt<'src>> {
         let offset = self.lex.peek_offset();
+        let consumed_before = self.lex.tokens_consumed();
 
         let stmt = match self.peek().clone() {
             Token::KwImport => self.parse_import(offset),
@@ -119,9 +120,21 @@ impl<'src> Parser<'src> {
             Token::KwAssert => self.parse_assert(offset),
             Token::At => self.parse_decorated(offset),
             Token::KwMatch => self.parse_match(offset),
+            Token::Name("type") if self.is_type_alias() => self.parse_type_alias(offset),
             // Everything else is an expression statement or assignment.
             _ => self.parse_expr_stmt(offset),
         };
+        // A token no statement can start with (e.g. a stray `)`) is left in
+        // place by every rule above; skip it so the caller's statement loop
+        // cannot spin on it forever.  Layout tokens are the caller's business.
+        if self.lex.tokens_consumed() == consumed_before
+            && !matches!(
+                self.peek(),
+                Token::Newline | Token::Indent | Token::Dedent | Token::Eof
+            )
+        {
+            self.lex.bump();
+        }
         Some(stmt)
     }
 
@@ -175,15 +188,20 @@ impl<'src> Parser<'src> {
         if matches!(self.peek(), Token::KwImport) {
             self.lex.bump();
         }
-        // Star import?
+        // Star import?  Recorded as a single `*` alias.
         if matches!(self.peek(), Token::Star) {
+            let star_offset = self.lex.peek_offset();
             self.lex.bump();
             self.eat_newline();
             return Stmt {
                 offset,
                 kind: StmtKind::ImportFrom {
                     module,
-                    names: vec![],
+                    names: vec![ImportAlias {
+                        name: "*",
+                        asname: None,
+                        offset: star_offset,
+                    }],
                     level,
                 },
             };
@@ -297,7 +315,6 @@ impl<'src> Parser<'src> {
         self.lex.bump(); // consume '('
 
         // pos-only args end at `/`; kw-only args start after `*` or `*args`.
-        // We don't distinguish these precisely — just collect all args in order.
         let mut seen_star = false; // bare `*` or `*args`
 
         loop {
@@ -308,8 +325,12 @@ impl<'src> Parser<'src> {
                     continue;
                 }
                 Token::Op => {
-                    // `/` positional-only separator
+                    // `/` positional-only separator: everything so far was
+                    // positional-only.
                     self.lex.bump();
+                    if !seen_star && args.posonlyargs.is_empty() {
+                        args.posonlyargs = std::mem::take(&mut args.args);
+                    }
                     continue;
                 }
                 Token::DblStar => {
@@ -317,16 +338,13 @@ impl<'src> Parser<'src> {
                     let arg_offset = self.lex.peek_offset();
                     let name = self.expect_name().unwrap_or("");
                     let annotation = self.parse_optional_annotation();
-                    // default value
-                    if matches!(self.peek(), Token::Eq) {
-                        self.lex.bump();
-                        self.skip_expr();
-                    }
+                    let default = self.parse_optional_default();
                     if !name.is_empty() {
                         args.kwarg = Some(ArgDef {
                             name,
                             offset: arg_offset,
                             annotation,
+                            default,
                         });
                     }
                 }
@@ -345,6 +363,7 @@ impl<'src> Parser<'src> {
                             name,
                             offset: arg_offset,
                             annotation,
+                            default: None,
                         });
                     }
                 }
@@ -356,15 +375,12 @@ impl<'src> Parser<'src> {
                         continue;
                     }
                     let annotation = self.parse_optional_annotation();
-                    // Skip default value.
-                    if matches!(self.peek(), Token::Eq) {
-                        self.lex.bump();
-                        self.skip_expr();
-                    }
+                    let default = self.parse_optional_default();
                     let arg = ArgDef {
                         name,
                         offset: arg_offset,
                         annotation,
+                        default,
                     };
                     if seen_star {
                         args.kwonlyargs.push(arg);
@@ -388,6 +404,15 @@ impl<'src> Parser<'src> {
         }
     }
 
+    fn parse_optional_default(&mut self) -> Option<ExprInfo<'src>> {
+        if matches!(self.peek(), Token::Eq) {
+            self.lex.bump(); // consume '='
+            Some(self.parse_expr_info_until(&[Token::Comma, Token::RParen]))
+        } else {
+            None
+        }
+    }
+
     // ── class ─────────────────────────────────────────────────────────────────
 
     fn parse_classdef(&mut self, offset: Offset) -> Stmt<'src> {
@@ -410,7 +435,16 @@ impl<'src> Parser<'src> {
                         let _ = self.parse_expr_info_until(&[Token::Comma, Token::RParen]);
                     }
                     _ => {
+                        let consumed_before = self.lex.tokens_consumed();
                         let info = self.parse_expr_info_until(&[Token::Comma, Token::RParen]);
+                        // A token the expression scanner stops at without
+                        // consuming (`:`, `;`, `+=` …) — skip it.
+                        if self.lex.tokens_consumed() == consumed_before
+                            && !matches!(self.peek(), Token::Eq)
+                        {
+                            self.lex.bump();
+                            continue;
+                        }
                         // Handle keyword class argument: `name=value`
                         // parse_expr_info_until stops at `=` (depth 0), so if the
                         // next token is `=` we must consume it + skip the value,
@@ -607,8 +641,8 @@ impl<'src> Parser<'src> {
             let handler_offset = self.lex.peek_offset();
             self.lex.bump(); // consume `except`
             // `except*` (Python 3.11)
-            let _is_star = matches!(self.peek(), Token::Star);
-            if _is_star {
+            let is_star = matches!(self.peek(), Token::Star);
+            if is_star {
                 self.lex.bump();
             }
             // Optional exception type.
@@ -631,6 +665,7 @@ impl<'src> Parser<'src> {
                 type_expr,
                 body: handler_body,
                 offset: handler_offset,
+                is_star,
             });
         }
         let orelse = self.parse_else_clause();
@@ -769,33 +804,13 @@ impl<'src> Parser<'src> {
     // ── match statement (Python 3.10+) ────────────────────────────────────────
 
     fn parse_match(&mut self, offset: Offset) -> Stmt<'src> {
-        // `match` is a soft keyword — it may also appear as an identifier.
-        // We consume it as a `match` statement only when the next token is not
-        // `=`, `:=`, `(`, `,`, or newline (which would make it an assignment).
-        // This is a heuristic that covers the vast majority of real match uses.
-        let tok = self.lex.bump(); // consume `match`
-        match self.peek() {
-            Token::Eq
-            | Token::Walrus
-            | Token::AugAssign
-            | Token::Colon
-            | Token::Newline
-            | Token::Semicolon
-            | Token::Eof
-            | Token::Dot => {
-                // Treat `match` as an identifier in an expression statement.
-                let match_name = match tok {
-                    Token::KwMatch => "match",
-                    _ => "",
-                };
-                let mut info = ExprInfo::default();
-                if !match_name.is_empty() {
-                    info.names.push((match_name, offset));
-                }
-                return self.finish_expr_stmt(offset, info);
-            }
-            _ => {}
+        // `match` is a soft keyword — it may also be an ordinary identifier
+        // (`match = 5`, `match(items)`, `match.group(0)`).  Those parse as
+        // expression statements, where `match` is treated like any name.
+        if !self.is_match_statement() {
+            return self.parse_expr_stmt(offset);
         }
+        self.lex.bump(); // consume `match`
         // Parse as a real match statement.
         let subject = self.parse_expr_info_until_colon();
         let _ = self.lex.eat(&Token::Colon);
@@ -812,18 +827,7 @@ impl<'src> Parser<'src> {
                 self.skip_newlines();
                 match self.peek().clone() {
                     Token::Dedent | Token::Eof => break,
-                    Token::KwCase => {
-                        // Collect every Name token from the case header line
-                        // (pattern + optional guard) — stops at Newline.
-                        let mut pattern_names: Vec<(&'src str, Offset)> = Vec::new();
-                        self.collect_until_newline(&mut pattern_names);
-                        // Parse the arm body as a proper indented suite.
-                        let body = self.parse_suite();
-                        arms.push(crate::ast::MatchArm {
-                            pattern_names,
-                            body,
-                        });
-                    }
+                    Token::KwCase => arms.push(self.parse_case_header()),
                     _ => {
                         // Unexpected token inside match body — consume the line
                         // and continue (defensive recovery).
@@ -840,13 +844,270 @@ impl<'src> Parser<'src> {
         }
     }
 
+    /// Scan a `case` header line, splitting its names into usages and
+    /// capture bindings.  Stops after the Newline that ends the header.
+    ///
+    /// Within the pattern, a bare name is a capture unless it is `_`, the
+    /// attribute part of a dotted value (`Color.RED`), a class name
+    /// (`Point(…)`) or a keyword-pattern key (`x=` in `Point(x=px)`).  Names
+    /// after `as`, `*` and `**` are captures too.  Everything in the guard
+    /// (and any inline body after the `:`) is a usage.
+    fn parse_case_header(&mut self) -> crate::ast::MatchArm<'src> {
+        self.lex.bump(); // consume `case`
+        let mut pattern_names: Vec<(&'src str, Offset)> = Vec::new();
+        let mut bindings: Vec<(&'src str, Offset)> = Vec::new();
+        let mut depth = 0i32;
+        let mut in_pattern = true;
+        let mut guarded = false;
+        let mut pattern_tokens = 0usize;
+        let mut lone_name = false;
+        let mut prev: Token<'src> = Token::KwCase;
+        loop {
+            let tok = self.peek().clone();
+            match tok {
+                Token::Eof | Token::Dedent => break,
+                Token::Newline | Token::Semicolon if depth == 0 => {
+                    self.lex.bump();
+                    break;
+                }
+                Token::LParen | Token::LBracket | Token::LBrace => depth += 1,
+                Token::RParen | Token::RBracket | Token::RBrace => depth = (depth - 1).max(0),
+                Token::KwIf if depth == 0 && in_pattern => {
+                    in_pattern = false;
+                    guarded = true;
+                }
+                Token::Colon if depth == 0 => in_pattern = false,
+                _ => {}
+            }
+            if in_pattern {
+                pattern_tokens += 1;
+            }
+            let off = self.lex.peek_offset();
+            self.lex.bump();
+            if let Token::Name(n) = tok {
+                if !in_pattern {
+                    pattern_names.push((n, off));
+                } else if matches!(prev, Token::Dot) {
+                    // `Color.RED` — attribute, neither a read nor a binding.
+                } else if matches!(self.peek(), Token::Dot | Token::LParen) {
+                    pattern_names.push((n, off));
+                } else if matches!(self.peek(), Token::Eq) {
+                    // `x=` key of a keyword pattern.
+                } else if n != "_" {
+                    bindings.push((n, off));
+                }
+                lone_name = pattern_tokens == 1;
+            }
+            prev = tok;
+        }
+        let irrefutable = !guarded && lone_name && pattern_tokens == 1;
+        let body = self.parse_suite();
+        crate::ast::MatchArm {
+            pattern_names,
+            bindings,
+            irrefutable,
+            body,
+        }
+    }
+
+    /// Decide whether the logical line starting at the current `match` token
+    /// is a match *statement*: `match` must be followed by something that can
+    /// start a subject expression, and the line must end with `:` at bracket
+    /// depth 0.  Scans a cloned lexer, so nothing is consumed.
+    fn is_match_statement(&self) -> bool {
+        let mut look = self.lex.clone();
+        look.bump(); // `match`
+        if matches!(
+            look.peek(),
+            Token::Eq
+                | Token::Walrus
+                | Token::AugAssign
+                | Token::Colon
+                | Token::Newline
+                | Token::Semicolon
+                | Token::Eof
+                | Token::Dot
+                | Token::Comma
+                | Token::RParen
+                | Token::RBracket
+                | Token::RBrace
+        ) {
+            return false;
+        }
+        let mut last = Token::Eof;
+        // Newlines are only emitted at bracket depth 0, so the first one
+        // marks the end of the header.
+        while !matches!(look.peek(), Token::Newline | Token::Eof | Token::Dedent) {
+            last = look.bump();
+        }
+        matches!(last, Token::Colon)
+    }
+
+    // ── type alias ────────────────────────────────────────────────────────────
+
+    /// `type` is a soft keyword: only `type Name =` or `type Name[` starts an
+    /// alias; `type = 3` and `type(x)` are ordinary expressions.
+    fn is_type_alias(&self) -> bool {
+        let mut look = self.lex.clone();
+        look.bump(); // `type`
+        matches!(look.bump(), Token::Name(_)) && matches!(look.peek(), Token::Eq | Token::LBracket)
+    }
+
+    fn parse_type_alias(&mut self, offset: Offset) -> Stmt<'src> {
+        self.lex.bump(); // consume `type`
+        let name = self.expect_name().unwrap_or("");
+        if self.lex.eat(&Token::LBracket) {
+            self.skip_balanced();
+        }
+        let _ = self.lex.eat(&Token::Eq);
+        let value = self.parse_expr_info_eol();
+        self.eat_newline();
+        Stmt {
+            offset,
+            kind: StmtKind::TypeAlias { name, value },
+        }
+    }
+
     // ── expression statement / assignment ─────────────────────────────────────
 
     fn parse_expr_stmt(&mut self, offset: Offset) -> Stmt<'src> {
+        if self.is_tuple_assignment() {
+            let target = self.parse_tuple_target();
+            return self.finish_assign(offset, vec![target]);
+        }
         let info = self.parse_expr_info_eol();
         self.finish_expr_stmt(offset, info)
     }
 
+    /// Decide whether the logical line is `a, b = …` — a comma at bracket
+    /// depth 0 before the first `=` — or `(a, b) = …` / `[a, b] = …`, a
+    /// bracketed group followed directly by `=`.  Scans a cloned lexer.
+    fn is_tuple_assignment(&self) -> bool {
+        if let Some(mut look) = self.after_group()
+            && matches!(look.peek(), Token::Eq)
+        {
+            return true;
+        }
+        let mut look = self.lex.clone();
+        let mut depth = 0i32;
+        let mut comma = false;
+        loop {
+            match look.peek() {
+                Token::LParen | Token::LBracket | Token::LBrace => depth += 1,
+                Token::RParen | Token::RBracket | Token::RBrace => depth -= 1,
+                Token::Comma if depth == 0 => comma = true,
+                Token::Eq if depth == 0 => return comma,
+                // `lambda a, b=1: …` has a comma before its `=`.
+                Token::KwLambda => return false,
+                Token::Newline
+                | Token::Semicolon
+                | Token::Eof
+                | Token::Dedent
+                | Token::Colon
+                | Token::AugAssign
+                    if depth == 0 =>
+                {
+                    return false;
+                }
+                Token::Eof => return false,
+                _ => {}
+            }
+            look.bump();
+        }
+    }
+
+    /// When the next token opens a `(…)` or `[…]` group, a cloned lexer
+    /// positioned just after its matching bracket.
+    fn after_group(&self) -> Option<Lexer<'src>> {
+        let mut look = self.lex.clone();
+        if !matches!(look.peek(), Token::LParen | Token::LBracket) {
+            return None;
+        }
+        let mut depth = 0i32;
+        loop {
+            match look.peek() {
+                Token::LParen | Token::LBracket | Token::LBrace => depth += 1,
+                Token::RParen | Token::RBracket | Token::RBrace => {
+                    depth -= 1;
+                    if depth == 0 {
+                        look.bump();
+                        return Some(look);
+                    }
+                }
+                Token::Eof => return None,
+                _ => {}
+            }
+            look.bump();
+        }
+    }
+
+    /// Parse the comma-separated targets of `a, *b, (c, d), e.f = …` up to
+    /// the `=`.  A single target without a comma (`(a) = …`, `[a, b] = …`)
+    /// is returned on its own rather than wrapped in a `Tuple`.
+    fn parse_tuple_target(&mut self) -> AssignTarget<'src> {
+        let (mut elts, comma) = self.parse_target_list(&Token::Eq);
+        if elts.len() == 1 && !comma {
+            elts.pop().unwrap()
+        } else {
+            AssignTarget::Tuple(elts)
+        }
+    }
+
+    /// Parse comma-separated target elements up to `close` (not consumed),
+    /// returning them and whether a comma was seen.
+    fn parse_target_list(&mut self, close: &Token<'src>) -> (Vec<AssignTarget<'src>>, bool) {
+        let mut elts = Vec::new();
+        let mut comma = false;
+        while self.peek() != close && !matches!(self.peek(), Token::Eq | Token::Eof) {
+            if let Some(target) = self.parse_target_elt() {
+                elts.push(target);
+            }
+            if self.lex.eat(&Token::Comma) {
+                comma = true;
+            } else if self.peek() != close && !matches!(self.peek(), Token::Eq) {
+                // Malformed line: fall back to skipping to the `=`.
+                self.lex.bump();
+            }
+        }
+        (elts, comma)
+    }
+
+    /// Parse one unpacking target: `*rest`, a bracketed `(…)`/`[…]` group,
+    /// or a name.  Attribute and subscript targets — including a group
+    /// followed by `.attr` or `[key]` — are `Complex` and keep their reads.
+    fn parse_target_elt(&mut self) -> Option<AssignTarget<'src>> {
+        if self.lex.eat(&Token::Star) {
+            let target = self
+                .parse_target_elt()
+                .unwrap_or_else(|| AssignTarget::Complex(ExprInfo::default()));
+            return Some(AssignTarget::Starred(Box::new(target)));
+        }
+        let whole_group = self.after_group().is_some_and(|mut look| {
+            matches!(
+                look.peek(),
+                Token::Comma | Token::Eq | Token::RParen | Token::RBracket
+            )
+        });
+        if whole_group {
+            let list = matches!(self.peek(), Token::LBracket);
+            let close = if list { Token::RBracket } else { Token::RParen };
+            self.lex.bump();
+            let (mut elts, comma) = self.parse_target_list(&close);
+            self.lex.eat(&close);
+            return Some(if list {
+                AssignTarget::List(elts)
+            } else if elts.len() == 1 && !comma {
+                elts.pop().unwrap()
+            } else {
+                AssignTarget::Tuple(elts)
+            });
+        }
+        let consumed_before = self.lex.tokens_consumed();
+        let info = self.parse_expr_info_until(&[Token::Comma]);
+        (consumed_before != self.lex.tokens_consumed())
+            .then(|| info_to_assign_target_single(&info, self.lex.source_str()))
+    }
+
     fn finish_expr_stmt(&mut self, offset: Offset, lhs_info: ExprInfo<'src>) -> Stmt<'src> {
         match self.peek().clone() {
             // Augmented assignment: `x += expr`
@@ -854,48 +1115,17 @@ impl<'src> Parser<'src> {
                 self.lex.bump();
                 let value = self.parse_expr_info_eol();
                 self.eat_newline();
-                // Determine target from lhs_info.kind
-                let target = expr_kind_to_assign_target(&lhs_info.kind, offset);
+                let target = info_to_assign_target_single(&lhs_info, self.lex.source_str());
                 Stmt {
                     offset,
                     kind: StmtKind::AugAssign { target, value },
                 }
             }
-            // Regular assignment: `a = b = expr` or annotated: `a: T = expr`
-            Token::Eq => {
-                // Could be chained assignments.
-                let mut targets = Vec::new();
-                // lhs is the first target.
-                let first_target = info_to_assign_targets(&lhs_info);
-                targets.extend(first_target);
-                // Keep consuming `= expr` chains.
-                while matches!(self.peek(), Token::Eq) {
-                    self.lex.bump();
-                    let next = self.parse_expr_info_until(&[Token::Eq]);
-                    // If followed by another `=`, this `next` is also a target.
-                    if matches!(self.peek(), Token::Eq) {
-                        targets.extend(info_to_assign_targets(&next));
-                    } else {
-                        // `next` is the final value.
-                        self.eat_newline();
-                        return Stmt {
-                            offset,
-                            kind: StmtKind::Assign {
-                                targets,
-                                value: next,
-                            },
-                        };
-                    }
-                }
-                // Fell off the end without a value (shouldn't happen in valid Python,
-                // but handle gracefully).
-                let value = ExprInfo::default();
-                self.eat_newline();
-                Stmt {
-                    offset,
-                    kind: StmtKind::Assign { targets, value },
-                }
-            }
+            // Regular assignment: `a = b = expr`
+            Token::Eq => self.finish_assign(
+                offset,
+                info_to_assign_targets(&lhs_info, self.lex.source_str()),
+            ),
             // Annotated assignment: `a: T` or `a: T = expr`
             Token::Colon => {
                 self.lex.bump();
@@ -907,7 +1137,7 @@ impl<'src> Parser<'src> {
                     None
                 };
                 self.eat_newline();
-                let target = info_to_assign_target_single(&lhs_info);
+                let target = info_to_assign_target_single(&lhs_info, self.lex.source_str());
                 Stmt {
                     offset,
                     kind: StmtKind::AnnAssign {
@@ -936,15 +1166,69 @@ impl<'src> Parser<'src> {
         }
     }
 
+    /// Finish `targets = … = value` once the first target is parsed and the
+    /// next token is `=`.
+    fn finish_assign(
+        &mut self,
+        offset: Offset,
+        mut targets: Vec<AssignTarget<'src>>,
+    ) -> Stmt<'src> {
+        // Keep consuming `= expr` chains.
+        while matches!(self.peek(), Token::Eq) {
+            self.lex.bump();
+            let next = self.parse_expr_info_until(&[Token::Eq]);
+            // If followed by another `=`, this `next` is also a target.
+            if matches!(self.peek(), Token::Eq) {
+                targets.extend(info_to_assign_targets(&next, self.lex.source_str()));
+            } else {
+                // `next` is the final value.
+                self.eat_newline();
+                return Stmt {
+                    offset,
+                    kind: StmtKind::Assign {
+                        targets,
+                        value: next,
+                    },
+                };
+            }
+        }
+        // Fell off the end without a value (shouldn't happen in valid Python,
+        // but handle gracefully).
+        let value = ExprInfo::default();
+        self.eat_newline();
+        Stmt {
+            offset,
+            kind: StmtKind::Assign { targets, value },
+        }
+    }
+
     // ── suite (indented block) ────────────────────────────────────────────────
 
     fn parse_suite(&mut self) -> Vec<Stmt<'src>> {
         self.skip_newlines();
         // Inline suite: `if cond: stmt`  (no newline before body)
         if !matches!(self.peek(), Token::Indent | Token::Newline | Token::Eof) {
-            // Single simple statement on the same line.
-            let s = self.parse_stmt();
-            return s.into_iter().collect();
+            // Simple statements on the same line: `while x: x -= 1; continue`.
+            // `eat_newline` consumes a `;` and a newline alike, so a following
+            // token on the same physical line marks another statement.
+            let mut stmts = Vec::new();
+            loop {
+                let start = self.lex.peek_offset();
+                stmts.extend(self.parse_stmt());
+                if matches!(self.peek(), Token::Indent | Token::Dedent | Token::Eof) {
+                    break;
+                }
+                let next = self.lex.peek_offset();
+                let same_line = self
+                    .lex
+                    .source_str()
+                    .get(start as usize..next as usize)
+                    .is_some_and(|between| !between.contains('\n'));
+                if !same_line || next == start {
+                    break;
+                }
+            }
+            return stmts;
         }
         // Block suite: INDENT stmts* DEDENT
         if !matches!(self.peek(), Token::Indent) {
@@ -991,10 +1275,39 @@ impl<'src> Parser<'src> {
         let mut info = ExprInfo::default();
         let mut depth = 0i32; // bracket nesting depth within this expression
         let mut first = true;
+        // One entry per open bracket: where its names start in `info.names`,
+        // and the comprehension targets (`for x in …`) bound inside it.
+        let mut brackets: Vec<(usize, Vec<&'src str>)> = Vec::new();
+        // Parallel to `brackets`: whether each open bracket is a call's `(`.
+        let mut call_parens: Vec<bool> = Vec::new();
+        // The run of adjacent string literals being read, which Python joins
+        // into one: (offset, has an f-string part, has a replacement field).
+        let mut strings: Option<(Offset, bool, bool)> = None;
+        // The current run of adjacent plain (non-f) literals: its length,
+        // where it starts, and the token before it.
+        let mut plain_run = 0u32;
+        let mut run_start: Offset = 0;
+        let mut run_after: Option<Token<'src>> = None;
+        let mut prev: Option<Token<'src>> = None;
 
         loop {
             let tok = self.peek().clone();
 
+            if !matches!(tok, Token::Str(_) | Token::FStr(_))
+                && let Some((offset, true, false)) = strings.take()
+            {
+                info.plain_fstrings.push(offset);
+            }
+            if !matches!(tok, Token::Str(_)) {
+                plain_run = 0;
+            }
+            let before_tok = prev.replace(tok.clone());
+
+            // End of input ends the expression even inside an unclosed bracket.
+            if matches!(tok, Token::Eof) {
+                break;
+            }
+
             // Always stop at logical end-of-line (depth 0 only).
             // Also stop at assignment/annotation operators so that
             // `finish_expr_stmt` can recognise `x = …`, `x += …`, `x: T = …`.
@@ -1027,8 +1340,13 @@ impl<'src> Parser<'src> {
             let tok_offset = self.lex.peek_offset();
 
             match tok {
-                // ── Name ──────────────────────────────────────────────────
-                Token::Name(n) => {
+                // ── Name (including the soft keywords `match` / `case`) ────
+                Token::Name(_) | Token::KwMatch | Token::KwCase => {
+                    let n = match tok {
+                        Token::Name(n) => n,
+                        Token::KwMatch => "match",
+                        _ => "case",
+                    };
                     self.lex.bump();
                     // Check for walrus `:=`
                     if matches!(self.peek(), Token::Walrus) {
@@ -1039,6 +1357,11 @@ impl<'src> Parser<'src> {
                         // We continue the loop to parse the value.
                         continue;
                     }
+                    // `f(key=value)`: a keyword argument name, not a usage.
+                    if depth > 0 && matches!(self.peek(), Token::Eq) {
+                        first = false;
+                        continue;
+                    }
                     // Record shape for the very first token.
                     if first {
                         // Check for attribute: `name.attr`
@@ -1047,9 +1370,8 @@ impl<'src> Parser<'src> {
                             // Try to read `.identifier`
                             if let Token::Dot = self.peek().clone() {
                                 self.lex.bump();
-                                if let Token::Name(attr) = self.peek().clone() {
+                                if let Some(attr) = self.expect_name() {
                                     attr_part = attr;
-                                    self.lex.bump();
                                 }
                             }
                             info.kind = ExprKind::Attr(n, attr_part);
@@ -1058,7 +1380,16 @@ impl<'src> Parser<'src> {
                             first = false;
                             continue;
                         }
-                        info.kind = ExprKind::Name(n, tok_offset);
+                        info.kind = if matches!(self.peek(), Token::LParen) {
+                            let mut look = self.lex.clone();
+                            look.bump();
+                            ExprKind::Call {
+                                func: n,
+                                args_count: count_call_args(&mut look),
+                            }
+                        } else {
+                            ExprKind::Name(n, tok_offset)
+                        };
                     }
                     info.names.push((n, tok_offset));
                     first = false;
@@ -1090,21 +1421,6 @@ impl<'src> Parser<'src> {
                     first = false;
                     continue;
                 }
-                Token::KwMatch | Token::KwCase => {
-                    // Soft keywords — may be used as identifiers in expressions.
-                    let n = if matches!(tok, Token::KwMatch) {
-                        "match"
-                    } else {
-                        "case"
-                    };
-                    self.lex.bump();
-                    info.names.push((n, tok_offset));
-                    if first {
-                        info.kind = ExprKind::Name(n, tok_offset);
-                    }
-                    first = false;
-                    continue;
-                }
                 Token::KwNot
                 | Token::KwAnd
                 | Token::KwOr
@@ -1126,6 +1442,21 @@ impl<'src> Parser<'src> {
                 Token::Str(raw) => {
                     let raw_copy = raw; // &'src str
                     self.lex.bump();
+                    strings.get_or_insert((tok_offset, false, false));
+                    plain_run += 1;
+                    if plain_run == 1 {
+                        run_start = tok_offset;
+                        run_after = before_tok;
+                    }
+                    if plain_run == 2
+                        && depth > 0
+                        && call_parens.last() == Some(&false)
+                        && self.is_suspicious_join(run_after.as_ref(), run_start, tok_offset)
+                    {
+                        info.implicit_concats.push(tok_offset);
+                    }
+                    info.format_calls
+                        .extend(self.scan_format_call(raw_copy, tok_offset));
                     if first {
                         let val = extract_str_value(raw_copy).unwrap_or_default();
                         info.kind = ExprKind::StringLit(val);
@@ -1133,7 +1464,7 @@ impl<'src> Parser<'src> {
                         // Collect string literals found inside list/tuple brackets,
                         // e.g. the individual items of `__all__ = ["foo", "bar"]`.
                         if !val.is_empty() {
-                            info.string_list.push(val);
+                            info.string_list.push((val, tok_offset));
                         }
                     }
                     first = false;
@@ -1143,6 +1474,10 @@ impl<'src> Parser<'src> {
                     let raw_copy = raw;
                     self.lex.bump();
                     collect_fstring_names(raw_copy, &mut info.names, tok_offset);
+                    let run = strings.get_or_insert((tok_offset, false, false));
+                    run.1 = true;
+                    // `{{` is an escaped brace, not a replacement field.
+                    run.2 |= raw_copy.replace("{{", "").contains('{');
                     first = false;
                     continue;
                 }
@@ -1160,11 +1495,42 @@ impl<'src> Parser<'src> {
                 // ── Brackets — recurse for inner names ────────────────────
                 Token::LParen | Token::LBracket | Token::LBrace => {
                     self.lex.bump(); // depth already incremented above
+                    brackets.push((info.names.len(), Vec::new()));
+                    let before = &self.lex.source_str()[..tok_offset as usize];
+                    call_parens.push(
+                        matches!(tok, Token::LParen)
+                            && before
+                                .trim_end()
+                                .ends_with(|c: char| c.is_alphanumeric() || "_)]".contains(c)),
+                    );
                     first = false;
                     continue;
                 }
                 Token::RParen | Token::RBracket | Token::RBrace => {
                     self.lex.bump(); // depth already decremented above
+                    call_parens.pop();
+                    // Comprehension variables are local to the comprehension:
+                    // their reads inside the brackets are not usages outside.
+                    if let Some((start, locals)) = brackets.pop()
+                        && !locals.is_empty()
+                    {
+                        let mut i = 0;
+                        info.names.retain(|(n, _)| {
+                            i += 1;
+                            i <= start || !locals.contains(n)
+                        });
+                    }
+                    first = false;
+                    continue;
+                }
+
+                // ── Comprehension `for` — its targets are bindings ────────
+                Token::KwFor if depth > 0 => {
+                    self.lex.bump();
+                    let targets = self.comprehension_targets();
+                    if let Some((_, locals)) = brackets.last_mut() {
+                        locals.extend(targets);
+                    }
                     first = false;
                     continue;
                 }
@@ -1173,7 +1539,7 @@ impl<'src> Parser<'src> {
                 Token::Dot => {
                     self.lex.bump();
                     // Skip the attribute name (it's not a standalone usage).
-                    if matches!(self.peek(), Token::Name(_)) {
+                    if matches!(self.peek(), Token::Name(_) | Token::KwMatch | Token::KwCase) {
                         self.lex.bump();
                     }
                     first = false;
@@ -1196,9 +1562,38 @@ impl<'src> Parser<'src> {
                 }
             }
         }
+        if let Some((offset, true, false)) = strings {
+            info.plain_fstrings.push(offset);
+        }
         info
     }
 
+    /// Consume the target of a comprehension `for` up to its `in`, returning
+    /// the names it binds.
+    fn comprehension_targets(&mut self) -> Vec<&'src str> {
+        let mut names = Vec::new();
+        let mut depth = 0i32;
+        loop {
+            match self.peek().clone() {
+                Token::KwIn if depth == 0 => break,
+                Token::Eof | Token::Newline | Token::Semicolon => break,
+                Token::LParen | Token::LBracket => depth += 1,
+                Token::RParen | Token::RBracket => {
+                    if depth == 0 {
+                        break;
+                    }
+                    depth -= 1;
+                }
+                Token::Name(n) => names.push(n),
+                Token::KwMatch => names.push("match"),
+                Token::KwCase => names.push("case"),
+                _ => {}
+            }
+            self.lex.bump();
+        }
+        names
+    }
+
     /// Skip lambda parameter list (up to the `:` that starts the body).
     fn skip_lambda_params(&mut self) {
         let mut depth = 0i32;
@@ -1336,8 +1731,9 @@ impl<'src> Parser<'src> {
                 }
                 _ => {
                     targets.push(self.parse_simple_assign_target());
-                    // Check for comma (tuple target).
-                    if matches!(self.peek(), Token::Comma) {
+                    // Check for comma (tuple target), unless a comma ends the
+                    // target (`with a as x, b as y:`).
+                    if matches!(self.peek(), Token::Comma) && !stops.contains(&Token::Comma) {
                         self.lex.bump();
                         continue;
                     }
@@ -1372,7 +1768,12 @@ impl<'src> Parser<'src> {
                     elts.push(AssignTarget::Starred(Box::new(inner)));
                 }
                 _ => {
+                    let consumed_before = self.lex.tokens_consumed();
                     elts.push(self.parse_simple_assign_target());
+                    // `skip_expr` stops short of some tokens (e.g. a stray `:`).
+                    if self.lex.tokens_consumed() == consumed_before {
+                        self.lex.bump();
+                    }
                 }
             }
         }
@@ -1535,13 +1936,8 @@ impl<'src> Parser<'src> {
     }
 
     fn eat_newline(&mut self) {
-        match self.peek() {
-            Token::Newline | Token::Semicolon | Token::Eof | Token::Dedent => {
-                if !matches!(self.peek(), Token::Eof | Token::Dedent) {
-                    self.lex.bump();
-                }
-            }
-            _ => {}
+        if matches!(self.peek(), Token::Newline | Token::Semicolon) {
+            self.lex.bump();
         }
     }
 
@@ -1578,6 +1974,152 @@ impl<'src> Parser<'src> {
     }
 }
 
+// ── String formatting ─────────────────────────────────────────────────────────
+
+impl<'src> Parser<'src> {
+    /// Whether two adjacent literals joined inside a non-call bracket look
+    /// like a missing comma.  `after` is the token before the first one.
+    ///
+    /// Only collection items count (the run follows `,` or an opening
+    /// bracket), so a dict value split over lines is fine.  A parenthesised
+    /// group split over lines — `msg = ("long …"\n "… text")` — is the usual
+    /// way to wrap text and is fine too; joined on one line it is not.
+    fn is_suspicious_join(
+        &self,
+        after: Option<&Token<'src>>,
+        first: Offset,
+        second: Offset,
+    ) -> bool {
+        match after {
+            Some(Token::Comma | Token::LBracket | Token::LBrace) => true,
+            Some(Token::LParen) => self
+                .lex
+                .source_str()
+                .get(first as usize..second as usize)
+                .is_some_and(|between| !between.contains('\n')),
+            _ => false,
+        }
+    }
+
+    /// Look past the string literal just consumed for `.format(…)` or
+    /// `% operand`.  `None` when neither follows or when the number of
+    /// positional arguments cannot be told from the tokens (`*args`, a bare
+    /// name on the right of `%`, …).
+    fn scan_format_call(&self, raw: &str, offset: Offset) -> Option<FormatCall> {
+        let src = self.lex.source_str();
+        // Implicit concatenation: `"a" "b".format()` formats the joined text.
+        if src.get(..offset as usize).is_some_and(|before| {
+            before
+                .trim_end_matches(|c: char| c.is_whitespace() || c == '\\')
+                .ends_with(['"', '\''])
+        }) {
+            return None;
+        }
+        let mut look = self.lex.clone();
+        let next_offset = look.peek_offset() as usize;
+        let (style, args) = match look.peek() {
+            Token::Dot => {
+                look.bump();
+                if !matches!(look.peek(), Token::Name("format")) {
+                    return None;
+                }
+                look.bump();
+                if !look.eat(&Token::LParen) {
+                    return None;
+                }
+                (FormatStyle::Brace, count_positional_items(&mut look)?.0)
+            }
+            Token::Op if src.get(next_offset..)?.starts_with('%') => {
+                look.bump();
+                let args = match look.peek() {
+                    Token::Str(_) | Token::FStr(_) | Token::Number => 1,
+                    Token::LParen => {
+                        look.bump();
+                        match count_positional_items(&mut look)? {
+                            (0, _) => 0,
+                            // `(x)` is just `x`, which may itself be a tuple.
+                            (_, false) => return None,
+                            (n, true) => n,
+                        }
+                    }
+                    _ => return None,
+                };
+                (FormatStyle::Percent, args)
+            }
+            _ => return None,
+        };
+        Some(FormatCall {
+            style,
+            template: extract_str_value(raw)?,
+            args,
+            offset,
+        })
+    }
+}
+
+/// Count the positional items up to the `)` closing an argument list or
+/// tuple, and whether a top-level comma was seen.  Keyword arguments and
+/// `**mapping` are skipped; `*iterable` makes the count unknowable.
+fn count_positional_items(look: &mut Lexer<'_>) -> Option<(usize, bool)> {
+    let mut count = 0;
+    let mut comma = false;
+    let mut depth = 0i32;
+    // State of the current item: has it any tokens, is it positional.
+    let mut started = false;
+    let mut positional = true;
+    loop {
+        let tok = look.peek().clone();
+        match tok {
+            Token::Eof => return None,
+            Token::RParen if depth == 0 => break,
+            Token::Comma if depth == 0 => {
+                comma = true;
+                count += usize::from(started && positional);
+                started = false;
+                positional = true;
+            }
+            Token::Star if depth == 0 && !started => return None,
+            Token::DblStar if depth == 0 && !started => positional = false,
+            Token::Eq if depth == 0 => positional = false,
+            Token::LParen | Token::LBracket | Token::LBrace => depth += 1,
+            Token::RParen | Token::RBracket | Token::RBrace => depth -= 1,
+            _ => {}
+        }
+        if !matches!(tok, Token::Comma) || depth > 0 {
+            started = true;
+        }
+        look.bump();
+    }
+    count += usize::from(started && positional);
+    Some((count, comma))
+}
+
+/// Count the arguments up to the `)` closing an argument list, whatever
+/// their kind.
+fn count_call_args(look: &mut Lexer<'_>) -> usize {
+    let mut count = 0;
+    let mut depth = 0i32;
+    let mut started = false;
+    loop {
+        match look.peek() {
+            Token::Eof => break,
+            Token::RParen if depth == 0 => break,
+            Token::Comma if depth == 0 => {
+                count += usize::from(started);
+                started = false;
+                look.bump();
+                continue;
+            }
+            Token::LParen | Token::LBracket | Token::LBrace => depth += 1,
+            Token::RParen | Token::RBracket | Token::RBrace => depth -= 1,
+            _ => {}
+        }
+        started = true;
+        look.bump();
+    }
+    count + usize::from(started)
+}
+
 // ── Lexer source access (need to add method to Lexer) ────────────────────────
 
 impl<'src> Lexer<'src> {
@@ -1588,28 +2130,25 @@ impl<'src> Lexer<'src> {
 
 // ── Conversion helpers ────────────────────────────────────────────────────────
 
-/// Convert an `ExprKind` to an `AssignTarget` (used for augmented assignments).
-fn expr_kind_to_assign_target<'src>(kind: &ExprKind<'src>, _offset: Offset) -> AssignTarget<'src> {
-    match kind {
-        ExprKind::Name(n, o) => AssignTarget::Name(n, *o),
-        // Attribute/subscript targets — no inner names available from kind alone,
-        // so emit an empty Complex. Callers that need inner names should use
-        // info_to_assign_target_single instead.
-        ExprKind::Attr(_, _) => AssignTarget::Complex(ExprInfo::default()),
-        _ => AssignTarget::Complex(ExprInfo::default()),
-    }
-}
-
 /// Convert an `ExprInfo` to a list of `AssignTarget`s.
 /// Handles comma-separated (tuple) targets implicitly encoded via the info.
-fn info_to_assign_targets<'src>(info: &ExprInfo<'src>) -> Vec<AssignTarget<'src>> {
+fn info_to_assign_targets<'src>(info: &ExprInfo<'src>, src: &str) -> Vec<AssignTarget<'src>> {
     // For simple cases, the ExprKind captures the top-level shape.
     // For tuple targets `a, b = ...`, the parser's loop handles accumulation.
-    vec![info_to_assign_target_single(info)]
+    vec![info_to_assign_target_single(info, src)]
 }
 
-fn info_to_assign_target_single<'src>(info: &ExprInfo<'src>) -> AssignTarget<'src> {
+fn info_to_assign_target_single<'src>(info: &ExprInfo<'src>, src: &str) -> AssignTarget<'src> {
     match &info.kind {
+        // `ExprKind::Name` records only the first token, so `d[k] = v` looks
+        // like a bare name; the source after the name tells them apart.
+        ExprKind::Name(n, o)
+            if src
+                .get(*o as usize + n.len()..)
+                .is_some_and(|rest| rest.trim_start_matches([' ', '\t']).starts_with('[')) =>
+        {
+            AssignTarget::Complex(info.clone())
+        }
         ExprKind::Name(n, o) => AssignTarget::Name(n, *o),
         // For attribute/subscript targets (e.g. `obj.attr`, `obj[key]`) all the
         // names in the expression are *usages*, not new bindings.  Carry the
@@ -1624,7 +2163,7 @@ fn info_to_assign_target_single<'src>(info: &ExprInfo<'src>) -> AssignTarget<'sr
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ast::StmtKind;
+    use crate::ast::{AssignTarget, StmtKind};
 
     fn stmts(src: &str) -> Vec<Stmt<'_>> {
         parse(src)
@@ -1703,6 +2242,38 @@ mod tests {
         assert!(matches!(s[0].kind, StmtKind::Try { .. }));
     }
 
+    #[test]
+    fn test_parse_except_star() {
+        let s = stmts(
+            "try:\n    run()\nexcept* (TypeError, ValueError) as eg:\n    log(eg)\nexcept* OSError:\n    pass\n",
+        );
+        let StmtKind::Try { handlers, .. } = &s[0].kind else {
+            panic!("expected Try");
+        };
+        assert_eq!(handlers.len(), 2);
+        assert!(handlers.iter().all(|h| h.is_star));
+        assert!(matches!(handlers[0].name, Some(("eg", _))));
+        let names: Vec<&str> = handlers[0]
+            .type_expr
+            .as_ref()
+            .unwrap()
+            .names
+            .iter()
+            .map(|(n, _)| *n)
+            .collect();
+        assert_eq!(names, ["TypeError", "ValueError"]);
+        assert!(handlers[1].name.is_none());
+    }
+
+    #[test]
+    fn test_plain_except_is_not_star() {
+        let s = stmts("try:\n    pass\nexcept E as e:\n    pass\n");
+        let StmtKind::Try { handlers, .. } = &s[0].kind else {
+            panic!("expected Try");
+        };
+        assert!(!handlers[0].is_star);
+    }
+
     #[test]
     fn test_parse_decorated_function() {
         let s = stmts("@decorator\ndef foo():\n    pass\n");
@@ -1721,6 +2292,29 @@ mod tests {
         assert!(matches!(s[0].kind, StmtKind::With { .. }));
     }
 
+    #[test]
+    fn test_parse_with_multiple_items() {
+        let s = stmts("with a as x, \\\n        b as y:\n    pass\n");
+        let StmtKind::With { items, body, .. } = &s[0].kind else {
+            panic!("expected With");
+        };
+        assert_eq!(items.len(), 2);
+        assert!(matches!(items[0].target, Some(AssignTarget::Name("x", _))));
+        assert!(matches!(items[1].target, Some(AssignTarget::Name("y", _))));
+        assert!(matches!(body[0].kind, StmtKind::Pass));
+    }
+
+    #[test]
+    fn test_inline_suite_with_semicolons() {
+        let s = stmts("while i: i = 0; continue\nx = 1\n");
+        assert_eq!(s.len(), 2);
+        let StmtKind::While { body, .. } = &s[0].kind else {
+            panic!("expected While");
+        };
+        assert_eq!(body.len(), 2);
+        assert!(matches!(body[1].kind, StmtKind::Continue));
+    }
+
     #[test]
     fn test_parse_names_collected() {
         let s = stmts("x = foo(bar, baz)\n");
@@ -1744,6 +2338,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_call_on_name_counts_args() {
+        let s = stmts("raise ValueError()\nraise KeyError(k, *rest, hint=h)\n");
+        let StmtKind::Raise { exc: Some(a), .. } = &s[0].kind else {
+            panic!("expected Raise");
+        };
+        assert!(matches!(
+            a.kind,
+            ExprKind::Call {
+                func: "ValueError",
+                args_count: 0
+            }
+        ));
+        let StmtKind::Raise { exc: Some(b), .. } = &s[1].kind else {
+            panic!("expected Raise");
+        };
+        assert!(matches!(b.kind, ExprKind::Call { args_count: 3, .. }));
+    }
+
     #[test]
     fn test_walrus_target_collected() {
         let s = stmts("def f():\n    x = (n := foo())\n");
@@ -1800,4 +2413,443 @@ mod tests {
             panic!("expected FunctionDef");
         }
     }
+
+    // ── soft keywords ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_match_assignment_is_not_match_stmt() {
+        let s = stmts("match = 5\n");
+        assert_eq!(s.len(), 1);
+        match &s[0].kind {
+            StmtKind::Assign { targets, .. } => {
+                assert!(matches!(targets[0], AssignTarget::Name("match", 0)));
+            }
+            other => panic!("expected Assign, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_match_call_is_expr() {
+        let s = stmts("match(items)\n");
+        assert_eq!(s.len(), 1);
+        match &s[0].kind {
+            StmtKind::Expr(info) => {
+                let names: Vec<&str> = info.names.iter().map(|(n, _)| *n).collect();
+                assert_eq!(names, vec!["match", "items"]);
+            }
+            other => panic!("expected Expr, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_match_subscript_is_expr() {
+        let s = stmts("match[0]\n");
+        assert!(matches!(s[0].kind, StmtKind::Expr(_)));
+    }
+
+    #[test]
+    fn test_match_attribute_is_expr() {
+        let s = stmts("match.something\n");
+        assert_eq!(s.len(), 1);
+        assert!(matches!(s[0].kind, StmtKind::Expr(_)));
+    }
+
+    #[test]
+    fn test_subscript_and_call_targets_are_complex() {
+        for src in [
+            "d[k] = v\n",
+            "d [k] = v\n",
+            "f(x).attr = v\n",
+            "d[k] += 1\n",
+        ] {
+            match &stmts(src)[0].kind {
+                StmtKind::Assign { targets, .. } => {
+                    assert!(matches!(targets[0], AssignTarget::Complex(_)), "{src}");
+                }
+                StmtKind::AugAssign { target, .. } => {
+                    assert!(matches!(target, AssignTarget::Complex(_)), "{src}");
+                }
+                other => panic!("expected assignment, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_match_attribute_assignment() {
+        let s = stmts("match.something = 1\n");
+        assert_eq!(s.len(), 1);
+        match &s[0].kind {
+            StmtKind::Assign { targets, .. } => {
+                assert!(matches!(targets[0], AssignTarget::Complex(_)));
+            }
+            other => panic!("expected Assign, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_match_annotated_assignment() {
+        let s = stmts("match: int = 5\n");
+        assert!(matches!(s[0].kind, StmtKind::AnnAssign { .. }));
+    }
+
+    #[test]
+    fn test_re_match_attribute_not_a_usage() {
+        let s = stmts("re.match(p, s)\n");
+        if let StmtKind::Expr(info) = &s[0].kind {
+            assert!(!info.names.iter().any(|(n, _)| *n == "match"));
+        } else {
+            panic!("expected Expr");
+        }
+    }
+
+    #[test]
+    fn test_real_match_statement() {
+        let s = stmts("match command:\n    case 1:\n        pass\n    case _:\n        pass\n");
+        assert_eq!(s.len(), 1);
+        match &s[0].kind {
+            StmtKind::Match { arms, .. } => assert_eq!(arms.len(), 2),
+            other => panic!("expected Match, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_match_parenthesised_subject_is_match_stmt() {
+        let s = stmts("match (a, b):\n    case (1, 2):\n        pass\n");
+        assert!(matches!(s[0].kind, StmtKind::Match { .. }));
+    }
+
+    #[test]
+    fn test_match_call_followed_by_statement() {
+        let s = stmts("match(items)\nx = 1\n");
+        assert_eq!(s.len(), 2);
+        assert!(matches!(s[1].kind, StmtKind::Assign { .. }));
+    }
+
+    #[test]
+    fn test_case_assignment() {
+        let s = stmts("case = 5\n");
+        match &s[0].kind {
+            StmtKind::Assign { targets, .. } => {
+                assert!(matches!(targets[0], AssignTarget::Name("case", 0)));
+            }
+            other => panic!("expected Assign, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_case_call_is_expr() {
+        let s = stmts("case(x)\n");
+        assert!(matches!(s[0].kind, StmtKind::Expr(_)));
+    }
+
+    #[test]
+    fn test_case_as_identifier_inside_match_arm() {
+        let s = stmts("match x:\n    case 1:\n        case = 2\n");
+        match &s[0].kind {
+            StmtKind::Match { arms, .. } => {
+                assert_eq!(arms.len(), 1);
+                assert!(matches!(arms[0].body[0].kind, StmtKind::Assign { .. }));
+            }
+            other => panic!("expected Match, got {other:?}"),
+        }
+    }
+
+    fn arm_names(src: &str) -> (Vec<&str>, Vec<&str>, bool) {
+        let s = stmts(src);
+        match &s[0].kind {
+            StmtKind::Match { arms, .. } => {
+                let arm = &arms[0];
+                (
+                    arm.pattern_names.iter().map(|(n, _)| *n).collect(),
+                    arm.bindings.iter().map(|(n, _)| *n).collect(),
+                    arm.irrefutable,
+                )
+            }
+            other => panic!("expected Match, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_case_capture_patterns_are_bindings() {
+        let (reads, binds, _) = arm_names("match p:\n    case [x, *rest]:\n        pass\n");
+        assert!(reads.is_empty());
+        assert_eq!(binds, ["x", "rest"]);
+        let (_, binds, _) = arm_names("match p:\n    case {\"k\": v, **extra}:\n        pass\n");
+        assert_eq!(binds, ["v", "extra"]);
+        let (_, binds, _) = arm_names("match p:\n    case 1 | 2 as n:\n        pass\n");
+        assert_eq!(binds, ["n"]);
+    }
+
+    #[test]
+    fn test_case_class_pattern_splits_reads_and_bindings() {
+        let (reads, binds, _) = arm_names("match p:\n    case Point(x=px, y=0):\n        pass\n");
+        assert_eq!(reads, ["Point"]);
+        assert_eq!(binds, ["px"]);
+    }
+
+    #[test]
+    fn test_case_dotted_value_and_guard_are_reads() {
+        let (reads, binds, _) =
+            arm_names("match p:\n    case Color.RED if limit > 0:\n        pass\n");
+        assert_eq!(reads, ["Color", "limit"]);
+        assert!(binds.is_empty());
+    }
+
+    #[test]
+    fn test_case_irrefutable_arms() {
+        assert!(arm_names("match p:\n    case _:\n        pass\n").2);
+        assert!(arm_names("match p:\n    case other:\n        pass\n").2);
+        assert!(!arm_names("match p:\n    case other if other:\n        pass\n").2);
+        assert!(!arm_names("match p:\n    case [_]:\n        pass\n").2);
+        assert!(!arm_names("match p:\n    case Color.RED:\n        pass\n").2);
+    }
+
+    #[test]
+    fn test_type_alias_statement() {
+        let s = stmts("type Vector = list[float]\ntype Pair[T] = tuple[T, T]\n");
+        assert_eq!(s.len(), 2);
+        match &s[0].kind {
+            StmtKind::TypeAlias { name, value } => {
+                assert_eq!(*name, "Vector");
+                let names: Vec<&str> = value.names.iter().map(|(n, _)| *n).collect();
+                assert_eq!(names, vec!["list", "float"]);
+            }
+            other => panic!("expected TypeAlias, got {other:?}"),
+        }
+        assert!(matches!(
+            s[1].kind,
+            StmtKind::TypeAlias { name: "Pair", .. }
+        ));
+    }
+
+    #[test]
+    fn test_type_as_identifier_is_not_alias() {
+        let s = stmts("type = 3\ntype(x)\nprint(type)\n");
+        assert_eq!(s.len(), 3);
+        assert!(matches!(s[0].kind, StmtKind::Assign { .. }));
+        assert!(matches!(s[1].kind, StmtKind::Expr(_)));
+        assert!(matches!(s[2].kind, StmtKind::Expr(_)));
+    }
+
+    #[test]
+    fn test_type_alias_value_names_are_usages() {
+        let s = stmts("from typing import Callable\ntype Handler = Callable[[int], None]\n");
+        let mut used = std::collections::HashSet::new();
+        crate::names::collect_stmt_names(&s, &mut used);
+        assert!(used.contains("Callable"));
+    }
+
+    // ── malformed input (found by fuzzing) ──────────────────────────────────
+
+    #[test]
+    fn test_empty_input() {
+        assert!(stmts("").is_empty());
+    }
+
+    #[test]
+    fn test_null_bytes_do_not_panic() {
+        stmts("\0");
+        stmts("x = \0\0\nimport os\0\n");
+    }
+
+    #[test]
+    fn test_very_long_single_token() {
+        let name = "a".repeat(100_000);
+        let src = format!("{name} = 1\n");
+        let s = stmts(&src);
+        assert!(matches!(s[0].kind, StmtKind::Assign { .. }));
+    }
+
+    #[test]
+    fn test_deeply_nested_brackets() {
+        let src = format!("x = {}1{}\n", "([{".repeat(5_000), "}])".repeat(5_000));
+        stmts(&src);
+        stmts(&"(".repeat(10_000));
+    }
+
+    #[test]
+    fn test_mixed_indentation_does_not_panic() {
+        stmts("if x:\n\ty = 1\n        z = 2\n  \tw = 3\n");
+    }
+
+    #[test]
+    fn test_unclosed_bracket_at_eof() {
+        // Truncated source: terminates and, as for any unclosed bracket, yields nothing.
+        assert!(stmts("x = [i").is_empty());
+    }
+
+    #[test]
+    fn test_unclosed_paren_at_eof() {
+        assert!(stmts("print(\"a\",\n").is_empty());
+    }
+
+    #[test]
+    fn test_unclosed_bracket_in_nested_function() {
+        let src = "def outer():\n    def inner():\n        return [1, 2\n";
+        assert!(stmts(src).is_empty());
+    }
+
+    #[test]
+    fn test_balanced_brackets_parse_normally() {
+        let s = stmts("x = (1, [2, {3: 4}])\ny = f(x)\n");
+        assert_eq!(s.len(), 2);
+    }
+
+    #[test]
+    fn test_statements_before_unclosed_bracket_are_dropped() {
+        // Deliberate: the rest of a truncated file may use `os`, so reporting
+        // on the complete statements before the break would be guesswork.
+        assert!(stmts("import os\n(").is_empty());
+    }
+
+    #[test]
+    fn test_colon_in_class_bases_terminates() {
+        let s = stmts("class A(B, metac: int):\n    pass\n");
+        assert!(matches!(s[0].kind, StmtKind::ClassDef(_)));
+    }
+
+    #[test]
+    fn test_colon_in_with_target_terminates() {
+        let s = stmts("with a as [b:]:\n    d\n");
+        assert!(matches!(s[0].kind, StmtKind::With { .. }));
+    }
+
+    #[test]
+    fn test_stray_closing_bracket_is_skipped() {
+        let s = stmts(")\nimport os\n");
+        assert!(s.iter().any(|s| matches!(s.kind, StmtKind::Import(_))));
+    }
+
+    #[test]
+    fn test_tuple_assignment_targets() {
+        let s = stmts("a, *b, c.d = f()\n");
+        match &s[0].kind {
+            StmtKind::Assign { targets, .. } => match &targets[0] {
+                AssignTarget::Tuple(elts) => {
+                    assert!(matches!(elts[0], AssignTarget::Name("a", 0)));
+                    assert!(
+                        matches!(&elts[1], AssignTarget::Starred(b) if matches!(**b, AssignTarget::Name("b", _)))
+                    );
+                    assert!(matches!(elts[2], AssignTarget::Complex(_)));
+                }
+                other => panic!("expected Tuple, got {other:?}"),
+            },
+            other => panic!("expected Assign, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parenthesised_tuple_assignment_target() {
+        let s = stmts("(a, (b, c), d.e) = f()\n");
+        match &s[0].kind {
+            StmtKind::Assign { targets, .. } => match &targets[0] {
+                AssignTarget::Tuple(elts) => {
+                    assert!(matches!(elts[0], AssignTarget::Name("a", 1)));
+                    assert!(matches!(&elts[1], AssignTarget::Tuple(inner) if inner.len() == 2));
+                    assert!(matches!(elts[2], AssignTarget::Complex(_)));
+                }
+                other => panic!("expected Tuple, got {other:?}"),
+            },
+            other => panic!("expected Assign, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_bracketed_list_assignment_target() {
+        let s = stmts("[a, *rest] = [\n    1,\n    2,\n]\n");
+        match &s[0].kind {
+            StmtKind::Assign { targets, .. } => match &targets[0] {
+                AssignTarget::List(elts) => {
+                    assert!(matches!(elts[0], AssignTarget::Name("a", 1)));
+                    assert!(
+                        matches!(&elts[1], AssignTarget::Starred(r) if matches!(**r, AssignTarget::Name("rest", _)))
+                    );
+                }
+                other => panic!("expected List, got {other:?}"),
+            },
+            other => panic!("expected Assign, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_bracketed_expression_targets_stay_complex() {
+        // A parenthesised name is a plain name; a group followed by `.attr`
+        // or `[key]` is an attribute or subscript target.
+        let s = stmts("(a) = 1\n(b or c).d = 2\n[e][0] = 3\n");
+        let targets: Vec<&AssignTarget<'_>> = s
+            .iter()
+            .map(|stmt| match &stmt.kind {
+                StmtKind::Assign { targets, .. } => &targets[0],
+                other => panic!("expected Assign, got {other:?}"),
+            })
+            .collect();
+        assert!(matches!(targets[0], AssignTarget::Name("a", 1)));
+        assert!(matches!(targets[1], AssignTarget::Complex(_)));
+        assert!(matches!(targets[2], AssignTarget::Complex(_)));
+    }
+
+    #[test]
+    fn test_chained_tuple_assignment() {
+        let s = stmts("a, b = c = f(x)\n");
+        match &s[0].kind {
+            StmtKind::Assign { targets, value } => {
+                assert!(matches!(&targets[0], AssignTarget::Tuple(elts) if elts.len() == 2));
+                assert!(matches!(targets[1], AssignTarget::Name("c", _)));
+                let names: Vec<&str> = value.names.iter().map(|(n, _)| *n).collect();
+                assert_eq!(names, ["f", "x"]);
+            }
+            other => panic!("expected Assign, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_lambda_with_comma_is_not_tuple_assignment() {
+        let s = stmts("lambda a, b=1: a\n");
+        assert!(matches!(s[0].kind, StmtKind::Expr(_)));
+    }
+
+    #[test]
+    fn test_keyword_argument_names_not_usages() {
+        let s = stmts("f(key=value, **opts)\n");
+        if let StmtKind::Expr(info) = &s[0].kind {
+            let names: Vec<&str> = info.names.iter().map(|(n, _)| *n).collect();
+            assert_eq!(names, ["f", "value", "opts"]);
+        } else {
+            panic!("expected Expr");
+        }
+    }
+
+    #[test]
+    fn test_comprehension_targets_not_usages() {
+        let s = stmts("x = [k + v for k, v in items if k] + [k]\n");
+        if let StmtKind::Assign { value, .. } = &s[0].kind {
+            let names: Vec<&str> = value.names.iter().map(|(n, _)| *n).collect();
+            assert_eq!(names, ["items", "k"]);
+        } else {
+            panic!("expected Assign");
+        }
+    }
+
+    #[test]
+    fn test_positional_only_parameters() {
+        let s = stmts("def f(a, b=1, /, c=2, *, d):\n    return c\n");
+        let StmtKind::FunctionDef(f) = &s[0].kind else {
+            panic!("expected FunctionDef");
+        };
+        let names = |args: &[ArgDef<'static>]| args.iter().map(|a| a.name).collect::<Vec<_>>();
+        assert_eq!(names(&f.args.posonlyargs), ["a", "b"]);
+        assert!(f.args.posonlyargs[1].default.is_some());
+        assert_eq!(names(&f.args.args), ["c"]);
+        assert_eq!(names(&f.args.kwonlyargs), ["d"]);
+    }
+
+    #[test]
+    fn test_no_slash_means_no_positional_only() {
+        let s = stmts("def f(a, b):\n    pass\n");
+        let StmtKind::FunctionDef(f) = &s[0].kind else {
+            panic!("expected FunctionDef");
+        };
+        assert!(f.args.posonlyargs.is_empty());
+        assert_eq!(f.args.args.len(), 2);
+    }
 }
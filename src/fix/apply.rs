@@ -0,0 +1,146 @@
+//! Applying a batch of [`Fix`]es to one file's source.
+//!
+//! Fixes are spliced in from the end of the file backwards so earlier byte
+//! offsets stay valid.  Two fixes whose ranges overlap cannot both be
+//! applied safely; they are reported as a [`FixConflict`] instead.
+
+use super::Fix;
+use std::fmt;
+
+/// Two fixes that touch the same bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixConflict {
+    pub a: Fix,
+    pub b: Fix,
+}
+
+impl fmt::Display for FixConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "fixes at bytes {}..{} and {}..{} overlap",
+            self.a.start, self.a.end, self.b.start, self.b.end
+        )
+    }
+}
+
+impl std::error::Error for FixConflict {}
+
+/// Apply every fix to `source`, or fail on the first overlapping pair.
+///
+/// Identical fixes (e.g. two diagnostics on the same import statement) are
+/// applied once.
+pub fn apply_fixes(source: &str, mut fixes: Vec<Fix>) -> Result<String, FixConflict> {
+    sort_and_dedup(&mut fixes);
+    if let Some(conflict) = fixes.windows(2).find_map(|w| conflict(&w[0], &w[1])) {
+        return Err(conflict);
+    }
+    Ok(splice(source, &fixes))
+}
+
+/// Split `fixes` into those that can be applied together and the conflicts
+/// between the rest.  Both members of a conflicting pair are dropped.
+pub fn partition_conflicts(mut fixes: Vec<Fix>) -> (Vec<Fix>, Vec<FixConflict>) {
+    sort_and_dedup(&mut fixes);
+    let mut conflicts = Vec::new();
+    let mut rejected = vec![false; fixes.len()];
+    for i in 0..fixes.len() {
+        for j in i + 1..fixes.len() {
+            // Sorted by start: once `j` begins past `i`'s end, nothing later
+            // can overlap `i` either.
+            if fixes[j].start > fixes[i].end {
+                break;
+            }
+            if let Some(c) = conflict(&fixes[i], &fixes[j]) {
+                conflicts.push(c);
+                rejected[i] = true;
+                rejected[j] = true;
+            }
+        }
+    }
+    let clean = fixes
+        .into_iter()
+        .zip(rejected)
+        .filter_map(|(f, r)| (!r).then_some(f))
+        .collect();
+    (clean, conflicts)
+}
+
+fn sort_and_dedup(fixes: &mut Vec<Fix>) {
+    fixes.sort_by(|a, b| a.start.cmp(&b.start).then(a.end.cmp(&b.end)));
+    fixes.dedup();
+}
+
+/// `a` must not start after `b`.  Ranges that merely touch are fine; two
+/// edits at the same start position have no well-defined order.
+fn conflict(a: &Fix, b: &Fix) -> Option<FixConflict> {
+    (b.start < a.end || b.start == a.start).then(|| FixConflict {
+        a: a.clone(),
+        b: b.clone(),
+    })
+}
+
+/// Splice non-overlapping, ascending `fixes` into `source`, last one first.
+fn splice(source: &str, fixes: &[Fix]) -> String {
+    let mut out = source.to_string();
+    for fix in fixes.iter().rev() {
+        out.replace_range(fix.start..fix.end, &fix.replacement);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fix(start: usize, end: usize, replacement: &str) -> Fix {
+        Fix {
+            start,
+            end,
+            replacement: replacement.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_non_overlapping_fixes_applied() {
+        let src = "import os\nimport sys\nx = 1\n";
+        let out = apply_fixes(src, vec![fix(0, 10, ""), fix(10, 21, "")]).unwrap();
+        assert_eq!(out, "x = 1\n");
+    }
+
+    #[test]
+    fn test_order_of_input_does_not_matter() {
+        let src = "abcdef";
+        let out = apply_fixes(src, vec![fix(4, 5, "E"), fix(0, 1, "A")]).unwrap();
+        assert_eq!(out, "AbcdEf");
+    }
+
+    #[test]
+    fn test_overlapping_fixes_error() {
+        let src = "import os\n";
+        let err = apply_fixes(src, vec![fix(0, 10, ""), fix(7, 9, "sys")]).unwrap_err();
+        assert_eq!(err.a, fix(0, 10, ""));
+        assert_eq!(err.b, fix(7, 9, "sys"));
+    }
+
+    #[test]
+    fn test_identical_fixes_applied_once() {
+        let out = apply_fixes("import os\n", vec![fix(0, 10, ""), fix(0, 10, "")]).unwrap();
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn test_partition_drops_both_sides_of_conflict() {
+        let fixes = vec![fix(0, 10, ""), fix(5, 12, "x"), fix(20, 25, "")];
+        let (clean, conflicts) = partition_conflicts(fixes);
+        assert_eq!(clean, vec![fix(20, 25, "")]);
+        assert_eq!(conflicts.len(), 1);
+    }
+
+    #[test]
+    fn test_partition_keeps_adjacent_fixes() {
+        let (clean, conflicts) = partition_conflicts(vec![fix(0, 10, ""), fix(10, 20, "")]);
+        assert_eq!(clean.len(), 2);
+        assert!(conflicts.is_empty());
+    }
+}
@@ -0,0 +1,233 @@
+//! Unified diffs of fixed sources, for `--diff`.
+//!
+//! Lines are compared with Myers' O(ND) algorithm, which finds a shortest
+//! edit script; fixes touch a handful of lines, so D stays small.
+
+/// Lines of context around each change.
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    /// Line `.0` of the original is line `.1` of the modified text.
+    Equal(usize, usize),
+    /// Line of the original that is removed.
+    Delete(usize),
+    /// Line of the modified text that is added.
+    Insert(usize),
+}
+
+/// A unified diff (`--- a/file`, `+++ b/file`, `@@` hunks) turning
+/// `original` into `modified`, or an empty string when they are equal.
+pub fn generate_diff(original: &str, modified: &str, filename: &str) -> String {
+    let a: Vec<&str> = original.split_inclusive('\n').collect();
+    let b: Vec<&str> = modified.split_inclusive('\n').collect();
+    let edits = myers(&a, &b);
+    if edits.iter().all(|e| matches!(e, Edit::Equal(..))) {
+        return String::new();
+    }
+
+    let mut out = format!("--- a/{filename}\n+++ b/{filename}\n");
+    for (start, end) in hunks(&edits) {
+        let hunk = &edits[start..end];
+        // Where the hunk begins in each text: the first line it shows, or
+        // the line it inserts after when it shows none of that text.
+        let (mut a_start, mut b_start) = position(&edits, start);
+        let a_count = hunk
+            .iter()
+            .filter(|e| !matches!(e, Edit::Insert(_)))
+            .count();
+        let b_count = hunk
+            .iter()
+            .filter(|e| !matches!(e, Edit::Delete(_)))
+            .count();
+        if a_count > 0 {
+            a_start += 1;
+        }
+        if b_count > 0 {
+            b_start += 1;
+        }
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(a_start, a_count),
+            range(b_start, b_count)
+        ));
+        for edit in hunk {
+            let (prefix, line) = match *edit {
+                Edit::Equal(i, _) => (' ', a[i]),
+                Edit::Delete(i) => ('-', a[i]),
+                Edit::Insert(j) => ('+', b[j]),
+            };
+            out.push(prefix);
+            out.push_str(line);
+            if !line.ends_with('\n') {
+                out.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+    out
+}
+
+/// `start,count`, or just `start` for a single line (as GNU diff prints).
+fn range(start: usize, count: usize) -> String {
+    if count == 1 {
+        start.to_string()
+    } else {
+        format!("{start},{count}")
+    }
+}
+
+/// Lines of each text consumed by the edits before `index`.
+fn position(edits: &[Edit], index: usize) -> (usize, usize) {
+    edits[..index]
+        .iter()
+        .fold((0, 0), |(a, b), edit| match edit {
+            Edit::Equal(..) => (a + 1, b + 1),
+            Edit::Delete(_) => (a + 1, b),
+            Edit::Insert(_) => (a, b + 1),
+        })
+}
+
+/// Ranges of `edits` to print: every change with [`CONTEXT`] lines around
+/// it, merging changes whose context overlaps.
+fn hunks(edits: &[Edit]) -> Vec<(usize, usize)> {
+    let mut out: Vec<(usize, usize)> = Vec::new();
+    for (i, edit) in edits.iter().enumerate() {
+        if matches!(edit, Edit::Equal(..)) {
+            continue;
+        }
+        let start = i.saturating_sub(CONTEXT);
+        let end = (i + 1 + CONTEXT).min(edits.len());
+        match out.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => out.push((start, end)),
+        }
+    }
+    out
+}
+
+/// A shortest edit script from `a` to `b` (Myers, "An O(ND) Difference
+/// Algorithm and Its Variations", 1986).
+fn myers(a: &[&str], b: &[&str]) -> Vec<Edit> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = n + m;
+    // `v[k + offset]` is the furthest x reached on diagonal k = x - y.
+    let offset = max + 1;
+    let idx = |k: isize| (k + offset) as usize;
+    let mut v = vec![0isize; 2 * max as usize + 3];
+    // The state of `v` before each round, for backtracking.
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    'search: for d in 0..=max {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[idx(k - 1)] < v[idx(k + 1)]) {
+                v[idx(k + 1)]
+            } else {
+                v[idx(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx(k)] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && v[idx(k - 1)] < v[idx(k + 1)]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[idx(prev_k)];
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            edits.push(Edit::Equal(x as usize, y as usize));
+        }
+        if d > 0 {
+            if x == prev_x {
+                edits.push(Edit::Insert(prev_y as usize));
+            } else {
+                edits.push(Edit::Delete(prev_x as usize));
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    edits.reverse();
+    edits
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equal_sources_have_no_diff() {
+        assert_eq!(generate_diff("a\nb\n", "a\nb\n", "f.py"), "");
+        assert_eq!(generate_diff("", "", "f.py"), "");
+    }
+
+    #[test]
+    fn test_removed_import() {
+        let diff = generate_diff(
+            "import os\nimport sys\n\nprint(sys)\n",
+            "import sys\n\nprint(sys)\n",
+            "app.py",
+        );
+        assert_eq!(
+            diff,
+            "--- a/app.py\n+++ b/app.py\n@@ -1,4 +1,3 @@\n-import os\n import sys\n \n print(sys)\n"
+        );
+    }
+
+    #[test]
+    fn test_changed_line_and_context() {
+        let original: String = (1..=10).map(|i| format!("line{i}\n")).collect();
+        let modified = original.replace("line5\n", "five\n");
+        let diff = generate_diff(&original, &modified, "f.py");
+        assert!(diff.contains("@@ -2,7 +2,7 @@\n line2\n line3\n line4\n-line5\n+five\n line6\n"));
+    }
+
+    #[test]
+    fn test_distant_changes_get_separate_hunks() {
+        let original: String = (1..=20).map(|i| format!("{i}\n")).collect();
+        let modified = original.replacen("2\n", "", 1).replace("19\n", "");
+        let diff = generate_diff(&original, &modified, "f.py");
+        assert_eq!(diff.matches("@@ -").count(), 2, "{diff}");
+        assert!(diff.contains("@@ -1,5 +1,4 @@\n 1\n-2\n"), "{diff}");
+        assert!(
+            diff.contains("@@ -16,5 +15,4 @@\n 16\n 17\n 18\n-19\n 20\n"),
+            "{diff}"
+        );
+    }
+
+    #[test]
+    fn test_insertion_into_empty_file() {
+        assert_eq!(
+            generate_diff("", "pass\n", "f.py"),
+            "--- a/f.py\n+++ b/f.py\n@@ -0,0 +1 @@\n+pass\n"
+        );
+    }
+
+    #[test]
+    fn test_missing_final_newline() {
+        let diff = generate_diff("import os\nx = 1", "x = 1", "f.py");
+        assert_eq!(
+            diff,
+            "--- a/f.py\n+++ b/f.py\n@@ -1,2 +1 @@\n-import os\n x = 1\n\\ No newline at end of file\n"
+        );
+    }
+}
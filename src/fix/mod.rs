@@ -0,0 +1,323 @@
+//! Machine-applicable fixes attached to diagnostics.
+//!
+//! A [`Fix`] is a single byte-range replacement in the diagnostic's source
+//! file.  Checkers only attach one when the edit is unambiguous and leaves
+//! the file syntactically valid; everything else is reported without a fix.
+//! [`apply`] splices a file's fixes back into its source for `--fix`.
+//! [`diff`] shows the result as a unified diff for `--diff`.
+
+pub mod apply;
+pub mod diff;
+
+use crate::ast::{Stmt, StmtKind};
+use serde::{Deserialize, Serialize};
+
+/// Replace `source[start..end]` with `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Fix {
+    /// Byte offset where the replaced range begins.
+    pub start: usize,
+    /// Byte offset one past the end of the replaced range.
+    pub end: usize,
+    pub replacement: String,
+}
+
+/// Fix that deletes the simple statement starting at `offset`.
+///
+/// The statement must sit alone on its physical line: nothing but
+/// indentation before it, no `;`, no bracketed or backslash continuation.
+/// The whole line, newline included, is removed.  When `sole_in_block` is
+/// set the statement is the only one in an indented body, so it is replaced
+/// by `pass` instead to keep the block non-empty.
+pub fn delete_statement(source: &str, offset: usize, sole_in_block: bool) -> Option<Fix> {
+    let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+    if !source[line_start..offset]
+        .chars()
+        .all(|c| c == ' ' || c == '\t')
+    {
+        return None;
+    }
+    let line_end = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |i| offset + i);
+    let text = source[offset..line_end].trim_end_matches('\r');
+    if text.contains([';', '(']) || text.trim_end().ends_with('\\') {
+        return None;
+    }
+
+    if sole_in_block && offset > line_start {
+        return Some(Fix {
+            start: offset,
+            end: offset + text.len(),
+            replacement: "pass".to_string(),
+        });
+    }
+    Some(Fix {
+        start: line_start,
+        end: (line_end + 1).min(source.len()),
+        replacement: String::new(),
+    })
+}
+
+/// Fix that removes the aliases at `remove` (indices into the statement's
+/// alias list) from the `import` or `from ... import` statement at `offset`.
+///
+/// The remaining aliases keep their original text and the separators that
+/// followed them, so a parenthesized list keeps its layout.  Removing every
+/// alias deletes the statement through [`delete_statement`].  Returns `None`
+/// when the alias list cannot be located unambiguously — a comment inside
+/// it, or an alias count that disagrees with `total`.
+pub fn remove_import_aliases(
+    source: &str,
+    offset: usize,
+    total: usize,
+    remove: &[usize],
+    sole_in_block: bool,
+) -> Option<Fix> {
+    if remove.is_empty() {
+        return None;
+    }
+    if remove.len() >= total {
+        return delete_statement(source, offset, sole_in_block);
+    }
+    let items = alias_spans(source, offset)?;
+    if items.len() != total {
+        return None;
+    }
+
+    let kept: Vec<usize> = (0..total).filter(|i| !remove.contains(i)).collect();
+    let mut replacement = String::new();
+    for (k, &i) in kept.iter().enumerate() {
+        let (start, end) = items[i];
+        replacement.push_str(&source[start..end]);
+        if k + 1 < kept.len() {
+            // Reuse the separator that originally followed this alias.
+            replacement.push_str(&source[end..items[i + 1].0]);
+        }
+    }
+    Some(Fix {
+        start: items[0].0,
+        end: items[total - 1].1,
+        replacement,
+    })
+}
+
+/// Byte spans of each alias (`name` or `name as other`) in the import
+/// statement at `offset`, whitespace trimmed.
+fn alias_spans(source: &str, offset: usize) -> Option<Vec<(usize, usize)>> {
+    let bytes = source.as_bytes();
+    let mut pos = import_keyword_end(source, offset)?;
+
+    let skip_blank = |mut p: usize, in_parens: bool| {
+        while p < bytes.len() {
+            match bytes[p] {
+                b' ' | b'\t' => p += 1,
+                b'\\' if bytes.get(p + 1) == Some(&b'\n') => p += 2,
+                b'\\' if source[p + 1..].starts_with("\r\n") => p += 3,
+                b'\n' | b'\r' if in_parens => p += 1,
+                _ => break,
+            }
+        }
+        p
+    };
+
+    pos = skip_blank(pos, false);
+    let in_parens = bytes.get(pos) == Some(&b'(');
+    if in_parens {
+        pos += 1;
+    }
+
+    let mut items = Vec::new();
+    loop {
+        pos = skip_blank(pos, in_parens);
+        let start = pos;
+        while pos < bytes.len() && !matches!(bytes[pos], b',' | b')' | b'#' | b';' | b'\n' | b'\r')
+        {
+            pos += if bytes[pos] == b'\\' { 2 } else { 1 };
+        }
+        pos = pos.min(bytes.len());
+        let end = start
+            + source[start..pos]
+                .trim_end_matches([' ', '\t', '\\', '\r', '\n'])
+                .len();
+        if end > start {
+            items.push((start, end));
+        }
+        pos = skip_blank(pos, in_parens);
+        match bytes.get(pos) {
+            Some(b',') => pos += 1,
+            Some(b')') if in_parens => return Some(items),
+            // A comment inside the parentheses would end up beside the
+            // wrong alias.
+            _ if in_parens => return None,
+            _ => return Some(items),
+        }
+    }
+}
+
+/// Byte offset just past the `import` keyword of the statement at `offset`.
+fn import_keyword_end(source: &str, offset: usize) -> Option<usize> {
+    let text = &source[offset..];
+    if text.starts_with("import") {
+        return Some(offset + "import".len());
+    }
+    if !text.starts_with("from") {
+        return None;
+    }
+    // `from pkg.importer import x`: the keyword is the first `import` that
+    // stands alone, not part of the module path.
+    let mut search = "from".len();
+    while let Some(i) = text[search..].find("import") {
+        let at = search + i;
+        let before = text.as_bytes()[at - 1];
+        let after = text.as_bytes().get(at + "import".len()).copied();
+        if matches!(before, b' ' | b'\t' | b'.' | b'\n')
+            && matches!(after, Some(b' ' | b'\t' | b'(' | b'\\'))
+        {
+            return Some(offset + at + "import".len());
+        }
+        search = at + "import".len();
+    }
+    None
+}
+
+/// Number of names bound by `import` statements anywhere in `source`.
+///
+/// `--fix` compares this before and after splicing to make sure a file lost
+/// exactly the imports that were reported, and nothing else.
+pub fn count_imported_names(source: &str) -> usize {
+    count_in(&crate::fast_parser::parse(source))
+}
+
+fn count_in(stmts: &[Stmt<'_>]) -> usize {
+    stmts
+        .iter()
+        .map(|stmt| match &stmt.kind {
+            StmtKind::Import(aliases) => aliases.len(),
+            StmtKind::ImportFrom { names, .. } => names.len(),
+            StmtKind::FunctionDef(f) => count_in(&f.body),
+            StmtKind::ClassDef(c) => count_in(&c.body),
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => count_in(body) + count_in(orelse),
+            StmtKind::With { body, .. } => count_in(body),
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                count_in(body)
+                    + handlers.iter().map(|h| count_in(&h.body)).sum::<usize>()
+                    + count_in(orelse)
+                    + count_in(finalbody)
+            }
+            StmtKind::Match { arms, .. } => arms.iter().map(|arm| count_in(&arm.body)).sum(),
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delete_whole_line() {
+        let fix = delete_statement("import os\n", 0, false).unwrap();
+        assert_eq!((fix.start, fix.end), (0, 10));
+        assert_eq!(fix.replacement, "");
+    }
+
+    #[test]
+    fn test_delete_last_line_without_newline() {
+        let src = "x = 1\nimport os";
+        let fix = delete_statement(src, 6, false).unwrap();
+        assert_eq!((fix.start, fix.end), (6, src.len()));
+    }
+
+    #[test]
+    fn test_indented_line_removed_with_indentation() {
+        let src = "def f():\n    import os\n    return 1\n";
+        let fix = delete_statement(src, 13, false).unwrap();
+        assert_eq!(&src[fix.start..fix.end], "    import os\n");
+    }
+
+    #[test]
+    fn test_sole_statement_becomes_pass() {
+        let src = "def f():\n    import os\n";
+        let fix = delete_statement(src, 13, true).unwrap();
+        assert_eq!(&src[fix.start..fix.end], "import os");
+        assert_eq!(fix.replacement, "pass");
+    }
+
+    #[test]
+    fn test_semicolon_line_not_fixable() {
+        assert!(delete_statement("import os; import sys\n", 0, false).is_none());
+        assert!(delete_statement("x = 1; import os\n", 7, false).is_none());
+    }
+
+    #[test]
+    fn test_continuation_not_fixable() {
+        assert!(delete_statement("from os import (\n    path,\n)\n", 0, false).is_none());
+        assert!(delete_statement("from os import \\\n    path\n", 0, false).is_none());
+    }
+
+    fn apply(src: &str, fix: Fix) -> String {
+        format!(
+            "{}{}{}",
+            &src[..fix.start],
+            fix.replacement,
+            &src[fix.end..]
+        )
+    }
+
+    #[test]
+    fn test_remove_one_alias() {
+        let src = "from m import a, b, c\n";
+        let fix = remove_import_aliases(src, 0, 3, &[1], false).unwrap();
+        assert_eq!(apply(src, fix), "from m import a, c\n");
+        let fix = remove_import_aliases(src, 0, 3, &[0], false).unwrap();
+        assert_eq!(apply(src, fix), "from m import b, c\n");
+        let fix = remove_import_aliases(src, 0, 3, &[1, 2], false).unwrap();
+        assert_eq!(apply(src, fix), "from m import a\n");
+    }
+
+    #[test]
+    fn test_remove_aliased_and_dotted_names() {
+        let src = "import os.path, numpy as np  # deps\n";
+        let fix = remove_import_aliases(src, 0, 2, &[0], false).unwrap();
+        assert_eq!(apply(src, fix), "import numpy as np  # deps\n");
+        let src = "from pkg.importer import load as l, save\n";
+        let fix = remove_import_aliases(src, 0, 2, &[1], false).unwrap();
+        assert_eq!(apply(src, fix), "from pkg.importer import load as l\n");
+    }
+
+    #[test]
+    fn test_parenthesized_list_keeps_layout() {
+        let src = "from m import (\n    a,\n    b,\n    c,\n)\n";
+        let fix = remove_import_aliases(src, 0, 3, &[1], false).unwrap();
+        assert_eq!(apply(src, fix), "from m import (\n    a,\n    c,\n)\n");
+        let fix = remove_import_aliases(src, 0, 3, &[2], false).unwrap();
+        assert_eq!(apply(src, fix), "from m import (\n    a,\n    b,\n)\n");
+    }
+
+    #[test]
+    fn test_remove_every_alias_deletes_statement() {
+        let src = "from m import a, b\nx = 1\n";
+        let fix = remove_import_aliases(src, 0, 2, &[0, 1], false).unwrap();
+        assert_eq!(apply(src, fix), "x = 1\n");
+    }
+
+    #[test]
+    fn test_comment_inside_parentheses_not_fixable() {
+        let src = "from m import (\n    a,  # keep\n    b,\n)\n";
+        assert!(remove_import_aliases(src, 0, 2, &[1], false).is_none());
+    }
+
+    #[test]
+    fn test_count_imported_names() {
+        let src = "import os, sys\nfrom m import a\ndef f():\n    import re\n";
+        assert_eq!(count_imported_names(src), 4);
+    }
+}
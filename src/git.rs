@@ -0,0 +1,146 @@
+//! Git integration: restrict analysis to the files a commit touches.
+//!
+//! Reaper shells out to the `git` binary rather than linking libgit2 — the
+//! only thing needed is a list of paths, and every environment that has a
+//! repository worth checking also has `git` on `PATH`.
+
+use crate::discovery::has_extension;
+use anyhow::{Context, Result, bail};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Python files (those ending in one of `extensions`) added or modified in
+/// the index (`git diff --cached`), as absolute paths.  `cwd` may be anywhere
+/// inside the work tree.
+pub fn staged_files<S: AsRef<str>>(cwd: &Path, extensions: &[S]) -> Result<Vec<PathBuf>> {
+    let root = toplevel(cwd)?;
+    let out = run_git(
+        cwd,
+        &["diff", "--cached", "--name-only", "-z", "--diff-filter=AM"],
+    )?;
+    Ok(parse_name_only(&out, &root, extensions))
+}
+
+/// Python files (those ending in one of `extensions`) that differ between
+/// `git_ref` and the work tree (`git diff --name-only <ref>`), as absolute
+/// paths.
+pub fn changed_files<S: AsRef<str>>(
+    cwd: &Path,
+    git_ref: &str,
+    extensions: &[S],
+) -> Result<Vec<PathBuf>> {
+    let root = toplevel(cwd)?;
+    let out = run_git(cwd, &["diff", "--name-only", "-z", git_ref, "--"])?;
+    Ok(parse_name_only(&out, &root, extensions))
+}
+
+/// Parse `git diff --name-only -z` output: repository-relative paths, each
+/// terminated by NUL.  With `-z` git writes paths verbatim instead of
+/// C-quoting those with non-ASCII characters, quotes or newlines.  Paths not
+/// ending in one of `extensions` are dropped; the rest are joined onto `root`.
+pub fn parse_name_only<S: AsRef<str>>(output: &str, root: &Path, extensions: &[S]) -> Vec<PathBuf> {
+    output
+        .split('\0')
+        .filter(|l| !l.is_empty() && has_extension(Path::new(l), extensions))
+        .map(|l| root.join(l))
+        .collect()
+}
+
+/// Keep only the entries of `files` that are also in `keep`.  Paths are
+/// compared after canonicalisation, so `./a.py` and `/repo/a.py` match.
+pub fn restrict_to(files: Vec<PathBuf>, keep: &[PathBuf]) -> Vec<PathBuf> {
+    let keep: HashSet<PathBuf> = keep.iter().map(|p| canonical(p)).collect();
+    files
+        .into_iter()
+        .filter(|f| keep.contains(&canonical(f)))
+        .collect()
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn toplevel(cwd: &Path) -> Result<PathBuf> {
+    let out = run_git(cwd, &["rev-parse", "--show-toplevel"])?;
+    Ok(PathBuf::from(out.trim()))
+}
+
+fn run_git(cwd: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .context("failed to run `git`")?;
+    if !output.status.success() {
+        bail!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::DEFAULT_EXTENSIONS;
+
+    #[test]
+    fn test_parse_name_only_filters_python() {
+        let out = "src/a.py\0README.md\0pkg/b.py\0setup.cfg\0gui.pyw\0c.pyc\0";
+        let files = parse_name_only(out, Path::new("/repo"), DEFAULT_EXTENSIONS);
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("/repo/src/a.py"),
+                PathBuf::from("/repo/pkg/b.py"),
+                PathBuf::from("/repo/gui.pyw")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_name_only_uses_given_extensions() {
+        let out = "a.py\0stubs/b.pyi\0c.pyw\0";
+        let files = parse_name_only(out, Path::new("/repo"), &["py", "pyi"]);
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("/repo/a.py"),
+                PathBuf::from("/repo/stubs/b.pyi")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_name_only_keeps_paths_verbatim() {
+        let out = "caf\u{e9}.py\0with space.py\0quote\"d.py\0";
+        let files = parse_name_only(out, Path::new("/repo"), DEFAULT_EXTENSIONS);
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("/repo/caf\u{e9}.py"),
+                PathBuf::from("/repo/with space.py"),
+                PathBuf::from("/repo/quote\"d.py")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_name_only_empty() {
+        assert!(parse_name_only("", Path::new("/repo"), DEFAULT_EXTENSIONS).is_empty());
+    }
+
+    #[test]
+    fn test_restrict_to_matches_canonical_paths() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.py"), "").unwrap();
+        std::fs::write(dir.path().join("b.py"), "").unwrap();
+        let files = vec![dir.path().join("a.py"), dir.path().join("./b.py")];
+        let keep = vec![dir.path().join("b.py")];
+        let kept = restrict_to(files, &keep);
+        assert_eq!(kept, vec![dir.path().join("./b.py")]);
+    }
+}
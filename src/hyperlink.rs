@@ -0,0 +1,98 @@
+//! OSC 8 terminal hyperlinks for the `file:line:col` prefix of diagnostics.
+//!
+//! Terminals that understand OSC 8 (iTerm2, WezTerm, Windows Terminal, VTE
+//! based ones, VS Code…) render the wrapped text as a clickable link that
+//! opens the file; the rest print the text unchanged, but some show the raw
+//! escape bytes, so links are only emitted on request (`--hyperlinks`).
+
+use crate::paths::{PathStyle, normalize_path, to_forward_slashes};
+use std::path::Path;
+
+/// `TERM_PROGRAM` values of terminals known to support OSC 8.
+const SUPPORTING_TERM_PROGRAMS: &[&str] = &["iTerm.app", "WezTerm", "vscode", "Hyper", "ghostty"];
+
+/// Wrap `text` in an OSC 8 hyperlink to `uri`.
+pub fn osc8(uri: &str, text: &str) -> String {
+    format!("\x1b]8;;{uri}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+/// `file:///absolute/path#line` for `path`, resolved against `cwd`.
+pub fn file_uri(path: &str, line: usize, cwd: &Path) -> String {
+    let absolute = to_forward_slashes(&normalize_path(path, PathStyle::Absolute, cwd));
+    // Windows paths (`C:/src/a.py`) need the extra slash of an empty host.
+    let slash = if absolute.starts_with('/') { "" } else { "/" };
+    format!("file://{slash}{}#{line}", percent_encode(&absolute))
+}
+
+/// Guess from the environment whether the terminal renders OSC 8 links.
+/// `var` looks up an environment variable (injectable for tests).
+pub fn terminal_supports_hyperlinks(var: impl Fn(&str) -> Option<String>) -> bool {
+    if var("TERM_PROGRAM").is_some_and(|p| SUPPORTING_TERM_PROGRAMS.contains(&p.as_str())) {
+        return true;
+    }
+    // VTE gained OSC 8 in 0.50 (VTE_VERSION 5000).
+    if var("VTE_VERSION")
+        .and_then(|v| v.parse::<u32>().ok())
+        .is_some_and(|v| v >= 5000)
+    {
+        return true;
+    }
+    var("WT_SESSION").is_some()
+        || var("COLORTERM").is_some_and(|c| c == "truecolor" || c == "24bit")
+}
+
+/// Percent-encode everything but unreserved characters and path separators.
+fn percent_encode(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for b in path.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'/' | b'-' | b'_' | b'.' | b'~' | b':') {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+    out
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn supports(vars: &[(&str, &str)]) -> bool {
+        terminal_supports_hyperlinks(|name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        })
+    }
+
+    #[test]
+    fn test_osc8_escape_sequence() {
+        assert_eq!(
+            osc8("file:///a.py#3", "a.py:3:1").as_bytes(),
+            b"\x1b]8;;file:///a.py#3\x1b\\a.py:3:1\x1b]8;;\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_file_uri_is_absolute_with_line() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("my mod.py"), "").unwrap();
+        let uri = file_uri("my mod.py", 12, dir.path());
+        assert!(uri.starts_with("file:///"), "got: {uri}");
+        assert!(uri.ends_with("/my%20mod.py#12"), "got: {uri}");
+    }
+
+    #[test]
+    fn test_terminal_detection() {
+        assert!(supports(&[("TERM_PROGRAM", "iTerm.app")]));
+        assert!(supports(&[("VTE_VERSION", "6003")]));
+        assert!(supports(&[("COLORTERM", "truecolor")]));
+        assert!(supports(&[("WT_SESSION", "abc")]));
+        assert!(!supports(&[("VTE_VERSION", "4601")]));
+        assert!(!supports(&[("TERM_PROGRAM", "Apple_Terminal")]));
+        assert!(!supports(&[]));
+    }
+}
@@ -1,9 +1,39 @@
+//! Reaper's analysis engine as a library.
+//!
+//! The CLI is a thin layer over this crate.  Downstream tools can reuse the
+//! zero-copy Python parser, whose statements borrow identifiers straight
+//! from the source buffer:
+//!
+//! ```
+//! use reaper::{StmtKind, parse};
+//!
+//! let stmts = parse("import os\n");
+//! assert!(matches!(stmts[0].kind, StmtKind::Import(_)));
+//! ```
+//!
+//! or run every check over a set of files with
+//! [`analyze::analyze_files`], which returns [`Diagnostic`]s.
+
 pub mod analyze;
 pub mod ast;
+pub mod builtins;
+pub mod cache;
 pub mod checks;
+pub mod config;
 pub mod discovery;
+pub mod encoding;
 pub mod fast_parser;
+pub mod fix;
+pub mod git;
+pub mod hyperlink;
 pub mod location;
 pub mod names;
+pub mod output;
 pub mod parser;
+pub mod paths;
+pub mod python;
 pub mod types;
+
+pub use ast::{Stmt, StmtKind};
+pub use fast_parser::parse;
+pub use types::Diagnostic;
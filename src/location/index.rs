@@ -0,0 +1,100 @@
+/// Newline positions of one source file, for O(log n) offset lookups.
+///
+/// Built once per file in a single O(n) scan; every diagnostic then costs a
+/// binary search instead of a rescan from the start of the file.
+#[derive(Debug, Clone, Default)]
+pub struct LineIndex {
+    /// Byte offset of every `\n` in the source, ascending.
+    newlines: Vec<usize>,
+    /// Length of the source in bytes.
+    len: usize,
+}
+
+impl LineIndex {
+    pub fn from_source(source: &str) -> Self {
+        let newlines = source
+            .bytes()
+            .enumerate()
+            .filter_map(|(i, b)| (b == b'\n').then_some(i))
+            .collect();
+        Self {
+            newlines,
+            len: source.len(),
+        }
+    }
+
+    /// Convert a byte offset into (line, col), both 1-indexed.
+    pub fn offset_to_line_col(&self, offset: usize) -> (usize, usize) {
+        // Number of newlines strictly before `offset` = zero-based line.
+        let line = self.newlines.partition_point(|&nl| nl < offset);
+        let line_start = if line == 0 {
+            0
+        } else {
+            self.newlines[line - 1] + 1
+        };
+        (line + 1, offset - line_start + 1)
+    }
+
+    /// Byte offset of the end of the line containing `offset`: its `\n`, or
+    /// the end of the source on the last line.
+    pub fn line_end(&self, offset: usize) -> usize {
+        let line = self.newlines.partition_point(|&nl| nl < offset);
+        self.newlines.get(line).copied().unwrap_or(self.len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(src: &str, offset: usize) -> (usize, usize) {
+        LineIndex::from_source(src).offset_to_line_col(offset)
+    }
+
+    #[test]
+    fn test_line_1_for_start() {
+        assert_eq!(at("import os\n", 0), (1, 1));
+    }
+
+    #[test]
+    fn test_second_line() {
+        assert_eq!(at("import os\nimport sys\n", 10), (2, 1));
+    }
+
+    #[test]
+    fn test_column_within_line() {
+        assert_eq!(at("x = 1\n", 4), (1, 5));
+    }
+
+    #[test]
+    fn test_empty_source() {
+        assert_eq!(at("", 0), (1, 1));
+    }
+
+    #[test]
+    fn test_offset_on_newline_belongs_to_its_line() {
+        // The `\n` at offset 5 ends line 1.
+        assert_eq!(at("x = 1\ny = 2\n", 5), (1, 6));
+        assert_eq!(at("x = 1\ny = 2\n", 6), (2, 1));
+    }
+
+    #[test]
+    fn test_many_lines() {
+        let src = "a\n".repeat(1000);
+        assert_eq!(at(&src, 2 * 999), (1000, 1));
+        assert_eq!(at(&src, 2 * 999 + 1), (1000, 2));
+    }
+
+    #[test]
+    fn test_line_end() {
+        let index = LineIndex::from_source("x = 1\ny = 2");
+        assert_eq!(index.line_end(0), 5);
+        assert_eq!(index.line_end(5), 5);
+        assert_eq!(index.line_end(6), 11);
+    }
+
+    #[test]
+    fn test_crlf_counts_carriage_return_in_column() {
+        assert_eq!(at("x\r\ny\r\n", 3), (2, 1));
+    }
+}
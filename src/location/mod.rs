@@ -0,0 +1,8 @@
+//! Source locations: byte offsets → 1-indexed (line, col).
+//!
+//! Checkers record byte offsets; [`LineIndex`] turns them into the line and
+//! column printed in diagnostics.  Build one per file and share it.
+
+mod index;
+
+pub use index::LineIndex;
@@ -1,18 +1,31 @@
 mod analyze;
 mod ast;
 mod banner;
+mod builtins;
+mod cache;
 mod checks;
+mod config;
 mod discovery;
+mod encoding;
 mod fast_parser;
+mod fix;
+mod git;
+mod hyperlink;
 mod location;
 mod names;
+mod output;
 mod parser;
+mod paths;
+mod python;
 mod types;
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use colored::Colorize;
 use serde_json::json;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
 use std::process;
 
 #[derive(Parser)]
@@ -30,10 +43,21 @@ struct Cli {
     #[arg()]
     paths: Vec<PathBuf>,
 
+    /// Also analyse the paths listed in FILE, one per line (`-` reads stdin).
+    /// Blank lines and lines starting with `#` are ignored.
+    #[arg(long, value_name = "FILE")]
+    paths_from: Option<PathBuf>,
+
     /// Only report the given comma-separated rule codes (e.g. --select RP001,RP003).
     #[arg(long, value_delimiter = ',')]
     select: Option<Vec<String>>,
 
+    /// Only report rules in the given comma-separated categories: imports,
+    /// variables, control-flow, definitions, arguments, expressions
+    /// (e.g. --select-category imports,variables).
+    #[arg(long, value_delimiter = ',', value_name = "CATEGORIES")]
+    select_category: Option<Vec<types::Category>>,
+
     /// Exclude directories or files whose path contains any of the given
     /// comma-separated names (e.g. --exclude tests,migrations,vendor).
     /// Hidden directories (.git, .venv, __pycache__, etc.) are always excluded
@@ -41,33 +65,386 @@ struct Cli {
     #[arg(long, value_delimiter = ',')]
     exclude: Option<Vec<String>>,
 
-    /// Emit results as JSON instead of the default text format.
+    /// Never report the given comma-separated names; `*` and `?` are
+    /// wildcards (e.g. --ignore-names dummy,UNUSED_*).
+    #[arg(long, value_delimiter = ',', value_name = "PATTERNS")]
+    ignore_names: Option<Vec<String>>,
+
+    /// Only analyse files matching one of the given comma-separated globs,
+    /// relative to each scanned directory (e.g. --include "src/**/*.py").
+    #[arg(long, value_delimiter = ',', value_name = "GLOB")]
+    include: Option<Vec<String>>,
+
+    /// Also scan hidden directories and files (e.g. `.github/scripts/`).
+    /// `.git`, `.venv` and the other always-excluded directories stay skipped.
     #[arg(long)]
+    include_hidden: bool,
+
+    /// Comma-separated file extensions to treat as Python source when
+    /// scanning directories (default: py,pyw).
+    #[arg(long, value_delimiter = ',', value_name = "EXT")]
+    extensions: Option<Vec<String>>,
+
+    /// Report format: `text` (the default), `json`, `sarif` or `github`.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+
+    /// Emit results as JSON; shorthand for `--output json`.
+    #[arg(long, conflicts_with = "output")]
     json: bool,
 
+    /// Apply available fixes in place and report only what remains.
+    #[arg(long)]
+    fix: bool,
+
+    /// Like --fix, but report (and fail on) only diagnostics that have no
+    /// automatic fix, so CI fails only on issues needing manual attention.
+    #[arg(long)]
+    fix_only: bool,
+
+    /// Print the changes --fix would make as a unified diff instead of
+    /// writing them; exits 1 when there is anything to fix.
+    #[arg(long)]
+    diff: bool,
+
+    /// Make each `file:line:col` a clickable OSC 8 terminal link.  `auto`
+    /// (the default when the flag has no value) only emits links when stdout
+    /// is a terminal that looks like it supports them.
+    #[arg(long, value_enum, value_name = "WHEN", num_args = 0..=1, default_missing_value = "auto")]
+    hyperlinks: Option<Hyperlinks>,
+
+    /// Append each rule's documentation link to its diagnostics.
+    #[arg(long)]
+    show_urls: bool,
+
+    /// Print a rule's name, severity and documentation link, then exit.
+    #[arg(long, value_name = "CODE")]
+    explain: Option<String>,
+
+    /// Print the --explain entry of every rule, then exit.  With --json,
+    /// prints an array of rule objects instead.
+    #[arg(long)]
+    explain_all: bool,
+
+    /// Print a table of every rule (code, name, severity and what it
+    /// reports), then exit.  With --json, prints an array of rule objects.
+    #[arg(long)]
+    list_rules: bool,
+
+    /// After the diagnostics, print each file's issue count broken down by
+    /// rule, most issues first.  With --json, adds a `per_file` object.
+    #[arg(long)]
+    per_file_summary: bool,
+
+    /// After the summary, print a health score: 100 minus the issues per
+    /// hundred lines analysed.  With --json, adds `health_score` and
+    /// `total_lines`.
+    #[arg(long)]
+    statistics: bool,
+
+    /// Print a header per file (with its issue count) and list that file's
+    /// diagnostics beneath it as `line:col`.  With --json, `diagnostics`
+    /// becomes an object keyed by file.
+    #[arg(long)]
+    group_by_file: bool,
+
+    /// Mark diagnostics that carry an automatic fix with `[fix available]`.
+    #[arg(long)]
+    show_fixes: bool,
+
+    /// Append how many of the issues have an automatic fix to the summary
+    /// line.  With --json, adds a top-level `fixable_count`.
+    #[arg(long)]
+    show_fixes_count: bool,
+
+    /// With --json, add each diagnostic's `start_byte` and `end_byte`
+    /// offsets into its source, decoded to UTF-8 without a BOM.
+    #[arg(long)]
+    show_bytes: bool,
+
+    /// Treat `# type: ignore` comments like a bare `# noqa`, suppressing
+    /// every rule on the line.
+    #[arg(long)]
+    respect_type_ignore: bool,
+
+    /// Web framework whose conventions to apply: `django` exempts signal
+    /// receivers, decorated views and `urlpatterns` views from RP008.
+    #[arg(long, value_name = "NAME", value_parser = config::FRAMEWORKS.to_vec())]
+    framework: Option<String>,
+
+    /// Comma-separated decorator names whose functions get their arguments
+    /// from a framework, exempt from RP008 (default: task,shared_task).
+    #[arg(long, value_delimiter = ',', value_name = "DECORATORS")]
+    framework_exemptions: Option<Vec<String>>,
+
+    /// Comma-separated names to treat as used in every file, for references
+    /// reaper cannot see (e.g. --always-used handler,urls).
+    #[arg(long, value_delimiter = ',', value_name = "NAMES")]
+    always_used: Option<Vec<String>>,
+
+    /// Comma-separated module dunders never reported by RP048 when listed in
+    /// `__all__` (default: __version__, __author__ and other packaging names).
+    #[arg(long, value_delimiter = ',', value_name = "NAMES")]
+    exempt_dunders: Option<Vec<String>>,
+
+    /// The Python version the code runs under (e.g. --python-version 3.11),
+    /// for version-dependent rules.  Defaults to the `python3` on PATH.
+    #[arg(long, value_name = "X.Y", value_parser = parse_python_version)]
+    python_version: Option<(u32, u32)>,
+
+    /// Exempt common implicit-use patterns to cut false positives.  Without a
+    /// value every heuristic is on; `--lenient=callbacks,loggers` picks some:
+    /// `callbacks` (`*_handler`, `*_callback`, `on_*` functions; RP003),
+    /// `meta` (classes with `Meta` in the name; RP004) and `loggers`
+    /// (`logger` / `log` locals; RP002).
+    #[arg(
+        long,
+        value_enum,
+        value_name = "HEURISTICS",
+        value_delimiter = ',',
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "callbacks,meta,loggers",
+        conflicts_with = "strict"
+    )]
+    lenient: Option<Vec<Heuristic>>,
+
+    /// Drop exemptions that can hide real findings: RP008 also checks the
+    /// parameters of pytest test functions and fixtures.
+    #[arg(long)]
+    strict: bool,
+
     /// Exit with code 0 even when issues are found (useful in CI with --json).
     #[arg(long)]
     no_exit_code: bool,
+
+    /// Exit with code 1 only when a finding is at least this severe: `error`,
+    /// `warning` or `info` (the default, any finding).
+    #[arg(long, value_name = "SEVERITY")]
+    exit_code_on_severity: Option<types::Severity>,
+
+    /// Print file paths relative to the current directory (the default).
+    #[arg(long, conflicts_with = "absolute_paths")]
+    relative_paths: bool,
+
+    /// Print fully resolved absolute file paths.
+    #[arg(long)]
+    absolute_paths: bool,
+
+    /// Pre-commit hook mode: report only diagnostics in Python files staged
+    /// in git (added or modified).  All files are still analysed, so
+    /// cross-file RP003/RP004 see usages in unstaged files.  Scans `.` when
+    /// no paths are given and always exits 1 on findings, ignoring
+    /// --no-exit-code.
+    #[arg(long)]
+    pre_commit: bool,
+
+    /// Report only diagnostics in files changed relative to a git ref
+    /// (default `origin/main`).  All files are still analysed, so cross-file
+    /// RP003/RP004 see usages in unchanged files.
+    #[arg(long, value_name = "REF", num_args = 0..=1, default_missing_value = "origin/main")]
+    changed_only: Option<String>,
+
+    /// Analyse every file from scratch instead of reusing the results cached
+    /// for unchanged files in `~/.cache/reaper/cache.json`.
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Print only the number of issues (`{"count": N}` with --json), for
+    /// budget gates in CI.
+    #[arg(long)]
+    count: bool,
+
+    /// Order of reported diagnostics.
+    #[arg(long, value_enum, default_value_t = SortBy::File)]
+    sort_by: SortBy,
+
+    /// Report at most N diagnostics (after sorting); when more are found,
+    /// say how many were left out and exit with code 2.
+    #[arg(long, value_name = "N")]
+    max_issues: Option<usize>,
+}
+
+/// A `--lenient` heuristic.
+#[derive(Clone, Copy, ValueEnum)]
+enum Heuristic {
+    /// `*_handler`, `*_callback` and `on_*` functions are exempt from RP003.
+    Callbacks,
+    /// Classes with `Meta` in their name are exempt from RP004.
+    Meta,
+    /// Locals named `logger` or `log` are exempt from RP002.
+    Loggers,
+}
+
+/// Report format selected with `--output`.
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum OutputFormat {
+    /// `file:line:col: CODE message` lines and a summary.
+    Text,
+    /// One JSON object with every diagnostic.
+    Json,
+    /// A SARIF 2.1.0 log, for code-scanning services.
+    Sarif,
+    /// GitHub Actions workflow commands, shown as pull request annotations.
+    Github,
+}
+
+/// When `--hyperlinks` emits OSC 8 links.
+#[derive(Clone, Copy, ValueEnum)]
+enum Hyperlinks {
+    /// Only when stdout is a terminal known to render them.
+    Auto,
+    Always,
+    Never,
+}
+
+/// Diagnostic ordering selected with `--sort-by`.
+#[derive(Clone, Copy, ValueEnum)]
+enum SortBy {
+    /// File, then line, then column.
+    File,
+    /// Rule code, then file/line/column.
+    Rule,
+    /// Most severe first, then file/line/column.
+    Severity,
 }
 
 fn main() {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    if cli.json {
+        cli.output = OutputFormat::Json;
+    }
+
+    // ── --explain-all ─────────────────────────────────────────────────────────
+    if cli.explain_all {
+        if cli.output == OutputFormat::Json {
+            let rules: Vec<serde_json::Value> = types::RuleCode::ALL
+                .iter()
+                .map(|rule| {
+                    json!({
+                        "code":        rule.to_string(),
+                        "name":        rule.title(),
+                        "severity":    rule.severity().to_string(),
+                        "description": rule.description(),
+                        "examples":    [rule.example()],
+                        "url":         rule.url(),
+                    })
+                })
+                .collect();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&rules)
+                    .expect("serde_json::Value is always serialisable")
+            );
+        } else {
+            for (i, rule) in types::RuleCode::ALL.iter().enumerate() {
+                if i > 0 {
+                    println!("{}", "─".repeat(60).dimmed());
+                }
+                print_explanation(rule);
+            }
+        }
+        return;
+    }
+
+    // ── --list-rules ──────────────────────────────────────────────────────────
+    if cli.list_rules {
+        if cli.output == OutputFormat::Json {
+            let rules: Vec<serde_json::Value> = types::RuleCode::ALL
+                .iter()
+                .map(|rule| {
+                    json!({
+                        "code":        rule.to_string(),
+                        "name":        rule.title(),
+                        "description": rule.description(),
+                        "severity":    rule.severity().to_string(),
+                    })
+                })
+                .collect();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&rules)
+                    .expect("serde_json::Value is always serialisable")
+            );
+        } else {
+            print_rule_table();
+        }
+        return;
+    }
+
+    // ── --explain CODE ────────────────────────────────────────────────────────
+    if let Some(code) = &cli.explain {
+        match types::RuleCode::from_code(code) {
+            Some(rule) => {
+                print_explanation(&rule);
+                return;
+            }
+            None => {
+                eprintln!("{}: rule `{code}` not found", "error".red().bold());
+                process::exit(2);
+            }
+        }
+    }
 
     // ── no paths → show animated welcome screen ───────────────────────────────
-    if cli.paths.is_empty() {
+    if cli.paths.is_empty() && cli.paths_from.is_none() && !cli.pre_commit {
         banner::show_welcome();
         return;
     }
+    let mut roots = cli.paths.clone();
+    if let Some(ref list) = cli.paths_from {
+        match read_path_list(list) {
+            Ok(listed) => roots.extend(listed),
+            Err(e) => {
+                eprintln!("{}: {}: {e}", "error".red().bold(), list.display());
+                process::exit(2);
+            }
+        }
+    } else if roots.is_empty() {
+        roots.push(PathBuf::from("."));
+    }
+
+    // ── reaper.toml / pyproject.toml: defaults for flags not given on the CLI ──
+    if let Some(path) = roots.first().and_then(|root| config::find_config(root)) {
+        match config::Config::load(&path) {
+            Ok(file) => {
+                cli.select = cli.select.or(file.select);
+                cli.exclude = cli.exclude.or(file.exclude);
+                cli.no_exit_code |= file.no_exit_code.unwrap_or(false);
+                cli.ignore_names = cli.ignore_names.or(file.ignore_names);
+                cli.python_version = cli.python_version.or(file.python_version);
+                cli.include = cli.include.or(file.include);
+                cli.framework = cli.framework.or(file.framework);
+                cli.framework_exemptions = cli.framework_exemptions.or(file.framework_exemptions);
+                cli.always_used = cli.always_used.or(file.always_used);
+                cli.exempt_dunders = cli.exempt_dunders.or(file.exempt_dunders);
+            }
+            Err(e) => {
+                eprintln!("{}: {e:#}", "error".red().bold());
+                process::exit(2);
+            }
+        }
+    }
 
-    let exclude: Vec<String> = cli.exclude.unwrap_or_default();
+    let discovery_options = discovery::DiscoveryOptions {
+        exclude: cli.exclude.clone().unwrap_or_default(),
+        include_hidden: cli.include_hidden,
+        extensions: cli.extensions.clone().unwrap_or_else(|| {
+            discovery::DEFAULT_EXTENSIONS
+                .iter()
+                .map(|e| e.to_string())
+                .collect()
+        }),
+        include: cli.include.clone().unwrap_or_default(),
+    };
 
     // ── file discovery ────────────────────────────────────────────────────────
     let mut files = Vec::new();
-    for path in &cli.paths {
+    for path in &roots {
         if path.is_file() {
             files.push(path.clone());
         } else {
-            match discovery::discover_python_files(path, &exclude) {
+            match discovery::discover_with_options(path, &discovery_options) {
                 Ok(found) => files.extend(found),
                 Err(e) => {
                     eprintln!("{}: {e}", "error".red().bold());
@@ -78,67 +455,641 @@ fn main() {
     }
 
     // ── analysis ──────────────────────────────────────────────────────────────
-    let mut diagnostics = match analyze::analyze_files(&files) {
-        Ok(d) => d,
+    let mut lenient = config::LenientHeuristics::default();
+    for h in cli.lenient.iter().flatten() {
+        match h {
+            Heuristic::Callbacks => lenient.callbacks = true,
+            Heuristic::Meta => lenient.meta_classes = true,
+            Heuristic::Loggers => lenient.loggers = true,
+        }
+    }
+    let defaults = config::ReaperConfig::default();
+    let config = config::ReaperConfig {
+        framework_exemptions: cli
+            .framework_exemptions
+            .clone()
+            .unwrap_or(defaults.framework_exemptions),
+        framework: cli.framework.clone(),
+        always_used: cli.always_used.clone().unwrap_or_default(),
+        exempt_dunders: cli
+            .exempt_dunders
+            .clone()
+            .unwrap_or(defaults.exempt_dunders),
+        respect_type_ignore: cli.respect_type_ignore,
+        lenient,
+        strict: cli.strict,
+        ignore_names: cli.ignore_names.clone().unwrap_or_default(),
+        python_version: cli.python_version.or_else(python::detect_version),
+        cache_path: if cli.no_cache {
+            None
+        } else {
+            cache::default_path()
+        },
+    };
+    let (mut diagnostics, total_lines) = match analyze::analyze_report(&files, &config) {
+        Ok(report) => (report.diagnostics, report.total_lines),
         Err(e) => {
             eprintln!("{}: {e}", "error".red().bold());
             process::exit(2);
         }
     };
 
+    // ── --pre-commit / --changed-only: report on those files, analyse all ───
+    // Every discovered file is analysed so the cross-file RP003/RP004/RP014
+    // pass still sees usages in files that are not staged or changed.
+    if cli.pre_commit {
+        let staged = git::staged_files(Path::new("."), &discovery_options.extensions);
+        retain_in_files(&mut diagnostics, &files, staged);
+    }
+    if let Some(ref git_ref) = cli.changed_only {
+        let changed = git::changed_files(Path::new("."), git_ref, &discovery_options.extensions);
+        retain_in_files(&mut diagnostics, &files, changed);
+    }
+
+    // ── path normalisation ────────────────────────────────────────────────────
+    let path_style = if cli.absolute_paths {
+        paths::PathStyle::Absolute
+    } else {
+        paths::PathStyle::Relative
+    };
+    let cwd = std::env::current_dir().unwrap_or_default();
+    for d in &mut diagnostics {
+        d.file = paths::normalize_path(&d.file, path_style, &cwd);
+    }
+
     // ── filter by --select ────────────────────────────────────────────────────
     if let Some(ref selected) = cli.select {
         diagnostics.retain(|d| selected.contains(&d.code.to_string()));
     }
+    if let Some(ref categories) = cli.select_category {
+        diagnostics.retain(|d| categories.contains(&d.code.category()));
+    }
+
+    // ── --diff: show what --fix would change, write nothing ──────────────────
+    if cli.diff {
+        let fixable = print_fix_diffs(&diagnostics);
+        eprintln!("Would fix {fixable} issue(s)");
+        if fixable > 0 && (cli.pre_commit || !cli.no_exit_code) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    // ── --fix: rewrite files, keep only what could not be fixed ──────────────
+    let fixed = if cli.fix || cli.fix_only {
+        apply_fixes_in_place(&mut diagnostics)
+    } else {
+        0
+    };
+    if cli.fix_only {
+        // Fixable diagnostics left behind (overlapping fixes) were already
+        // warned about; only issues without any fix are reported.
+        diagnostics.retain(|d| d.fix.is_none());
+    }
 
-    // ── sort: file → line → col ───────────────────────────────────────────────
+    // ── sort (default: file → line → col) ─────────────────────────────────────
     diagnostics.sort_by(|a, b| {
-        a.file
+        let by_location = a
+            .file
             .cmp(&b.file)
             .then(a.line.cmp(&b.line))
-            .then(a.col.cmp(&b.col))
+            .then(a.col.cmp(&b.col));
+        match cli.sort_by {
+            SortBy::File => by_location,
+            SortBy::Rule => a
+                .code
+                .to_string()
+                .cmp(&b.code.to_string())
+                .then(by_location),
+            SortBy::Severity => a.code.severity().cmp(&b.code.severity()).then(by_location),
+        }
     });
 
+    // ── --count: the number alone ─────────────────────────────────────────────
+    if cli.count {
+        let count = diagnostics.len();
+        if matches!(cli.output, OutputFormat::Json) {
+            println!("{}", json!({ "count": count }));
+        } else {
+            println!("{count}");
+        }
+        exit_on_findings(&cli, &diagnostics, 0);
+        return;
+    }
+
     // ── output ────────────────────────────────────────────────────────────────
-    if cli.json {
-        print_json(&diagnostics);
-    } else {
-        for d in &diagnostics {
-            println!("{d}");
+    let per_file = cli.per_file_summary.then(|| per_file_summary(&diagnostics));
+    let fixable = cli
+        .show_fixes_count
+        .then(|| diagnostics.iter().filter(|d| d.fix.is_some()).count());
+    let lines = cli.statistics.then_some(total_lines);
+
+    // ── --max-issues: keep the first N, after the summaries above ─────────────
+    let hidden = match cli.max_issues {
+        Some(max) if diagnostics.len() > max => {
+            let hidden = diagnostics.len() - max;
+            diagnostics.truncate(max);
+            hidden
         }
-        if diagnostics.is_empty() {
-            println!("{}", "No issues found".green());
-        } else {
-            let count = diagnostics.len();
-            println!("{}", format!("Found {count} issue(s)").yellow().bold());
+        _ => 0,
+    };
+    let more = format!("... and {hidden} more");
+
+    match cli.output {
+        OutputFormat::Json => print_json(
+            &diagnostics,
+            hidden,
+            per_file.as_deref(),
+            fixable,
+            lines,
+            cli.group_by_file,
+            cli.show_bytes,
+        ),
+        OutputFormat::Sarif => {
+            let artifacts: Vec<String> = files
+                .iter()
+                .map(|f| paths::normalize_path(&f.to_string_lossy(), path_style, &cwd))
+                .collect();
+            print_sarif(&diagnostics, &artifacts);
+        }
+        OutputFormat::Github => print_github_annotations(&diagnostics),
+        OutputFormat::Text => {
+            let hyperlinks = match cli.hyperlinks {
+                Some(Hyperlinks::Always) => true,
+                Some(Hyperlinks::Auto) => {
+                    std::io::stdout().is_terminal()
+                        && hyperlink::terminal_supports_hyperlinks(|v| std::env::var(v).ok())
+                }
+                Some(Hyperlinks::Never) | None => false,
+            };
+            let print_diagnostic = |d: &types::Diagnostic, location: String| {
+                let location = if hyperlinks {
+                    let uri = hyperlink::file_uri(&d.file, d.line, &cwd);
+                    hyperlink::osc8(&uri, &location)
+                } else {
+                    location
+                };
+                let mut line = format!("{location}: {} {}", d.code, d.message);
+                if cli.show_urls {
+                    line.push_str(&format!(" [{}: {}]", d.code, d.code.url()));
+                }
+                if cli.show_fixes && d.fix.is_some() {
+                    println!("{line} {}", "[fix available]".cyan());
+                } else {
+                    println!("{line}");
+                }
+            };
+            if cli.group_by_file {
+                let groups = output::grouped::group_by_file(&diagnostics);
+                for (i, group) in groups.iter().enumerate() {
+                    if i > 0 {
+                        println!();
+                    }
+                    let header = output::grouped::header(group.file, group.diagnostics.len());
+                    println!("{}", header.bold());
+                    for d in &group.diagnostics {
+                        print_diagnostic(d, format!("{}:{}", d.line, d.col));
+                    }
+                }
+            } else {
+                for d in &diagnostics {
+                    print_diagnostic(d, format!("{}:{}:{}", d.file, d.line, d.col));
+                }
+            }
+            if hidden > 0 {
+                println!("{more}");
+            }
+            if let Some(rows) = per_file.as_deref().filter(|rows| !rows.is_empty()) {
+                println!();
+                print_per_file_summary(rows);
+            }
+            if fixed > 0 {
+                println!("{}", format!("Fixed {fixed} issue(s)").green());
+            }
+            if diagnostics.is_empty() {
+                println!("{}", "No issues found".green());
+            } else {
+                let count = diagnostics.len() + hidden;
+                let summary = match fixable {
+                    Some(n) => format!("Found {count} issue(s) ({n} auto-fixable)"),
+                    None => format!("Found {count} issue(s)"),
+                };
+                println!("{}", summary.yellow().bold());
+            }
+            if let Some(total_lines) = lines {
+                println!(
+                    "{}",
+                    output::stats::summary(diagnostics.len() + hidden, total_lines).bold()
+                );
+            }
+        }
+    }
+
+    // Machine-readable output stays parseable; the note goes to stderr.
+    if hidden > 0 && !matches!(cli.output, OutputFormat::Text) {
+        eprintln!("{more}");
+    }
+
+    exit_on_findings(&cli, &diagnostics, hidden);
+}
+
+/// Keep only the diagnostics in those of `files` that git listed in
+/// `touched`; exit 2 when the git query failed.
+fn retain_in_files(
+    diagnostics: &mut Vec<types::Diagnostic>,
+    files: &[PathBuf],
+    touched: anyhow::Result<Vec<PathBuf>>,
+) {
+    match touched {
+        Ok(touched) => {
+            let keep: HashSet<String> = git::restrict_to(files.to_vec(), &touched)
+                .iter()
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect();
+            diagnostics.retain(|d| keep.contains(&d.file));
+        }
+        Err(e) => {
+            eprintln!("{}: {e}", "error".red().bold());
+            process::exit(2);
         }
     }
+}
 
-    // ── exit code ─────────────────────────────────────────────────────────────
-    if !cli.no_exit_code && !diagnostics.is_empty() {
+/// Exit with code 2 when `--max-issues` left `hidden` diagnostics out, or 1
+/// when a reported one is at least as severe as `--exit-code-on-severity`;
+/// return otherwise.  `--no-exit-code` always returns, unless `--pre-commit`.
+fn exit_on_findings(cli: &Cli, diagnostics: &[types::Diagnostic], hidden: usize) {
+    if cli.no_exit_code && !cli.pre_commit {
+        return;
+    }
+    if hidden > 0 {
+        process::exit(2);
+    }
+    let threshold = cli.exit_code_on_severity.unwrap_or(types::Severity::Info);
+    if diagnostics.iter().any(|d| d.code.severity() <= threshold) {
         process::exit(1);
     }
 }
 
-/// Emit valid, well-formatted JSON using serde_json.
-fn print_json(diagnostics: &[types::Diagnostic]) {
-    let items: Vec<serde_json::Value> = diagnostics
+/// Parse the `--python-version` value.
+fn parse_python_version(text: &str) -> Result<(u32, u32), String> {
+    python::parse_target(text).ok_or_else(|| "expected MAJOR.MINOR, e.g. 3.11".to_string())
+}
+
+/// Read a `--paths-from` list from `path`, or from stdin when it is `-`.
+fn read_path_list(path: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let content = if path == Path::new("-") {
+        let mut buf = String::new();
+        std::io::stdin().read_to_string(&mut buf)?;
+        buf
+    } else {
+        fs::read_to_string(path)?
+    };
+    Ok(paths::parse_path_list(&content))
+}
+
+/// The `--explain` entry for one rule: name, severity, documentation link,
+/// description and example.
+fn print_explanation(rule: &types::RuleCode) {
+    println!("{} — {}", rule.to_string().bold(), rule.title());
+    println!("Severity: {}", rule.severity());
+    println!("Docs:     {}", rule.url().cyan().underline());
+    println!();
+    println!("{}", rule.description());
+    println!();
+    print!("{}", rule.example());
+}
+
+/// `--list-rules`: one aligned row per rule.
+fn print_rule_table() {
+    let name_width = types::RuleCode::ALL
+        .iter()
+        .map(|r| r.title().chars().count())
+        .max()
+        .unwrap_or(0);
+    println!(
+        "{}",
+        format!(
+            "{:<5}  {:<name_width$}  {:<8}  DESCRIPTION",
+            "CODE", "NAME", "SEVERITY"
+        )
+        .bold()
+    );
+    for rule in types::RuleCode::ALL {
+        println!(
+            "{}  {:<name_width$}  {:<8}  {}",
+            rule.to_string().bold(),
+            rule.title(),
+            rule.severity().to_string(),
+            rule.description()
+        );
+    }
+}
+
+/// Apply every diagnostic's fix to its file on disk and drop the diagnostics
+/// that were fixed.  Overlapping fixes are skipped with a warning and their
+/// diagnostics stay reported.  Returns the number of diagnostics fixed.
+fn apply_fixes_in_place(diagnostics: &mut Vec<types::Diagnostic>) -> usize {
+    let mut applied: HashSet<(String, fix::Fix)> = HashSet::new();
+    for planned in plan_fixes(diagnostics) {
+        let result = planned
+            .source
+            .encode(&planned.fixed)
+            .and_then(|bytes| Ok(fs::write(&planned.file, bytes)?));
+        match result {
+            Ok(()) => {
+                let file = planned.file;
+                applied.extend(planned.fixes.into_iter().map(|f| (file.clone(), f)));
+            }
+            Err(e) => eprintln!("{}: {}: {e}", "warning".yellow().bold(), planned.file),
+        }
+    }
+
+    let before = diagnostics.len();
+    diagnostics.retain(|d| {
+        d.fix
+            .as_ref()
+            .is_none_or(|f| !applied.contains(&(d.file.clone(), f.clone())))
+    });
+    before - diagnostics.len()
+}
+
+/// A file's source, what it becomes once `fixes` are applied, and the fixes.
+struct PlannedFix {
+    file: String,
+    source: encoding::Source,
+    fixed: String,
+    fixes: Vec<fix::Fix>,
+}
+
+/// The edit `--fix` makes to each file, sorted by path.  Overlapping fixes
+/// and files whose result fails the sanity check are skipped with a warning.
+fn plan_fixes(diagnostics: &[types::Diagnostic]) -> Vec<PlannedFix> {
+    let mut by_file: BTreeMap<String, Vec<fix::Fix>> = BTreeMap::new();
+    for d in diagnostics {
+        if let Some(f) = &d.fix {
+            by_file.entry(d.file.clone()).or_default().push(f.clone());
+        }
+    }
+
+    let mut planned = Vec::new();
+    for (file, fixes) in by_file {
+        let (clean, conflicts) = fix::apply::partition_conflicts(fixes);
+        for c in &conflicts {
+            eprintln!("{}: {file}: {c}; skipping both", "warning".yellow().bold());
+        }
+        if clean.is_empty() {
+            continue;
+        }
+        // Every fixed diagnostic removes one imported name; a file that would
+        // lose more (say, a name whose report was suppressed) is left alone.
+        let expected = diagnostics
+            .iter()
+            .filter(|d| d.file == file && d.fix.as_ref().is_some_and(|f| clean.contains(f)))
+            .count();
+        let result = encoding::Source::read(Path::new(&file)).and_then(|source| {
+            let fixed = fix::apply::apply_fixes(&source.text, clean.clone())?;
+            let removed = fix::count_imported_names(&source.text)
+                .saturating_sub(fix::count_imported_names(&fixed));
+            if removed != expected {
+                anyhow::bail!(
+                    "fixes would remove {removed} import(s), expected {expected}; skipping file"
+                );
+            }
+            Ok((source, fixed))
+        });
+        match result {
+            Ok((source, fixed)) => planned.push(PlannedFix {
+                file,
+                source,
+                fixed,
+                fixes: clean,
+            }),
+            Err(e) => eprintln!("{}: {file}: {e}", "warning".yellow().bold()),
+        }
+    }
+    planned
+}
+
+/// `--diff`: print the edit `--fix` would make to each file as a unified
+/// diff, coloured on a terminal.  Returns how many diagnostics it fixes.
+fn print_fix_diffs(diagnostics: &[types::Diagnostic]) -> usize {
+    let color = std::io::stdout().is_terminal();
+    let mut fixed = 0;
+    for planned in plan_fixes(diagnostics) {
+        let diff = fix::diff::generate_diff(&planned.source.text, &planned.fixed, &planned.file);
+        for line in diff.lines() {
+            if !color || line.starts_with("---") || line.starts_with("+++") {
+                println!("{line}");
+            } else if line.starts_with('-') {
+                println!("{}", line.red());
+            } else if line.starts_with('+') {
+                println!("{}", line.green());
+            } else if line.starts_with("@@") {
+                println!("{}", line.cyan());
+            } else {
+                println!("{line}");
+            }
+        }
+        fixed += diagnostics
+            .iter()
+            .filter(|d| {
+                d.file == planned.file && d.fix.as_ref().is_some_and(|f| planned.fixes.contains(f))
+            })
+            .count();
+    }
+    fixed
+}
+
+/// One row of `--per-file-summary`: a file's diagnostic count, by rule.
+struct FileSummary {
+    file: String,
+    count: usize,
+    by_rule: BTreeMap<String, usize>,
+}
+
+/// Group `diagnostics` by file, most issues first (ties broken by path).
+fn per_file_summary(diagnostics: &[types::Diagnostic]) -> Vec<FileSummary> {
+    let mut by_file: HashMap<&str, BTreeMap<String, usize>> = HashMap::new();
+    for d in diagnostics {
+        *by_file
+            .entry(&d.file)
+            .or_default()
+            .entry(d.code.to_string())
+            .or_default() += 1;
+    }
+    let mut rows: Vec<FileSummary> = by_file
+        .into_iter()
+        .map(|(file, by_rule)| FileSummary {
+            file: file.to_string(),
+            count: by_rule.values().sum(),
+            by_rule,
+        })
+        .collect();
+    rows.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.file.cmp(&b.file)));
+    rows
+}
+
+/// `file.py   4 issue(s) (RP001:2, RP005:1, RP008:1)`, paths left-aligned.
+fn print_per_file_summary(rows: &[FileSummary]) {
+    let width = rows.iter().map(|r| r.file.len()).max().unwrap_or(0);
+    for r in rows {
+        let rules: Vec<String> = r
+            .by_rule
+            .iter()
+            .map(|(code, n)| format!("{code}:{n}"))
+            .collect();
+        println!(
+            "{:<width$}  {} issue(s) ({})",
+            r.file,
+            r.count,
+            rules.join(", ")
+        );
+    }
+}
+
+/// Emit a SARIF 2.1.0 log: one run whose driver lists every rule, one result
+/// per diagnostic, and the analysed `files` as artifacts.
+fn print_sarif(diagnostics: &[types::Diagnostic], files: &[String]) {
+    let level = |rule: &types::RuleCode| match rule.severity() {
+        types::Severity::Error => "error",
+        types::Severity::Warning => "warning",
+        types::Severity::Info => "note",
+    };
+    let rules: Vec<serde_json::Value> = types::RuleCode::ALL
+        .iter()
+        .map(|rule| {
+            json!({
+                "id":                   rule.to_string(),
+                "name":                 rule.title(),
+                "shortDescription":     { "text": rule.title() },
+                "helpUri":              rule.url(),
+                "defaultConfiguration": { "level": level(rule) },
+            })
+        })
+        .collect();
+    let results: Vec<serde_json::Value> = diagnostics
         .iter()
         .map(|d| {
             json!({
-                "file":    d.file,
-                "line":    d.line,
-                "col":     d.col,
-                "code":    d.code.to_string(),
-                "message": d.message,
+                "ruleId":    d.code.to_string(),
+                "ruleIndex": types::RuleCode::ALL.iter().position(|r| *r == d.code),
+                "level":     level(&d.code),
+                "message":   { "text": d.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": paths::to_forward_slashes(&d.file) },
+                        "region": { "startLine": d.line, "startColumn": d.col },
+                    },
+                }],
             })
         })
         .collect();
+    let artifacts: Vec<serde_json::Value> = files
+        .iter()
+        .map(|f| json!({ "location": { "uri": paths::to_forward_slashes(f) } }))
+        .collect();
 
     let output = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name":           "reaper",
+                    "version":        env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/taradepan/reaper",
+                    "rules":          rules,
+                },
+            },
+            "artifacts": artifacts,
+            "results":   results,
+        }],
+    });
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&output).expect("serde_json::Value is always serialisable")
+    );
+}
+
+/// Emit one GitHub Actions annotation command per diagnostic.
+fn print_github_annotations(diagnostics: &[types::Diagnostic]) {
+    for d in diagnostics {
+        println!("{}", output::github::annotation(d));
+    }
+}
+
+/// Emit valid, well-formatted JSON using serde_json.
+fn print_json(
+    diagnostics: &[types::Diagnostic],
+    hidden: usize,
+    per_file: Option<&[FileSummary]>,
+    fixable: Option<usize>,
+    total_lines: Option<usize>,
+    group_by_file: bool,
+    show_bytes: bool,
+) {
+    let item = |d: &types::Diagnostic| {
+        let mut item = json!({
+            "file":    paths::to_forward_slashes(&d.file),
+            "line":    d.line,
+            "col":     d.col,
+            "code":    d.code.to_string(),
+            "message": d.message,
+            "url":     d.code.url(),
+        });
+        if show_bytes {
+            item["start_byte"] = d.start_byte.into();
+            item["end_byte"] = d.end_byte.into();
+        }
+        if let Some(fix) = &d.fix {
+            item["fix"] = json!({
+                "start":       fix.start,
+                "end":         fix.end,
+                "replacement": fix.replacement,
+            });
+        }
+        item
+    };
+    let items: serde_json::Value = if group_by_file {
+        output::grouped::group_by_file(diagnostics)
+            .into_iter()
+            .map(|g| {
+                let items: Vec<serde_json::Value> = g.diagnostics.into_iter().map(item).collect();
+                (paths::to_forward_slashes(g.file), items.into())
+            })
+            .collect::<serde_json::Map<String, serde_json::Value>>()
+            .into()
+    } else {
+        diagnostics.iter().map(item).collect()
+    };
+
+    // `count` and `health_score` cover the diagnostics --max-issues left out.
+    let count = diagnostics.len() + hidden;
+    let mut output = json!({
         "diagnostics": items,
-        "count":       diagnostics.len(),
+        "count":       count,
     });
+    if hidden > 0 {
+        output["hidden"] = hidden.into();
+    }
+    if let Some(rows) = per_file {
+        let per_file: serde_json::Map<String, serde_json::Value> = rows
+            .iter()
+            .map(|r| {
+                let entry = json!({ "count": r.count, "by_rule": r.by_rule });
+                (paths::to_forward_slashes(&r.file), entry)
+            })
+            .collect();
+        output["per_file"] = per_file.into();
+    }
+    if let Some(n) = fixable {
+        output["fixable_count"] = n.into();
+    }
+    if let Some(lines) = total_lines {
+        output["health_score"] = output::stats::health_score(count, lines).into();
+        output["total_lines"] = lines.into();
+    }
 
     println!(
         "{}",
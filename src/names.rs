@@ -3,7 +3,8 @@
 //! These replace the old `names.rs` functions that depended on
 //! `rustpython_parser::ast`.  All functions operate on `crate::ast` types.
 
-use crate::ast::{AssignTarget, ExprInfo, ExprKind, Stmt, StmtKind};
+use crate::ast::{AssignTarget, ExprInfo, ExprKind, Offset, Stmt, StmtKind};
+use crate::fast_parser::lexer::{Lexer, Token};
 use std::collections::{HashMap, HashSet};
 
 // ── Public helpers ────────────────────────────────────────────────────────────
@@ -14,8 +15,19 @@ use std::collections::{HashMap, HashSet};
 /// does NOT add function/class definition names themselves — those are
 /// definitions, not usages.
 pub fn collect_stmt_names<'src>(stmts: &[Stmt<'src>], out: &mut HashSet<String>) {
+    collect_names(stmts, out, true);
+}
+
+/// Like [`collect_stmt_names`], but skipping parameter, return and variable
+/// annotations: only the names Python evaluates when the code runs (under
+/// PEP 563 semantics).
+pub fn collect_runtime_names<'src>(stmts: &[Stmt<'src>], out: &mut HashSet<String>) {
+    collect_names(stmts, out, false);
+}
+
+fn collect_names(stmts: &[Stmt<'_>], out: &mut HashSet<String>, annotations: bool) {
     for stmt in stmts {
-        collect_stmt_names_one(stmt, out);
+        collect_stmt_names_one(stmt, out, annotations);
     }
 }
 
@@ -25,7 +37,7 @@ fn collect_expr_names_into(info: &ExprInfo<'_>, out: &mut HashSet<String>) {
     }
 }
 
-fn collect_stmt_names_one(stmt: &Stmt<'_>, out: &mut HashSet<String>) {
+fn collect_stmt_names_one(stmt: &Stmt<'_>, out: &mut HashSet<String>, annotations: bool) {
     match &stmt.kind {
         StmtKind::Import(_) | StmtKind::ImportFrom { .. } => {
             // Import statements themselves are not "usages".
@@ -35,10 +47,13 @@ fn collect_stmt_names_one(stmt: &Stmt<'_>, out: &mut HashSet<String>) {
             for dec in &f.decorators {
                 collect_expr_names_into(dec, out);
             }
-            if let Some(ret) = &f.returns {
+            if let Some(ret) = &f.returns
+                && annotations
+            {
                 collect_expr_names_into(ret, out);
             }
-            // Argument annotations are usages — includes *args and **kwargs.
+            // Argument annotations and defaults are usages — includes *args
+            // and **kwargs.
             for arg in f
                 .args
                 .posonlyargs
@@ -48,11 +63,16 @@ fn collect_stmt_names_one(stmt: &Stmt<'_>, out: &mut HashSet<String>) {
                 .chain(f.args.kwonlyargs.iter())
                 .chain(f.args.kwarg.as_ref())
             {
-                if let Some(ann) = &arg.annotation {
+                if let Some(ann) = &arg.annotation
+                    && annotations
+                {
                     collect_expr_names_into(ann, out);
                 }
+                if let Some(default) = &arg.default {
+                    collect_expr_names_into(default, out);
+                }
             }
-            collect_stmt_names(&f.body, out);
+            collect_names(&f.body, out, annotations);
         }
         StmtKind::ClassDef(c) => {
             for dec in &c.decorators {
@@ -61,7 +81,7 @@ fn collect_stmt_names_one(stmt: &Stmt<'_>, out: &mut HashSet<String>) {
             for base in &c.bases {
                 collect_expr_names_into(base, out);
             }
-            collect_stmt_names(&c.body, out);
+            collect_names(&c.body, out, annotations);
         }
         StmtKind::Assign { targets, value } => {
             collect_expr_names_into(value, out);
@@ -82,7 +102,9 @@ fn collect_stmt_names_one(stmt: &Stmt<'_>, out: &mut HashSet<String>) {
             annotation,
             value,
         } => {
-            collect_expr_names_into(annotation, out);
+            if annotations {
+                collect_expr_names_into(annotation, out);
+            }
             if let Some(v) = value {
                 collect_expr_names_into(v, out);
                 for (n, _) in &v.walrus {
@@ -93,6 +115,10 @@ fn collect_stmt_names_one(stmt: &Stmt<'_>, out: &mut HashSet<String>) {
         StmtKind::AugAssign { target: _, value } => {
             collect_expr_names_into(value, out);
         }
+        // The value is evaluated lazily, but its names are still references.
+        StmtKind::TypeAlias { value, .. } => {
+            collect_expr_names_into(value, out);
+        }
         StmtKind::For {
             target: _,
             iter,
@@ -104,24 +130,24 @@ fn collect_stmt_names_one(stmt: &Stmt<'_>, out: &mut HashSet<String>) {
             for (n, _) in &iter.walrus {
                 out.insert(n.to_string());
             }
-            collect_stmt_names(body, out);
-            collect_stmt_names(orelse, out);
+            collect_names(body, out, annotations);
+            collect_names(orelse, out, annotations);
         }
         StmtKind::While { test, body, orelse } => {
             collect_expr_names_into(test, out);
             for (n, _) in &test.walrus {
                 out.insert(n.to_string());
             }
-            collect_stmt_names(body, out);
-            collect_stmt_names(orelse, out);
+            collect_names(body, out, annotations);
+            collect_names(orelse, out, annotations);
         }
         StmtKind::If { test, body, orelse } => {
             collect_expr_names_into(test, out);
             for (n, _) in &test.walrus {
                 out.insert(n.to_string());
             }
-            collect_stmt_names(body, out);
-            collect_stmt_names(orelse, out);
+            collect_names(body, out, annotations);
+            collect_names(orelse, out, annotations);
         }
         StmtKind::Return(v) => {
             if let Some(v) = v {
@@ -143,7 +169,7 @@ fn collect_stmt_names_one(stmt: &Stmt<'_>, out: &mut HashSet<String>) {
             for item in items {
                 collect_expr_names_into(&item.context, out);
             }
-            collect_stmt_names(body, out);
+            collect_names(body, out, annotations);
         }
         StmtKind::Try {
             body,
@@ -151,15 +177,15 @@ fn collect_stmt_names_one(stmt: &Stmt<'_>, out: &mut HashSet<String>) {
             orelse,
             finalbody,
         } => {
-            collect_stmt_names(body, out);
+            collect_names(body, out, annotations);
             for h in handlers {
                 if let Some(te) = &h.type_expr {
                     collect_expr_names_into(te, out);
                 }
-                collect_stmt_names(&h.body, out);
+                collect_names(&h.body, out, annotations);
             }
-            collect_stmt_names(orelse, out);
-            collect_stmt_names(finalbody, out);
+            collect_names(orelse, out, annotations);
+            collect_names(finalbody, out, annotations);
         }
         StmtKind::Match { subject, arms } => {
             collect_expr_names_into(subject, out);
@@ -167,7 +193,7 @@ fn collect_stmt_names_one(stmt: &Stmt<'_>, out: &mut HashSet<String>) {
                 for (n, _) in &arm.pattern_names {
                     out.insert(n.to_string());
                 }
-                collect_stmt_names(&arm.body, out);
+                collect_names(&arm.body, out, annotations);
             }
         }
         StmtKind::Delete(targets) => {
@@ -204,52 +230,345 @@ fn collect_stmt_names_one(stmt: &Stmt<'_>, out: &mut HashSet<String>) {
     }
 }
 
+// ── Decorators ────────────────────────────────────────────────────────────────
+
+/// The identifier a decorator resolves to: `route` for `@route`,
+/// `@app.route` and `@app.route("/")`.  `None` for anything more exotic.
+pub fn decorator_name<'src>(info: &ExprInfo<'src>) -> Option<&'src str> {
+    match info.kind {
+        ExprKind::Name(n, _) | ExprKind::Call { func: n, .. } => Some(n),
+        ExprKind::Attr(_, attr) if !attr.is_empty() => Some(attr),
+        _ => None,
+    }
+}
+
+/// `@pytest.fixture`, `@fixture(scope=…)`, `@pytest.mark.*` — decorators that
+/// hand a function to pytest, which calls it and injects its parameters by name.
+pub fn is_pytest_decorator(info: &ExprInfo<'_>) -> bool {
+    matches!(
+        info.kind,
+        ExprKind::Name("fixture", _)
+            | ExprKind::Call {
+                func: "fixture",
+                ..
+            }
+            | ExprKind::Attr(_, "fixture")
+    ) || info.names.first().is_some_and(|&(n, _)| n == "pytest")
+}
+
 // ── __all__ extraction ────────────────────────────────────────────────────────
 
 /// Extract the names listed in `__all__`.
 ///
-/// Recognises:
+/// Recognises, at module level:
 /// - `__all__ = ["a", "b"]`
 /// - `__all__ = ("a", "b")`
 /// - `__all__ += ["a"]`
+/// - `__all__.extend(["a"])`
+/// - `__all__.append("a")`
 ///
-/// Returns an empty `Vec` if `__all__` is absent or in a form we can't analyse
-/// statically.
+/// Names from every such statement are merged.  Returns an empty `Vec` if
+/// `__all__` is absent or in a form we can't analyse statically.
 pub fn collect_dunder_all(stmts: &[Stmt<'_>]) -> Vec<String> {
+    dunder_all_values(stmts)
+        .flat_map(|(value, _, _)| extract_str_list_from_expr(value))
+        .collect()
+}
+
+/// The value of each top-level statement that builds `__all__` (see
+/// [`collect_dunder_all`]), with the statement's offset and whether it
+/// replaces `__all__` outright (`=`) rather than adding to it.
+fn dunder_all_values<'a, 'src>(
+    stmts: &'a [Stmt<'src>],
+) -> impl Iterator<Item = (&'a ExprInfo<'src>, Offset, bool)> {
+    stmts.iter().filter_map(|stmt| match &stmt.kind {
+        StmtKind::Assign { targets, value }
+            if targets
+                .iter()
+                .any(|t| matches!(t, AssignTarget::Name("__all__", _))) =>
+        {
+            Some((value, stmt.offset, true))
+        }
+        StmtKind::AugAssign {
+            target: AssignTarget::Name("__all__", _),
+            value,
+        } => Some((value, stmt.offset, false)),
+        StmtKind::Expr(info)
+            if matches!(info.kind, ExprKind::Attr("__all__", "extend" | "append")) =>
+        {
+            Some((info, stmt.offset, false))
+        }
+        _ => None,
+    })
+}
+
+fn extract_str_list_from_expr(info: &ExprInfo<'_>) -> Vec<String> {
+    extract_str_entries_from_expr(info, 0)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Like [`extract_str_list_from_expr`] but keeps the offset of each literal.
+/// `fallback` is used for the single-string form, which has no inner literal.
+fn extract_str_entries_from_expr(info: &ExprInfo<'_>, fallback: Offset) -> Vec<(String, Offset)> {
+    // Single-string case: `__all__ = "foo"` → ExprKind::StringLit.
+    if let ExprKind::StringLit(s) = &info.kind {
+        return vec![(s.clone(), fallback)];
+    }
+    // List/tuple case: `__all__ = ["foo", "bar"]` or `("foo", "bar")`.
+    // The parser now populates ExprInfo::string_list with every string literal
+    // found inside bracket pairs, so we can return it directly.
+    info.string_list.clone()
+}
+
+/// Every `__all__` entry in the module with the offset of its string literal.
+///
+/// Entries appear in source order, repeats included.
+pub fn collect_dunder_all_entries(stmts: &[Stmt<'_>]) -> Vec<(String, Offset)> {
+    dunder_all_values(stmts)
+        .flat_map(|(value, offset, _)| extract_str_entries_from_expr(value, offset))
+        .collect()
+}
+
+/// Like [`collect_dunder_all_entries`] but grouped by statement, each group
+/// flagged `true` when its statement reassigns `__all__` outright and so
+/// discards the entries before it.
+pub fn collect_dunder_all_statements(stmts: &[Stmt<'_>]) -> Vec<(bool, Vec<(String, Offset)>)> {
+    dunder_all_values(stmts)
+        .map(|(value, offset, replaces)| (replaces, extract_str_entries_from_expr(value, offset)))
+        .collect()
+}
+
+// ── Django urlpatterns ────────────────────────────────────────────────────────
+
+/// URL-building helpers whose own names appear in `urlpatterns` but are not
+/// view callables.
+const URLCONF_HELPERS: &[&str] = &["path", "re_path", "url", "include"];
+
+/// Collect names referenced inside top-level `urlpatterns = [...]` (and
+/// `urlpatterns += [...]`) assignments, minus the `path()` / `re_path()`
+/// helpers themselves.  What remains are the view callables the URL
+/// dispatcher will invoke, e.g. `index` in `path("", index)`.
+pub fn collect_urlpatterns_views<'src>(stmts: &[Stmt<'src>]) -> HashSet<&'src str> {
+    let mut out = HashSet::new();
     for stmt in stmts {
-        match &stmt.kind {
-            StmtKind::Assign { targets, value } => {
+        let value = match &stmt.kind {
+            StmtKind::Assign { targets, value }
                 if targets
                     .iter()
-                    .any(|t| matches!(t, AssignTarget::Name("__all__", _)))
-                {
-                    return extract_str_list_from_expr(value);
-                }
+                    .any(|t| matches!(t, AssignTarget::Name("urlpatterns", _))) =>
+            {
+                value
             }
             StmtKind::AugAssign {
-                target: AssignTarget::Name("__all__", _),
+                target: AssignTarget::Name("urlpatterns", _),
                 value,
+            } => value,
+            _ => continue,
+        };
+        out.extend(
+            value
+                .names
+                .iter()
+                .map(|&(n, _)| n)
+                .filter(|n| !URLCONF_HELPERS.contains(n)),
+        );
+    }
+    out
+}
+
+// ── Module bindings ───────────────────────────────────────────────────────────
+
+/// Collect every name bound at module scope: function and class definitions,
+/// assignment / `for` / `with` / `except` targets, walrus targets and import
+/// local names.  Descends into module-level compound statements (`if`, `try`,
+/// …) but not into function or class bodies.
+///
+/// Returns `false` if the module contains a star import, in which case the set
+/// of bound names cannot be known statically.
+pub fn collect_module_bindings<'src>(stmts: &[Stmt<'src>], out: &mut HashSet<&'src str>) -> bool {
+    let mut complete = true;
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Import(aliases) => {
+                for a in aliases {
+                    out.insert(
+                        a.asname
+                            .unwrap_or_else(|| a.name.split('.').next().unwrap_or("")),
+                    );
+                }
+            }
+            StmtKind::ImportFrom { names, .. } => {
+                for a in names {
+                    if a.name == "*" {
+                        complete = false;
+                    } else {
+                        out.insert(a.asname.unwrap_or(a.name));
+                    }
+                }
+            }
+            StmtKind::FunctionDef(f) => {
+                out.insert(f.name);
+            }
+            StmtKind::ClassDef(c) => {
+                out.insert(c.name);
+            }
+            StmtKind::Assign { targets, value } => {
+                for t in targets {
+                    collect_target_bindings(t, out);
+                }
+                out.extend(value.walrus.iter().map(|(n, _)| *n));
+            }
+            StmtKind::AnnAssign { target, .. } | StmtKind::AugAssign { target, .. } => {
+                collect_target_bindings(target, out);
+            }
+            StmtKind::TypeAlias { name, .. } => {
+                out.insert(name);
+            }
+            StmtKind::Expr(info) => {
+                out.extend(info.walrus.iter().map(|(n, _)| *n));
+            }
+            StmtKind::For {
+                target,
+                body,
+                orelse,
+                ..
             } => {
-                return extract_str_list_from_expr(value);
+                collect_target_bindings(target, out);
+                complete &= collect_module_bindings(body, out);
+                complete &= collect_module_bindings(orelse, out);
+            }
+            StmtKind::While { test, body, orelse } | StmtKind::If { test, body, orelse } => {
+                out.extend(test.walrus.iter().map(|(n, _)| *n));
+                complete &= collect_module_bindings(body, out);
+                complete &= collect_module_bindings(orelse, out);
+            }
+            StmtKind::With { items, body, .. } => {
+                for item in items {
+                    if let Some(t) = &item.target {
+                        collect_target_bindings(t, out);
+                    }
+                }
+                complete &= collect_module_bindings(body, out);
+            }
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                complete &= collect_module_bindings(body, out);
+                for h in handlers {
+                    if let Some((n, _)) = h.name {
+                        out.insert(n);
+                    }
+                    complete &= collect_module_bindings(&h.body, out);
+                }
+                complete &= collect_module_bindings(orelse, out);
+                complete &= collect_module_bindings(finalbody, out);
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    out.extend(arm.bindings.iter().map(|(n, _)| *n));
+                    complete &= collect_module_bindings(&arm.body, out);
+                }
             }
             _ => {}
         }
     }
-    vec![]
+    complete
 }
 
-fn extract_str_list_from_expr(info: &ExprInfo<'_>) -> Vec<String> {
-    // Single-string case: `__all__ = "foo"` → ExprKind::StringLit.
-    if let ExprKind::StringLit(s) = &info.kind {
-        return vec![s.clone()];
+/// Names bound by an assignment / `for` / `with` target.
+pub fn collect_target_bindings<'src>(target: &AssignTarget<'src>, out: &mut HashSet<&'src str>) {
+    match target {
+        AssignTarget::Name(n, _) => {
+            out.insert(n);
+        }
+        AssignTarget::Tuple(elts) | AssignTarget::List(elts) => {
+            for e in elts {
+                collect_target_bindings(e, out);
+            }
+        }
+        AssignTarget::Starred(inner) => collect_target_bindings(inner, out),
+        AssignTarget::Complex(_) => {}
     }
-    // List/tuple case: `__all__ = ["foo", "bar"]` or `("foo", "bar")`.
-    // The parser now populates ExprInfo::string_list with every string literal
-    // found inside bracket pairs, so we can return it directly.
-    if !info.string_list.is_empty() {
-        return info.string_list.clone();
+}
+
+// ── `# type:` comments ────────────────────────────────────────────────────────
+
+/// Collect every name referenced in a mypy-style `# type: T` comment.
+///
+/// Legacy code annotates with comments (`x = []  # type: List[str]`), so an
+/// import used only there would otherwise look unused.  `# type: ignore` is a
+/// suppression marker, not an annotation, and is skipped.
+pub fn collect_type_comment_names(source: &str, out: &mut HashSet<String>) {
+    if !source.contains("# type:") {
+        return;
+    }
+    for line in source.lines() {
+        let Some(idx) = line.find("# type:") else {
+            continue;
+        };
+        let text = line[idx + 7..].split('#').next().unwrap_or("").trim();
+        if text.is_empty() || text.starts_with("ignore") {
+            continue;
+        }
+        let mut lex = Lexer::new(text);
+        loop {
+            match lex.bump() {
+                Token::Name(n) => {
+                    out.insert(n.to_string());
+                }
+                Token::Eof => break,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Collect the names a file can reach an attribute by: `.attr` accesses,
+/// keyword arguments (`Model(field=…)`), and string literals spelling an
+/// identifier (`getattr(obj, "attr")`).
+///
+/// Class variables are read this way rather than as bare names, so RP014
+/// checks them against this set as well as the ordinary usages.
+pub fn collect_attribute_names(source: &str, out: &mut HashSet<String>) {
+    let mut lex = Lexer::new(source);
+    let mut prev = Token::Newline;
+    let mut depth = 0usize;
+    loop {
+        let tok = lex.bump();
+        match &tok {
+            Token::Eof => break,
+            Token::LParen | Token::LBracket | Token::LBrace => depth += 1,
+            Token::RParen | Token::RBracket | Token::RBrace => depth = depth.saturating_sub(1),
+            Token::Name(n) if prev == Token::Dot => {
+                out.insert(n.to_string());
+            }
+            Token::Eq if depth > 0 => {
+                if let Token::Name(n) = prev {
+                    out.insert(n.to_string());
+                }
+            }
+            Token::Str(raw) => {
+                let value = raw
+                    .trim_start_matches(|c: char| c.is_ascii_alphabetic())
+                    .trim_matches(|c| c == '"' || c == '\'');
+                let is_identifier = value
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_alphabetic() || c == '_')
+                    && value.chars().all(|c| c.is_alphanumeric() || c == '_');
+                if is_identifier {
+                    out.insert(value.to_string());
+                }
+            }
+            _ => {}
+        }
+        prev = tok;
     }
-    vec![]
 }
 
 // ── collect_assigns_and_usages (for RP002) ────────────────────────────────────
@@ -281,8 +600,12 @@ fn collect_assigns_and_usages_one<'src>(
             for (n, o) in &value.walrus {
                 assigns.insert(n.to_string(), *o as usize);
             }
+            // Unpacking (`a, b = pair`) routinely binds names only to discard
+            // them, so only plain `name = …` targets are tracked.
             for t in targets {
-                collect_assign_target_names(t, assigns);
+                if let AssignTarget::Name(..) = t {
+                    collect_assign_target_names(t, assigns);
+                }
             }
         }
         StmtKind::AnnAssign {
@@ -317,6 +640,7 @@ fn collect_assigns_and_usages_one<'src>(
             }
             add_expr_usages(value, usages);
         }
+        StmtKind::TypeAlias { value, .. } => add_expr_usages(value, usages),
         StmtKind::For {
             target: _,
             iter,
@@ -335,8 +659,12 @@ fn collect_assigns_and_usages_one<'src>(
         StmtKind::With { items, body, .. } => {
             for item in items {
                 add_expr_usages(&item.context, usages);
+                for (n, o) in &item.context.walrus {
+                    assigns.insert(n.to_string(), *o as usize);
+                }
+                // Do NOT add the `as` target to assigns — RP011 owns that.
                 if let Some(t) = &item.target {
-                    collect_assign_target_names(t, assigns);
+                    collect_assign_target_usages(t, usages);
                 }
             }
             collect_assigns_and_usages(body, assigns, usages);
@@ -392,9 +720,7 @@ fn collect_assigns_and_usages_one<'src>(
                 if let Some(te) = &h.type_expr {
                     add_expr_usages(te, usages);
                 }
-                if let Some((n, o)) = h.name {
-                    assigns.insert(n.to_string(), o as usize);
-                }
+                // Do NOT add the handler name to assigns — RP010 owns that.
                 collect_assigns_and_usages(&h.body, assigns, usages);
             }
             collect_assigns_and_usages(orelse, assigns, usages);
@@ -417,6 +743,9 @@ fn collect_assigns_and_usages_one<'src>(
             }
             if let Some(c) = cause {
                 add_expr_usages(c, usages);
+                for (n, o) in &c.walrus {
+                    assigns.insert(n.to_string(), *o as usize);
+                }
             }
         }
         StmtKind::Expr(info) => {
@@ -432,6 +761,9 @@ fn collect_assigns_and_usages_one<'src>(
             }
             if let Some(m) = msg {
                 add_expr_usages(m, usages);
+                for (n, o) in &m.walrus {
+                    assigns.insert(n.to_string(), *o as usize);
+                }
             }
         }
         StmtKind::Delete(targets) => {
@@ -450,6 +782,9 @@ fn collect_assigns_and_usages_one<'src>(
                 for (n, _) in &arm.pattern_names {
                     usages.insert(n.to_string());
                 }
+                for (n, o) in &arm.bindings {
+                    assigns.insert(n.to_string(), *o as usize);
+                }
                 collect_assigns_and_usages(&arm.body, assigns, usages);
             }
         }
@@ -522,7 +857,9 @@ fn stmt_contains_any_name(stmt: &Stmt<'_>, needles: &[&str]) -> bool {
             expr_contains_any_name(info, needles)
         }
         StmtKind::Assign { value, .. } => expr_contains_any_name(value, needles),
-        StmtKind::AugAssign { value, .. } => expr_contains_any_name(value, needles),
+        StmtKind::AugAssign { value, .. } | StmtKind::TypeAlias { value, .. } => {
+            expr_contains_any_name(value, needles)
+        }
         StmtKind::AnnAssign { value: Some(v), .. } => expr_contains_any_name(v, needles),
         StmtKind::FunctionDef(f) => stmts_contain_any_name(&f.body, needles),
         StmtKind::ClassDef(c) => stmts_contain_any_name(&c.body, needles),
@@ -620,6 +957,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_collect_dunder_all_extend() {
+        let stmts = parse("__all__ = []\n__all__.extend([\"foo\", \"bar\"])\n");
+        assert_eq!(collect_dunder_all(&stmts), vec!["foo", "bar"]);
+    }
+
+    #[test]
+    fn test_collect_dunder_all_append() {
+        let stmts = parse("__all__ = [\"foo\"]\n__all__.append(\"bar\")\n");
+        assert_eq!(collect_dunder_all(&stmts), vec!["foo", "bar"]);
+        // Other methods and other lists are not `__all__` entries.
+        let stmts = parse("__all__.remove(\"foo\")\nnames.append(\"bar\")\n");
+        assert!(collect_dunder_all(&stmts).is_empty());
+    }
+
+    #[test]
+    fn test_collect_dunder_all_merges_assign_and_augassign() {
+        let stmts = parse("__all__ = [\"a\"]\n__all__ += [\"b\", \"c\"]\n");
+        assert_eq!(collect_dunder_all(&stmts), vec!["a", "b", "c"]);
+    }
+
     #[test]
     fn test_stmts_contain_any_name_found() {
         let stmts = parse("def f():\n    return locals()\n");
@@ -656,4 +1014,186 @@ mod tests {
             assert!(!u.contains("n"), "walrus target n should NOT be in usages");
         }
     }
+
+    /// `collect_assigns_and_usages` over the body of the first function.
+    fn function_assigns_and_usages(src: &str) -> (HashMap<String, usize>, HashSet<String>) {
+        let stmts = parse(src);
+        let crate::ast::StmtKind::FunctionDef(f) = &stmts[0].kind else {
+            panic!("expected a function");
+        };
+        let mut a = HashMap::new();
+        let mut u = HashSet::new();
+        collect_assigns_and_usages(&f.body, &mut a, &mut u);
+        (a, u)
+    }
+
+    #[test]
+    fn test_walrus_in_comprehension_condition() {
+        let (a, u) = function_assigns_and_usages(
+            "def f(data):\n    return [x for x in data if (y := g(x))]\n",
+        );
+        assert!(a.contains_key("y"));
+        assert!(!u.contains("y"));
+        let (a, u) = function_assigns_and_usages(
+            "def f(data):\n    r = [x for x in data if (y := g(x))]\n    return r, y\n",
+        );
+        assert!(a.contains_key("y"));
+        assert!(u.contains("y"));
+    }
+
+    #[test]
+    fn test_nested_walrus_binds_both_targets() {
+        let (a, u) = function_assigns_and_usages("def f():\n    (a := (b := 0))\n    return a\n");
+        assert!(a.contains_key("a") && a.contains_key("b"));
+        assert!(u.contains("a"));
+        assert!(!u.contains("b"));
+    }
+
+    #[test]
+    fn test_walrus_in_assert_message() {
+        let (a, u) = function_assigns_and_usages("def f(ok):\n    assert ok, (msg := \"bad\")\n");
+        assert!(a.contains_key("msg"));
+        assert!(!u.contains("msg"));
+        let (_, u) = function_assigns_and_usages(
+            "def f(ok):\n    assert ok, (msg := \"bad\")\n    return msg\n",
+        );
+        assert!(u.contains("msg"));
+    }
+
+    #[test]
+    fn test_walrus_in_with_context() {
+        let (a, u) =
+            function_assigns_and_usages("def f():\n    with (cm := open_it()):\n        pass\n");
+        assert!(a.contains_key("cm"));
+        assert!(!u.contains("cm"));
+        let (_, u) = function_assigns_and_usages(
+            "def f():\n    with (cm := open_it()):\n        return cm\n",
+        );
+        assert!(u.contains("cm"));
+    }
+
+    #[test]
+    fn test_type_comment_names_collected() {
+        let mut out = HashSet::new();
+        collect_type_comment_names("x = []  # type: List[str]\n", &mut out);
+        assert!(out.contains("List"));
+        assert!(out.contains("str"));
+    }
+
+    #[test]
+    fn test_type_comment_function_signature() {
+        let mut out = HashSet::new();
+        collect_type_comment_names(
+            "def f(a, b):\n    # type: (int, Optional[str]) -> Dict[str, int]\n    pass\n",
+            &mut out,
+        );
+        assert!(out.contains("Optional"));
+        assert!(out.contains("Dict"));
+    }
+
+    #[test]
+    fn test_type_ignore_is_not_an_annotation() {
+        let mut out = HashSet::new();
+        collect_type_comment_names("import foo  # type: ignore[import]\n", &mut out);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_attribute_names_collected() {
+        let mut out = HashSet::new();
+        collect_attribute_names(
+            "x = self.limit + Config.retries\nm = Model(name=1)\ngetattr(o, 'label')\ny = z\n",
+            &mut out,
+        );
+        for name in ["limit", "retries", "name", "label"] {
+            assert!(out.contains(name), "missing {name}");
+        }
+        for name in ["self", "Config", "Model", "y", "z"] {
+            assert!(!out.contains(name), "unexpected {name}");
+        }
+    }
+
+    #[test]
+    fn test_dunder_all_entries_with_offsets() {
+        let src = "__all__ = [\"a\"]\n__all__ += [\"b\"]\n";
+        let entries = collect_dunder_all_entries(&parse(src));
+        let names: Vec<&str> = entries.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["a", "b"]);
+        assert_eq!(entries[0].1, 11);
+    }
+
+    #[test]
+    fn test_module_bindings() {
+        let src = "import os.path\nfrom x import y as z\ndef f(): pass\nclass C: pass\n\
+                   a = 1\nb: int = 2\nif True:\n    c = 1\ntry:\n    pass\nexcept E as e:\n    pass\n\
+                   def g():\n    hidden = 1\n";
+        let stmts = parse(src);
+        let mut out = HashSet::new();
+        assert!(collect_module_bindings(&stmts, &mut out));
+        for name in ["os", "z", "f", "C", "a", "b", "c", "e", "g"] {
+            assert!(out.contains(name), "{name} should be bound");
+        }
+        assert!(!out.contains("hidden"));
+    }
+
+    #[test]
+    fn test_module_bindings_star_import_incomplete() {
+        let stmts = parse("from os import *\n");
+        let mut out = HashSet::new();
+        assert!(!collect_module_bindings(&stmts, &mut out));
+    }
+
+    #[test]
+    fn test_decorator_name() {
+        let src = "@task\n@app.task\n@app.task(bind=True)\n@shared_task()\ndef f(): pass\n";
+        let stmts = parse(src);
+        let StmtKind::FunctionDef(f) = &stmts[0].kind else {
+            panic!("expected FunctionDef");
+        };
+        let names: Vec<_> = f.decorators.iter().map(decorator_name).collect();
+        assert_eq!(
+            names,
+            vec![
+                Some("task"),
+                Some("task"),
+                Some("task"),
+                Some("shared_task")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_urlpatterns_views() {
+        let src = "urlpatterns = [path('', index), re_path(r'^a/', detail, name='d')]\nurlpatterns += [path('x/', include(other))]\nunrelated = [path('', skip)]\n";
+        let stmts = parse(src);
+        let views = collect_urlpatterns_views(&stmts);
+        assert!(views.contains("index"));
+        assert!(views.contains("detail"));
+        assert!(views.contains("other"));
+        assert!(!views.contains("path"));
+        assert!(!views.contains("re_path"));
+        assert!(!views.contains("include"));
+        assert!(!views.contains("skip"));
+    }
+
+    #[test]
+    fn test_argument_default_is_usage() {
+        let stmts = parse("def f(timeout=DEFAULT_TIMEOUT, *, db=Depends(get_db)):\n    pass\n");
+        let mut out = HashSet::new();
+        collect_stmt_names(&stmts, &mut out);
+        assert!(out.contains("DEFAULT_TIMEOUT"));
+        assert!(out.contains("Depends"));
+        assert!(out.contains("get_db"));
+    }
+
+    #[test]
+    fn test_is_pytest_decorator() {
+        let src = "@pytest.fixture\n@fixture(scope='module')\n@pytest.fixture(autouse=True)\n@pytest.mark.slow\n@app.route('/')\ndef f(): pass\n";
+        let stmts = parse(src);
+        let StmtKind::FunctionDef(f) = &stmts[0].kind else {
+            panic!("expected FunctionDef");
+        };
+        let flags: Vec<_> = f.decorators.iter().map(is_pytest_decorator).collect();
+        assert_eq!(flags, vec![true, true, true, true, false]);
+    }
 }
@@ -0,0 +1,81 @@
+//! `--output github`: GitHub Actions workflow commands, which the runner turns
+//! into inline annotations on the pull request diff.
+//!
+//! ```text
+//! ::warning file=src/app.py,line=3,col=1,title=RP001::`os` imported but unused
+//! ```
+
+use crate::types::{Diagnostic, Severity};
+
+/// The workflow command for one diagnostic.  Severities map onto GitHub's
+/// `error`, `warning` and `notice` levels.
+pub fn annotation(d: &Diagnostic) -> String {
+    let level = match d.code.severity() {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "notice",
+    };
+    format!(
+        "::{level} file={},line={},col={},title={}::{}",
+        escape_property(&d.file),
+        d.line,
+        d.col,
+        escape_property(&d.code.to_string()),
+        escape_data(&d.message)
+    )
+}
+
+/// Escape a command's message, where `%` and line breaks are special.
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a `key=value` property, where `,` and `:` also end the value.
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RuleCode;
+
+    fn diag(file: &str, code: RuleCode, message: &str) -> Diagnostic {
+        Diagnostic {
+            file: file.to_string(),
+            line: 3,
+            col: 5,
+            start_byte: 0,
+            end_byte: 0,
+            code,
+            message: message.to_string(),
+            fix: None,
+        }
+    }
+
+    #[test]
+    fn test_annotation_levels() {
+        let d = diag("app.py", RuleCode::UnusedImport, "`os` imported but unused");
+        assert_eq!(
+            annotation(&d),
+            "::warning file=app.py,line=3,col=5,title=RP001::`os` imported but unused"
+        );
+        let d = diag("app.py", RuleCode::UnreachableCode, "Code is unreachable");
+        assert!(annotation(&d).starts_with("::error file=app.py,"));
+        let d = diag("app.py", RuleCode::TryElseBody, "…");
+        assert!(annotation(&d).starts_with("::notice "));
+    }
+
+    #[test]
+    fn test_file_with_comma_and_colon_is_escaped() {
+        let d = diag("a,b/c:d.py", RuleCode::UnusedImport, "100% unused\nreally");
+        assert_eq!(
+            annotation(&d),
+            "::warning file=a%2Cb/c%3Ad.py,line=3,col=5,title=RP001::100%25 unused%0Areally"
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//! `--group-by-file`: one header per file, followed by that file's
+//! diagnostics without the repeated path.
+//!
+//! ```text
+//! ── src/main.py (2 issues) ──────────────────────────────────────────────────
+//! 3:5: RP001 `os` imported but unused
+//! 7:1: RP005 Code is unreachable
+//! ```
+
+use crate::types::Diagnostic;
+
+/// Width the header rule is padded to with `─`.
+const HEADER_WIDTH: usize = 78;
+
+/// One file's diagnostics, in their reported order.
+pub struct FileGroup<'a> {
+    pub file: &'a str,
+    pub diagnostics: Vec<&'a Diagnostic>,
+}
+
+/// Group `diagnostics` by file.  Files appear in the order of their first
+/// diagnostic, so the caller's sort order decides the group order.
+pub fn group_by_file(diagnostics: &[Diagnostic]) -> Vec<FileGroup<'_>> {
+    let mut groups: Vec<FileGroup<'_>> = Vec::new();
+    for d in diagnostics {
+        match groups.iter_mut().find(|g| g.file == d.file) {
+            Some(group) => group.diagnostics.push(d),
+            None => groups.push(FileGroup {
+                file: &d.file,
+                diagnostics: vec![d],
+            }),
+        }
+    }
+    groups
+}
+
+/// `── path (N issues) ────…`, padded with `─` to [`HEADER_WIDTH`] columns
+/// (always at least three).
+pub fn header(file: &str, count: usize) -> String {
+    let noun = if count == 1 { "issue" } else { "issues" };
+    let title = format!("── {file} ({count} {noun}) ");
+    let pad = HEADER_WIDTH.saturating_sub(title.chars().count()).max(3);
+    format!("{title}{}", "─".repeat(pad))
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RuleCode;
+
+    fn diag(file: &str, line: usize) -> Diagnostic {
+        Diagnostic {
+            file: file.to_string(),
+            line,
+            col: 1,
+            start_byte: 0,
+            end_byte: 0,
+            code: RuleCode::UnusedImport,
+            message: "`os` imported but unused".to_string(),
+            fix: None,
+        }
+    }
+
+    #[test]
+    fn test_groups_keep_first_appearance_order() {
+        let diags = [diag("b.py", 1), diag("a.py", 2), diag("b.py", 3)];
+        let groups = group_by_file(&diags);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].file, "b.py");
+        let lines: Vec<usize> = groups[0].diagnostics.iter().map(|d| d.line).collect();
+        assert_eq!(lines, [1, 3]);
+        assert_eq!(groups[1].file, "a.py");
+    }
+
+    #[test]
+    fn test_no_diagnostics_no_groups() {
+        assert!(group_by_file(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_header_counts_and_pads() {
+        let h = header("src/main.py", 2);
+        assert!(h.starts_with("── src/main.py (2 issues) ─"), "got: {h}");
+        assert_eq!(h.chars().count(), HEADER_WIDTH);
+        assert!(header("a.py", 1).contains("(1 issue)"));
+        let long = "x".repeat(100);
+        assert!(header(&long, 1).ends_with(" ───"));
+    }
+}
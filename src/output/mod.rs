@@ -0,0 +1,5 @@
+//! Alternative layouts for reporting diagnostics.
+
+pub mod github;
+pub mod grouped;
+pub mod stats;
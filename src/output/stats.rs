@@ -0,0 +1,56 @@
+//! `--statistics`: a health score relating the issue count to the size of
+//! the analysed code.
+//!
+//! ```text
+//! Health score: 94.1%  (48 issues across 812 lines)
+//! ```
+
+/// `100 - issues per hundred lines`, floored at 0 and rounded to one decimal
+/// place.  An empty codebase scores 100.
+pub fn health_score(issues: usize, total_lines: usize) -> f64 {
+    if total_lines == 0 {
+        return 100.0;
+    }
+    let score = 100.0 - (issues as f64 / total_lines as f64 * 100.0).min(100.0);
+    (score * 10.0).round() / 10.0
+}
+
+/// The line printed after the summary.
+pub fn summary(issues: usize, total_lines: usize) -> String {
+    format!(
+        "Health score: {:.1}%  ({issues} issues across {total_lines} lines)",
+        health_score(issues, total_lines)
+    )
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_scales_with_issue_density() {
+        assert_eq!(health_score(2, 10), 80.0);
+        assert_eq!(health_score(48, 812), 94.1);
+    }
+
+    #[test]
+    fn test_clean_and_empty_score_full() {
+        assert_eq!(health_score(0, 10), 100.0);
+        assert_eq!(health_score(0, 0), 100.0);
+    }
+
+    #[test]
+    fn test_score_never_negative() {
+        assert_eq!(health_score(30, 10), 0.0);
+    }
+
+    #[test]
+    fn test_summary_line() {
+        assert_eq!(
+            summary(2, 10),
+            "Health score: 80.0%  (2 issues across 10 lines)"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Path normalisation for diagnostic output.
+//!
+//! Diagnostics carry the path exactly as discovery produced it, which depends
+//! on how the user spelled the CLI argument (`.`, `./src`, an absolute path…).
+//! Before printing, paths are rewritten to one consistent style so output is
+//! stable across invocations and easy to diff.
+
+use std::path::{Path, PathBuf};
+
+/// How file paths are rendered in diagnostic output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathStyle {
+    /// Relative to the current working directory (the default).
+    #[default]
+    Relative,
+    /// Fully resolved absolute paths.
+    Absolute,
+}
+
+/// Rewrite `path` according to `style`, resolving relative paths against `cwd`.
+///
+/// Paths that cannot be canonicalised (e.g. deleted between analysis and
+/// output) are resolved lexically instead, so this never fails.
+pub fn normalize_path(path: &str, style: PathStyle, cwd: &Path) -> String {
+    let absolute = absolutize(Path::new(path), cwd);
+    match style {
+        PathStyle::Absolute => absolute.to_string_lossy().into_owned(),
+        PathStyle::Relative => {
+            let base = cwd.canonicalize().unwrap_or_else(|_| cwd.to_path_buf());
+            pathdiff::diff_paths(&absolute, &base)
+                .unwrap_or(absolute)
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+}
+
+/// Render a path with forward slashes regardless of platform (used for JSON).
+pub fn to_forward_slashes(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+/// Parse a `--paths-from` list: one path per line, surrounding whitespace
+/// trimmed, blank lines and `#` comment lines skipped.
+pub fn parse_path_list(content: &str) -> Vec<PathBuf> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn absolutize(path: &Path, cwd: &Path) -> PathBuf {
+    let joined = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        cwd.join(path)
+    };
+    joined.canonicalize().unwrap_or(joined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_strips_cwd_prefix() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("pkg")).unwrap();
+        std::fs::write(dir.path().join("pkg/mod.py"), "").unwrap();
+        let abs = dir.path().join("pkg/mod.py");
+        let out = normalize_path(abs.to_str().unwrap(), PathStyle::Relative, dir.path());
+        assert_eq!(Path::new(&out), Path::new("pkg/mod.py"));
+    }
+
+    #[test]
+    fn test_relative_removes_dot_prefix() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.py"), "").unwrap();
+        let out = normalize_path("./a.py", PathStyle::Relative, dir.path());
+        assert_eq!(out, "a.py");
+    }
+
+    #[test]
+    fn test_absolute_resolves_against_cwd() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.py"), "").unwrap();
+        let out = normalize_path("a.py", PathStyle::Absolute, dir.path());
+        assert!(Path::new(&out).is_absolute());
+        assert!(out.ends_with("a.py"));
+    }
+
+    #[test]
+    fn test_outside_cwd_uses_parent_segments() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("a.py"), "").unwrap();
+        let abs = dir.path().join("a.py");
+        let out = normalize_path(
+            abs.to_str().unwrap(),
+            PathStyle::Relative,
+            &dir.path().join("sub"),
+        );
+        assert_eq!(Path::new(&out), Path::new("../a.py"));
+    }
+
+    #[test]
+    fn test_parse_path_list_skips_blanks_and_comments() {
+        let list = "# changed files\nsrc/a.py\n\n  src/b.py  \n#src/c.py\nsrc/d.py";
+        assert_eq!(
+            parse_path_list(list),
+            vec![
+                PathBuf::from("src/a.py"),
+                PathBuf::from("src/b.py"),
+                PathBuf::from("src/d.py")
+            ]
+        );
+        assert!(parse_path_list("\n# nothing\n").is_empty());
+    }
+
+    #[test]
+    fn test_forward_slashes() {
+        assert_eq!(to_forward_slashes(r"src\pkg\a.py"), "src/pkg/a.py");
+    }
+}
@@ -0,0 +1,82 @@
+//! The Python interpreter on `PATH`, for checks that depend on the version
+//! the code will run under (RP015).
+//!
+//! Like [`crate::git`], this shells out (`python3 --version`) instead of
+//! embedding an interpreter.  When no interpreter answers, the version is
+//! unknown and version-dependent checks stay silent.  `--python-version` (or
+//! the `python_version` config key) names the target version instead.
+
+use std::process::Command;
+
+/// `(major, minor)` of the `python3` on `PATH`, or `None` when it cannot be
+/// run or its output is not recognised.
+pub fn detect_version() -> Option<(u32, u32)> {
+    let out = Command::new("python3").arg("--version").output().ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    // Python 2 printed the version to stderr, and some builds still do.
+    let text = if out.stdout.is_empty() {
+        out.stderr
+    } else {
+        out.stdout
+    };
+    parse_version(&String::from_utf8_lossy(&text))
+}
+
+/// Parse `python --version` output such as `Python 3.11.7` or
+/// `Python 3.13.0rc1`.
+pub fn parse_version(output: &str) -> Option<(u32, u32)> {
+    let version = output.trim().strip_prefix("Python ")?;
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor: String = parts
+        .next()?
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    Some((major, minor.parse().ok()?))
+}
+
+/// Parse a target version written `3.11`, as `--python-version` and the
+/// `python_version` config key take it.
+pub fn parse_target(text: &str) -> Option<(u32, u32)> {
+    let (major, minor) = text.trim().split_once('.')?;
+    let number = |s: &str| {
+        (!s.is_empty() && s.chars().all(|c| c.is_ascii_digit()))
+            .then(|| s.parse().ok())
+            .flatten()
+    };
+    Some((number(major)?, number(minor)?))
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("Python 3.11.7\n"), Some((3, 11)));
+        assert_eq!(parse_version("Python 3.13.0rc1"), Some((3, 13)));
+        assert_eq!(parse_version("Python 3.14a"), Some((3, 14)));
+    }
+
+    #[test]
+    fn test_parse_version_rejects_other_output() {
+        assert_eq!(parse_version(""), None);
+        assert_eq!(parse_version("pyenv: python3: command not found"), None);
+        assert_eq!(parse_version("Python three"), None);
+    }
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(parse_target("3.11"), Some((3, 11)));
+        assert_eq!(parse_target("2.7"), Some((2, 7)));
+        assert_eq!(parse_target("3"), None);
+        assert_eq!(parse_target("3.11.2"), None);
+        assert_eq!(parse_target("3.x"), None);
+        assert_eq!(parse_target("Python 3.11"), None);
+    }
+}
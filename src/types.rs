@@ -1,6 +1,8 @@
+use crate::fix::Fix;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RuleCode {
     UnusedImport,
     UnusedVariable,
@@ -11,6 +13,40 @@ pub enum RuleCode {
     RedefinedUnused,
     UnusedArgument,
     UnusedLoopVariable,
+    UnusedExceptVar,
+    UnusedWithTarget,
+    ShadowedBuiltin,
+    RedundantElse,
+    UnusedClassVar,
+    DeadVersionBranch,
+    UnusedGlobal,
+    StarImport,
+    UndefinedExport,
+    InitParamNotStored,
+    InconsistentReturn,
+    UnusedOverrideParam,
+    LoopVarUsedAfterLoop,
+    UndefinedNonlocal,
+    GlobalAfterUse,
+    UnimportedAnnotation,
+    ReimportAfterBranches,
+    TryElseBody,
+    ThrowawayNameUsed,
+    AnnotationOnlyImport,
+    ImportShadowedByLocal,
+    DuplicateExport,
+    EmptyTryBody,
+    LoopControlOutsideLoop,
+    StringFormatMismatch,
+    PointlessFString,
+    ImplicitStringConcat,
+    EqWithoutHash,
+    ExceptionWithoutMessage,
+    ExplicitReraise,
+    SelfAssignment,
+    PrintStatement,
+    GlobalBuiltin,
+    SuppressedAssignment,
 }
 
 impl fmt::Display for RuleCode {
@@ -25,18 +61,912 @@ impl fmt::Display for RuleCode {
             RuleCode::RedefinedUnused => "RP007",
             RuleCode::UnusedArgument => "RP008",
             RuleCode::UnusedLoopVariable => "RP009",
+            RuleCode::UnusedExceptVar => "RP010",
+            RuleCode::UnusedWithTarget => "RP011",
+            RuleCode::ShadowedBuiltin => "RP012",
+            RuleCode::RedundantElse => "RP013",
+            RuleCode::UnusedClassVar => "RP014",
+            RuleCode::DeadVersionBranch => "RP015",
+            RuleCode::UnusedGlobal => "RP016",
+            RuleCode::StarImport => "RP047",
+            RuleCode::UndefinedExport => "RP048",
+            RuleCode::InitParamNotStored => "RP049",
+            RuleCode::InconsistentReturn => "RP050",
+            RuleCode::UnusedOverrideParam => "RP051",
+            RuleCode::LoopVarUsedAfterLoop => "RP052",
+            RuleCode::UndefinedNonlocal => "RP053",
+            RuleCode::GlobalAfterUse => "RP054",
+            RuleCode::UnimportedAnnotation => "RP055",
+            RuleCode::ReimportAfterBranches => "RP056",
+            RuleCode::TryElseBody => "RP057",
+            RuleCode::ThrowawayNameUsed => "RP058",
+            RuleCode::AnnotationOnlyImport => "RP059",
+            RuleCode::ImportShadowedByLocal => "RP061",
+            RuleCode::DuplicateExport => "RP062",
+            RuleCode::EmptyTryBody => "RP063",
+            RuleCode::LoopControlOutsideLoop => "RP064",
+            RuleCode::StringFormatMismatch => "RP065",
+            RuleCode::PointlessFString => "RP066",
+            RuleCode::ImplicitStringConcat => "RP067",
+            RuleCode::EqWithoutHash => "RP068",
+            RuleCode::ExceptionWithoutMessage => "RP069",
+            RuleCode::ExplicitReraise => "RP070",
+            RuleCode::SelfAssignment => "RP071",
+            RuleCode::PrintStatement => "RP072",
+            RuleCode::GlobalBuiltin => "RP073",
+            RuleCode::SuppressedAssignment => "RP074",
         };
         write!(f, "{code}")
     }
 }
 
-#[derive(Debug, Clone)]
+/// How serious a finding is.  Variants are ordered most-severe first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Code that can never execute, or that CPython refuses to compile.
+    Error,
+    /// Everything else: unused or redundant code.
+    Warning,
+    /// Style hints that are worth a look but rarely a bug on their own.
+    Info,
+}
+
+/// What part of the code a rule looks at, for `--select-category`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    /// Import statements: unused, redefined, star or misplaced imports.
+    Imports,
+    /// Local, loop, exception and global bindings.
+    Variables,
+    /// Unreachable code, constant branches, returns and `try` structure.
+    ControlFlow,
+    /// Functions, classes and the `__all__` entries that export them.
+    Definitions,
+    /// Function parameters.
+    Arguments,
+    /// Strings, `print` statements and other single expressions.
+    Expressions,
+}
+
+impl Severity {
+    /// Every severity, most severe first.
+    pub const ALL: &[Severity] = &[Severity::Error, Severity::Warning, Severity::Info];
+}
+
+impl std::str::FromStr for Severity {
+    type Err = String;
+
+    /// Parse a severity name as printed by `Display` (case-insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Severity::ALL
+            .iter()
+            .copied()
+            .find(|v| v.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                format!("unknown severity `{s}` (expected one of: error, warning, info)")
+            })
+    }
+}
+
+impl Category {
+    /// Every category, in the order `--help` lists them.
+    pub const ALL: &[Category] = &[
+        Category::Imports,
+        Category::Variables,
+        Category::ControlFlow,
+        Category::Definitions,
+        Category::Arguments,
+        Category::Expressions,
+    ];
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Category::Imports => "imports",
+            Category::Variables => "variables",
+            Category::ControlFlow => "control-flow",
+            Category::Definitions => "definitions",
+            Category::Arguments => "arguments",
+            Category::Expressions => "expressions",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::str::FromStr for Category {
+    type Err = String;
+
+    /// Parse a category name as printed by `Display` (case-insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Category::ALL
+            .iter()
+            .copied()
+            .find(|c| c.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                let names: Vec<String> = Category::ALL.iter().map(|c| c.to_string()).collect();
+                format!(
+                    "unknown category `{s}` (expected one of: {})",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+impl RuleCode {
+    /// Every rule, in code order.
+    pub const ALL: &[RuleCode] = &[
+        RuleCode::UnusedImport,
+        RuleCode::UnusedVariable,
+        RuleCode::UnusedFunction,
+        RuleCode::UnusedClass,
+        RuleCode::UnreachableCode,
+        RuleCode::DeadBranch,
+        RuleCode::RedefinedUnused,
+        RuleCode::UnusedArgument,
+        RuleCode::UnusedLoopVariable,
+        RuleCode::UnusedExceptVar,
+        RuleCode::UnusedWithTarget,
+        RuleCode::ShadowedBuiltin,
+        RuleCode::RedundantElse,
+        RuleCode::UnusedClassVar,
+        RuleCode::DeadVersionBranch,
+        RuleCode::UnusedGlobal,
+        RuleCode::StarImport,
+        RuleCode::UndefinedExport,
+        RuleCode::InitParamNotStored,
+        RuleCode::InconsistentReturn,
+        RuleCode::UnusedOverrideParam,
+        RuleCode::LoopVarUsedAfterLoop,
+        RuleCode::UndefinedNonlocal,
+        RuleCode::GlobalAfterUse,
+        RuleCode::UnimportedAnnotation,
+        RuleCode::ReimportAfterBranches,
+        RuleCode::TryElseBody,
+        RuleCode::ThrowawayNameUsed,
+        RuleCode::AnnotationOnlyImport,
+        RuleCode::ImportShadowedByLocal,
+        RuleCode::DuplicateExport,
+        RuleCode::EmptyTryBody,
+        RuleCode::LoopControlOutsideLoop,
+        RuleCode::StringFormatMismatch,
+        RuleCode::PointlessFString,
+        RuleCode::ImplicitStringConcat,
+        RuleCode::EqWithoutHash,
+        RuleCode::ExceptionWithoutMessage,
+        RuleCode::ExplicitReraise,
+        RuleCode::SelfAssignment,
+        RuleCode::PrintStatement,
+        RuleCode::GlobalBuiltin,
+        RuleCode::SuppressedAssignment,
+    ];
+
+    /// Parse a rule code such as `RP001` (case-insensitive).
+    pub fn from_code(code: &str) -> Option<RuleCode> {
+        Self::ALL
+            .iter()
+            .find(|r| r.to_string().eq_ignore_ascii_case(code))
+            .cloned()
+    }
+
+    /// Human-readable rule name, as used for the rule's README heading.
+    pub fn title(&self) -> &'static str {
+        match self {
+            RuleCode::UnusedImport => "Unused Import",
+            RuleCode::UnusedVariable => "Unused Variable",
+            RuleCode::UnusedFunction => "Unused Function (Cross-File)",
+            RuleCode::UnusedClass => "Unused Class (Cross-File)",
+            RuleCode::UnreachableCode => "Unreachable Code",
+            RuleCode::DeadBranch => "Dead Branch",
+            RuleCode::RedefinedUnused => "Import Redefined Before Use",
+            RuleCode::UnusedArgument => "Unused Function Argument",
+            RuleCode::UnusedLoopVariable => "Unused Loop Variable",
+            RuleCode::UnusedExceptVar => "Unused Exception Variable",
+            RuleCode::UnusedWithTarget => "Unused With Target",
+            RuleCode::ShadowedBuiltin => "Shadowed Builtin",
+            RuleCode::RedundantElse => "Redundant Else",
+            RuleCode::UnusedClassVar => "Unused Class Variable",
+            RuleCode::DeadVersionBranch => "Dead Version Branch",
+            RuleCode::UnusedGlobal => "Unused Global Declaration",
+            RuleCode::StarImport => "Star Import",
+            RuleCode::UndefinedExport => "Undefined `__all__` Entry",
+            RuleCode::InitParamNotStored => "`__init__` Parameter Never Stored",
+            RuleCode::InconsistentReturn => "Inconsistent Return",
+            RuleCode::UnusedOverrideParam => "Unused Override Parameter",
+            RuleCode::LoopVarUsedAfterLoop => "Loop Variable Used After Loop",
+            RuleCode::UndefinedNonlocal => "Undefined Nonlocal Name",
+            RuleCode::GlobalAfterUse => "Global Declared After Use",
+            RuleCode::UnimportedAnnotation => "Unimported Annotation Name",
+            RuleCode::ReimportAfterBranches => "Re-import After Conditional Import",
+            RuleCode::TryElseBody => "Unnecessary Try Else",
+            RuleCode::ThrowawayNameUsed => "Throwaway Name Used",
+            RuleCode::AnnotationOnlyImport => "Import Only Used in Annotations",
+            RuleCode::ImportShadowedByLocal => "Import Shadowed by Local",
+            RuleCode::DuplicateExport => "Duplicate Export",
+            RuleCode::EmptyTryBody => "Empty Try Body",
+            RuleCode::LoopControlOutsideLoop => "Loop Control Outside Loop",
+            RuleCode::StringFormatMismatch => "String Format Argument Mismatch",
+            RuleCode::PointlessFString => "F-String Without Placeholders",
+            RuleCode::ImplicitStringConcat => "Implicit String Concatenation",
+            RuleCode::EqWithoutHash => "Eq Without Hash",
+            RuleCode::ExceptionWithoutMessage => "Exception Without Message",
+            RuleCode::ExplicitReraise => "Explicit Re-raise",
+            RuleCode::SelfAssignment => "Self Assignment",
+            RuleCode::PrintStatement => "Print Statement",
+            RuleCode::GlobalBuiltin => "Global Builtin",
+            RuleCode::SuppressedAssignment => "Possibly Unbound After With",
+        }
+    }
+
+    /// One sentence on what the rule reports, for `--list-rules`.
+    pub fn description(&self) -> &'static str {
+        match self {
+            RuleCode::UnusedImport => "An imported name is never used in the module.",
+            RuleCode::UnusedVariable => "A local variable is assigned but never read.",
+            RuleCode::UnusedFunction => {
+                "A module-level function is never called or imported anywhere in the project."
+            }
+            RuleCode::UnusedClass => {
+                "A module-level class is never used or imported anywhere in the project."
+            }
+            RuleCode::UnreachableCode => {
+                "A statement follows a `return`, `raise`, `break` or `continue` and can never run."
+            }
+            RuleCode::DeadBranch => {
+                "An `if` or `while` test is a constant, so one of its branches never runs."
+            }
+            RuleCode::RedefinedUnused => {
+                "An import is rebound before the imported name is ever used."
+            }
+            RuleCode::UnusedArgument => "A function parameter is never read in the function body.",
+            RuleCode::UnusedLoopVariable => "A `for` loop variable is never read in the loop body.",
+            RuleCode::UnusedExceptVar => {
+                "The name bound by `except ... as e` is never read in the handler."
+            }
+            RuleCode::UnusedWithTarget => "The name bound by `with ... as x` is never read.",
+            RuleCode::ShadowedBuiltin => {
+                "A variable, parameter, function or class reuses the name of a builtin."
+            }
+            RuleCode::RedundantElse => {
+                "An `else` or `elif` follows an `if` body that always returns, raises, breaks or continues."
+            }
+            RuleCode::UnusedClassVar => {
+                "A class variable is never read through the class, its instances or by name."
+            }
+            RuleCode::DeadVersionBranch => {
+                "A `sys.version_info` comparison is decided by the running Python, so one branch never runs."
+            }
+            RuleCode::UnusedGlobal => {
+                "A function declares a name `global` or `nonlocal` but never assigns or reads it."
+            }
+            RuleCode::StarImport => "A `from module import *` hides which names are in scope.",
+            RuleCode::UndefinedExport => {
+                "An `__all__` entry names something the module does not define."
+            }
+            RuleCode::InitParamNotStored => {
+                "An `__init__` parameter is read but never stored on `self` or passed to `super()`."
+            }
+            RuleCode::InconsistentReturn => {
+                "A function returns a value on some paths and falls off the end on others."
+            }
+            RuleCode::UnusedOverrideParam => {
+                "A dunder method ignores a parameter its protocol requires."
+            }
+            RuleCode::LoopVarUsedAfterLoop => {
+                "A `for` loop variable is read after the loop, where it is unbound if the iterable was empty."
+            }
+            RuleCode::UndefinedNonlocal => {
+                "A `nonlocal` name is not bound in any enclosing function."
+            }
+            RuleCode::GlobalAfterUse => {
+                "A name is used in a scope before its `global` declaration."
+            }
+            RuleCode::UnimportedAnnotation => {
+                "An annotation refers to a name that is never imported or defined."
+            }
+            RuleCode::ReimportAfterBranches => {
+                "An import repeats one that every branch of an earlier `if`/`else` already made."
+            }
+            RuleCode::TryElseBody => {
+                "A `try` statement's `else` clause could be part of the `try` body."
+            }
+            RuleCode::ThrowawayNameUsed => {
+                "A variable named `_`, which marks a discarded value, is read."
+            }
+            RuleCode::AnnotationOnlyImport => {
+                "An import is only used in annotations and could move under `if TYPE_CHECKING:`."
+            }
+            RuleCode::ImportShadowedByLocal => {
+                "A local assignment hides a module-level import inside a function."
+            }
+            RuleCode::DuplicateExport => "A name is listed more than once in `__all__`.",
+            RuleCode::EmptyTryBody => {
+                "A `try` body is only `pass` or `...`, so its handlers never run."
+            }
+            RuleCode::LoopControlOutsideLoop => "A `break` or `continue` has no enclosing loop.",
+            RuleCode::StringFormatMismatch => {
+                "A `.format()` or `%` call passes a different number of arguments than the string has placeholders."
+            }
+            RuleCode::PointlessFString => "An f-string has no placeholders.",
+            RuleCode::ImplicitStringConcat => {
+                "Adjacent string literals in a collection are joined, usually because of a missing comma."
+            }
+            RuleCode::EqWithoutHash => {
+                "A class defines `__eq__` without `__hash__`, which makes its instances unhashable."
+            }
+            RuleCode::ExceptionWithoutMessage => "A builtin exception is raised without a message.",
+            RuleCode::ExplicitReraise => {
+                "`raise e` in an `except ... as e` handler, where a bare `raise` would keep the traceback intact."
+            }
+            RuleCode::SelfAssignment => "A name, attribute or subscript is assigned to itself.",
+            RuleCode::PrintStatement => {
+                "A Python 2 `print` statement, which is a syntax error in Python 3."
+            }
+            RuleCode::GlobalBuiltin => {
+                "A builtin name is declared `global`, so assigning it shadows the builtin module-wide."
+            }
+            RuleCode::SuppressedAssignment => {
+                "A name assigned inside a `with` block that suppresses exceptions may be unbound after it."
+            }
+        }
+    }
+
+    /// A short Python snippet showing what the rule reports, the same as the
+    /// rule's README example.  Used by `--explain` and `--explain-all`.
+    pub fn example(&self) -> &'static str {
+        match self {
+            RuleCode::UnusedImport => {
+                r#"import os          # RP001 — `os` imported but unused
+import json        # ✅ OK — used below
+
+data = json.loads('{}')
+"#
+            }
+            RuleCode::UnusedVariable => {
+                r#"def calculate():
+    temp = 42       # RP002 — assigned but never read
+    result = 100
+    return result
+"#
+            }
+            RuleCode::UnusedFunction => {
+                r#"# utils.py
+def helper():        # ✅ OK — called from main.py
+    return 42
+
+def orphan():        # RP003 — defined but never called from anywhere
+    return 0
+"#
+            }
+            RuleCode::UnusedClass => {
+                r#"class UserSerializer:     # ✅ OK — instantiated in views.py
+    pass
+
+class LegacyParser:       # RP004 — never instantiated or referenced anywhere
+    pass
+"#
+            }
+            RuleCode::UnreachableCode => {
+                r#"def process():
+    return True
+    cleanup()        # RP005 — Code is unreachable
+    log("done")      # RP005 — Code is unreachable
+"#
+            }
+            RuleCode::DeadBranch => {
+                r#"if False:                     # RP006 — branch never executes
+    enable_debug()
+
+if None:                      # RP006 — branch never executes
+    setup_logging()
+
+from typing import TYPE_CHECKING
+if TYPE_CHECKING:             # RP006 — correctly identified as dead at runtime
+    import heavy_module       # (but NOT flagged as RP001 — Reaper knows this is intentional)
+"#
+            }
+            RuleCode::RedefinedUnused => {
+                r#"import os                    # RP007 — overwritten before it's ever read
+os = "not the module anymore"
+
+import sys                   # ✅ OK — used before reassignment
+print(sys.version)
+sys = "overwritten later"
+"#
+            }
+            RuleCode::UnusedArgument => {
+                r#"def send_email(to, subject, priority):   # RP008 — `priority` is never used
+    return mailer.send(to=to, subject=subject)
+
+def callback(_event, data):              # ✅ OK — underscore prefix = intentionally unused
+    return process(data)
+
+class Base(ABC):
+    @abstractmethod
+    def handle(self, request):           # ✅ OK — abstract methods are skipped
+        ...
+
+@app.task
+def send_report(recipient):              # ✅ OK — Celery tasks are called by the worker
+    return 1
+"#
+            }
+            RuleCode::UnusedLoopVariable => {
+                r#"for i in range(10):          # RP009 — `i` is never used
+    print("tick")
+
+for _ in range(10):          # ✅ OK — underscore convention
+    print("tick")
+
+for key, value in data.items():  # ✅ OK — both used
+    result[key] = transform(value)
+"#
+            }
+            RuleCode::UnusedExceptVar => {
+                r#"try:
+    connect()
+except ConnectionError as e:     # RP010 — `e` is never read
+    retry()
+
+try:
+    connect()
+except ConnectionError as e:     # ✅ OK
+    log.warning("retrying: %s", e)
+"#
+            }
+            RuleCode::UnusedWithTarget => {
+                r#"with open("config.json") as fh:     # RP011 — `fh` is never read
+    load_defaults()
+
+with Timer() as t:                  # ✅ OK — read after the block
+    run()
+print(t.elapsed)
+"#
+            }
+            RuleCode::ShadowedBuiltin => {
+                r#"list = [1, 2, 3]                    # RP012 — hides the builtin `list`
+
+def ask(input):                     # RP012 — parameter hides `input`
+    return input.strip()
+
+class Row:
+    id = None                       # ✅ OK — class attribute
+"#
+            }
+            RuleCode::RedundantElse => {
+                r#"def sign(x):
+    if x < 0:
+        return -1
+    else:                           # RP013 — the `if` body always returns
+        return 1
+
+    if x < 0:
+        raise ValueError(x)
+    elif x == 0:                    # RP013 — can be a plain `if`
+        ...
+"#
+            }
+            RuleCode::UnusedClassVar => {
+                r#"class Client:
+    timeout = 30                    # ✅ OK — read as self.timeout below
+    retries = 3                     # RP014 — never read anywhere
+
+    def get(self, url):
+        return fetch(url, self.timeout)
+"#
+            }
+            RuleCode::DeadVersionBranch => {
+                r#"import sys
+
+if sys.version_info < (3, 8):       # RP015 — always false on Python 3.11
+    from typing_extensions import Literal
+else:
+    from typing import Literal
+"#
+            }
+            RuleCode::UnusedGlobal => {
+                r#"counter = 0
+
+def report(total):
+    global counter       # RP016 — `counter` is never assigned or read here
+    return total
+"#
+            }
+            RuleCode::StarImport => {
+                r#"# utils.py
+from os.path import *        # RP047 — namespace analysis is incomplete
+
+# pkg/__init__.py
+from .core import *          # ✅ OK — re-exporting from a package is conventional
+"#
+            }
+            RuleCode::UndefinedExport => {
+                r#"__all__ = ["parse", "render"]   # RP048 — `render` is not defined in this module
+
+def parse(text):
+    ...
+"#
+            }
+            RuleCode::InitParamNotStored => {
+                r#"class Point:
+    def __init__(self, x, y):   # RP049 — `y` is read but never assigned to `self`
+        self.x = x
+        log.debug("y=%s", y)
+"#
+            }
+            RuleCode::InconsistentReturn => {
+                r#"def find(items, key):           # RP050 — returns a value, or falls off the end
+    for item in items:
+        if item.key == key:
+            return item
+"#
+            }
+            RuleCode::UnusedOverrideParam => {
+                r#"class MyList(list):
+    def __contains__(self, item):   # RP051 — `item` is unused
+        return True
+"#
+            }
+            RuleCode::LoopVarUsedAfterLoop => {
+                r#"for item in items:
+    process(item)
+print(item)         # RP052 — NameError when `items` is empty
+"#
+            }
+            RuleCode::UndefinedNonlocal => {
+                r#"def outer():
+    def inner():
+        nonlocal x   # RP053 — `outer` never binds `x`
+        x = 1
+"#
+            }
+            RuleCode::GlobalAfterUse => {
+                r#"def reset():
+    count = 0
+    global count     # RP054 — `count` is assigned to before `global` declaration
+"#
+            }
+            RuleCode::UnimportedAnnotation => {
+                r#"def total(prices: List[float]) -> float:   # RP055 — `List` is never imported
+    return sum(prices)
+"#
+            }
+            RuleCode::ReimportAfterBranches => {
+                r#"if FAST:
+    import ujson as json
+else:
+    import json
+import json          # RP056 — overrides whichever branch ran
+"#
+            }
+            RuleCode::TryElseBody => {
+                r#"try:
+    risky()
+except Exception:
+    handle()
+else:
+    safe_continuation()   # RP057 — could follow risky() inside try
+"#
+            }
+            RuleCode::ThrowawayNameUsed => {
+                r#"def report():
+    _ = expensive_computation()
+    print(_)                  # RP058 — `_` is read, so it is not a throwaway
+"#
+            }
+            RuleCode::AnnotationOnlyImport => {
+                r#"from __future__ import annotations
+from typing import Optional      # RP059 — only read inside annotations
+
+def find(key: str) -> Optional[int]:
+    ...
+"#
+            }
+            RuleCode::ImportShadowedByLocal => {
+                r#"import json
+
+def process():
+    json = {}        # RP061 — hides the `json` module in this function
+    return json
+"#
+            }
+            RuleCode::DuplicateExport => {
+                r#"__all__ = ["foo", "bar", "foo"]   # RP062 — second "foo" is redundant
+"#
+            }
+            RuleCode::EmptyTryBody => {
+                r#"try:                  # RP063 — nothing here can raise
+    pass
+except Exception:
+    handle_it()       # never runs
+"#
+            }
+            RuleCode::LoopControlOutsideLoop => {
+                r#"def drain(queue):
+    if not queue:
+        break          # RP064 — SyntaxError: 'break' outside loop
+"#
+            }
+            RuleCode::StringFormatMismatch => {
+                r#""{} {}".format(a)        # RP065 — IndexError: expects 2 args, got 1
+"%s %s" % (a,)           # RP065 — TypeError: not enough arguments
+"#
+            }
+            RuleCode::PointlessFString => {
+                r#"greeting = f"hello world"   # RP066 — nothing to interpolate
+"#
+            }
+            RuleCode::ImplicitStringConcat => {
+                r#"users = [
+    "alice",
+    "bob"        # RP067 — missing comma: this is "bobcarol"
+    "carol",
+]
+"#
+            }
+            RuleCode::EqWithoutHash => {
+                r#"class Point:         # RP068 — Python sets Point.__hash__ = None
+    def __eq__(self, other):
+        return (self.x, self.y) == (other.x, other.y)
+"#
+            }
+            RuleCode::ExceptionWithoutMessage => {
+                r#"def parse(value):
+    if not value:
+        raise ValueError()   # RP069 — the traceback says only "ValueError"
+"#
+            }
+            RuleCode::ExplicitReraise => {
+                r#"try:
+    risky()
+except OSError as e:
+    log.warning("risky failed")
+    raise e          # RP070 — use bare `raise`
+"#
+            }
+            RuleCode::SelfAssignment => {
+                r#"def update(self, other):
+    self.name = self.name    # RP071 — meant other.name?
+    self.tags[0] = self.tags[0]
+"#
+            }
+            RuleCode::PrintStatement => {
+                r#"print "hello"        # RP072 — Python 2 syntax
+"#
+            }
+            RuleCode::GlobalBuiltin => {
+                r#"def reset():
+    global list      # RP073
+    list = []        # every later `list(...)` in the module now fails
+"#
+            }
+            RuleCode::SuppressedAssignment => {
+                r#"with suppress(KeyError):
+    timeout = config["timeout"]
+connect(timeout=timeout)   # RP074 — NameError when the key is missing
+"#
+            }
+        }
+    }
+
+    /// Canonical documentation URL: the rule's section of the README.
+    pub fn url(&self) -> &'static str {
+        match self {
+            RuleCode::UnusedImport => "https://github.com/taradepan/reaper#rp001--unused-import",
+            RuleCode::UnusedVariable => {
+                "https://github.com/taradepan/reaper#rp002--unused-variable"
+            }
+            RuleCode::UnusedFunction => {
+                "https://github.com/taradepan/reaper#rp003--unused-function-cross-file-"
+            }
+            RuleCode::UnusedClass => {
+                "https://github.com/taradepan/reaper#rp004--unused-class-cross-file-"
+            }
+            RuleCode::UnreachableCode => {
+                "https://github.com/taradepan/reaper#rp005--unreachable-code"
+            }
+            RuleCode::DeadBranch => "https://github.com/taradepan/reaper#rp006--dead-branch",
+            RuleCode::RedefinedUnused => {
+                "https://github.com/taradepan/reaper#rp007--import-redefined-before-use"
+            }
+            RuleCode::UnusedArgument => {
+                "https://github.com/taradepan/reaper#rp008--unused-function-argument"
+            }
+            RuleCode::UnusedLoopVariable => {
+                "https://github.com/taradepan/reaper#rp009--unused-loop-variable"
+            }
+            RuleCode::UnusedExceptVar => {
+                "https://github.com/taradepan/reaper#rp010--unused-exception-variable"
+            }
+            RuleCode::UnusedWithTarget => {
+                "https://github.com/taradepan/reaper#rp011--unused-with-target"
+            }
+            RuleCode::ShadowedBuiltin => {
+                "https://github.com/taradepan/reaper#rp012--shadowed-builtin"
+            }
+            RuleCode::RedundantElse => "https://github.com/taradepan/reaper#rp013--redundant-else",
+            RuleCode::UnusedClassVar => {
+                "https://github.com/taradepan/reaper#rp014--unused-class-variable"
+            }
+            RuleCode::DeadVersionBranch => {
+                "https://github.com/taradepan/reaper#rp015--dead-version-branch"
+            }
+            RuleCode::UnusedGlobal => {
+                "https://github.com/taradepan/reaper#rp016--unused-global-declaration"
+            }
+            RuleCode::StarImport => "https://github.com/taradepan/reaper#rp047--star-import",
+            RuleCode::UndefinedExport => {
+                "https://github.com/taradepan/reaper#rp048--undefined-__all__-entry"
+            }
+            RuleCode::InitParamNotStored => {
+                "https://github.com/taradepan/reaper#rp049--__init__-parameter-never-stored"
+            }
+            RuleCode::InconsistentReturn => {
+                "https://github.com/taradepan/reaper#rp050--inconsistent-return"
+            }
+            RuleCode::UnusedOverrideParam => {
+                "https://github.com/taradepan/reaper#rp051--unused-override-parameter"
+            }
+            RuleCode::LoopVarUsedAfterLoop => {
+                "https://github.com/taradepan/reaper#rp052--loop-variable-used-after-loop"
+            }
+            RuleCode::UndefinedNonlocal => {
+                "https://github.com/taradepan/reaper#rp053--undefined-nonlocal-name"
+            }
+            RuleCode::GlobalAfterUse => {
+                "https://github.com/taradepan/reaper#rp054--global-declared-after-use"
+            }
+            RuleCode::UnimportedAnnotation => {
+                "https://github.com/taradepan/reaper#rp055--unimported-annotation-name"
+            }
+            RuleCode::ReimportAfterBranches => {
+                "https://github.com/taradepan/reaper#rp056--re-import-after-conditional-import"
+            }
+            RuleCode::TryElseBody => {
+                "https://github.com/taradepan/reaper#rp057--unnecessary-try-else"
+            }
+            RuleCode::ThrowawayNameUsed => {
+                "https://github.com/taradepan/reaper#rp058--throwaway-name-used"
+            }
+            RuleCode::AnnotationOnlyImport => {
+                "https://github.com/taradepan/reaper#rp059--import-only-used-in-annotations"
+            }
+            RuleCode::ImportShadowedByLocal => {
+                "https://github.com/taradepan/reaper#rp061--import-shadowed-by-local"
+            }
+            RuleCode::DuplicateExport => {
+                "https://github.com/taradepan/reaper#rp062--duplicate-export"
+            }
+            RuleCode::EmptyTryBody => "https://github.com/taradepan/reaper#rp063--empty-try-body",
+            RuleCode::LoopControlOutsideLoop => {
+                "https://github.com/taradepan/reaper#rp064--loop-control-outside-loop"
+            }
+            RuleCode::StringFormatMismatch => {
+                "https://github.com/taradepan/reaper#rp065--string-format-argument-mismatch"
+            }
+            RuleCode::PointlessFString => {
+                "https://github.com/taradepan/reaper#rp066--f-string-without-placeholders"
+            }
+            RuleCode::ImplicitStringConcat => {
+                "https://github.com/taradepan/reaper#rp067--implicit-string-concatenation"
+            }
+            RuleCode::EqWithoutHash => "https://github.com/taradepan/reaper#rp068--eq-without-hash",
+            RuleCode::ExceptionWithoutMessage => {
+                "https://github.com/taradepan/reaper#rp069--exception-without-message"
+            }
+            RuleCode::ExplicitReraise => {
+                "https://github.com/taradepan/reaper#rp070--explicit-re-raise"
+            }
+            RuleCode::SelfAssignment => {
+                "https://github.com/taradepan/reaper#rp071--self-assignment"
+            }
+            RuleCode::PrintStatement => {
+                "https://github.com/taradepan/reaper#rp072--print-statement"
+            }
+            RuleCode::GlobalBuiltin => "https://github.com/taradepan/reaper#rp073--global-builtin",
+            RuleCode::SuppressedAssignment => {
+                "https://github.com/taradepan/reaper#rp074--possibly-unbound-after-with"
+            }
+        }
+    }
+
+    /// Default severity of this rule.
+    pub fn severity(&self) -> Severity {
+        match self {
+            RuleCode::UnreachableCode
+            | RuleCode::DeadBranch
+            | RuleCode::UndefinedNonlocal
+            | RuleCode::GlobalAfterUse
+            | RuleCode::LoopControlOutsideLoop
+            | RuleCode::PrintStatement
+            | RuleCode::GlobalBuiltin => Severity::Error,
+            RuleCode::InconsistentReturn
+            | RuleCode::TryElseBody
+            | RuleCode::RedundantElse
+            | RuleCode::AnnotationOnlyImport
+            | RuleCode::PointlessFString
+            | RuleCode::ImplicitStringConcat
+            | RuleCode::ExceptionWithoutMessage => Severity::Info,
+            _ => Severity::Warning,
+        }
+    }
+
+    /// The category `--select-category` groups this rule under.
+    pub fn category(&self) -> Category {
+        match self {
+            RuleCode::UnusedImport
+            | RuleCode::RedefinedUnused
+            | RuleCode::StarImport
+            | RuleCode::UnimportedAnnotation
+            | RuleCode::ReimportAfterBranches
+            | RuleCode::AnnotationOnlyImport
+            | RuleCode::ImportShadowedByLocal => Category::Imports,
+            RuleCode::UnusedVariable
+            | RuleCode::UnusedLoopVariable
+            | RuleCode::UnusedExceptVar
+            | RuleCode::UnusedWithTarget
+            | RuleCode::ShadowedBuiltin
+            | RuleCode::LoopVarUsedAfterLoop
+            | RuleCode::UndefinedNonlocal
+            | RuleCode::GlobalAfterUse
+            | RuleCode::ThrowawayNameUsed
+            | RuleCode::SelfAssignment
+            | RuleCode::GlobalBuiltin
+            | RuleCode::UnusedGlobal
+            | RuleCode::SuppressedAssignment => Category::Variables,
+            RuleCode::UnreachableCode
+            | RuleCode::DeadBranch
+            | RuleCode::DeadVersionBranch
+            | RuleCode::RedundantElse
+            | RuleCode::InconsistentReturn
+            | RuleCode::TryElseBody
+            | RuleCode::EmptyTryBody
+            | RuleCode::LoopControlOutsideLoop
+            | RuleCode::ExplicitReraise => Category::ControlFlow,
+            RuleCode::UnusedFunction
+            | RuleCode::UnusedClass
+            | RuleCode::UnusedClassVar
+            | RuleCode::UndefinedExport
+            | RuleCode::DuplicateExport
+            | RuleCode::EqWithoutHash => Category::Definitions,
+            RuleCode::UnusedArgument
+            | RuleCode::InitParamNotStored
+            | RuleCode::UnusedOverrideParam => Category::Arguments,
+            RuleCode::StringFormatMismatch
+            | RuleCode::PointlessFString
+            | RuleCode::ImplicitStringConcat
+            | RuleCode::ExceptionWithoutMessage
+            | RuleCode::PrintStatement => Category::Expressions,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        };
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Diagnostic {
     pub file: String,
     pub line: usize,
     pub col: usize,
+    /// Byte range in the source that the diagnostic points at.  For a name
+    /// it covers the name; otherwise it runs to the end of the line.
+    /// Offsets are into the decoded UTF-8 text ([`crate::encoding::Source::text`]),
+    /// so they skip a BOM and differ from file offsets in other encodings.
+    pub start_byte: usize,
+    pub end_byte: usize,
     pub code: RuleCode,
     pub message: String,
+    /// Machine-applicable edit that resolves this diagnostic, if one is safe.
+    pub fix: Option<Fix>,
 }
 
 impl fmt::Display for Diagnostic {
@@ -59,8 +989,11 @@ mod tests {
             file: "src/foo.py".to_string(),
             line: 12,
             col: 5,
+            start_byte: 0,
+            end_byte: 0,
             code: RuleCode::UnusedImport,
             message: "`os` imported but unused".to_string(),
+            fix: None,
         };
         assert_eq!(
             d.to_string(),
@@ -79,6 +1012,83 @@ mod tests {
         assert_eq!(RuleCode::RedefinedUnused.to_string(), "RP007");
         assert_eq!(RuleCode::UnusedArgument.to_string(), "RP008");
         assert_eq!(RuleCode::UnusedLoopVariable.to_string(), "RP009");
+        assert_eq!(RuleCode::UnusedExceptVar.to_string(), "RP010");
+        assert_eq!(RuleCode::UnusedWithTarget.to_string(), "RP011");
+        assert_eq!(RuleCode::ShadowedBuiltin.to_string(), "RP012");
+        assert_eq!(RuleCode::RedundantElse.to_string(), "RP013");
+        assert_eq!(RuleCode::UnusedClassVar.to_string(), "RP014");
+        assert_eq!(RuleCode::DeadVersionBranch.to_string(), "RP015");
+        assert_eq!(RuleCode::UnusedGlobal.to_string(), "RP016");
+        assert_eq!(RuleCode::StarImport.to_string(), "RP047");
+        assert_eq!(RuleCode::UndefinedExport.to_string(), "RP048");
+        assert_eq!(RuleCode::InitParamNotStored.to_string(), "RP049");
+        assert_eq!(RuleCode::InconsistentReturn.to_string(), "RP050");
+        assert_eq!(RuleCode::UnusedOverrideParam.to_string(), "RP051");
+        assert_eq!(RuleCode::LoopVarUsedAfterLoop.to_string(), "RP052");
+        assert_eq!(RuleCode::UndefinedNonlocal.to_string(), "RP053");
+        assert_eq!(RuleCode::GlobalAfterUse.to_string(), "RP054");
+        assert_eq!(RuleCode::UnimportedAnnotation.to_string(), "RP055");
+        assert_eq!(RuleCode::ReimportAfterBranches.to_string(), "RP056");
+        assert_eq!(RuleCode::TryElseBody.to_string(), "RP057");
+        assert_eq!(RuleCode::ThrowawayNameUsed.to_string(), "RP058");
+        assert_eq!(RuleCode::AnnotationOnlyImport.to_string(), "RP059");
+        assert_eq!(RuleCode::ImportShadowedByLocal.to_string(), "RP061");
+        assert_eq!(RuleCode::DuplicateExport.to_string(), "RP062");
+        assert_eq!(RuleCode::EmptyTryBody.to_string(), "RP063");
+        assert_eq!(RuleCode::LoopControlOutsideLoop.to_string(), "RP064");
+        assert_eq!(RuleCode::StringFormatMismatch.to_string(), "RP065");
+        assert_eq!(RuleCode::PointlessFString.to_string(), "RP066");
+        assert_eq!(RuleCode::ImplicitStringConcat.to_string(), "RP067");
+        assert_eq!(RuleCode::EqWithoutHash.to_string(), "RP068");
+        assert_eq!(RuleCode::ExceptionWithoutMessage.to_string(), "RP069");
+        assert_eq!(RuleCode::ExplicitReraise.to_string(), "RP070");
+        assert_eq!(RuleCode::SelfAssignment.to_string(), "RP071");
+        assert_eq!(RuleCode::PrintStatement.to_string(), "RP072");
+        assert_eq!(RuleCode::GlobalBuiltin.to_string(), "RP073");
+        assert_eq!(RuleCode::SuppressedAssignment.to_string(), "RP074");
+    }
+
+    #[test]
+    fn test_severity_ordering() {
+        assert_eq!(RuleCode::UnreachableCode.severity(), Severity::Error);
+        assert_eq!(RuleCode::UnusedImport.severity(), Severity::Warning);
+        assert!(Severity::Error < Severity::Warning);
+        assert_eq!(Severity::Warning.to_string(), "warning");
+        assert_eq!(RuleCode::InconsistentReturn.severity(), Severity::Info);
+        assert!(Severity::Warning < Severity::Info);
+    }
+
+    #[test]
+    fn test_severity_from_str() {
+        for severity in Severity::ALL {
+            assert_eq!(severity.to_string().parse::<Severity>(), Ok(*severity));
+        }
+        assert_eq!("ERROR".parse::<Severity>(), Ok(Severity::Error));
+        assert!("fatal".parse::<Severity>().unwrap_err().contains("fatal"));
+    }
+
+    #[test]
+    fn test_categories() {
+        assert_eq!(RuleCode::UnusedImport.category(), Category::Imports);
+        assert_eq!(RuleCode::RedefinedUnused.category(), Category::Imports);
+        assert_eq!(RuleCode::UnusedVariable.category(), Category::Variables);
+        assert_eq!(RuleCode::UnusedLoopVariable.category(), Category::Variables);
+        assert_eq!(RuleCode::DeadBranch.category(), Category::ControlFlow);
+        assert_eq!(RuleCode::UnusedClass.category(), Category::Definitions);
+        assert_eq!(RuleCode::UnusedArgument.category(), Category::Arguments);
+    }
+
+    #[test]
+    fn test_category_parse_round_trip() {
+        for category in Category::ALL {
+            assert_eq!(category.to_string().parse::<Category>(), Ok(*category));
+        }
+        assert_eq!(
+            "Control-Flow".parse::<Category>(),
+            Ok(Category::ControlFlow)
+        );
+        let err = "loops".parse::<Category>().unwrap_err();
+        assert!(err.contains("control-flow"), "got: {err}");
     }
 
     #[test]
@@ -87,4 +1097,52 @@ mod tests {
         let b = a.clone();
         assert_eq!(a, b);
     }
+
+    #[test]
+    fn test_rule_codes_round_trip() {
+        for rule in RuleCode::ALL {
+            assert_eq!(RuleCode::from_code(&rule.to_string()).as_ref(), Some(rule));
+        }
+        assert_eq!(RuleCode::from_code("rp002"), Some(RuleCode::UnusedVariable));
+        assert_eq!(RuleCode::from_code("RP999"), None);
+    }
+
+    #[test]
+    fn test_every_rule_has_a_one_sentence_description() {
+        for rule in RuleCode::ALL {
+            let d = rule.description();
+            assert!(d.ends_with('.') && !d.contains('\n'), "{rule}: {d}");
+        }
+    }
+
+    #[test]
+    fn test_rule_urls_point_at_readme_sections() {
+        assert_eq!(
+            RuleCode::UnusedImport.url(),
+            "https://github.com/taradepan/reaper#rp001--unused-import"
+        );
+        // Anchors are derived from the README headings; keep them in sync.
+        let readme = include_str!("../README.md");
+        for rule in RuleCode::ALL {
+            let heading = format!("### {rule} — {}", rule.title());
+            assert!(readme.contains(&heading), "README lacks `{heading}`");
+            let anchor = rule.url().rsplit('#').next().unwrap();
+            assert!(anchor.starts_with(&rule.to_string().to_lowercase()));
+        }
+    }
+
+    #[test]
+    fn test_rule_examples_match_readme() {
+        let readme = include_str!("../README.md");
+        for rule in RuleCode::ALL {
+            let heading = format!("### {rule} — {}", rule.title());
+            let section = &readme[readme.find(&heading).unwrap()..];
+            let block = section.split("```python\n").nth(1).unwrap();
+            assert!(
+                block.starts_with(&format!("{}```", rule.example())),
+                "README example for {rule} differs"
+            );
+            assert!(rule.example().contains(&rule.to_string()), "{rule}");
+        }
+    }
 }
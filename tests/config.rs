@@ -0,0 +1,267 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+// ── helpers ──────────────────────────────────────────────────────────────────
+
+fn reaper_bin() -> PathBuf {
+    PathBuf::from(env!("CARGO_BIN_EXE_reaper"))
+}
+
+/// A temporary project: write files into it, then run reaper from its root.
+struct Project {
+    dir: tempfile::TempDir,
+    /// `XDG_CACHE_HOME` for every run, so the analysis cache never touches
+    /// `~/.cache/reaper` or races with other tests.
+    cache_home: tempfile::TempDir,
+}
+
+impl Project {
+    fn new() -> Self {
+        Self {
+            dir: tempfile::TempDir::new().unwrap(),
+            cache_home: tempfile::TempDir::new().unwrap(),
+        }
+    }
+
+    fn file(&self, name: &str, content: &str) -> &Self {
+        let path = self.dir.path().join(name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        std::fs::write(path, content).unwrap();
+        self
+    }
+
+    fn root(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Run reaper with `args` from the project root.
+    fn run(&self, args: &[&str]) -> Output {
+        Command::new(reaper_bin())
+            .args(args)
+            .current_dir(self.root())
+            .env("XDG_CACHE_HOME", self.cache_home.path())
+            .output()
+            .expect("failed to run reaper")
+    }
+}
+
+fn stdout(out: &Output) -> String {
+    String::from_utf8_lossy(&out.stdout).into_owned()
+}
+
+const UNUSED_IMPORT_AND_FUNCTION: &str = "import os\n\ndef helper():\n    pass\n";
+
+// ── reaper.toml ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_select_from_config_file() {
+    let p = Project::new();
+    p.file("reaper.toml", "select = [\"RP001\"]\n")
+        .file("app.py", UNUSED_IMPORT_AND_FUNCTION);
+
+    let out = stdout(&p.run(&[".", "--no-exit-code"]));
+    assert!(out.contains("RP001"), "got: {out}");
+    assert!(!out.contains("RP003"), "got: {out}");
+}
+
+#[test]
+fn test_cli_select_overrides_config_file() {
+    let p = Project::new();
+    p.file("reaper.toml", "select = [\"RP001\"]\n")
+        .file("app.py", UNUSED_IMPORT_AND_FUNCTION);
+
+    let out = stdout(&p.run(&[".", "--select", "RP003", "--no-exit-code"]));
+    assert!(out.contains("RP003"), "got: {out}");
+    assert!(!out.contains("RP001"), "got: {out}");
+}
+
+#[test]
+fn test_config_file_found_in_ancestor() {
+    let p = Project::new();
+    p.file("reaper.toml", "select = [\"RP001\"]\n")
+        .file("src/pkg/app.py", UNUSED_IMPORT_AND_FUNCTION);
+
+    let out = stdout(&p.run(&["src/pkg", "--no-exit-code"]));
+    assert!(out.contains("RP001"), "got: {out}");
+    assert!(!out.contains("RP003"), "got: {out}");
+}
+
+#[test]
+fn test_exclude_from_config_file() {
+    let p = Project::new();
+    p.file("reaper.toml", "exclude = [\"migrations\"]\n")
+        .file("app.py", "import os\n")
+        .file("migrations/0001.py", "import sys\n");
+
+    let out = stdout(&p.run(&[".", "--no-exit-code"]));
+    assert!(out.contains("app.py"), "got: {out}");
+    assert!(!out.contains("0001.py"), "got: {out}");
+}
+
+#[test]
+fn test_no_exit_code_from_config_file() {
+    let p = Project::new();
+    p.file("app.py", "import os\n");
+    assert_eq!(p.run(&["."]).status.code(), Some(1));
+
+    p.file("reaper.toml", "no_exit_code = true\n");
+    assert_eq!(p.run(&["."]).status.code(), Some(0));
+}
+
+#[test]
+fn test_ignore_names_from_config_file() {
+    let p = Project::new();
+    p.file("reaper.toml", "ignore_names = [\"helper\"]\n")
+        .file("app.py", UNUSED_IMPORT_AND_FUNCTION);
+
+    let out = stdout(&p.run(&[".", "--no-exit-code"]));
+    assert!(out.contains("RP001"), "got: {out}");
+    assert!(!out.contains("helper"), "got: {out}");
+}
+
+#[test]
+fn test_cli_ignore_names_overrides_config_file() {
+    let p = Project::new();
+    p.file("reaper.toml", "ignore_names = [\"helper\"]\n")
+        .file("app.py", UNUSED_IMPORT_AND_FUNCTION);
+
+    let out = stdout(&p.run(&[".", "--ignore-names", "o?", "--no-exit-code"]));
+    assert!(out.contains("helper"), "got: {out}");
+    assert!(!out.contains("`os`"), "got: {out}");
+}
+
+#[test]
+fn test_python_version_from_config_file() {
+    let p = Project::new();
+    p.file("reaper.toml", "python_version = \"3.12\"\n").file(
+        "app.py",
+        "import sys\n\nif sys.version_info >= (3, 12):\n    print(1)\nelse:\n    print(2)\n",
+    );
+
+    let out = stdout(&p.run(&[".", "--no-exit-code"]));
+    assert!(out.contains("always true on Python 3.12"), "got: {out}");
+    let out = stdout(&p.run(&[".", "--python-version", "3.11", "--no-exit-code"]));
+    assert!(out.contains("always false on Python 3.11"), "got: {out}");
+}
+
+#[test]
+fn test_analysis_settings_from_config_file() {
+    let p = Project::new();
+    p.file(
+        "reaper.toml",
+        "framework = \"django\"\nframework_exemptions = [\"route\"]\n\
+         always_used = [\"helper\"]\nexempt_dunders = [\"__build__\"]\n",
+    )
+    .file(
+        "app.py",
+        "@app.route(\"/\")\ndef view(request):\n    return 1\n\n\
+         @receiver(post_save)\ndef on_save(sender):\n    return 1\n\n\
+         def helper():\n    pass\n\n__all__ = [\"view\", \"on_save\", \"__build__\"]\n",
+    );
+
+    let out = stdout(&p.run(&[".", "--no-exit-code"]));
+    assert_eq!(out.trim(), "No issues found", "got: {out}");
+    // A flag replaces the file's list.
+    let out = stdout(&p.run(&[".", "--always-used", "other", "--no-exit-code"]));
+    assert!(out.contains("Function `helper`"), "got: {out}");
+}
+
+#[test]
+fn test_include_from_config_file() {
+    let p = Project::new();
+    p.file("reaper.toml", "include = [\"src/**\"]\n")
+        .file("src/app.py", "import os\n")
+        .file("tests/test_app.py", "import sys\n");
+
+    let out = stdout(&p.run(&[".", "--no-exit-code"]));
+    assert!(out.contains("app.py"), "got: {out}");
+    assert!(!out.contains("test_app.py"), "got: {out}");
+}
+
+#[test]
+fn test_unknown_framework_is_an_error() {
+    let p = Project::new();
+    p.file("reaper.toml", "framework = \"rails\"\n")
+        .file("app.py", "import os\n");
+
+    let out = p.run(&["."]);
+    assert_eq!(out.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("unknown framework"), "got: {stderr}");
+}
+
+// ── pyproject.toml ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_select_from_pyproject() {
+    let p = Project::new();
+    p.file(
+        "pyproject.toml",
+        "[project]\nname = \"app\"\n\n[tool.reaper]\nselect = [\"RP001\"]\n",
+    )
+    .file("app.py", UNUSED_IMPORT_AND_FUNCTION);
+
+    let out = stdout(&p.run(&[".", "--no-exit-code"]));
+    assert!(out.contains("RP001"), "got: {out}");
+    assert!(!out.contains("RP003"), "got: {out}");
+}
+
+#[test]
+fn test_cli_select_overrides_pyproject() {
+    let p = Project::new();
+    p.file("pyproject.toml", "[tool.reaper]\nselect = [\"RP001\"]\n")
+        .file("app.py", UNUSED_IMPORT_AND_FUNCTION);
+
+    let out = stdout(&p.run(&[".", "--select", "RP003", "--no-exit-code"]));
+    assert!(out.contains("RP003"), "got: {out}");
+    assert!(!out.contains("RP001"), "got: {out}");
+}
+
+#[test]
+fn test_reaper_toml_preferred_over_pyproject() {
+    let p = Project::new();
+    p.file("pyproject.toml", "[tool.reaper]\nselect = [\"RP003\"]\n")
+        .file("reaper.toml", "select = [\"RP001\"]\n")
+        .file("app.py", UNUSED_IMPORT_AND_FUNCTION);
+
+    let out = stdout(&p.run(&[".", "--no-exit-code"]));
+    assert!(out.contains("RP001"), "got: {out}");
+    assert!(!out.contains("RP003"), "got: {out}");
+}
+
+#[test]
+fn test_pyproject_without_reaper_table_ignored() {
+    let p = Project::new();
+    p.file("pyproject.toml", "[tool.black]\nline-length = 100\n")
+        .file("app.py", "import os\n");
+
+    let out = p.run(&["."]);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(stdout(&out).contains("RP001"));
+}
+
+#[test]
+fn test_invalid_pyproject_table_is_an_error() {
+    let p = Project::new();
+    p.file("pyproject.toml", "[tool.reaper]\nselct = [\"RP001\"]\n")
+        .file("app.py", "import os\n");
+
+    let out = p.run(&["."]);
+    assert_eq!(out.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("pyproject.toml"), "got: {stderr}");
+}
+
+#[test]
+fn test_invalid_config_file_is_an_error() {
+    let p = Project::new();
+    p.file("reaper.toml", "selct = [\"RP001\"]\n")
+        .file("app.py", "import os\n");
+
+    let out = p.run(&["."]);
+    assert_eq!(out.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("reaper.toml"), "got: {stderr}");
+}
@@ -11,6 +11,9 @@ fn reaper_bin() -> PathBuf {
 struct TempPy {
     dir: tempfile::TempDir,
     files: Vec<PathBuf>,
+    /// `XDG_CACHE_HOME` for every run, so the analysis cache never touches
+    /// `~/.cache/reaper` or races with other tests.
+    cache_home: tempfile::TempDir,
 }
 
 impl TempPy {
@@ -18,9 +21,17 @@ impl TempPy {
         Self {
             dir: tempfile::TempDir::new().unwrap(),
             files: Vec::new(),
+            cache_home: tempfile::TempDir::new().unwrap(),
         }
     }
 
+    /// A reaper command using this project's private cache directory.
+    fn command(&self) -> Command {
+        let mut cmd = Command::new(reaper_bin());
+        cmd.env("XDG_CACHE_HOME", self.cache_home.path());
+        cmd
+    }
+
     fn file(&mut self, name: &str, content: &str) -> &mut Self {
         let path = self.dir.path().join(name);
         if let Some(parent) = path.parent() {
@@ -33,7 +44,7 @@ impl TempPy {
 
     /// Run reaper with the given extra args.  Returns (stdout, stderr, exit_code).
     fn run(&self, extra: &[&str]) -> (String, String, i32) {
-        let mut cmd = Command::new(reaper_bin());
+        let mut cmd = self.command();
         for f in &self.files {
             cmd.arg(f);
         }
@@ -95,6 +106,148 @@ fn test_no_exit_code_flag() {
     assert_eq!(code, 0);
 }
 
+#[test]
+fn test_exit_code_on_severity_error_ignores_warnings() {
+    let mut t = TempPy::new();
+    t.file("loop.py", "for item in range(3):\n    print('hi')\n");
+    let (out, _, code) = t.run(&["--exit-code-on-severity", "error"]);
+    assert!(out.contains("RP009"), "got: {out}");
+    assert_eq!(code, 0);
+}
+
+#[test]
+fn test_exit_code_on_severity_error_fails_on_errors() {
+    let mut t = TempPy::new();
+    t.file(
+        "dead.py",
+        "def f():\n    return 1\n    print('never')\n\nf()\n",
+    );
+    let (out, _, code) = t.run(&["--exit-code-on-severity", "error"]);
+    assert!(out.contains("RP005"), "got: {out}");
+    assert_eq!(code, 1);
+}
+
+#[test]
+fn test_exit_code_on_unknown_severity_is_an_error() {
+    let mut t = TempPy::new();
+    t.file("clean.py", "x = 1\nprint(x)\n");
+    let (_, stderr, code) = t.run(&["--exit-code-on-severity", "fatal"]);
+    assert_eq!(code, 2);
+    assert!(stderr.contains("unknown severity"), "got: {stderr}");
+}
+
+#[test]
+fn test_max_issues_truncates_output() {
+    let mut t = TempPy::new();
+    t.file("bad.py", "import os\nimport sys\nimport re\n");
+    let (out, _, code) = t.run(&["--max-issues", "1"]);
+    assert_eq!(out.matches("RP001").count(), 1, "got: {out}");
+    assert!(out.contains("`os`"), "got: {out}");
+    assert!(out.contains("... and 2 more"), "got: {out}");
+    assert!(out.contains("Found 3 issue(s)"), "got: {out}");
+    assert_eq!(code, 2);
+}
+
+#[test]
+fn test_max_issues_not_exceeded() {
+    let mut t = TempPy::new();
+    t.file("bad.py", "import os\n");
+    let (out, _, code) = t.run(&["--max-issues", "1"]);
+    assert!(!out.contains("more"), "got: {out}");
+    assert_eq!(code, 1);
+}
+
+#[test]
+fn test_max_issues_with_no_exit_code() {
+    let mut t = TempPy::new();
+    t.file("bad.py", "import os\nimport sys\n");
+    let (out, _, code) = t.run(&["--max-issues", "1", "--no-exit-code"]);
+    assert!(out.contains("... and 1 more"), "got: {out}");
+    assert_eq!(code, 0);
+}
+
+#[test]
+fn test_max_issues_json_reports_full_count() {
+    let mut t = TempPy::new();
+    t.file(
+        "bad.py",
+        "import os\nimport sys\nimport re\nimport json\nx = 1\n",
+    );
+    let (out, _, code) = t.run(&["--max-issues", "1", "--json", "--statistics"]);
+    let v: serde_json::Value = serde_json::from_str(&out).expect("valid JSON");
+    assert_eq!(v["diagnostics"].as_array().unwrap().len(), 1, "got: {out}");
+    assert_eq!(v["count"], 4, "got: {out}");
+    assert_eq!(v["hidden"], 3, "got: {out}");
+    assert_eq!(v["health_score"], 20.0, "got: {out}");
+    assert_eq!(code, 2);
+}
+
+#[test]
+fn test_count_prints_bare_integer() {
+    let mut t = TempPy::new();
+    t.file(
+        "bad.py",
+        "import os\nimport sys\n\ndef helper():\n    pass\n",
+    );
+    let full = t.run_no_exit(&[]);
+    let (out, _, code) = t.run(&["--count"]);
+    assert_eq!(out, "3\n");
+    assert!(full.contains("Found 3 issue(s)"), "got: {full}");
+    assert_eq!(code, 1);
+}
+
+#[test]
+fn test_count_json_is_minimal_object() {
+    let mut t = TempPy::new();
+    t.file("bad.py", "import os\n");
+    let (out, _, _) = t.run(&["--count", "--json"]);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(v, serde_json::json!({ "count": 1 }));
+}
+
+#[test]
+fn test_count_respects_filters_and_no_exit_code() {
+    let mut t = TempPy::new();
+    t.file(
+        "bad.py",
+        "import os\nimport sys\n\ndef helper():\n    pass\n",
+    );
+    let (out, _, code) = t.run(&["--count", "--select", "RP003", "--no-exit-code"]);
+    assert_eq!(out.trim(), "1");
+    assert_eq!(code, 0);
+
+    let mut t = TempPy::new();
+    t.file("clean.py", "x = 1\nprint(x)\n");
+    let (out, _, code) = t.run(&["--count"]);
+    assert_eq!(out.trim(), "0");
+    assert_eq!(code, 0);
+}
+
+#[test]
+fn test_cache_written_unless_disabled() {
+    let mut t = TempPy::new();
+    t.file("bad.py", "import os\n");
+    let cache_home = tempfile::TempDir::new().unwrap();
+    let cache_file = cache_home.path().join("reaper").join("cache.json");
+    let run = |extra: &[&str]| {
+        let out = Command::new(reaper_bin())
+            .arg(&t.files[0])
+            .args(extra)
+            .env("XDG_CACHE_HOME", cache_home.path())
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+
+    let out = run(&["--no-cache"]);
+    assert!(out.contains("RP001"), "got: {out}");
+    assert!(!cache_file.exists());
+
+    let first = run(&[]);
+    assert!(cache_file.exists());
+    assert_eq!(run(&[]), first, "a cached run reports the same");
+}
+
 #[test]
 fn test_issue_count_in_summary() {
     let mut t = TempPy::new();
@@ -169,6 +322,40 @@ fn test_rp001_annotation_usage_not_flagged() {
     );
 }
 
+#[test]
+fn test_rp001_type_comment_usage_not_flagged() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "from typing import List\nx = []  # type: List[str]\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP001"), "got: {out}");
+}
+
+#[test]
+fn test_rp001_try_except_import_fallback() {
+    let mut t = TempPy::new();
+    let fallback = "try:\n    import ujson as json\nexcept ImportError:\n    import json\n";
+    t.file("used.py", &format!("{fallback}print(json.dumps({{}}))\n"));
+    t.file("unused.py", fallback);
+    let out = t.run_no_exit(&[]);
+    assert_eq!(out.matches("RP001").count(), 1, "got: {out}");
+    assert!(out.contains("unused.py:2:12: RP001"), "got: {out}");
+}
+
+#[test]
+fn test_rp001_import_used_in_type_alias() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "from collections.abc import Callable\nimport os\ntype Handler = Callable[[int], None]\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("Callable"), "got: {out}");
+    assert!(out.contains("`os`"), "got: {out}");
+}
+
 // ── RP002: unused variables ───────────────────────────────────────────────────
 
 #[test]
@@ -202,6 +389,18 @@ fn test_rp002_no_duplicate_from_nested_scope() {
     assert!(!out.contains("`x`"), "x is used and must not be flagged");
 }
 
+#[test]
+fn test_rp002_unused_match_capture() {
+    let mut t = TempPy::new();
+    t.file(
+        "m.py",
+        "def area(shape):\n    match shape:\n        case Circle(radius=r):\n            return 3\n        case _:\n            return 0\n",
+    );
+    let out = t.run_no_exit(&["--select", "RP002"]);
+    assert!(out.contains("m.py:3:28: RP002"), "{out}");
+    assert!(out.contains("`r`"));
+}
+
 // ── RP003/RP004: unused defs (cross-file) ────────────────────────────────────
 
 #[test]
@@ -221,6 +420,18 @@ fn test_rp003_used_function_not_flagged() {
     assert!(!out.contains("RP003"));
 }
 
+#[test]
+fn test_rp003_unicode_function_name() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "def café():\n    pass\n\ndef 数量():\n    pass\n\n数量()\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("Function `café` is defined"), "got: {out}");
+    assert!(!out.contains("数量"), "got: {out}");
+}
+
 #[test]
 fn test_rp003_main_not_flagged() {
     let mut t = TempPy::new();
@@ -277,6 +488,50 @@ fn test_cross_file_truly_unused_flagged() {
     );
 }
 
+#[test]
+fn test_rp003_pytest_fixture_exempt() {
+    let mut t = TempPy::new();
+    t.file(
+        "conftest.py",
+        "import pytest\n\n@pytest.fixture(scope=\"module\")\ndef conn():\n    return 1\n\n@pytest.fixture\ndef db(conn):\n    return conn\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP003"), "got: {out}");
+    assert!(!out.contains("RP008"), "got: {out}");
+}
+
+#[test]
+fn test_rp003_flask_add_url_rule_view_not_flagged() {
+    let mut t = TempPy::new();
+    t.file(
+        "views.py",
+        "def index():\n    return \"hi\"\n\ndef orphan():\n    pass\n",
+    )
+    .file(
+        "app.py",
+        "from flask import Flask\nimport views\n\napp = Flask(__name__)\napp.add_url_rule(\"/\", view_func=views.index)\n",
+    );
+    let out = t.run_no_exit(&["--select", "RP003"]);
+    assert!(out.contains("`orphan`"), "got: {out}");
+    assert!(!out.contains("`index`"), "got: {out}");
+}
+
+#[test]
+fn test_rp004_nested_package_reexport_not_flagged() {
+    let mut t = TempPy::new();
+    t.file(
+        "pkg/__init__.py",
+        "from .models import Record\n\n__all__ = [\"Record\"]\n",
+    )
+    .file(
+        "pkg/models/__init__.py",
+        "from .base import BaseRecord as Record\n",
+    )
+    .file("pkg/models/base.py", "class BaseRecord:\n    pass\n");
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP004"), "got: {out}");
+}
+
 // ── RP005: unreachable code ───────────────────────────────────────────────────
 
 #[test]
@@ -401,6 +656,30 @@ fn test_rp008_stub_body_exempt() {
     assert!(!out.contains("RP008"));
 }
 
+#[test]
+fn test_rp008_celery_task_exempt() {
+    let mut t = TempPy::new();
+    t.file(
+        "tasks.py",
+        "@app.task\ndef send_email(recipient):\n    return 1\n\ndef helper(x):\n    return 1\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("`recipient`"));
+    assert!(out.contains("Argument `x` is not used"));
+}
+
+#[test]
+fn test_rp008_fastapi_depends_exempt() {
+    let mut t = TempPy::new();
+    t.file(
+        "api.py",
+        "from fastapi import Depends, FastAPI\n\napp = FastAPI()\n\ndef get_db():\n    return 1\n\n@app.get('/items')\ndef list_items(db=Depends(get_db)):\n    return []\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP008"), "got: {out}");
+    assert!(!out.contains("get_db"), "default names are usages: {out}");
+}
+
 // ── RP009: unused loop variable ───────────────────────────────────────────────
 
 #[test]
@@ -428,6 +707,44 @@ fn test_rp009_underscore_exempt() {
     assert!(!out.contains("RP009"));
 }
 
+// ── RP010: unused exception variable ──────────────────────────────────────────
+
+#[test]
+fn test_rp010_unused_except_var() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "def load():\n    try:\n        return read()\n    except OSError as exc:\n        return None\n\nload()\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("RP010"), "got: {out}");
+    assert!(out.contains("`exc`"), "got: {out}");
+    // Reported once, by RP010 rather than RP002.
+    assert!(!out.contains("RP002"), "got: {out}");
+}
+
+#[test]
+fn test_rp010_used_except_var_not_flagged() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "try:\n    read()\nexcept OSError as exc:\n    print(exc)\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP010"), "got: {out}");
+}
+
+#[test]
+fn test_rp010_underscore_exempt() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "try:\n    read()\nexcept OSError as _exc:\n    pass\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP010"), "got: {out}");
+}
+
 #[test]
 fn test_rp009_no_rp002_double_fire_inside_function() {
     // Regression test: an unused loop variable inside a function must produce
@@ -451,119 +768,2005 @@ fn test_rp009_no_rp002_double_fire_inside_function() {
     );
 }
 
-// ── --select filter ───────────────────────────────────────────────────────────
-
 #[test]
-fn test_select_only_rp001() {
+fn test_rp010_except_star() {
     let mut t = TempPy::new();
-    // produces RP001 (unused import) and RP005 (unreachable)
-    t.file("f.py", "import os\ndef foo():\n    return 1\n    x = 2\n");
-    let out = t.run_no_exit(&["--select", "RP001"]);
-    assert!(out.contains("RP001"));
-    assert!(!out.contains("RP005"));
+    t.file(
+        "f.py",
+        "try:\n    run()\nexcept* ValueError as eg:\n    for e in eg.exceptions:\n        print(e)\nexcept* OSError as group:\n    pass\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("`eg`"), "got: {out}");
+    assert!(
+        out.contains("Exception group variable `group` is never used"),
+        "got: {out}"
+    );
 }
 
+// ── RP011: unused with target ─────────────────────────────────────────────────
+
 #[test]
-fn test_select_multiple_codes() {
+fn test_rp011_unused_with_target() {
     let mut t = TempPy::new();
-    t.file("f.py", "import os\ndef foo():\n    return 1\n    x = 2\n");
-    let out = t.run_no_exit(&["--select", "RP001,RP005"]);
-    assert!(out.contains("RP001"));
-    assert!(out.contains("RP005"));
+    t.file(
+        "f.py",
+        "def load(p):\n    with open(p) as fh:\n        return 1\n\nload('x')\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("RP011"), "got: {out}");
+    assert!(out.contains("`fh`"), "got: {out}");
+    assert!(!out.contains("RP002"), "got: {out}");
 }
 
 #[test]
-fn test_select_nonexistent_code_no_output() {
+fn test_rp011_used_with_target_not_flagged() {
     let mut t = TempPy::new();
-    t.file("f.py", "import os\n");
-    let out = t.run_no_exit(&["--select", "RP999"]);
-    assert!(!out.contains("RP001"));
-    assert!(out.contains("No issues found"));
+    t.file("f.py", "with open('x') as fh:\n    print(fh.read())\n");
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP011"), "got: {out}");
 }
 
-// ── --json output ─────────────────────────────────────────────────────────────
-
 #[test]
-fn test_json_output_is_valid_structure() {
+fn test_rp011_underscore_exempt() {
     let mut t = TempPy::new();
-    t.file("f.py", "import os\n");
-    let out = t.run_no_exit(&["--json"]);
-    assert!(out.contains("\"diagnostics\""), "must have diagnostics key");
-    assert!(out.contains("\"code\": \"RP001\""), "must include code");
-    assert!(out.contains("\"file\""), "must include file");
-    assert!(out.contains("\"line\""), "must include line");
-    assert!(out.contains("\"count\""), "must include count");
-    // Verify comma is correctly placed (not on its own line)
-    for line in out.lines() {
-        let trimmed = line.trim();
-        // A line should not be just a bare comma
-        assert_ne!(trimmed, ",", "bare comma line detected — invalid JSON");
-    }
+    t.file("f.py", "with open('x') as _fh:\n    pass\n");
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP011"), "got: {out}");
 }
 
 #[test]
-fn test_json_clean_file() {
+fn test_rp011_partially_used_tuple_not_flagged() {
     let mut t = TempPy::new();
-    t.file("f.py", "x = 1\nprint(x)\n");
-    let out = t.run_no_exit(&["--json"]);
-    assert!(out.contains("\"diagnostics\": []") || out.contains("\"count\": 0"));
+    t.file("f.py", "with pair() as (a, b):\n    print(a)\n");
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP011"), "got: {out}");
 }
 
 #[test]
-fn test_json_message_escaping() {
+fn test_rp011_nested_with() {
     let mut t = TempPy::new();
-    // backtick names in messages must not break JSON strings
-    t.file("f.py", "import os\n");
-    let out = t.run_no_exit(&["--json"]);
-    // The message contains backtick chars — those are fine in JSON, but
-    // double-quotes inside the message must be escaped.
-    assert!(out.contains("imported but unused"));
+    t.file(
+        "f.py",
+        "with outer() as a:\n    with inner(a) as b:\n        pass\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("`b`"), "got: {out}");
+    assert!(!out.contains("`a`"), "got: {out}");
 }
 
-// ── # noqa suppression ────────────────────────────────────────────────────────
+// ── RP012: shadowed builtin ───────────────────────────────────────────────────
 
 #[test]
-fn test_noqa_bare_suppresses_all() {
+fn test_rp012_assignment_shadows_builtin() {
     let mut t = TempPy::new();
-    t.file("f.py", "import os  # noqa\n");
+    t.file("f.py", "list = [1, 2]\nprint(list)\n");
     let out = t.run_no_exit(&[]);
-    assert!(!out.contains("RP001"), "bare # noqa must suppress RP001");
-    assert!(out.contains("No issues found"));
+    assert!(out.contains("RP012"), "got: {out}");
+    assert!(
+        out.contains("Variable `list` shadows a builtin"),
+        "got: {out}"
+    );
 }
 
 #[test]
-fn test_noqa_specific_code_suppresses() {
+fn test_rp012_builtin_method_call_not_flagged() {
     let mut t = TempPy::new();
-    t.file("f.py", "import os  # noqa: RP001\n");
+    t.file("f.py", "items = []\nlist.append(items, 1)\nprint(items)\n");
     let out = t.run_no_exit(&[]);
-    assert!(!out.contains("RP001"));
+    assert!(!out.contains("RP012"), "got: {out}");
 }
 
 #[test]
-fn test_noqa_wrong_code_does_not_suppress() {
+fn test_rp012_parameter_shadows_builtin() {
     let mut t = TempPy::new();
-    t.file("f.py", "import os  # noqa: RP002\n");
+    t.file(
+        "f.py",
+        "def ask(input):\n    return input.strip()\n\nask(' x ')\n",
+    );
     let out = t.run_no_exit(&[]);
     assert!(
-        out.contains("RP001"),
-        "wrong noqa code must not suppress RP001"
+        out.contains("Parameter `input` shadows a builtin"),
+        "got: {out}"
     );
 }
 
 #[test]
-fn test_noqa_multi_code() {
+fn test_rp012_class_attribute_not_flagged() {
     let mut t = TempPy::new();
-    t.file("f.py", "import os  # noqa: RP001, RP002\n");
+    t.file(
+        "f.py",
+        "class Row:\n    id = None\n\n    def type(self):\n        return self.id\n\nRow().type()\n",
+    );
     let out = t.run_no_exit(&[]);
-    assert!(!out.contains("RP001"));
+    assert!(!out.contains("RP012"), "got: {out}");
 }
 
-// ── output format ─────────────────────────────────────────────────────────────
+// ── RP013: redundant else ─────────────────────────────────────────────────────
 
 #[test]
-fn test_output_format_file_line_col_code() {
+fn test_rp013_else_after_return() {
     let mut t = TempPy::new();
-    t.file("f.py", "import os\n");
+    t.file(
+        "f.py",
+        "def sign(x):\n    if x < 0:\n        return -1\n    else:\n        return 1\n\nsign(2)\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("RP013"), "got: {out}");
+    assert!(
+        out.contains("Unnecessary `else` after `return`"),
+        "got: {out}"
+    );
+}
+
+#[test]
+fn test_rp013_plain_if_else_not_flagged() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "def pick(c):\n    if c:\n        x = 1\n    else:\n        x = 2\n    return x\n\npick(True)\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP013"), "got: {out}");
+}
+
+#[test]
+fn test_rp013_branch_that_falls_through_not_flagged() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "def pick(a, b):\n    if a:\n        if b:\n            return 1\n    else:\n        return 2\n\npick(1, 2)\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP013"), "got: {out}");
+}
+
+// ── RP014: unused class variables ─────────────────────────────────────────────
+
+#[test]
+fn test_rp014_unused_class_var() {
+    let mut t = TempPy::new();
+    t.file("f.py", "class Foo:\n    bar = 1\n\nFoo()\n");
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("RP014"), "got: {out}");
+    assert!(
+        out.contains("Class variable `bar` is defined but never used"),
+        "got: {out}"
+    );
+}
+
+#[test]
+fn test_rp014_attribute_read_in_other_file_not_flagged() {
+    let mut t = TempPy::new();
+    t.file("settings.py", "class Defaults:\n    timeout = 30\n")
+        .file(
+            "app.py",
+            "from settings import Defaults\n\nprint(Defaults.timeout)\n",
+        );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP014"), "got: {out}");
+}
+
+#[test]
+fn test_rp014_subclass_not_flagged() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "from enum import Enum\n\nclass Color(Enum):\n    RED = 1\n\nprint(Color)\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP014"), "got: {out}");
+}
+
+// ── RP015: dead version branch ────────────────────────────────────────────────
+
+#[test]
+fn test_rp015_branch_for_python_2() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "import sys\n\nif sys.version_info < (3,):\n    text = unicode\nelse:\n    text = str\nprint(text)\n",
+    );
+    let out = t.run_no_exit(&["--python-version", "3.11"]);
+    assert!(out.contains("f.py:3:1: RP015"), "got: {out}");
+    assert!(out.contains("is always false on Python 3."), "got: {out}");
+}
+
+#[test]
+fn test_rp015_follows_python_version() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "import sys\n\nif sys.version_info >= (3, 12):\n    fast = True\nelse:\n    fast = False\nprint(fast)\n",
+    );
+    let out = t.run_no_exit(&["--python-version", "3.12"]);
+    assert!(out.contains("always true on Python 3.12"), "got: {out}");
+    let out = t.run_no_exit(&["--python-version", "3.9"]);
+    assert!(out.contains("always false on Python 3.9"), "got: {out}");
+}
+
+#[test]
+fn test_invalid_python_version_is_a_usage_error() {
+    let mut t = TempPy::new();
+    t.file("f.py", "x = 1\n");
+    let (_, stderr, code) = t.run(&["--python-version", "three"]);
+    assert_eq!(code, 2);
+    assert!(stderr.contains("MAJOR.MINOR"), "got: {stderr}");
+}
+
+// ── RP016: unused global declaration ─────────────────────────────────────────
+
+#[test]
+fn test_rp016_unused_global() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "count = 0\n\ndef report(total):\n    global count\n    return total\n\ndef bump():\n    global count\n    count += 1\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert_eq!(out.matches("RP016").count(), 1, "got: {out}");
+    assert!(out.contains("f.py:4:5: RP016"), "got: {out}");
+}
+
+// ── RP047: star imports ───────────────────────────────────────────────────────
+
+#[test]
+fn test_rp047_star_import_in_module() {
+    let mut t = TempPy::new();
+    t.file("utils.py", "from os import *\n");
+    let out = t.run_no_exit(&[]);
+    assert_eq!(out.matches("RP047").count(), 1);
+}
+
+#[test]
+fn test_rp047_star_import_in_init_not_flagged() {
+    let mut t = TempPy::new();
+    t.file("pkg/__init__.py", "from os import *\n");
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP047"));
+}
+
+// ── RP048: undefined __all__ entries ─────────────────────────────────────────
+
+#[test]
+fn test_rp048_undefined_all_entry() {
+    let mut t = TempPy::new();
+    t.file("f.py", "__all__ = [\"undefined\"]\n");
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("RP048"));
+    assert!(out.contains("`undefined`"));
+}
+
+#[test]
+fn test_rp048_defined_entry_clean() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "__all__ = [\"defined\"]\ndef defined():\n    pass\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP048"));
+}
+
+#[test]
+fn test_rp048_star_import_suppresses() {
+    let mut t = TempPy::new();
+    t.file(
+        "pkg/__init__.py",
+        "from os.path import *\n__all__ = [\"join\"]\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP048"));
+}
+
+// ── RP049: __init__ parameter never stored ───────────────────────────────────
+
+#[test]
+fn test_rp049_init_param_not_stored() {
+    let mut t = TempPy::new();
+    t.file(
+        "p.py",
+        "class Point:\n    def __init__(self, x, y):\n        self.x = x\n        print(y)\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("p.py:2:27: RP049"), "got: {out}");
+    assert!(out.contains("`y`"));
+}
+
+#[test]
+fn test_rp049_all_params_stored_clean() {
+    let mut t = TempPy::new();
+    t.file(
+        "p.py",
+        "class Point:\n    def __init__(self, x, y):\n        self.x = x\n        self.y = y\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP049"), "got: {out}");
+}
+
+// ── RP050: inconsistent returns ──────────────────────────────────────────────
+
+#[test]
+fn test_rp050_implicit_none_fallthrough() {
+    let mut t = TempPy::new();
+    t.file("r.py", "def foo(x):\n    if x:\n        return 1\n");
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("r.py:1:1: RP050"), "got: {out}");
+}
+
+#[test]
+fn test_rp050_all_paths_explicit_clean() {
+    let mut t = TempPy::new();
+    t.file(
+        "r.py",
+        "def foo(x):\n    if x != 0:\n        return 1\n    return None\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP050"), "got: {out}");
+}
+
+#[test]
+fn test_rp050_fallthrough_reported_once() {
+    let mut t = TempPy::new();
+    t.file(
+        "r.py",
+        "def get_value(x):\n    if x > 0:\n        return x\n\nget_value(1)\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert_eq!(out.matches("r.py:1:1:").count(), 1, "got: {out}");
+    assert!(out.contains("r.py:1:1: RP050"), "got: {out}");
+}
+
+#[test]
+fn test_rp050_property_clean() {
+    let mut t = TempPy::new();
+    t.file(
+        "r.py",
+        "class C:\n    @property\n    def v(self):\n        if self.x:\n            return 1\n\nC()\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP050"), "got: {out}");
+}
+
+// ── RP051: unused override parameters ────────────────────────────────────────
+
+#[test]
+fn test_rp051_dunder_override_unused_param() {
+    let mut t = TempPy::new();
+    t.file(
+        "l.py",
+        "class MyList(list):\n    def __contains__(self, item):\n        return True\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("l.py:2:28: RP051"), "got: {out}");
+    assert!(!out.contains("RP008"), "got: {out}");
+}
+
+// ── RP052: loop variable used after loop ─────────────────────────────────────
+
+#[test]
+fn test_rp052_loop_var_read_after_loop() {
+    let mut t = TempPy::new();
+    t.file("l.py", "for i in items:\n    pass\nprint(i)\n");
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("l.py:3:1: RP052"), "got: {out}");
+    // The read after the loop is the point, so RP009 stays quiet.
+    assert!(!out.contains("RP009"), "got: {out}");
+}
+
+#[test]
+fn test_rp052_initialized_before_loop_clean() {
+    let mut t = TempPy::new();
+    t.file("l.py", "i = None\nfor i in items:\n    pass\nprint(i)\n");
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP052"), "got: {out}");
+}
+
+// ── RP053: undefined nonlocal name ───────────────────────────────────────────
+
+#[test]
+fn test_rp053_nonlocal_without_enclosing_binding() {
+    let mut t = TempPy::new();
+    t.file(
+        "n.py",
+        "def outer():\n    def inner():\n        nonlocal x\n        x = 1\n    return inner\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("n.py:3:9: RP053"), "got: {out}");
+}
+
+// ── RP054: global declared after use ─────────────────────────────────────────
+
+#[test]
+fn test_rp054_global_after_local_assignment() {
+    let mut t = TempPy::new();
+    t.file("g.py", "def f():\n    x = 1\n    global x\n");
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("g.py:3:5: RP054"), "got: {out}");
+}
+
+#[test]
+fn test_rp054_global_before_assignment_clean() {
+    let mut t = TempPy::new();
+    t.file("g.py", "def f():\n    global x\n    x = 1\n");
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP054"), "got: {out}");
+}
+
+// ── RP055: unimported annotation name ────────────────────────────────────────
+
+#[test]
+fn test_rp055_annotation_without_import() {
+    let mut t = TempPy::new();
+    t.file("a.py", "def f(x: List[int]):\n    pass\n\nf([])\n");
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("a.py:1:10: RP055"), "got: {out}");
+
+    t.file(
+        "b.py",
+        "from typing import List\n\ndef g(x: List[int]):\n    pass\n\ng([])\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("b.py:3:10: RP055"), "got: {out}");
+}
+
+// ── RP056: re-import after conditional import ────────────────────────────────
+
+#[test]
+fn test_rp056_reimport_after_if_else() {
+    let mut t = TempPy::new();
+    t.file(
+        "j.py",
+        "if FAST:\n    import ujson as json\nelse:\n    import json\nimport json\n\nprint(json.dumps(1))\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("j.py:5:8: RP056"), "got: {out}");
+
+    t.file(
+        "k.py",
+        "if FAST:\n    import ujson as json\nimport json\n\nprint(json.dumps(1))\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("k.py:3:8: RP056"), "got: {out}");
+}
+
+// ── RP057: unnecessary try else ──────────────────────────────────────────────
+
+#[test]
+fn test_rp057_try_else_body() {
+    let mut t = TempPy::new();
+    t.file(
+        "t.py",
+        "try:\n    risky()\nexcept ValueError:\n    pass\nelse:\n    do_more()\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("t.py:6:5: RP057"), "got: {out}");
+}
+
+#[test]
+fn test_rp057_try_without_else_clean() {
+    let mut t = TempPy::new();
+    t.file("t.py", "try:\n    f()\nexcept ValueError:\n    g()\n");
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP057"), "got: {out}");
+}
+
+// ── RP058: throwaway name used ───────────────────────────────────────────────
+
+#[test]
+fn test_rp058_throwaway_name_used() {
+    let mut t = TempPy::new();
+    t.file("t.py", "def f():\n    _ = compute()\n    print(_)\n");
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("t.py:2:5: RP058"), "got: {out}");
+    assert!(!out.contains("RP002"), "got: {out}");
+}
+
+#[test]
+fn test_rp058_unused_throwaway_clean() {
+    let mut t = TempPy::new();
+    t.file("t.py", "def f():\n    _ = compute()\n");
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP058"), "got: {out}");
+    assert!(!out.contains("RP002"), "got: {out}");
+}
+
+// ── RP059: import only used in annotations ───────────────────────────────────
+
+#[test]
+fn test_rp059_annotation_only_import() {
+    let mut t = TempPy::new();
+    t.file(
+        "t.py",
+        "from typing import Optional\n\ndef f(x: Optional[int]):\n    pass\n\nf(1)\n",
+    );
+    // Annotations are evaluated eagerly before 3.14, so the import must stay.
+    let out = t.run_no_exit(&["--python-version", "3.13"]);
+    assert!(!out.contains("RP059"), "got: {out}");
+    let out = t.run_no_exit(&["--python-version", "3.14"]);
+    assert!(out.contains("t.py:1:20: RP059"), "got: {out}");
+}
+
+#[test]
+fn test_rp059_future_annotations() {
+    let mut t = TempPy::new();
+    t.file(
+        "t.py",
+        "from __future__ import annotations\nfrom typing import Optional\n\ndef f(x: Optional[int]):\n    pass\n\nf(1)\n",
+    );
+    let out = t.run_no_exit(&["--python-version", "3.9"]);
+    assert!(out.contains("t.py:2:20: RP059"), "got: {out}");
+}
+
+#[test]
+fn test_rp059_runtime_use_clean() {
+    let mut t = TempPy::new();
+    t.file("t.py", "from typing import cast\n\nprint(cast(int, 1))\n");
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP059"), "got: {out}");
+}
+
+// ── RP061: import shadowed by local ──────────────────────────────────────────
+
+#[test]
+fn test_rp061_local_shadows_import() {
+    let mut t = TempPy::new();
+    t.file(
+        "s.py",
+        "import json\n\ndef process():\n    json = {}\n    return json\n\nprocess()\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("s.py:4:5: RP061"), "got: {out}");
+}
+
+#[test]
+fn test_rp061_distinct_local_clean() {
+    let mut t = TempPy::new();
+    t.file(
+        "s.py",
+        "import os\n\ndef f():\n    my_os = os\n    return my_os\n\nf()\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP061"), "got: {out}");
+}
+
+// ── RP062: duplicate __all__ entry ──────────────────────────────────────────
+
+#[test]
+fn test_rp062_duplicate_export() {
+    let mut t = TempPy::new();
+    t.file(
+        "m.py",
+        "__all__ = [\"foo\", \"bar\", \"foo\"]\n\ndef foo():\n    pass\n\ndef bar():\n    pass\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("m.py:1:26: RP062"), "got: {out}");
+    assert!(!out.contains("RP048"), "got: {out}");
+}
+
+// ── RP063: empty try body ────────────────────────────────────────────────────
+
+#[test]
+fn test_rp063_pass_try_body() {
+    let mut t = TempPy::new();
+    t.file(
+        "t.py",
+        "def handle():\n    pass\n\ntry:\n    pass\nexcept Exception:\n    handle()\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("t.py:4:1: RP063"), "got: {out}");
+}
+
+// ── RP064: loop control outside loop ─────────────────────────────────────────
+
+#[test]
+fn test_rp064_continue_in_nested_function() {
+    let mut t = TempPy::new();
+    t.file(
+        "l.py",
+        "for i in range(3):\n    def g():\n        continue\n    g()\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("l.py:3:9: RP064"), "got: {out}");
+}
+
+// ── RP065: string format argument mismatch ───────────────────────────────────
+
+#[test]
+fn test_rp065_format_arg_count() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "a = 1\nb = 2\nprint(\"{} {}\".format(a))\nprint(\"{} {}\".format(a, b))\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(
+        out.contains("f.py:3:7: RP065 Format string expects 2 args, got 1"),
+        "got: {out}"
+    );
+    assert_eq!(out.matches("RP065").count(), 1, "got: {out}");
+}
+
+// ── RP066: f-string without placeholders ────────────────────────────────────
+
+#[test]
+fn test_rp066_plain_fstring() {
+    let mut t = TempPy::new();
+    t.file(
+        "g.py",
+        "name = 'x'\nprint(f\"hello world\")\nprint(f\"hello {name}\")\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("g.py:2:7: RP066"), "got: {out}");
+    assert_eq!(out.matches("RP066").count(), 1, "got: {out}");
+}
+
+// ── RP067: implicit string concatenation ─────────────────────────────────────
+
+#[test]
+fn test_rp067_missing_comma_in_list() {
+    let mut t = TempPy::new();
+    t.file(
+        "c.py",
+        "users = [\n    \"alice\",\n    \"bob\"\n    \"carol\",\n]\nprint(users)\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("c.py:4:5: RP067"), "got: {out}");
+}
+
+// ── RP068: __eq__ without __hash__ ───────────────────────────────────────────
+
+#[test]
+fn test_rp068_eq_without_hash() {
+    let mut t = TempPy::new();
+    t.file(
+        "p.py",
+        "class Point:\n    def __eq__(self, other):\n        return True\n\nclass Key(tuple):\n    def __eq__(self, other):\n        return True\n\nprint(Point, Key)\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("p.py:1:1: RP068 Class `Point`"), "got: {out}");
+    assert!(!out.contains("`Key`"), "got: {out}");
+}
+
+// ── RP069: exception without message ─────────────────────────────────────────
+
+#[test]
+fn test_rp069_exception_without_message() {
+    let mut t = TempPy::new();
+    t.file(
+        "e.py",
+        "def check(x):\n    if x < 0:\n        raise ValueError()\n    if x > 9:\n        raise ValueError(\"too big\")\n    return x\n\ncheck(1)\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("e.py:3:9: RP069"), "got: {out}");
+    assert!(!out.contains("e.py:5:"), "got: {out}");
+}
+
+// ── RP070: re-raise by name ──────────────────────────────────────────────────
+
+#[test]
+fn test_rp070_explicit_reraise() {
+    let mut t = TempPy::new();
+    t.file(
+        "r.py",
+        "def load(path):\n    try:\n        return open(path).read()\n    except OSError as exc:\n        raise exc\n\nload(\"x\")\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(
+        out.contains("r.py:5:9: RP070 `raise exc` loses original traceback"),
+        "got: {out}"
+    );
+}
+
+// ── RP071: self assignment ───────────────────────────────────────────────────
+
+#[test]
+fn test_rp071_self_assignment() {
+    let mut t = TempPy::new();
+    t.file(
+        "s.py",
+        "class Box:\n    def fill(self, other):\n        self.items = self.items\n        self.size = other.size\n\nBox().fill(Box())\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(
+        out.contains("s.py:3:9: RP071 `self.items` is assigned to itself"),
+        "got: {out}"
+    );
+    assert!(!out.contains("s.py:4:"), "got: {out}");
+}
+
+// ── RP072: Python 2 print statement ──────────────────────────────────────────
+
+#[test]
+fn test_rp072_print_statement() {
+    let mut t = TempPy::new();
+    t.file("p.py", "print \"hello\"\nprint(\"world\")\n");
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("p.py:1:1: RP072"), "got: {out}");
+    assert!(!out.contains("p.py:2:"), "got: {out}");
+}
+
+// ── RP073: global builtin ────────────────────────────────────────────────────
+
+#[test]
+fn test_rp073_global_builtin() {
+    let mut t = TempPy::new();
+    t.file(
+        "g.py",
+        "def reset():\n    global list\n    list = []\n\nreset()\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(
+        out.contains("g.py:2:5: RP073 Global declaration of `list`"),
+        "got: {out}"
+    );
+}
+
+// ── RP074: possibly unbound after with ───────────────────────────────────────
+
+#[test]
+fn test_rp074_possibly_unbound_after_suppress() {
+    let mut t = TempPy::new();
+    t.file(
+        "w.py",
+        "from contextlib import suppress\n\ndef load(config):\n    with suppress(KeyError):\n        timeout = config[\"timeout\"]\n    return timeout\n\nload({})\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(
+        out.contains("w.py:4:5: RP074 Variable `timeout` assigned inside `with` block"),
+        "got: {out}"
+    );
+}
+
+// ── --select filter ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_select_only_rp001() {
+    let mut t = TempPy::new();
+    // produces RP001 (unused import) and RP005 (unreachable)
+    t.file("f.py", "import os\ndef foo():\n    return 1\n    x = 2\n");
+    let out = t.run_no_exit(&["--select", "RP001"]);
+    assert!(out.contains("RP001"));
+    assert!(!out.contains("RP005"));
+}
+
+#[test]
+fn test_select_multiple_codes() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\ndef foo():\n    return 1\n    x = 2\n");
+    let out = t.run_no_exit(&["--select", "RP001,RP005"]);
+    assert!(out.contains("RP001"));
+    assert!(out.contains("RP005"));
+}
+
+#[test]
+fn test_select_nonexistent_code_no_output() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
+    let out = t.run_no_exit(&["--select", "RP999"]);
+    assert!(!out.contains("RP001"));
+    assert!(out.contains("No issues found"));
+}
+
+// ── --ignore-names ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_ignore_names_suppresses_named_diagnostics() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "def f():\n    dummy = 1\n    other = 2\n    return 0\n\nf()\n",
+    );
+    let out = t.run_no_exit(&["--ignore-names", "dummy"]);
+    assert!(!out.contains("dummy"), "got: {out}");
+    assert!(out.contains("`other`"), "got: {out}");
+}
+
+#[test]
+fn test_ignore_names_does_not_match_rule_codes() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
+    let out = t.run_no_exit(&["--ignore-names", "RP*"]);
+    assert!(out.contains("RP001"), "got: {out}");
+}
+
+#[test]
+fn test_ignore_names_glob() {
+    let mut t = TempPy::new();
+    t.file("f.py", "from m import UNUSED_FOO, UNUSED_BAR, used_baz\n");
+    let out = t.run_no_exit(&["--ignore-names", "UNUSED_*"]);
+    assert!(!out.contains("UNUSED_"), "got: {out}");
+    assert!(out.contains("`used_baz`"), "got: {out}");
+}
+
+// ── --sort-by ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_sort_by_rule_groups_codes() {
+    let mut t = TempPy::new();
+    t.file("a.py", "import os\ndef foo():\n    x = 1\n");
+    t.file("b.py", "import sys\ndef bar():\n    y = 1\n");
+    let out = t.run_no_exit(&["--sort-by", "rule"]);
+    let codes: Vec<&str> = out
+        .lines()
+        .filter_map(|l| l.split(": ").nth(1))
+        .filter_map(|rest| rest.split(' ').next())
+        .filter(|c| c.starts_with("RP00"))
+        .collect();
+    let last_rp001 = codes.iter().rposition(|c| *c == "RP001").unwrap();
+    let first_rp002 = codes.iter().position(|c| *c == "RP002").unwrap();
+    assert!(last_rp001 < first_rp002, "got: {codes:?}");
+}
+
+#[test]
+fn test_sort_by_file_interleaves_rules() {
+    let mut t = TempPy::new();
+    t.file("a.py", "import os\ndef foo():\n    x = 1\n");
+    t.file("b.py", "import sys\ndef bar():\n    y = 1\n");
+    let out = t.run_no_exit(&["--sort-by", "file"]);
+    let files: Vec<&str> = out
+        .lines()
+        .filter(|l| l.contains(": RP00"))
+        .map(|l| if l.contains("a.py") { "a" } else { "b" })
+        .collect();
+    let last_a = files.iter().rposition(|f| *f == "a").unwrap();
+    let first_b = files.iter().position(|f| *f == "b").unwrap();
+    assert!(last_a < first_b, "got: {files:?}");
+}
+
+#[test]
+fn test_sort_by_rule_reorders_json() {
+    let mut t = TempPy::new();
+    t.file("a.py", "def foo():\n    x = 1\n");
+    t.file("b.py", "import sys\n");
+    let out = t.run_no_exit(&["--json", "--sort-by", "rule"]);
+    let rp001 = out.find("\"RP001\"").unwrap();
+    let rp002 = out.find("\"RP002\"").unwrap();
+    assert!(rp001 < rp002);
+}
+
+// ── --json output ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_json_output_is_valid_structure() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
+    let out = t.run_no_exit(&["--json"]);
+    assert!(out.contains("\"diagnostics\""), "must have diagnostics key");
+    assert!(out.contains("\"code\": \"RP001\""), "must include code");
+    assert!(out.contains("\"file\""), "must include file");
+    assert!(out.contains("\"line\""), "must include line");
+    assert!(out.contains("\"count\""), "must include count");
+    // Verify comma is correctly placed (not on its own line)
+    for line in out.lines() {
+        let trimmed = line.trim();
+        // A line should not be just a bare comma
+        assert_ne!(trimmed, ",", "bare comma line detected — invalid JSON");
+    }
+}
+
+#[test]
+fn test_json_clean_file() {
+    let mut t = TempPy::new();
+    t.file("f.py", "x = 1\nprint(x)\n");
+    let out = t.run_no_exit(&["--json"]);
+    assert!(out.contains("\"diagnostics\": []") || out.contains("\"count\": 0"));
+}
+
+#[test]
+fn test_json_message_escaping() {
+    let mut t = TempPy::new();
+    // backtick names in messages must not break JSON strings
+    t.file("f.py", "import os\n");
+    let out = t.run_no_exit(&["--json"]);
+    // The message contains backtick chars — those are fine in JSON, but
+    // double-quotes inside the message must be escaped.
+    assert!(out.contains("imported but unused"));
+}
+
+#[test]
+fn test_json_includes_fix_when_present() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n\ndef f():\n    return g()\n");
+    let out = t.run_no_exit(&["--json"]);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    let diags = v["diagnostics"].as_array().unwrap();
+    let rp001 = diags.iter().find(|d| d["code"] == "RP001").unwrap();
+    assert_eq!(rp001["fix"]["start"], 0);
+    assert_eq!(rp001["fix"]["end"], 10);
+    assert_eq!(rp001["fix"]["replacement"], "");
+    let rp003 = diags.iter().find(|d| d["code"] == "RP003").unwrap();
+    assert!(rp003.get("fix").is_none());
+}
+
+#[test]
+fn test_show_fixes_marks_fixable_diagnostics() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n\ndef f():\n    return g()\n");
+    let out = t.run_no_exit(&["--show-fixes"]);
+    let rp001 = out.lines().find(|l| l.contains("RP001")).unwrap();
+    assert!(rp001.ends_with("[fix available]"), "got: {rp001}");
+    let rp003 = out.lines().find(|l| l.contains("RP003")).unwrap();
+    assert!(!rp003.contains("[fix available]"));
+
+    let plain = t.run_no_exit(&[]);
+    assert!(!plain.contains("[fix available]"));
+}
+
+#[test]
+fn test_show_fixes_count_in_summary() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\nimport sys\n\ndef unused():\n    pass\n");
+    let out = t.run_no_exit(&["--show-fixes-count"]);
+    assert!(
+        out.trim_end()
+            .ends_with("Found 3 issue(s) (2 auto-fixable)"),
+        "got: {out}"
+    );
+    assert!(t.run_no_exit(&[]).trim_end().ends_with("Found 3 issue(s)"));
+}
+
+#[test]
+fn test_show_fixes_count_json() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\nimport sys\n\ndef unused():\n    pass\n");
+    let out = t.run_no_exit(&["--json", "--show-fixes-count"]);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(v["fixable_count"], 2);
+    assert_eq!(v["count"], 3);
+
+    let out = t.run_no_exit(&["--json"]);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert!(v.get("fixable_count").is_none());
+}
+
+// ── --output sarif ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_sarif_output_is_valid_log() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
+    let out = t.run_no_exit(&["--output", "sarif"]);
+    let v: serde_json::Value = serde_json::from_str(&out).expect("SARIF must be valid JSON");
+    assert!(v["$schema"].as_str().unwrap().contains("sarif-2.1.0"));
+    assert_eq!(v["version"], "2.1.0");
+
+    let run = &v["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "reaper");
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert!(rules.iter().any(|r| r["id"] == "RP001"));
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert_eq!(result["ruleId"], "RP001");
+    assert_eq!(
+        rules[result["ruleIndex"].as_u64().unwrap() as usize]["id"],
+        "RP001"
+    );
+    assert!(result["message"]["text"].as_str().unwrap().contains("`os`"));
+    let location = &result["locations"][0]["physicalLocation"];
+    assert!(
+        location["artifactLocation"]["uri"]
+            .as_str()
+            .unwrap()
+            .ends_with("f.py")
+    );
+    assert_eq!(location["region"]["startLine"], 1);
+    assert_eq!(location["region"]["startColumn"], 8);
+}
+
+#[test]
+fn test_sarif_clean_file_has_empty_results() {
+    let mut t = TempPy::new();
+    t.file("f.py", "x = 1\nprint(x)\n");
+    let (out, _, code) = t.run(&["--output", "sarif"]);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(v["runs"][0]["results"], serde_json::json!([]));
+    assert_eq!(code, 0);
+}
+
+// ── --select-category ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_select_category_keeps_only_listed_categories() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "import os\n\ndef f():\n    x = 1\n    return 2\n\nf()\n",
+    );
+    let out = t.run_no_exit(&["--select-category", "imports"]);
+    assert!(out.contains("RP001"), "got: {out}");
+    assert!(!out.contains("RP002"), "got: {out}");
+
+    let out = t.run_no_exit(&["--select-category", "imports,variables"]);
+    assert!(out.contains("RP001"), "got: {out}");
+    assert!(out.contains("RP002"), "got: {out}");
+}
+
+#[test]
+fn test_select_and_select_category_intersect() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n\ndef f():\n    x = 1\n\nf()\n");
+    let out = t.run_no_exit(&["--select", "RP001,RP002", "--select-category", "variables"]);
+    assert!(out.contains("RP002"), "got: {out}");
+    assert!(!out.contains("RP001"), "got: {out}");
+
+    let out = t.run_no_exit(&["--select", "RP001", "--select-category", "variables"]);
+    assert!(!out.contains("RP00"), "got: {out}");
+}
+
+#[test]
+fn test_unknown_category_is_a_usage_error() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
+    let (_, stderr, code) = t.run(&["--select-category", "loops"]);
+    assert_eq!(code, 2);
+    assert!(stderr.contains("unknown category"), "got: {stderr}");
+}
+
+// ── --output github ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_github_output_annotations() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n\ndef f():\n    return 1\n    x = 2\n");
+    let out = t.run_no_exit(&["--output", "github"]);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 3, "one line per diagnostic, no summary: {out}");
+    for line in &lines {
+        assert!(
+            line.starts_with("::error ") || line.starts_with("::warning "),
+            "got: {line}"
+        );
+        for key in ["file=", ",line=", ",col=", ",title=RP0"] {
+            assert!(line.contains(key), "missing {key}: {line}");
+        }
+    }
+    assert!(out.contains(",line=1,col=8,title=RP001::`os` imported but unused"));
+    assert!(out.contains("::error file="));
+}
+
+#[test]
+fn test_github_output_escapes_commas_in_paths() {
+    let mut t = TempPy::new();
+    t.file("a,b.py", "import os\n");
+    let out = t.run_no_exit(&["--output", "github"]);
+    assert!(out.contains("a%2Cb.py,line=1"), "got: {out}");
+}
+
+#[test]
+fn test_output_json_matches_json_flag() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
+    assert_eq!(
+        t.run_no_exit(&["--output", "json"]),
+        t.run_no_exit(&["--json"])
+    );
+}
+
+// ── framework settings ────────────────────────────────────────────────────────
+
+#[test]
+fn test_framework_flags() {
+    let mut t = TempPy::new();
+    t.file(
+        "views.py",
+        "@app.route(\"/\")\ndef view(request):\n    return 1\n\n\
+         @receiver(post_save)\ndef on_save(sender):\n    return 1\n\n\
+         def helper():\n    pass\n\n__all__ = [\"view\", \"on_save\", \"__build__\"]\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("Argument `request`"), "got: {out}");
+    assert!(out.contains("Argument `sender`"), "got: {out}");
+    assert!(out.contains("Function `helper`"), "got: {out}");
+    assert!(out.contains("`__build__`"), "got: {out}");
+
+    let out = t.run_no_exit(&[
+        "--framework",
+        "django",
+        "--framework-exemptions",
+        "route",
+        "--always-used",
+        "helper",
+        "--exempt-dunders",
+        "__build__",
+    ]);
+    assert!(out.contains("No issues found"), "got: {out}");
+}
+
+// ── --show-bytes ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_show_bytes_adds_byte_range() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
+    let out = t.run_no_exit(&["--json", "--show-bytes"]);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    let d = &v["diagnostics"][0];
+    assert_eq!(d["code"], "RP001");
+    assert_eq!(d["start_byte"], 7);
+    assert_eq!(d["end_byte"], 9);
+
+    let out = t.run_no_exit(&["--json"]);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert!(v["diagnostics"][0].get("start_byte").is_none());
+}
+
+#[test]
+fn test_show_bytes_are_decoded_text_offsets() {
+    let mut t = TempPy::new();
+    t.file("f.py", "\u{feff}import os\n");
+    let out = t.run_no_exit(&["--json", "--show-bytes"]);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    let d = &v["diagnostics"][0];
+    // The three BOM bytes are not counted.
+    assert_eq!(d["start_byte"], 7);
+    assert_eq!(d["end_byte"], 9);
+}
+
+// ── --per-file-summary ────────────────────────────────────────────────────────
+
+#[test]
+fn test_per_file_summary_counts_by_rule() {
+    let mut t = TempPy::new();
+    t.file("a.py", "import os\nimport sys\n\ndef f():\n    x = 1\n");
+    t.file("b.py", "import re\n");
+    let (out, _, code) = t.run(&["--no-exit-code", "--per-file-summary"]);
+    assert_eq!(code, 0);
+    let rows: Vec<&str> = out.lines().filter(|l| l.contains("issue(s) (")).collect();
+    assert_eq!(rows.len(), 2, "got: {out}");
+    assert!(rows[0].contains("a.py") && rows[0].contains("4 issue(s) (RP001:2, RP002:1, RP003:1)"));
+    assert!(rows[1].contains("b.py") && rows[1].contains("1 issue(s) (RP001:1)"));
+    assert!(out.trim_end().ends_with("Found 5 issue(s)"), "got: {out}");
+
+    let plain = t.run_no_exit(&[]);
+    assert!(!plain.contains("issue(s) ("));
+}
+
+#[test]
+fn test_per_file_summary_json() {
+    let mut t = TempPy::new();
+    t.file("a.py", "import os\nimport sys\n");
+    let out = t.run_no_exit(&["--json", "--per-file-summary"]);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    let per_file = v["per_file"].as_object().unwrap();
+    assert_eq!(per_file.len(), 1);
+    let (file, entry) = per_file.iter().next().unwrap();
+    assert!(file.ends_with("a.py"));
+    assert_eq!(entry["count"], 2);
+    assert_eq!(entry["by_rule"]["RP001"], 2);
+
+    let out = t.run_no_exit(&["--json"]);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert!(v.get("per_file").is_none());
+}
+
+// ── --statistics ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_statistics_health_score() {
+    let mut t = TempPy::new();
+    t.file(
+        "a.py",
+        "import os\nimport sys\n\n\ndef main():\n    return 1\n\n\nif __name__ == \"__main__\":\n    main()\n",
+    );
+    let out = t.run_no_exit(&["--statistics"]);
+    assert!(
+        out.contains("Health score: 80.0%  (2 issues across 10 lines)"),
+        "got: {out}"
+    );
+
+    let out = t.run_no_exit(&["--json", "--statistics"]);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(v["health_score"], 80.0);
+    assert_eq!(v["total_lines"], 10);
+
+    let out = t.run_no_exit(&["--json"]);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert!(v.get("health_score").is_none());
+}
+
+#[test]
+fn test_statistics_clean_file_scores_full() {
+    let mut t = TempPy::new();
+    t.file("a.py", "print(1)\n");
+    let out = t.run_no_exit(&["--statistics"]);
+    assert!(
+        out.contains("Health score: 100.0%  (0 issues across 1 lines)"),
+        "got: {out}"
+    );
+}
+
+// ── --lenient / --strict ──────────────────────────────────────────────────────
+
+#[test]
+fn test_lenient_exempts_callbacks_meta_and_loggers() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "def on_click():\n    pass\nclass BaseMeta:\n    pass\ndef g():\n    logger = 1\n    return 0\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("RP003") && out.contains("RP004") && out.contains("RP002"));
+
+    let out = t.run_no_exit(&["--lenient"]);
+    assert!(!out.contains("RP002"), "got: {out}");
+    assert!(!out.contains("RP004"), "got: {out}");
+    assert!(!out.contains("`on_click`"), "got: {out}");
+
+    let out = t.run_no_exit(&["--lenient=loggers"]);
+    assert!(!out.contains("RP002"), "got: {out}");
+    assert!(out.contains("RP004"), "got: {out}");
+}
+
+#[test]
+fn test_strict_checks_pytest_parameters() {
+    let mut t = TempPy::new();
+    t.file("test_f.py", "def test_a(client, db):\n    assert client\n");
+    assert!(!t.run_no_exit(&[]).contains("RP008"));
+    let out = t.run_no_exit(&["--strict"]);
+    assert!(out.contains("RP008") && out.contains("`db`"), "got: {out}");
+}
+
+// ── --group-by-file ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_group_by_file_prints_one_group_per_file() {
+    let mut t = TempPy::new();
+    t.file("a.py", "import os\nimport sys\n");
+    t.file("b.py", "import re\n");
+    t.file("clean.py", "x = 1\n");
+    let out = t.run_no_exit(&["--group-by-file"]);
+    let headers: Vec<&str> = out.lines().filter(|l| l.starts_with("── ")).collect();
+    assert_eq!(headers.len(), 2, "got: {out}");
+    assert!(headers[0].contains("a.py (2 issues)"), "got: {out}");
+    assert!(headers[1].contains("b.py (1 issue)"), "got: {out}");
+    assert!(!out.contains("clean.py"), "got: {out}");
+    assert!(out.contains("\n1:8: RP001"), "got: {out}");
+    assert!(out.contains("\n2:8: RP001"), "got: {out}");
+}
+
+#[test]
+fn test_group_by_file_clean_file_has_no_group() {
+    let mut t = TempPy::new();
+    t.file("clean.py", "x = 1\n");
+    let out = t.run_no_exit(&["--group-by-file"]);
+    assert!(!out.contains("── "), "got: {out}");
+    assert!(out.contains("No issues found"));
+}
+
+#[test]
+fn test_group_by_file_json_keys_by_file() {
+    let mut t = TempPy::new();
+    t.file("a.py", "import os\nimport sys\n");
+    t.file("b.py", "import re\n");
+    let out = t.run_no_exit(&["--json", "--group-by-file"]);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    let groups = v["diagnostics"].as_object().unwrap();
+    assert_eq!(groups.len(), 2);
+    let (file, items) = groups.iter().find(|(f, _)| f.ends_with("a.py")).unwrap();
+    assert_eq!(items.as_array().unwrap().len(), 2, "{file}");
+    assert_eq!(v["count"], 3);
+}
+
+// ── rule documentation links ──────────────────────────────────────────────────
+
+#[test]
+fn test_show_urls_appends_rule_link() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
+    let out = t.run_no_exit(&["--show-urls"]);
+    let line = out.lines().find(|l| l.contains("RP001")).unwrap();
+    assert!(
+        line.ends_with("[RP001: https://github.com/taradepan/reaper#rp001--unused-import]"),
+        "got: {line}"
+    );
+    assert!(!t.run_no_exit(&[]).contains("https://"));
+}
+
+#[test]
+fn test_json_always_includes_url() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
+    let out = t.run_no_exit(&["--json"]);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(
+        v["diagnostics"][0]["url"],
+        "https://github.com/taradepan/reaper#rp001--unused-import"
+    );
+}
+
+#[test]
+fn test_explain_prints_url() {
+    let out = Command::new(reaper_bin())
+        .args(["--explain", "RP002"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("RP002 — Unused Variable"), "got: {stdout}");
+    assert!(stdout.contains("https://github.com/taradepan/reaper#rp002--unused-variable"));
+    assert!(
+        stdout.contains("A local variable is assigned but never read."),
+        "got: {stdout}"
+    );
+    assert!(stdout.contains("temp = 42"), "got: {stdout}");
+}
+
+#[test]
+fn test_explain_unknown_code_not_found() {
+    let out = Command::new(reaper_bin())
+        .args(["--explain", "RP999"])
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("rule `RP999` not found"), "got: {stderr}");
+}
+
+#[test]
+fn test_explain_all_lists_every_rule() {
+    let out = Command::new(reaper_bin())
+        .arg("--explain-all")
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    for code in ["RP001", "RP002", "RP048", "RP072"] {
+        assert!(stdout.contains(code), "missing {code} in: {stdout}");
+    }
+}
+
+#[test]
+fn test_explain_all_json() {
+    let out = Command::new(reaper_bin())
+        .args(["--explain-all", "--json"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let rules = v.as_array().unwrap();
+    assert_eq!(rules[0]["code"], "RP001");
+    assert_eq!(rules[0]["name"], "Unused Import");
+    assert_eq!(
+        rules[0]["url"],
+        "https://github.com/taradepan/reaper#rp001--unused-import"
+    );
+    assert_eq!(
+        rules[0]["description"],
+        "An imported name is never used in the module."
+    );
+    assert!(
+        rules[0]["examples"][0]
+            .as_str()
+            .unwrap()
+            .contains("import os")
+    );
+    for rule in rules {
+        for key in ["code", "name", "description", "examples", "url"] {
+            assert!(rule.get(key).is_some(), "missing {key} in {rule}");
+        }
+    }
+    assert!(rules.iter().any(|r| r["code"] == "RP072"));
+}
+
+fn rule_codes() -> Vec<String> {
+    let out = Command::new(reaper_bin())
+        .args(["--explain-all", "--json"])
+        .output()
+        .unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    v.as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["code"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_list_rules_table_has_every_rule() {
+    let out = Command::new(reaper_bin())
+        .arg("--list-rules")
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("DESCRIPTION"), "got: {stdout}");
+    for code in rule_codes() {
+        assert!(stdout.contains(&code), "missing {code} in: {stdout}");
+    }
+    assert!(
+        stdout.contains("An imported name is never used in the module."),
+        "got: {stdout}"
+    );
+}
+
+#[test]
+fn test_list_rules_json() {
+    let out = Command::new(reaper_bin())
+        .args(["--list-rules", "--json"])
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(0));
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let rules = v.as_array().unwrap();
+    let codes: Vec<&str> = rules.iter().map(|r| r["code"].as_str().unwrap()).collect();
+    assert_eq!(codes, rule_codes());
+    assert_eq!(rules[0]["name"], "Unused Import");
+    assert_eq!(rules[0]["severity"], "warning");
+    assert!(rules.iter().all(|r| r["description"].is_string()));
+}
+
+// ── --hyperlinks ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_hyperlinks_wrap_location_in_osc8() {
+    let mut t = TempPy::new();
+    t.file("f.py", "\nimport os\n");
+    let out = t.run_no_exit(&["--hyperlinks=always", "--absolute-paths"]);
+    let line = out.lines().find(|l| l.contains("RP001")).unwrap();
+    let path = t.files[0].canonicalize().unwrap();
+    let path = path.to_string_lossy().replace('\\', "/");
+    let slash = if path.starts_with('/') { "" } else { "/" };
+    let expected = format!(
+        "\x1b]8;;file://{slash}{path}#2\x1b\\{}:2:8\x1b]8;;\x1b\\: RP001 `os` imported but unused",
+        t.files[0].canonicalize().unwrap().display()
+    );
+    assert_eq!(line.as_bytes(), expected.as_bytes());
+}
+
+#[test]
+fn test_hyperlinks_off_by_default_and_when_piped() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
+    assert!(!t.run_no_exit(&[]).contains('\x1b'));
+    // `auto` never links when stdout is not a terminal.
+    assert!(!t.run_no_exit(&["--hyperlinks"]).contains('\x1b'));
+}
+
+// ── --fix ─────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_fix_removes_unused_imports() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "import os\nimport sys\nfrom re import compile\n\nprint(sys.argv)\n",
+    );
+    let (stdout, _, code) = t.run(&["--fix"]);
+    assert_eq!(code, 0, "everything was fixable: {stdout}");
+    assert!(stdout.contains("Fixed 2 issue(s)"));
+    let fixed = std::fs::read_to_string(&t.files[0]).unwrap();
+    assert_eq!(fixed, "import sys\n\nprint(sys.argv)\n");
+}
+
+#[test]
+fn test_fix_reports_remaining_diagnostics() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os; import sys\nimport re\n");
+    let (stdout, _, code) = t.run(&["--fix"]);
+    assert_eq!(code, 1);
+    assert!(stdout.contains("Fixed 1 issue(s)"));
+    assert!(stdout.contains("`os` imported but unused"));
+    assert!(!stdout.contains("`re`"));
+    let fixed = std::fs::read_to_string(&t.files[0]).unwrap();
+    assert_eq!(fixed, "import os; import sys\n");
+}
+
+#[test]
+fn test_fix_removes_unused_names_from_multi_name_imports() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "import os, sys\nfrom typing import Any, List, Optional\n\nprint(sys.argv, Optional)\n",
+    );
+    let (stdout, _, code) = t.run(&["--fix"]);
+    assert_eq!(code, 0, "everything was fixable: {stdout}");
+    assert!(stdout.contains("Fixed 3 issue(s)"));
+    let fixed = std::fs::read_to_string(&t.files[0]).unwrap();
+    assert_eq!(
+        fixed,
+        "import sys\nfrom typing import Optional\n\nprint(sys.argv, Optional)\n"
+    );
+}
+
+#[test]
+fn test_fix_removes_statement_when_every_name_unused() {
+    let mut t = TempPy::new();
+    t.file("f.py", "from os import path, sep\nx = 1\n");
+    let (stdout, _, code) = t.run(&["--fix"]);
+    assert_eq!(code, 0, "got: {stdout}");
+    assert!(stdout.contains("Fixed 2 issue(s)"));
+    assert_eq!(std::fs::read_to_string(&t.files[0]).unwrap(), "x = 1\n");
+}
+
+#[test]
+fn test_fix_parenthesized_import_keeps_layout() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "from m import (\n    a,\n    b,\n    c,\n)\nprint(a, c)\n",
+    );
+    let (stdout, _, code) = t.run(&["--fix"]);
+    assert_eq!(code, 0, "got: {stdout}");
+    assert_eq!(
+        std::fs::read_to_string(&t.files[0]).unwrap(),
+        "from m import (\n    a,\n    c,\n)\nprint(a, c)\n"
+    );
+}
+
+#[test]
+fn test_fix_keeps_file_ending_without_newline() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os, sys\nprint(sys)");
+    t.run(&["--fix"]);
+    assert_eq!(
+        std::fs::read_to_string(&t.files[0]).unwrap(),
+        "import sys\nprint(sys)"
+    );
+}
+
+#[test]
+fn test_fix_skips_file_that_would_lose_unreported_import() {
+    // `sep` is unused too but its report is filtered out, so removing the
+    // statement's unused names together would take it along: leave the file.
+    let mut t = TempPy::new();
+    t.file("f.py", "from os import path, sep\n");
+    let (stdout, stderr, code) = t.run(&["--fix", "--ignore-names", "sep"]);
+    assert_eq!(code, 1, "got: {stdout}");
+    assert!(stderr.contains("skipping file"), "got: {stderr}");
+    assert!(stdout.contains("`path` imported but unused"));
+    assert_eq!(
+        std::fs::read_to_string(&t.files[0]).unwrap(),
+        "from os import path, sep\n"
+    );
+}
+
+#[test]
+fn test_without_fix_flag_files_untouched() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
+    t.run_no_exit(&[]);
+    assert_eq!(std::fs::read_to_string(&t.files[0]).unwrap(), "import os\n");
+}
+
+#[test]
+fn test_fix_only_fails_on_unfixable() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n\ndef orphan():\n    return 0\n");
+    let (stdout, _, code) = t.run(&["--fix-only"]);
+    assert_eq!(code, 1);
+    assert!(stdout.contains("RP003"));
+    assert!(!stdout.contains("RP001"));
+    let fixed = std::fs::read_to_string(&t.files[0]).unwrap();
+    assert_eq!(fixed, "\ndef orphan():\n    return 0\n");
+}
+
+#[test]
+fn test_fix_only_exits_zero_when_all_fixed() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\nprint(1)\n");
+    let (stdout, _, code) = t.run(&["--fix-only"]);
+    assert_eq!(code, 0, "got: {stdout}");
+    assert!(stdout.contains("Fixed 1 issue(s)"));
+    assert_eq!(std::fs::read_to_string(&t.files[0]).unwrap(), "print(1)\n");
+}
+
+// ── --diff ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_diff_prints_removed_import() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\nimport sys\nprint(sys)\n");
+    let (stdout, stderr, code) = t.run(&["--fix", "--diff"]);
+    assert_eq!(code, 1, "got: {stdout}");
+    assert!(stdout.contains("--- a/"), "got: {stdout}");
+    assert!(
+        stdout.contains("\n-import os\n import sys\n"),
+        "got: {stdout}"
+    );
+    assert!(!stdout.contains("RP001"), "got: {stdout}");
+    assert!(stderr.contains("Would fix 1 issue(s)"), "got: {stderr}");
+}
+
+#[test]
+fn test_diff_leaves_files_unmodified() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\nprint(1)\n");
+    t.run(&["--fix", "--diff"]);
+    assert_eq!(
+        std::fs::read_to_string(&t.files[0]).unwrap(),
+        "import os\nprint(1)\n"
+    );
+}
+
+#[test]
+fn test_diff_no_exit_code_exits_zero() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\nprint(1)\n");
+    let (stdout, _, code) = t.run(&["--fix", "--diff", "--no-exit-code"]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("-import os"), "got: {stdout}");
+}
+
+#[test]
+fn test_diff_nothing_to_fix_exits_zero() {
+    let mut t = TempPy::new();
+    t.file("f.py", "def f():\n    return 1\n    print(2)\n");
+    let (stdout, _, code) = t.run(&["--diff"]);
+    assert_eq!(code, 0);
+    assert!(stdout.is_empty(), "got: {stdout}");
+}
+
+// ── --pre-commit ──────────────────────────────────────────────────────────────
+
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run git")
+        .status;
+    assert!(status.success(), "git {args:?} failed");
+}
+
+#[test]
+fn test_pre_commit_analyzes_only_staged_files() {
+    let mut t = TempPy::new();
+    t.file("staged.py", "import os\n");
+    t.file("unstaged.py", "import sys\n");
+    t.file("notes.txt", "import re\n");
+    git(t.dir.path(), &["init", "-q"]);
+    git(t.dir.path(), &["add", "staged.py", "notes.txt"]);
+
+    let out = t
+        .command()
+        .args(["--pre-commit", "--no-exit-code"])
+        .current_dir(t.dir.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("staged.py"), "got: {stdout}");
+    assert!(!stdout.contains("unstaged.py"), "got: {stdout}");
+    // --no-exit-code is ignored in pre-commit mode.
+    assert_eq!(out.status.code(), Some(1));
+}
+
+#[test]
+fn test_pre_commit_sees_usages_in_unstaged_files() {
+    let mut t = TempPy::new();
+    t.file("utils.py", "def helper():\n    return 1\n");
+    t.file(
+        "main.py",
+        "import sys\nfrom utils import helper\nprint(helper())\n",
+    );
+    git(t.dir.path(), &["init", "-q"]);
+    git(t.dir.path(), &["add", "utils.py"]);
+
+    let out = t
+        .command()
+        .args(["--pre-commit"])
+        .current_dir(t.dir.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    // main.py's call to `helper` still counts for cross-file RP003 …
+    assert!(!stdout.contains("RP003"), "got: {stdout}");
+    // … but its unused `sys` is not reported.
+    assert!(!stdout.contains("main.py"), "got: {stdout}");
+    assert_eq!(out.status.code(), Some(0));
+}
+
+#[test]
+fn test_pre_commit_respects_exclude() {
+    let mut t = TempPy::new();
+    t.file("tests/test_a.py", "import os\n");
+    git(t.dir.path(), &["init", "-q"]);
+    git(t.dir.path(), &["add", "."]);
+
+    let out = t
+        .command()
+        .args(["--pre-commit", "--exclude", "tests"])
+        .current_dir(t.dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(0));
+}
+
+#[test]
+fn test_pre_commit_uses_configured_extensions() {
+    let mut t = TempPy::new();
+    t.file("tool.py3", "import os\n");
+    git(t.dir.path(), &["init", "-q"]);
+    git(t.dir.path(), &["add", "."]);
+
+    let out = t
+        .command()
+        .args(["--pre-commit", "--extensions", "py,py3", "."])
+        .current_dir(t.dir.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("tool.py3:1:8: RP001"), "got: {stdout}");
+}
+
+// ── --include ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_include_glob_limits_discovery() {
+    let mut t = TempPy::new();
+    t.file("src/app.py", "import os\n");
+    t.file("tests/test_app.py", "import sys\n");
+
+    let out = t
+        .command()
+        .arg(t.dir.path())
+        .args(["--include", "src/**", "--no-exit-code"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("app.py"), "got: {stdout}");
+    assert!(!stdout.contains("test_app.py"), "got: {stdout}");
+}
+
+// ── --paths-from ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_paths_from_file_analyzes_listed_files() {
+    let mut t = TempPy::new();
+    t.file("a.py", "import os\n");
+    t.file("b.py", "import os\n");
+    t.file("c.py", "import os\n");
+    t.file("unlisted.py", "import os\n");
+    std::fs::write(
+        t.dir.path().join("files.txt"),
+        "# from git diff\na.py\n\nb.py\nc.py\n",
+    )
+    .unwrap();
+
+    let out = t
+        .command()
+        .args(["--paths-from", "files.txt", "--no-exit-code"])
+        .current_dir(t.dir.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    for name in ["a.py", "b.py", "c.py"] {
+        assert!(stdout.contains(name), "missing {name}: {stdout}");
+    }
+    assert!(!stdout.contains("unlisted.py"), "got: {stdout}");
+}
+
+#[test]
+fn test_paths_from_stdin() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut t = TempPy::new();
+    t.file("a.py", "import os\n");
+    t.file("b.py", "import os\n");
+
+    let mut child = t
+        .command()
+        .args(["--paths-from", "-", "--no-exit-code"])
+        .current_dir(t.dir.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"a.py\n").unwrap();
+    let out = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("a.py"), "got: {stdout}");
+    assert!(!stdout.contains("b.py"), "got: {stdout}");
+}
+
+#[test]
+fn test_paths_from_merges_with_positional_paths() {
+    let mut t = TempPy::new();
+    t.file("a.py", "import os\n");
+    t.file("b.py", "import os\n");
+    t.file("c.py", "import os\n");
+    std::fs::write(t.dir.path().join("files.txt"), "a.py\n").unwrap();
+
+    let out = t
+        .command()
+        .args(["b.py", "--paths-from", "files.txt", "--no-exit-code"])
+        .current_dir(t.dir.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("a.py"), "got: {stdout}");
+    assert!(stdout.contains("b.py"), "got: {stdout}");
+    assert!(!stdout.contains("c.py"), "got: {stdout}");
+}
+
+#[test]
+fn test_paths_from_missing_file_errors() {
+    let t = TempPy::new();
+    let out = t
+        .command()
+        .args(["--paths-from", "missing.txt"])
+        .current_dir(t.dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(2));
+}
+
+// ── --changed-only ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_changed_only_reports_changed_files() {
+    let mut t = TempPy::new();
+    t.file("changed.py", "def helper():\n    return 1\n");
+    t.file(
+        "unchanged.py",
+        "import sys\nfrom changed import helper\nhelper()\n",
+    );
+    let dir = t.dir.path();
+    git(dir, &["init", "-q"]);
+    git(dir, &["add", "."]);
+    git(
+        dir,
+        &[
+            "-c",
+            "user.name=t",
+            "-c",
+            "user.email=t@t",
+            "commit",
+            "-qm",
+            "init",
+        ],
+    );
+    std::fs::write(
+        dir.join("changed.py"),
+        "import os\n\ndef helper():\n    return 1\n",
+    )
+    .unwrap();
+
+    let out = t
+        .command()
+        .args(["--changed-only", "HEAD", "--no-exit-code", "."])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("changed.py:1:8: RP001"), "got: {stdout}");
+    // unchanged.py's unused `sys` is suppressed …
+    assert!(!stdout.contains("unchanged.py"), "got: {stdout}");
+    // … but its call to `helper` still counts for cross-file RP003.
+    assert!(!stdout.contains("RP003"), "got: {stdout}");
+}
+
+#[test]
+fn test_changed_only_non_ascii_file_name() {
+    let mut t = TempPy::new();
+    t.file("caf\u{e9}.py", "x = 1\n");
+    let dir = t.dir.path();
+    git(dir, &["init", "-q"]);
+    git(dir, &["add", "."]);
+    git(
+        dir,
+        &[
+            "-c",
+            "user.name=t",
+            "-c",
+            "user.email=t@t",
+            "commit",
+            "-qm",
+            "init",
+        ],
+    );
+    std::fs::write(dir.join("caf\u{e9}.py"), "import os\n").unwrap();
+
+    let out = t
+        .command()
+        .args(["--changed-only", "HEAD", "--no-exit-code", "."])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("caf\u{e9}.py:1:8: RP001"), "got: {stdout}");
+}
+
+// ── # noqa suppression ────────────────────────────────────────────────────────
+
+#[test]
+fn test_noqa_bare_suppresses_all() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os  # noqa\n");
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP001"), "bare # noqa must suppress RP001");
+    assert!(out.contains("No issues found"));
+}
+
+#[test]
+fn test_noqa_specific_code_suppresses() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os  # noqa: RP001\n");
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP001"));
+}
+
+#[test]
+fn test_noqa_wrong_code_does_not_suppress() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os  # noqa: RP002\n");
+    let out = t.run_no_exit(&[]);
+    assert!(
+        out.contains("RP001"),
+        "wrong noqa code must not suppress RP001"
+    );
+}
+
+#[test]
+fn test_noqa_multi_code() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os  # noqa: RP001, RP002\n");
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP001"));
+}
+
+#[test]
+fn test_reaper_disable_inline() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "import os  # reaper: disable=RP001\nimport re  # reaper: disable\nimport sys  # reaper: disable=RP002\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("`os`"), "got: {out}");
+    assert!(!out.contains("`re`"), "got: {out}");
+    assert!(out.contains("f.py:3:8: RP001"), "got: {out}");
+}
+
+#[test]
+fn test_reaper_disable_file_wide() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "# reaper: disable=RP001\nimport os\n\ndef f():\n    import sys\n    x = 1\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("RP001"), "got: {out}");
+    assert!(out.contains("RP002"), "got: {out}");
+}
+
+#[test]
+fn test_reaper_enable_after_file_wide_disable() {
+    let mut t = TempPy::new();
+    t.file(
+        "f.py",
+        "# reaper: disable=RP001\nimport os\n# reaper: enable=RP001\nimport sys\n",
+    );
+    let out = t.run_no_exit(&[]);
+    assert!(!out.contains("`os`"), "got: {out}");
+    assert!(out.contains("f.py:4:8: RP001"), "got: {out}");
+}
+
+#[test]
+fn test_type_ignore_suppresses_with_flag() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os  # type: ignore\n");
+    let out = t.run_no_exit(&["--respect-type-ignore"]);
+    assert!(!out.contains("RP001"), "got: {out}");
+    assert!(out.contains("No issues found"));
+}
+
+#[test]
+fn test_type_ignore_ignored_without_flag() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os  # type: ignore\n");
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("RP001"), "got: {out}");
+}
+
+// ── output format ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_output_format_file_line_col_code() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
     let out = t.run_no_exit(&[]);
     // Each diagnostic line must follow: path:line:col: RPxxx message
     let diag_line = out
@@ -582,21 +2785,72 @@ fn test_output_format_file_line_col_code() {
 
 #[test]
 fn test_scan_directory() {
-    let dir = tempfile::TempDir::new().unwrap();
-    std::fs::write(dir.path().join("a.py"), "import os\n").unwrap();
-    std::fs::write(dir.path().join("b.py"), "import sys\n").unwrap();
-    std::fs::write(dir.path().join("readme.txt"), "not python\n").unwrap();
+    let t = TempPy::new();
+    let dir = t.dir.path();
+    std::fs::write(dir.join("a.py"), "import os\n").unwrap();
+    std::fs::write(dir.join("b.py"), "import sys\n").unwrap();
+    std::fs::write(dir.join("readme.txt"), "not python\n").unwrap();
 
-    let out = Command::new(reaper_bin())
-        .arg(dir.path())
-        .arg("--no-exit-code")
-        .output()
-        .unwrap();
+    let out = t.command().arg(dir).arg("--no-exit-code").output().unwrap();
 
     let stdout = String::from_utf8_lossy(&out.stdout);
     assert!(stdout.contains("Found 2 issue(s)"), "got: {stdout}");
 }
 
+#[test]
+fn test_include_hidden_scans_dot_directories() {
+    let t = TempPy::new();
+    let dir = t.dir.path();
+    std::fs::create_dir_all(dir.join(".github/scripts")).unwrap();
+    std::fs::write(dir.join(".github/scripts/deploy.py"), "import os\n").unwrap();
+    std::fs::create_dir_all(dir.join(".git/hooks")).unwrap();
+    std::fs::write(dir.join(".git/hooks/pre-commit.py"), "import sys\n").unwrap();
+
+    let run = |extra: &[&str]| {
+        let out = t
+            .command()
+            .arg(dir)
+            .arg("--no-exit-code")
+            .args(extra)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+
+    assert!(run(&[]).contains("No issues found"));
+    let out = run(&["--include-hidden"]);
+    assert!(out.contains("deploy.py"), "got: {out}");
+    assert!(!out.contains("pre-commit.py"), "got: {out}");
+}
+
+#[test]
+fn test_pyw_files_discovered_and_extensions_flag() {
+    let t = TempPy::new();
+    let dir = t.dir.path();
+    std::fs::write(dir.join("gui.pyw"), "import os\n").unwrap();
+    std::fs::write(dir.join("app.py"), "import sys\n").unwrap();
+    std::fs::write(dir.join("app.pyc"), "import re\n").unwrap();
+
+    let run = |extra: &[&str]| {
+        let out = t
+            .command()
+            .arg(dir)
+            .arg("--no-exit-code")
+            .args(extra)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+
+    let out = run(&[]);
+    assert!(out.contains("gui.pyw"), "got: {out}");
+    assert!(out.contains("app.py:"), "got: {out}");
+    assert!(!out.contains("app.pyc"), "got: {out}");
+    let out = run(&["--extensions", "py"]);
+    assert!(!out.contains("gui.pyw"), "got: {out}");
+    assert!(out.contains("app.py:"), "got: {out}");
+}
+
 #[test]
 fn test_unparseable_file_skipped_gracefully() {
     let mut t = TempPy::new();
@@ -605,3 +2859,81 @@ fn test_unparseable_file_skipped_gracefully() {
     assert_eq!(code, 0);
     assert!(out.contains("No issues found"));
 }
+
+// ── source encodings ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_latin1_declared_file_analyzed() {
+    let mut t = TempPy::new();
+    t.file("legacy.py", "");
+    std::fs::write(
+        &t.files[0],
+        b"# -*- coding: latin-1 -*-\nimport os\nname = '\xe9t\xe9'\n",
+    )
+    .unwrap();
+    let out = t.run_no_exit(&[]);
+    assert!(out.contains("legacy.py:2:8: RP001"), "got: {out}");
+}
+
+#[test]
+fn test_fix_preserves_encoding_and_bom() {
+    let mut t = TempPy::new();
+    t.file("legacy.py", "");
+    t.file("bom.py", "");
+    std::fs::write(
+        &t.files[0],
+        b"# coding: latin-1\nimport os\nname = '\xe9'\n",
+    )
+    .unwrap();
+    std::fs::write(&t.files[1], b"\xef\xbb\xbfimport os\nprint(1)\n").unwrap();
+    let (stdout, _, code) = t.run(&["--fix"]);
+    assert_eq!(code, 0, "got: {stdout}");
+    assert!(stdout.contains("Fixed 2 issue(s)"), "got: {stdout}");
+    assert_eq!(
+        std::fs::read(&t.files[0]).unwrap(),
+        b"# coding: latin-1\nname = '\xe9'\n"
+    );
+    assert_eq!(
+        std::fs::read(&t.files[1]).unwrap(),
+        b"\xef\xbb\xbfprint(1)\n"
+    );
+}
+
+// ── path style ────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_absolute_paths_flag() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
+    let out = t.run_no_exit(&["--absolute-paths"]);
+    let diag_line = out
+        .lines()
+        .find(|l| l.contains("RP001"))
+        .expect("must have RP001 line");
+    assert!(diag_line.starts_with('/'), "got: {diag_line}");
+}
+
+#[test]
+fn test_relative_paths_flag() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
+    let out = t.run_no_exit(&["--relative-paths"]);
+    let diag_line = out
+        .lines()
+        .find(|l| l.contains("RP001"))
+        .expect("must have RP001 line");
+    assert!(!diag_line.starts_with('/'), "got: {diag_line}");
+    assert!(diag_line.contains("f.py"));
+}
+
+#[test]
+fn test_relative_paths_is_default() {
+    let mut t = TempPy::new();
+    t.file("f.py", "import os\n");
+    let out = t.run_no_exit(&[]);
+    let diag_line = out
+        .lines()
+        .find(|l| l.contains("RP001"))
+        .expect("must have RP001 line");
+    assert!(!diag_line.starts_with('/'), "got: {diag_line}");
+}